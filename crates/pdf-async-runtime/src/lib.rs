@@ -1,4 +1,5 @@
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
 
 // Re-export types from library crates
 pub use pdf_flashcards::{Flashcard, FlashcardOptions};
@@ -27,30 +28,70 @@ pub enum PdfCommand {
         options: ImpositionOptions,
     },
     ImposeGenerate {
+        operation_id: OperationId,
         options: ImpositionOptions,
         output_path: PathBuf,
     },
+    /// Stop a running operation started with the given id
+    CancelOperation {
+        operation_id: OperationId,
+    },
     ImposeLoadConfig {
         path: PathBuf,
     },
+    /// List the bundled and saved imposition presets
+    ImposeListPresets,
+    /// Load a named preset, answered with `ImposeConfigLoaded`
+    ImposeLoadPreset {
+        name: String,
+    },
+    ImposeSavePreset {
+        name: String,
+        options: ImpositionOptions,
+    },
+    /// Work out the statistics for the inputs, or for `page_count` pages
+    /// when given, which needs no inputs at all
     ImposeCalculateStats {
         options: ImpositionOptions,
+        page_count: Option<usize>,
     },
     ViewerLoad {
         path: PathBuf,
     },
+    /// Render a page at `scale` times the default preview size
     ViewerRenderPage {
         doc_id: DocumentId,
         page_index: usize,
+        scale: f32,
     },
     /// Prefetch pages for faster navigation (lower priority than direct renders)
     ViewerPrefetchPages {
         doc_id: DocumentId,
         page_indices: Vec<usize>,
+        scale: f32,
+    },
+    /// Render small thumbnails of pages for the page navigator
+    ViewerRenderThumbnails {
+        doc_id: DocumentId,
+        page_indices: Vec<usize>,
+    },
+    /// Find `query` in the document's text, ignoring case
+    ViewerSearch {
+        doc_id: DocumentId,
+        query: String,
     },
     ViewerClose {
         doc_id: DocumentId,
     },
+    /// Render each page of a loaded document at `dpi` to a PNG in
+    /// `output_dir`, named by sheet and side when `double_sided`, otherwise
+    /// by page
+    ExportImages {
+        doc_id: DocumentId,
+        dpi: f32,
+        double_sided: bool,
+        output_dir: PathBuf,
+    },
 }
 
 /// Updates sent from worker to UI
@@ -75,35 +116,97 @@ pub enum PdfUpdate {
     ImposeComplete {
         path: PathBuf,
     },
+    OperationCancelled {
+        operation_id: OperationId,
+    },
+    /// Sent once an operation has ended, however it ended
+    OperationFinished {
+        operation_id: OperationId,
+    },
     ImposePreviewGenerated {
         doc_id: DocumentId,
         page_count: usize,
     },
     ImposeConfigLoaded {
-        options: ImpositionOptions,
+        options: Box<ImpositionOptions>,
     },
     ImposeStatsCalculated {
         stats: ImpositionStatistics,
     },
+    /// Names of the imposition presets, bundled ones first
+    ImposePresetsListed {
+        names: Vec<String>,
+    },
+    /// An input is encrypted: ask for its password, then try again with it
+    /// set in the options
+    ImposePasswordRequired {
+        /// Whether the password already in the options was wrong
+        wrong_password: bool,
+    },
     Error {
         message: String,
     },
+    /// A problem the operation worked around and carried on from
+    Warning {
+        message: String,
+    },
     ViewerLoaded {
         doc_id: DocumentId,
         page_count: usize,
     },
     ViewerPageRendered {
+        doc_id: DocumentId,
+        page_index: usize,
+        scale: f32,
+        width: usize,
+        height: usize,
+        rgba_data: Vec<u8>,
+    },
+    ViewerThumbnailRendered {
         doc_id: DocumentId,
         page_index: usize,
         width: usize,
         height: usize,
         rgba_data: Vec<u8>,
     },
+    /// Where `query` was found, as (page index, bounds) in page order; empty
+    /// when it wasn't, or the document has no text to search
+    SearchResults {
+        doc_id: DocumentId,
+        query: String,
+        hits: Vec<(usize, PageRect)>,
+    },
     ViewerClosed {
         doc_id: DocumentId,
     },
+    ImagesExported {
+        output_dir: PathBuf,
+        image_count: usize,
+    },
+}
+
+/// An area of a page, as fractions of its width and height measured from
+/// its top-left corner, so it holds at any render scale
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PageRect {
+    pub left: f32,
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
 }
 
 /// Handle to a loaded document
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct DocumentId(pub u64);
+
+/// Handle to a cancellable operation, chosen by the UI when it starts one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OperationId(pub u64);
+
+impl OperationId {
+    /// An id not handed out before in this process
+    pub fn next() -> Self {
+        static NEXT: AtomicU64 = AtomicU64::new(0);
+        Self(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+}
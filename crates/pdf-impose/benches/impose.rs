@@ -0,0 +1,115 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+use lopdf::{Dictionary, Document, Object, Stream};
+use pdf_impose::*;
+use std::path::PathBuf;
+
+const PAGES: usize = 512;
+
+/// A document whose pages each carry a compressed content stream and an
+/// image of their own, and share one font
+fn synthetic_document(num_pages: usize) -> Document {
+    let mut doc = Document::with_version("1.7");
+    let pages_id = doc.new_object_id();
+    let font_id = doc.add_object(Dictionary::from_iter(vec![
+        ("Type", Object::Name(b"Font".to_vec())),
+        ("Subtype", Object::Name(b"Type1".to_vec())),
+        ("BaseFont", Object::Name(b"Helvetica".to_vec())),
+    ]));
+
+    let mut kids = Vec::new();
+    for page in 0..num_pages {
+        let text: String = (0..200)
+            .map(|line| {
+                format!(
+                    "BT /F1 9 Tf 40 {} Td (Page {page} line {line}) Tj ET\n",
+                    40 + line * 3
+                )
+            })
+            .collect();
+        let mut content = Stream::new(Dictionary::new(), text.into_bytes());
+        let _ = content.compress();
+        let content_id = doc.add_object(content);
+
+        let pixels: Vec<u8> = (0..128 * 128 * 3)
+            .map(|i| ((i + page) % 251) as u8)
+            .collect();
+        let image_id = doc.add_object(Stream::new(
+            Dictionary::from_iter(vec![
+                ("Type", Object::Name(b"XObject".to_vec())),
+                ("Subtype", Object::Name(b"Image".to_vec())),
+                ("Width", Object::Integer(128)),
+                ("Height", Object::Integer(128)),
+                ("ColorSpace", Object::Name(b"DeviceRGB".to_vec())),
+                ("BitsPerComponent", Object::Integer(8)),
+            ]),
+            pixels,
+        ));
+
+        let resources = Dictionary::from_iter(vec![
+            (
+                "Font",
+                Object::Dictionary(Dictionary::from_iter(vec![(
+                    "F1",
+                    Object::Reference(font_id),
+                )])),
+            ),
+            (
+                "XObject",
+                Object::Dictionary(Dictionary::from_iter(vec![(
+                    "Im1",
+                    Object::Reference(image_id),
+                )])),
+            ),
+        ]);
+        let page_id = doc.add_object(Dictionary::from_iter(vec![
+            ("Type", Object::Name(b"Page".to_vec())),
+            ("Parent", Object::Reference(pages_id)),
+            (
+                "MediaBox",
+                Object::Array(vec![0.into(), 0.into(), 612.into(), 792.into()]),
+            ),
+            ("Resources", Object::Dictionary(resources)),
+            ("Contents", Object::Reference(content_id)),
+        ]));
+        kids.push(Object::Reference(page_id));
+    }
+
+    doc.objects.insert(
+        pages_id,
+        Object::Dictionary(Dictionary::from_iter(vec![
+            ("Type", Object::Name(b"Pages".to_vec())),
+            ("Kids", Object::Array(kids)),
+            ("Count", Object::Integer(num_pages as i64)),
+        ])),
+    );
+    let catalog_id = doc.add_object(Dictionary::from_iter(vec![
+        ("Type", Object::Name(b"Catalog".to_vec())),
+        ("Pages", Object::Reference(pages_id)),
+    ]));
+    doc.trailer.set("Root", catalog_id);
+    doc
+}
+
+fn bench_impose(c: &mut Criterion) {
+    let documents = [synthetic_document(PAGES)];
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("synthetic.pdf")],
+        page_arrangement: PageArrangement::Octavo,
+        compress_output: false,
+        ..Default::default()
+    };
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+
+    let mut group = c.benchmark_group("impose");
+    group.sample_size(10);
+    group.bench_function("512_pages_octavo", |b| {
+        b.to_async(&runtime)
+            .iter(|| async { impose(&documents, &options).await.unwrap() })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_impose);
+criterion_main!(benches);
@@ -1,7 +1,9 @@
-//! Test example that generates imposed PDFs for all three main formats:
+//! Test example that generates imposed PDFs for all the main formats:
 //! - Folio (4 pages)
 //! - Quarto (8 pages)
 //! - Octavo (16 pages)
+//! - Sexto (12 pages)
+//! - Duodecimo (24 pages)
 //!
 //! Usage: cargo run --example test_all_formats -p pdf-impose
 //!
@@ -28,6 +30,19 @@
 //! 1. Print test_octavo_imposed.pdf double-sided (flip on short edge)
 //! 2. Three folds are needed - see detailed instructions in output
 //! 3. Pages should read 1-16 in order
+//!
+//! ### Sexto (12 pages):
+//! 1. Print test_sexto_imposed.pdf double-sided (flip on short edge)
+//! 2. Cut off the bottom row and fold it once along the vertical center
+//! 3. Fold the rest as a quarto and nest the small folio in its center
+//! 4. Pages should read 1-12 in order
+//!
+//! ### Duodecimo (24 pages):
+//! 1. Print test_duodecimo_imposed.pdf double-sided (flip on short edge)
+//! 2. Cut off the bottom row, cut it in half, and fold each half once
+//! 3. Fold the rest as an octavo; nest the right-hand folio in its center
+//!    and the left-hand folio inside that
+//! 4. Pages should read 1-24 in order
 
 use lopdf::{Dictionary, Document, Object, Stream};
 use pdf_impose::*;
@@ -161,10 +176,18 @@ async fn create_test_output(
     // Enable printer's marks
     options.marks = PrinterMarks {
         fold_lines: true,
+        fold_labels: false,
         cut_lines: true,
         crop_marks: true,
         registration_marks: true,
         trim_marks: false,
+        collation_marks: true,
+        sewing_marks: Some(SewingMarks::default()),
+        spine_marks: true,
+        punch_guides: None,
+        color_bars: false,
+        slot_labels: false,
+        style: MarkStyle::default(),
     };
 
     // Perform imposition
@@ -172,7 +195,7 @@ async fn create_test_output(
 
     // Save imposed
     let imposed_name = format!("{}_imposed.pdf", name);
-    save_pdf(imposed, &imposed_name).await?;
+    save_pdf(imposed, &imposed_name, options.save_options()).await?;
 
     println!("Created {} and {}", source_name, imposed_name);
     Ok(())
@@ -217,6 +240,36 @@ async fn main() -> Result<()> {
     println!("Folds: Three folds needed.");
     println!();
 
+    // Sexto: 12 pages -> A4 portrait (6 pages in 2x3 grid)
+    println!("--- SEXTO (12 pages) ---");
+    create_test_output(12, PageArrangement::Sexto, PaperSize::A4, "test_sexto").await?;
+    println!("Output: A4 portrait");
+    println!("Expected page order (Side B mirrored for duplex):");
+    println!("  Side A: Top [9, 4] (rotated 180), Middle [12, 1], Bottom [8, 5]");
+    println!("  Side B: Top [3, 10] (rotated 180), Middle [2, 11], Bottom [6, 7]");
+    println!("Cut off the bottom row and nest it inside the folded quarto.");
+    println!();
+
+    // Duodecimo: 24 pages -> A4 landscape (12 pages in 4x3 grid)
+    println!("--- DUODECIMO (24 pages) ---");
+    create_test_output(
+        24,
+        PageArrangement::Duodecimo,
+        a4_landscape,
+        "test_duodecimo",
+    )
+    .await?;
+    println!("Output: A4 landscape");
+    println!("Expected page order (Side B mirrored for duplex):");
+    println!(
+        "  Side A: Top [5, 20, 17, 8] (rotated 180), Middle [4, 21, 24, 1], Bottom [14, 11, 16, 9]"
+    );
+    println!(
+        "  Side B: Top [6, 19, 18, 7] (rotated 180), Middle [3, 22, 23, 2], Bottom [10, 15, 12, 13]"
+    );
+    println!("Cut off the bottom row, halve it, and nest both folios inside the folded octavo.");
+    println!();
+
     println!("=== Instructions ===");
     println!("1. Print each *_imposed.pdf file double-sided (flip on short edge)");
     println!("2. Fold according to the instructions above");
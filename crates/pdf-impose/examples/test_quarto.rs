@@ -101,7 +101,7 @@ async fn main() -> Result<()> {
     let imposed = impose(&[source_doc], &options).await?;
 
     // Save the imposed PDF
-    save_pdf(imposed, "test_quarto_imposed.pdf").await?;
+    save_pdf(imposed, "test_quarto_imposed.pdf", options.save_options()).await?;
     println!("Created test_quarto_imposed.pdf");
     println!("\nExpected layout:");
     println!("  Front side (sheet 1): pages 8, 1, 2, 7 (pages 8,1 rotated 180°)");
@@ -45,6 +45,15 @@ pub const FOLD_LINE_WIDTH: f32 = 0.5;
 /// Line width for cut lines (points)
 pub const CUT_LINE_WIDTH: f32 = 0.5;
 
+/// Line width for the frames drawn around placed pages (points)
+pub const CELL_BORDER_WIDTH: f32 = 0.5;
+
+/// Largest font size for the page numbers labelling cells (points)
+pub const SLOT_LABEL_FONT_SIZE: f32 = 36.0;
+
+/// Gray level of the page numbers labelling cells (0 black, 1 white)
+pub const SLOT_LABEL_GRAY: f32 = 0.75;
+
 /// Line width for crop marks (points)
 pub const CROP_MARK_WIDTH: f32 = 0.25;
 
@@ -63,6 +72,39 @@ pub const REGISTRATION_MARK_SIZE: f32 = 10.0;
 /// Size of scissors symbol (points)
 pub const SCISSORS_SIZE: f32 = 8.0;
 
+/// Width of a collation mark across the spine fold (points, ~3mm)
+pub const COLLATION_MARK_WIDTH: f32 = 8.5;
+
+/// Length of a collation mark along the spine fold (points, ~6mm)
+pub const COLLATION_MARK_LENGTH: f32 = 17.0;
+
+/// Length of a sewing station tick across the spine fold (points)
+pub const SEWING_MARK_LENGTH: f32 = 8.0;
+
+/// Line width for sewing station ticks (points)
+pub const SEWING_MARK_WIDTH: f32 = 0.5;
+
+/// Length of a spine tick beyond the head or tail of the spine fold (points)
+pub const SPINE_MARK_LENGTH: f32 = 8.0;
+
+/// Line width for spine ticks (points)
+pub const SPINE_MARK_WIDTH: f32 = 0.5;
+
+/// Line width for punch-hole guide circles (points)
+pub const PUNCH_GUIDE_WIDTH: f32 = 0.3;
+
+/// Font size of the order and direction labels on fold lines (points)
+pub const FOLD_LABEL_FONT_SIZE: f32 = 6.0;
+
+/// Distance between a fold label and its fold line or the leaf edge (points)
+pub const FOLD_LABEL_OFFSET: f32 = 2.0;
+
+/// Side of each solid patch in a color bar (points, ~3.5mm)
+pub const COLOR_BAR_PATCH_SIZE: f32 = 10.0;
+
+/// Clearance between a color bar and the marks around the leaf area (points)
+pub const COLOR_BAR_CLEARANCE: f32 = 3.0;
+
 // =============================================================================
 // Page Numbers
 // =============================================================================
@@ -83,7 +125,7 @@ pub const HELVETICA_CHAR_WIDTH_RATIO: f32 = 0.5;
 /// Control point factor for approximating circles with Bezier curves.
 /// This magic number comes from: 4 * (sqrt(2) - 1) / 3 ≈ 0.552284749831
 /// Using 4 cubic Bezier curves with this factor gives a very close circle approximation.
-pub const BEZIER_CIRCLE_FACTOR: f32 = 0.552284749831;
+pub const BEZIER_CIRCLE_FACTOR: f32 = 0.552_284_8;
 
 // =============================================================================
 // Flyleaves
@@ -0,0 +1,213 @@
+//! Carrying the inputs' link annotations onto the imposed sheets
+//!
+//! Pages become Form XObjects on the sheet, which can't carry annotations,
+//! so each placed page's links are rebuilt on the sheet side itself. URI
+//! links keep their target; internal links point at the output page where
+//! their target page is first placed. Other annotations are left behind.
+
+use super::ImposedOutput;
+use super::outline::{book_page_sources, destination_page, page_targets};
+use super::plan::ImpositionPlan;
+use super::sheet::{placement_matrix, side_offset_pt};
+use crate::options::ImpositionOptions;
+use crate::render::page_form_frame;
+use crate::types::*;
+use lopdf::{Dictionary, Document, Object, ObjectId};
+
+/// Copy the link annotations of every placed page onto its sheet side
+///
+/// `source` is the merged document the plan was rendered from, whose pages
+/// give the form placed on the sheet. The links themselves are read from
+/// `documents`, where internal destinations still point at the input pages.
+pub(crate) fn copy_link_annotations(
+    imposed: &mut ImposedOutput,
+    source: &Document,
+    source_page_ids: &[ObjectId],
+    plan: &ImpositionPlan,
+    documents: &[Document],
+    options: &ImpositionOptions,
+) -> Result<()> {
+    let origins = book_page_sources(documents, options);
+    let targets = page_targets(documents, options, &imposed.placed_on);
+
+    let sides = plan.sheets.iter().flat_map(|sheet| &sheet.sides);
+    let pages = imposed.sheets.iter().flat_map(|sheet| &sheet.pages);
+    for (layout, &page_id) in sides.zip(pages) {
+        let (offset_x, offset_y) = side_offset_pt(layout, options);
+        let mut annots = Vec::new();
+
+        for placement in &layout.placements {
+            let Some(source_idx) = placement.source_page else {
+                continue;
+            };
+            let (Some(&merged_id), Some(&Some((doc_idx, origin_id)))) =
+                (source_page_ids.get(source_idx), origins.get(source_idx))
+            else {
+                continue;
+            };
+
+            // Source page space -> upright form -> sheet, as in the content stream
+            let (bounds, form) = page_form_frame(source, merged_id, options.source_box)?;
+            let matrix = concat(
+                concat(form, placement_matrix(placement)),
+                [1.0, 0.0, 0.0, 1.0, offset_x, offset_y],
+            );
+
+            let doc = &documents[doc_idx];
+            let target = |page| targets.get(&(doc_idx, page)).map(|placed| placed.page);
+            for link in page_links(doc, origin_id) {
+                // Each link becomes a new object, so pages sharing a sheet
+                // can't end up sharing or overwriting each other's links
+                if let Some(annot) = imposed_link(doc, link, bounds, matrix, page_id, &target) {
+                    annots.push(Object::Reference(imposed.document.add_object(annot)));
+                }
+            }
+        }
+
+        if !annots.is_empty() {
+            imposed
+                .document
+                .get_dictionary_mut(page_id)?
+                .set("Annots", Object::Array(annots));
+        }
+    }
+    Ok(())
+}
+
+/// The `/Link` annotations on a page
+fn page_links(doc: &Document, page_id: ObjectId) -> Vec<&Dictionary> {
+    let Some(annots) = doc
+        .get_dictionary(page_id)
+        .ok()
+        .and_then(|page| page.get(b"Annots").ok())
+        .and_then(|annots| doc.dereference(annots).ok())
+        .and_then(|(_, annots)| annots.as_array().ok())
+    else {
+        return Vec::new();
+    };
+
+    annots
+        .iter()
+        .filter_map(|annot| doc.dereference(annot).ok()?.1.as_dict().ok())
+        .filter(|annot| {
+            annot
+                .get(b"Subtype")
+                .and_then(Object::as_name)
+                .is_ok_and(|subtype| subtype == b"Link")
+        })
+        .collect()
+}
+
+/// Rebuild a link for the sheet page `page_id`, or `None` when it falls
+/// outside the placed part of its page or its target isn't imposed
+///
+/// `bounds` is the placed part of the source page and `matrix` maps source
+/// page space onto the sheet.
+fn imposed_link(
+    doc: &Document,
+    link: &Dictionary,
+    bounds: [f32; 4],
+    matrix: [f32; 6],
+    page_id: ObjectId,
+    target: &dyn Fn(ObjectId) -> Option<ObjectId>,
+) -> Option<Dictionary> {
+    let (_, rect) = doc.dereference(link.get(b"Rect").ok()?).ok()?;
+    let rect: Vec<f32> = rect
+        .as_array()
+        .ok()?
+        .iter()
+        .map(Object::as_float)
+        .collect::<std::result::Result<_, _>>()
+        .ok()?;
+    let [x0, y0, x1, y1] = rect[..] else {
+        return None;
+    };
+
+    // Only the part of the link over the placed page box stays clickable
+    let left = x0.min(x1).max(bounds[0]);
+    let bottom = y0.min(y1).max(bounds[1]);
+    let right = x0.max(x1).min(bounds[2]);
+    let top = y0.max(y1).min(bounds[3]);
+    if left >= right || bottom >= top {
+        return None;
+    }
+    let corners = [(left, bottom), (right, bottom), (right, top), (left, top)]
+        .map(|point| transform(matrix, point));
+    let xs = corners.map(|(x, _)| x);
+    let ys = corners.map(|(_, y)| y);
+    let sheet_rect = [
+        xs.into_iter().fold(f32::INFINITY, f32::min),
+        ys.into_iter().fold(f32::INFINITY, f32::min),
+        xs.into_iter().fold(f32::NEG_INFINITY, f32::max),
+        ys.into_iter().fold(f32::NEG_INFINITY, f32::max),
+    ];
+
+    let mut annot = Dictionary::from_iter(vec![
+        ("Type", Object::Name(b"Annot".to_vec())),
+        ("Subtype", Object::Name(b"Link".to_vec())),
+        (
+            "Rect",
+            Object::Array(sheet_rect.iter().map(|&v| Object::Real(v)).collect()),
+        ),
+        ("P", Object::Reference(page_id)),
+    ]);
+    if let Some((_, border)) = link
+        .get(b"Border")
+        .ok()
+        .and_then(|border| doc.dereference(border).ok())
+    {
+        annot.set("Border", border.clone());
+    }
+
+    let action = link
+        .get(b"A")
+        .ok()
+        .and_then(|action| doc.dereference(action).ok())
+        .and_then(|(_, action)| action.as_dict().ok());
+    let is_uri = |action: &&Dictionary| {
+        action
+            .get(b"S")
+            .and_then(Object::as_name)
+            .is_ok_and(|kind| kind == b"URI")
+    };
+    match action.filter(is_uri) {
+        Some(action) => {
+            let (_, uri) = doc.dereference(action.get(b"URI").ok()?).ok()?;
+            annot.set(
+                "A",
+                Dictionary::from_iter(vec![
+                    ("S", Object::Name(b"URI".to_vec())),
+                    ("URI", uri.clone()),
+                ]),
+            );
+        }
+        None => {
+            let page = destination_page(doc, link).and_then(target)?;
+            annot.set(
+                "Dest",
+                Object::Array(vec![Object::Reference(page), Object::Name(b"Fit".to_vec())]),
+            );
+        }
+    }
+    Some(annot)
+}
+
+/// The matrix applying `first`, then `second`
+fn concat(first: [f32; 6], second: [f32; 6]) -> [f32; 6] {
+    let [a1, b1, c1, d1, e1, f1] = first;
+    let [a2, b2, c2, d2, e2, f2] = second;
+    [
+        a1 * a2 + b1 * c2,
+        a1 * b2 + b1 * d2,
+        c1 * a2 + d1 * c2,
+        c1 * b2 + d1 * d2,
+        e1 * a2 + f1 * c2 + e2,
+        e1 * b2 + f1 * d2 + f2,
+    ]
+}
+
+/// Map a point through a PDF matrix
+fn transform(matrix: [f32; 6], (x, y): (f32, f32)) -> (f32, f32) {
+    let [a, b, c, d, e, f] = matrix;
+    (a * x + c * y + e, b * x + d * y + f)
+}
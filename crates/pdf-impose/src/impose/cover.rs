@@ -0,0 +1,132 @@
+//! Cover handling for imposition
+//!
+//! Case-bound and perfect-bound books take their cover from separate stock,
+//! so the cover pages are split off the source before the body is imposed.
+//! They are either dropped or laid out as a wraparound cover: back cover,
+//! spine and front cover side by side on one sheet.
+
+use super::io::extract_pages;
+use super::sheet::finalize_document;
+use crate::constants::DEFAULT_PAGE_DIMENSIONS;
+use crate::options::ImpositionOptions;
+use crate::render::{create_page_xobject, get_page_dimensions};
+use crate::types::*;
+use lopdf::{Dictionary, Document, Object, ObjectId, Stream};
+use std::collections::HashMap;
+
+/// Split the cover pages off the front and back of the selected source pages
+///
+/// Returns the body, and the cover pages (front pages first) as a document
+/// of their own when the cover is imposed separately.
+pub(crate) fn split_cover(
+    selected: Document,
+    cover: &CoverOptions,
+) -> Result<(Document, Option<Document>)> {
+    if cover.pages() == 0 {
+        return Ok((selected, None));
+    }
+
+    let page_ids: Vec<ObjectId> = selected.get_pages().into_values().collect();
+    let front = cover.front_pages.min(page_ids.len());
+    let back = cover.back_pages.min(page_ids.len() - front);
+    let body_end = page_ids.len() - back;
+
+    let body = extract_pages(&selected, &page_ids[front..body_end])?;
+    let cover_pages = if cover.separate() {
+        let ids: Vec<ObjectId> = page_ids[..front]
+            .iter()
+            .chain(&page_ids[body_end..])
+            .copied()
+            .collect();
+        Some(extract_pages(&selected, &ids)?)
+    } else {
+        None
+    };
+    Ok((body, cover_pages))
+}
+
+/// Impose cover pages as a wraparound cover with a spine `spine_width_pt` wide
+///
+/// The outside of the cover holds the back and front covers; the inside, if
+/// there are inside pages, holds the inside front and inside back covers in
+/// mirror order so they back up correctly. Each sheet is sized to the cover:
+/// two page widths plus the spine, by one page height.
+pub(crate) fn impose_cover(
+    cover: &Document,
+    options: &ImpositionOptions,
+    spine_width_pt: f32,
+) -> Result<Document> {
+    let page_ids: Vec<ObjectId> = cover.get_pages().into_values().collect();
+    let (front, rest) = page_ids.split_at(options.cover.front_pages.min(page_ids.len()));
+    let (outside_front, inside_front) = (front.first(), front.get(1));
+    let (outside_back, inside_back) = match rest {
+        [] => (None, None),
+        [back] => (Some(back), None),
+        [inside, outside, ..] => (Some(outside), Some(inside)),
+    };
+
+    let (page_width, page_height) = page_ids
+        .first()
+        .and_then(|&id| get_page_dimensions(cover, id, options.source_box).ok())
+        .unwrap_or(DEFAULT_PAGE_DIMENSIONS);
+    let sheet_width = 2.0 * page_width + spine_width_pt;
+
+    // With the spine on the left, the front cover is the right-hand panel
+    let mut sides = vec![[outside_back, outside_front]];
+    if inside_front.is_some() || inside_back.is_some() {
+        sides.push([inside_front, inside_back]);
+    }
+    if options.binding_edge == BindingEdge::Right {
+        sides.iter_mut().for_each(|panels| panels.reverse());
+    }
+
+    let mut output = Document::with_version("1.7");
+    let pages_tree_id = output.new_object_id();
+    let mut page_refs = Vec::new();
+    let mut cache = HashMap::new();
+
+    for panels in sides {
+        let mut content = String::new();
+        let mut xobjects = Dictionary::new();
+
+        for (idx, page_id) in panels.into_iter().enumerate() {
+            let Some(&page_id) = page_id else {
+                continue;
+            };
+            let xobject_id =
+                create_page_xobject(&mut output, cover, page_id, options.source_box, &mut cache)?;
+            let name = format!("C{idx}");
+            xobjects.set(name.as_bytes(), Object::Reference(xobject_id));
+
+            // Scale pages that differ from the first one into its panel
+            let (width, height) = get_page_dimensions(cover, page_id, options.source_box)
+                .unwrap_or((page_width, page_height));
+            let scale = (page_width / width).min(page_height / height);
+            let x = idx as f32 * (page_width + spine_width_pt);
+            content.push_str(&format!("q {scale} 0 0 {scale} {x} 0 cm /{name} Do Q\n"));
+        }
+
+        let content_id = output.add_object(Stream::new(Dictionary::new(), content.into_bytes()));
+        let mut resources = Dictionary::new();
+        resources.set("XObject", Object::Dictionary(xobjects));
+        let page_id = output.add_object(Dictionary::from_iter(vec![
+            ("Type", Object::Name(b"Page".to_vec())),
+            ("Parent", Object::Reference(pages_tree_id)),
+            (
+                "MediaBox",
+                Object::Array(vec![
+                    Object::Integer(0),
+                    Object::Integer(0),
+                    Object::Real(sheet_width),
+                    Object::Real(page_height),
+                ]),
+            ),
+            ("Resources", Object::Dictionary(resources)),
+            ("Contents", Object::Reference(content_id)),
+        ]));
+        page_refs.push(Object::Reference(page_id));
+    }
+
+    finalize_document(&mut output, pages_tree_id, page_refs);
+    Ok(output)
+}
@@ -1,9 +1,13 @@
-//! Flyleaf handling for imposition
+//! Flyleaf and blank page handling for imposition
 //!
 //! Flyleaves are blank pages added to the front and back of a book.
-//! Each flyleaf consists of 2 pages (front and back of one leaf).
+//! Each flyleaf consists of 2 pages (front and back of one leaf). Single
+//! blank pages can also be inserted between content pages.
 
+use super::io::selected_page_counts;
 use crate::constants::PAGES_PER_LEAF;
+use crate::options::ImpositionOptions;
+use crate::render::inherited_attribute;
 use crate::types::*;
 use lopdf::{Dictionary, Document, Object, ObjectId, Stream};
 
@@ -46,11 +50,124 @@ pub(crate) fn add_flyleaves(mut doc: Document, front: usize, back: usize) -> Res
     Ok(doc)
 }
 
-/// Get the MediaBox from a page
+/// Every blank to insert into the body: the requested ones plus one before
+/// each chapter start that would otherwise fall on a verso
+///
+/// Chapters start at the pages in `chapter_starts_recto` and, with
+/// `chapter_recto`, at the first page of each input. Positions count the
+/// selected pages left once cover pages are split off. Front flyleaves come
+/// in whole leaves, so they don't change which side a page falls on.
+pub(crate) fn blank_positions(documents: &[Document], options: &ImpositionOptions) -> Vec<usize> {
+    let file_starts = if options.chapter_recto {
+        file_starts(documents, options)
+    } else {
+        Vec::new()
+    };
+    blank_positions_with_file_starts(&file_starts, options)
+}
+
+/// [`blank_positions`] for inputs whose later files start at the body
+/// positions in `file_starts`
+pub(crate) fn blank_positions_with_file_starts(
+    file_starts: &[usize],
+    options: &ImpositionOptions,
+) -> Vec<usize> {
+    let mut positions = options.blank_inserts.clone();
+    let mut chapter_starts = options.chapter_starts_recto.clone();
+    if options.chapter_recto {
+        chapter_starts.extend_from_slice(file_starts);
+    }
+    chapter_starts.sort_unstable();
+    chapter_starts.dedup();
+
+    let mut chapter_blanks = 0;
+    for start in chapter_starts {
+        let requested = options
+            .blank_inserts
+            .iter()
+            .filter(|&&position| position <= start)
+            .count();
+        // Index 0 is the first recto, so odd indices are versos
+        if !(start + requested + chapter_blanks).is_multiple_of(2) {
+            positions.push(start);
+            chapter_blanks += 1;
+        }
+    }
+    positions
+}
+
+/// Body positions of the first page of every input but the first
+fn file_starts(documents: &[Document], options: &ImpositionOptions) -> Vec<usize> {
+    let mut counts = selected_page_counts(documents, &options.input_ranges);
+    if options.reverse_source {
+        counts.reverse();
+    }
+
+    let body_end = counts
+        .iter()
+        .sum::<usize>()
+        .saturating_sub(options.cover.back_pages);
+    counts
+        .iter()
+        .take(counts.len().saturating_sub(1))
+        .scan(0, |file_start, &count| {
+            *file_start += count;
+            Some(*file_start)
+        })
+        .filter(|&file_start| file_start > options.cover.front_pages && file_start < body_end)
+        .map(|file_start| file_start - options.cover.front_pages)
+        .collect()
+}
+
+/// Insert a blank page before each of `positions` (0-based page indices)
+///
+/// Positions refer to the document as given, so inserting before 2 and 5
+/// leaves the original pages 2 and 5 right after their blanks. A repeated
+/// position inserts several blanks; positions past the end append blanks.
+/// Each blank takes the media box of the page it precedes.
+pub(crate) fn insert_blanks(mut doc: Document, positions: &[usize]) -> Result<Document> {
+    if positions.is_empty() {
+        return Ok(doc);
+    }
+
+    let page_ids: Vec<ObjectId> = doc.get_pages().into_values().collect();
+    let Some(&last_page_id) = page_ids.last() else {
+        return Ok(doc);
+    };
+
+    let mut positions = positions.to_vec();
+    positions.sort_unstable();
+
+    let (pages_id, _) = get_pages_tree(&doc)?;
+    let mut new_kids = Vec::with_capacity(page_ids.len() + positions.len());
+    let mut pending = positions.iter().peekable();
+    for (idx, &page_id) in page_ids.iter().enumerate() {
+        while pending.next_if(|&&position| position <= idx).is_some() {
+            let media_box = get_media_box(&doc, page_id)?;
+            new_kids.push(Object::Reference(create_blank_page(
+                &mut doc, &media_box, pages_id,
+            )?));
+        }
+        new_kids.push(Object::Reference(page_id));
+    }
+    let media_box = get_media_box(&doc, last_page_id)?;
+    for _ in pending {
+        new_kids.push(Object::Reference(create_blank_page(
+            &mut doc, &media_box, pages_id,
+        )?));
+    }
+
+    update_pages_tree(&mut doc, pages_id, new_kids)?;
+    Ok(doc)
+}
+
+/// Get the MediaBox from a page, which may be inherited from the page tree
 fn get_media_box(doc: &Document, page_id: ObjectId) -> Result<Vec<Object>> {
     let page_dict = doc.get_dictionary(page_id)?;
+    let media_box = inherited_attribute(doc, page_dict, b"MediaBox")
+        .ok_or_else(|| ImposeError::Config("Page has no MediaBox".to_string()))?;
 
-    match page_dict.get(b"MediaBox")? {
+    match doc.dereference(media_box)?.1 {
         Object::Array(arr) => Ok(arr.clone()),
         _ => Err(ImposeError::Config("MediaBox is not an array".to_string())),
     }
@@ -66,7 +183,7 @@ fn get_pages_tree(doc: &Document) -> Result<(ObjectId, Vec<Object>)> {
     let kids = pages_dict
         .get(b"Kids")
         .and_then(|obj| obj.as_array())
-        .map(|arr| arr.clone())
+        .cloned()
         .ok()
         .ok_or_else(|| ImposeError::Config("Pages Kids array not found".to_string()))?;
 
@@ -0,0 +1,354 @@
+//! Building a document straight from image files
+//!
+//! Each image becomes one page of the given paper size, turned to match the
+//! image's orientation, with the image fitted and centered on it. JPEGs are
+//! embedded as they are (`/DCTDecode`); other formats are decoded and their
+//! pixels Flate-compressed, with any alpha channel kept as a soft mask.
+
+use super::io::{finish_page_tree, load_pdf_with_password};
+use crate::constants::mm_to_pt;
+use crate::types::*;
+use image::GenericImageView;
+use lopdf::{Dictionary, Document, Object, ObjectId, Stream};
+use std::cmp::Ordering;
+use std::path::{Path, PathBuf};
+
+/// File extensions read as images, in lower case
+const IMAGE_EXTENSIONS: [&str; 3] = ["jpg", "jpeg", "png"];
+
+/// Whether `path` names an image file that [`load_images`] reads, going by
+/// its extension
+pub fn is_image_path(path: impl AsRef<Path>) -> bool {
+    path.as_ref()
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| IMAGE_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+}
+
+/// Build a document with one `page_size` page per image in `paths`
+///
+/// Pages follow the file names in natural order, so `page2.png` comes
+/// before `page10.png`.
+pub async fn load_images(paths: &[impl AsRef<Path>], page_size: PaperSize) -> Result<Document> {
+    let mut paths: Vec<PathBuf> = paths.iter().map(|p| p.as_ref().to_owned()).collect();
+    paths.sort_by(|a, b| natural_cmp(&file_name(a), &file_name(b)));
+
+    let mut images = Vec::with_capacity(paths.len());
+    for path in paths {
+        let bytes = tokio::fs::read(&path).await?;
+        images.push((path, bytes));
+    }
+    tokio::task::spawn_blocking(move || build_document(&images, page_size)).await?
+}
+
+/// [`load_images`] for every image file directly inside `dir`
+pub async fn load_image_folder(dir: impl AsRef<Path>, page_size: PaperSize) -> Result<Document> {
+    let mut paths = Vec::new();
+    let mut entries = tokio::fs::read_dir(dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        if is_image_path(&path) && entry.file_type().await?.is_file() {
+            paths.push(path);
+        }
+    }
+    load_images(&paths, page_size).await
+}
+
+/// Load each of `paths` as one document: a folder of images or an image
+/// file at `image_page_size`, and anything else as a PDF, decrypted with
+/// `password` if it is encrypted
+pub async fn load_inputs(
+    paths: &[impl AsRef<Path>],
+    password: Option<&str>,
+    image_page_size: PaperSize,
+) -> Result<Vec<Document>> {
+    let mut documents = Vec::with_capacity(paths.len());
+    for path in paths {
+        let path = path.as_ref();
+        let doc = if path.is_dir() {
+            load_image_folder(path, image_page_size).await?
+        } else if is_image_path(path) {
+            load_images(&[path], image_page_size).await?
+        } else {
+            load_pdf_with_password(path, password).await?
+        };
+        documents.push(doc);
+    }
+    Ok(documents)
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Compare names with runs of digits taken as numbers, ignoring ASCII case
+fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (mut a, mut b) = (a.chars().peekable(), b.chars().peekable());
+    loop {
+        let (x, y) = match (a.peek(), b.peek()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(&x), Some(&y)) => (x, y),
+        };
+        let order = if x.is_ascii_digit() && y.is_ascii_digit() {
+            let digits_a = digit_run(&mut a);
+            let digits_b = digit_run(&mut b);
+            let (num_a, num_b) = (
+                digits_a.trim_start_matches('0'),
+                digits_b.trim_start_matches('0'),
+            );
+            // Equal numbers with fewer leading zeros sort first
+            num_a
+                .len()
+                .cmp(&num_b.len())
+                .then_with(|| num_a.cmp(num_b))
+                .then_with(|| digits_a.len().cmp(&digits_b.len()))
+        } else {
+            a.next();
+            b.next();
+            x.to_ascii_lowercase().cmp(&y.to_ascii_lowercase())
+        };
+        if order != Ordering::Equal {
+            return order;
+        }
+    }
+}
+
+fn digit_run(chars: &mut std::iter::Peekable<std::str::Chars>) -> String {
+    let mut digits = String::new();
+    while let Some(c) = chars.next_if(char::is_ascii_digit) {
+        digits.push(c);
+    }
+    digits
+}
+
+fn build_document(images: &[(PathBuf, Vec<u8>)], page_size: PaperSize) -> Result<Document> {
+    if images.is_empty() {
+        return Err(ImposeError::NoPages);
+    }
+
+    let mut doc = Document::with_version("1.7");
+    let pages_id = doc.new_object_id();
+    let mut kids = Vec::with_capacity(images.len());
+    for (path, bytes) in images {
+        let image = embed_image(&mut doc, bytes).map_err(|message| ImposeError::Image {
+            path: path.clone(),
+            message,
+        })?;
+        kids.push(Object::Reference(add_image_page(
+            &mut doc, pages_id, &image, page_size,
+        )?));
+    }
+    finish_page_tree(&mut doc, pages_id, kids);
+    Ok(doc)
+}
+
+/// An image XObject added to a document
+struct EmbeddedImage {
+    id: ObjectId,
+    width: u32,
+    height: u32,
+}
+
+/// Add `bytes` as an image XObject
+fn embed_image(doc: &mut Document, bytes: &[u8]) -> std::result::Result<EmbeddedImage, String> {
+    if let Some(jpeg) = jpeg_header(bytes) {
+        let mut dict = image_dict(jpeg.width, jpeg.height, jpeg.color_space());
+        dict.set("Filter", Object::Name(b"DCTDecode".to_vec()));
+        if jpeg.components == 4 && jpeg.adobe {
+            dict.set(
+                "Decode",
+                Object::Array([1, 0, 1, 0, 1, 0, 1, 0].map(Object::Integer).to_vec()),
+            );
+        }
+        let stream = Stream::new(dict, bytes.to_vec()).with_compression(false);
+        return Ok(EmbeddedImage {
+            id: doc.add_object(stream),
+            width: jpeg.width,
+            height: jpeg.height,
+        });
+    }
+
+    let decoded = image::load_from_memory(bytes).map_err(|err| err.to_string())?;
+    let (width, height) = decoded.dimensions();
+    let color = decoded.color();
+    let (pixels, color_space) = if color.has_color() {
+        (decoded.to_rgb8().into_raw(), "DeviceRGB")
+    } else {
+        (decoded.to_luma8().into_raw(), "DeviceGray")
+    };
+
+    let mut dict = image_dict(width, height, color_space);
+    if color.has_alpha() {
+        let alpha: Vec<u8> = decoded.to_rgba8().pixels().map(|pixel| pixel[3]).collect();
+        let smask = compressed_stream(image_dict(width, height, "DeviceGray"), alpha)?;
+        dict.set("SMask", Object::Reference(doc.add_object(smask)));
+    }
+    let stream = compressed_stream(dict, pixels)?;
+    Ok(EmbeddedImage {
+        id: doc.add_object(stream),
+        width,
+        height,
+    })
+}
+
+fn image_dict(width: u32, height: u32, color_space: &str) -> Dictionary {
+    Dictionary::from_iter(vec![
+        ("Type", Object::Name(b"XObject".to_vec())),
+        ("Subtype", Object::Name(b"Image".to_vec())),
+        ("Width", Object::Integer(width.into())),
+        ("Height", Object::Integer(height.into())),
+        ("ColorSpace", Object::Name(color_space.as_bytes().to_vec())),
+        ("BitsPerComponent", Object::Integer(8)),
+    ])
+}
+
+fn compressed_stream(dict: Dictionary, data: Vec<u8>) -> std::result::Result<Stream, String> {
+    let mut stream = Stream::new(dict, data);
+    stream.compress().map_err(|err| err.to_string())?;
+    Ok(stream)
+}
+
+/// Add a page of `page_size`, turned to the image's orientation, with the
+/// image fitted and centered on it
+fn add_image_page(
+    doc: &mut Document,
+    pages_id: ObjectId,
+    image: &EmbeddedImage,
+    page_size: PaperSize,
+) -> Result<ObjectId> {
+    let (short, long) = {
+        let (w, h) = page_size.dimensions_mm();
+        (mm_to_pt(w.min(h)), mm_to_pt(w.max(h)))
+    };
+    let (page_w, page_h) = if image.width > image.height {
+        (long, short)
+    } else {
+        (short, long)
+    };
+    let scale = (page_w / image.width as f32).min(page_h / image.height as f32);
+    let (draw_w, draw_h) = (image.width as f32 * scale, image.height as f32 * scale);
+    let (x, y) = ((page_w - draw_w) / 2.0, (page_h - draw_h) / 2.0);
+
+    let content = format!("q {draw_w:.4} 0 0 {draw_h:.4} {x:.4} {y:.4} cm /Im0 Do Q");
+    let content_id = doc.add_object(Stream::new(Dictionary::new(), content.into_bytes()));
+    let resources = Dictionary::from_iter(vec![(
+        "XObject",
+        Object::Dictionary(Dictionary::from_iter(vec![(
+            "Im0",
+            Object::Reference(image.id),
+        )])),
+    )]);
+    Ok(doc.add_object(Dictionary::from_iter(vec![
+        ("Type", Object::Name(b"Page".to_vec())),
+        ("Parent", Object::Reference(pages_id)),
+        (
+            "MediaBox",
+            Object::Array(vec![
+                Object::Integer(0),
+                Object::Integer(0),
+                Object::Real(page_w),
+                Object::Real(page_h),
+            ]),
+        ),
+        ("Resources", Object::Dictionary(resources)),
+        ("Contents", Object::Reference(content_id)),
+    ])))
+}
+
+/// Size and components of a JPEG, read from its frame header
+struct JpegHeader {
+    width: u32,
+    height: u32,
+    components: u8,
+    /// Whether an Adobe marker came first, as it does on the inverted CMYK
+    /// JPEGs Adobe software writes
+    adobe: bool,
+}
+
+impl JpegHeader {
+    fn color_space(&self) -> &'static str {
+        match self.components {
+            1 => "DeviceGray",
+            4 => "DeviceCMYK",
+            _ => "DeviceRGB",
+        }
+    }
+}
+
+/// Read the frame header of a baseline or progressive 8-bit JPEG; `None`
+/// for anything else, which is then decoded instead
+fn jpeg_header(bytes: &[u8]) -> Option<JpegHeader> {
+    if !bytes.starts_with(&[0xFF, 0xD8]) {
+        return None;
+    }
+    let mut pos = 2;
+    let mut adobe = false;
+    while pos + 4 <= bytes.len() {
+        if bytes[pos] != 0xFF {
+            return None;
+        }
+        let marker = bytes[pos + 1];
+        // Fill bytes before a marker
+        if marker == 0xFF {
+            pos += 1;
+            continue;
+        }
+        let length = u16::from_be_bytes([bytes[pos + 2], bytes[pos + 3]]) as usize;
+        match marker {
+            // Baseline, extended and progressive Huffman frames
+            0xC0..=0xC2 => {
+                let frame = bytes.get(pos + 4..pos + 10)?;
+                let header = JpegHeader {
+                    height: u16::from_be_bytes([frame[1], frame[2]]).into(),
+                    width: u16::from_be_bytes([frame[3], frame[4]]).into(),
+                    components: frame[5],
+                    adobe,
+                };
+                let usable = frame[0] == 8
+                    && header.width > 0
+                    && header.height > 0
+                    && matches!(header.components, 1 | 3 | 4);
+                return usable.then_some(header);
+            }
+            0xEE => {
+                adobe = bytes.get(pos + 4..pos + 9) == Some(b"Adobe");
+                pos += 2 + length;
+            }
+            // Other frame types (lossless, arithmetic) or the scan itself
+            0xC3 | 0xC5..=0xC7 | 0xC9..=0xCB | 0xCD..=0xCF | 0xDA => return None,
+            _ => pos += 2 + length,
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_natural_order_takes_numbers_whole() {
+        let mut names = vec![
+            "page10.png",
+            "Page2.png",
+            "page1.png",
+            "page02.png",
+            "cover.png",
+        ];
+        names.sort_by(|a, b| natural_cmp(a, b));
+        assert_eq!(
+            names,
+            vec![
+                "cover.png",
+                "page1.png",
+                "Page2.png",
+                "page02.png",
+                "page10.png"
+            ]
+        );
+    }
+}
@@ -1,15 +1,49 @@
 //! Document I/O operations for imposition
 
+use crate::render::{copy_object_deep, get_page_dimensions, inherited_attribute, page_rotation};
 use crate::types::*;
-use lopdf::Document;
-use std::path::Path;
+use lopdf::xref::XrefType;
+use lopdf::{Dictionary, Document, Object, ObjectId};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 /// Load a single PDF document
+///
+/// Documents that need a password load still encrypted; imposing them
+/// decrypts them with [`ImpositionOptions::password`](crate::ImpositionOptions::password).
 pub async fn load_pdf(path: impl AsRef<Path>) -> Result<Document> {
     let path = path.as_ref().to_owned();
     let bytes = tokio::fs::read(&path).await?;
-    let doc = tokio::task::spawn_blocking(move || Document::load_mem(&bytes)).await??;
-    Ok(doc)
+    tokio::task::spawn_blocking(move || {
+        let doc = Document::load_mem(&bytes)?;
+        // Until decrypted, pages kept in object streams can't be seen
+        if !doc.is_encrypted() {
+            check_document(&doc)?;
+        }
+        Ok(doc)
+    })
+    .await?
+}
+
+/// Load a PDF document, decrypting it with `password` if it is encrypted
+///
+/// Unlike [`load_pdf`], an encrypted document that `password` doesn't open
+/// is an [`ImposeError::Encrypted`] error.
+pub async fn load_pdf_with_password(
+    path: impl AsRef<Path>,
+    password: Option<&str>,
+) -> Result<Document> {
+    let path = path.as_ref().to_owned();
+    let bytes = tokio::fs::read(&path).await?;
+    let password = password.map(str::to_owned);
+    tokio::task::spawn_blocking(move || {
+        let mut doc = Document::load_mem(&bytes)?;
+        decrypt(&mut doc, password.as_deref())?;
+        check_document(&doc)?;
+        Ok(doc)
+    })
+    .await?
 }
 
 /// Load multiple PDF documents
@@ -21,10 +55,89 @@ pub async fn load_multiple_pdfs(paths: &[impl AsRef<Path>]) -> Result<Vec<Docume
     Ok(documents)
 }
 
+/// Load multiple PDF documents, decrypting any encrypted ones with
+/// `password` like [`load_pdf_with_password`]
+pub async fn load_multiple_pdfs_with_password(
+    paths: &[impl AsRef<Path>],
+    password: Option<&str>,
+) -> Result<Vec<Document>> {
+    let mut documents = Vec::new();
+    for path in paths {
+        documents.push(load_pdf_with_password(path, password).await?);
+    }
+    Ok(documents)
+}
+
+/// Decrypt whichever of `documents` are encrypted with `password`
+///
+/// lopdf already opens documents with an empty user password, so only
+/// those needing a real password are still encrypted here.
+pub(crate) fn decrypt_inputs(
+    mut documents: Vec<Document>,
+    password: Option<&str>,
+) -> Result<Vec<Document>> {
+    for doc in documents.iter_mut().filter(|doc| doc.is_encrypted()) {
+        decrypt(doc, password)?;
+        check_document(doc)?;
+    }
+    Ok(documents)
+}
+
+/// Decrypt `doc` if it is encrypted, failing with [`ImposeError::Encrypted`]
+/// when `password` doesn't open it
+fn decrypt(doc: &mut Document, password: Option<&str>) -> Result<()> {
+    if !doc.is_encrypted() {
+        return Ok(());
+    }
+    match password {
+        Some(password) if doc.authenticate_password(password).is_ok() => {
+            doc.decrypt(password)?;
+            Ok(())
+        }
+        password => Err(ImposeError::Encrypted {
+            needs_password: password.is_none(),
+        }),
+    }
+}
+
+/// Check that a loaded document has a version and a page tree this crate
+/// can work with
+fn check_document(doc: &Document) -> Result<()> {
+    let supported = match doc.version.trim().split_once('.') {
+        Some(("1", minor)) => minor.len() == 1 && minor.as_bytes()[0].is_ascii_digit(),
+        Some(("2", minor)) => minor == "0",
+        _ => false,
+    };
+    if !supported {
+        return Err(ImposeError::UnsupportedPdfVersion(
+            doc.version.trim().to_string(),
+        ));
+    }
+
+    let pages = doc
+        .catalog()
+        .and_then(|catalog| catalog.get(b"Pages"))
+        .and_then(|pages| doc.dereference(pages))
+        .and_then(|(_, pages)| pages.as_dict())
+        .map_err(|_| ImposeError::CorruptPageTree("no /Pages in the catalog".to_string()))?;
+    let count = pages.get(b"Count").and_then(Object::as_i64).unwrap_or(0);
+    if count > 0 && doc.get_pages().is_empty() {
+        return Err(ImposeError::CorruptPageTree(format!(
+            "/Count is {count} but no pages could be read"
+        )));
+    }
+    Ok(())
+}
+
 /// Save the imposed document
-pub async fn save_pdf(mut doc: Document, path: impl AsRef<Path>) -> Result<()> {
+pub async fn save_pdf(
+    mut doc: Document,
+    path: impl AsRef<Path>,
+    save_options: SaveOptions,
+) -> Result<()> {
     let path = path.as_ref().to_owned();
     let bytes = tokio::task::spawn_blocking(move || {
+        prepare_for_saving(&mut doc, save_options);
         let mut writer = Vec::new();
         doc.save_to(&mut writer)?;
         Ok::<_, ImposeError>(writer)
@@ -34,9 +147,116 @@ pub async fn save_pdf(mut doc: Document, path: impl AsRef<Path>) -> Result<()> {
     Ok(())
 }
 
+/// Apply `save_options` to a document about to be written, and stamp its
+/// modification date
+fn prepare_for_saving(doc: &mut Document, save_options: SaveOptions) {
+    set_mod_date(doc, SystemTime::now());
+    if save_options.compress_streams {
+        doc.compress();
+    }
+    doc.reference_table.cross_reference_type = if save_options.use_object_streams {
+        // Cross-reference streams arrived in PDF 1.5
+        if doc.version.as_str() < "1.5" {
+            doc.version = "1.5".to_string();
+        }
+        XrefType::CrossReferenceStream
+    } else {
+        XrefType::CrossReferenceTable
+    };
+}
+
+/// Set `ModDate` in `doc`'s document information, adding the dictionary if
+/// it has none
+fn set_mod_date(doc: &mut Document, time: SystemTime) {
+    let date = Object::string_literal(pdf_date(time));
+    let info_id = match doc.trailer.get(b"Info") {
+        Ok(Object::Reference(id)) if doc.get_dictionary(*id).is_ok() => *id,
+        Ok(Object::Dictionary(info)) => doc.add_object(info.clone()),
+        _ => doc.add_object(Dictionary::new()),
+    };
+    if let Ok(info) = doc.get_dictionary_mut(info_id) {
+        info.set("ModDate", date);
+    }
+    doc.trailer.set("Info", Object::Reference(info_id));
+}
+
+/// Format `time` as a PDF date string in UTC, e.g. `D:20240131235959Z`
+fn pdf_date(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs());
+    let (days, secs_of_day) = ((secs / 86_400) as i64, secs % 86_400);
+
+    // Days since 1970-01-01 to a civil date, after Howard Hinnant's
+    // days_from_civil inverse
+    let shifted = days + 719_468;
+    let era = shifted.div_euclid(146_097);
+    let day_of_era = shifted - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "D:{year:04}{month:02}{day:02}{:02}{:02}{:02}Z",
+        secs_of_day / 3_600,
+        secs_of_day / 60 % 60,
+        secs_of_day % 60
+    )
+}
+
+/// Save split output, inserting each part's suffix before the file extension
+///
+/// For a base path of `output.pdf` and suffix `_sig01` this writes
+/// `output_sig01.pdf`. Returns the paths written, in order.
+pub async fn save_split_pdfs(
+    docs: Vec<(String, Document)>,
+    base_path: impl AsRef<Path>,
+    save_options: SaveOptions,
+) -> Result<Vec<PathBuf>> {
+    let base_path = base_path.as_ref();
+    let mut paths = Vec::with_capacity(docs.len());
+
+    for (suffix, doc) in docs {
+        let path = split_output_path(base_path, &suffix);
+        save_pdf(doc, &path, save_options).await?;
+        paths.push(path);
+    }
+
+    Ok(paths)
+}
+
+/// Build the path for one part of a split output
+fn split_output_path(base_path: &Path, suffix: &str) -> PathBuf {
+    let stem = base_path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "output".to_string());
+    let extension = base_path
+        .extension()
+        .map(|e| e.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "pdf".to_string());
+
+    base_path.with_file_name(format!("{stem}{suffix}.{extension}"))
+}
+
+/// Page attributes that may be inherited from ancestor nodes in the page tree
+const INHERITABLE_PAGE_ATTRIBUTES: [&[u8]; 4] = [b"MediaBox", b"CropBox", b"Resources", b"Rotate"];
+
 /// Merge multiple documents into one
-pub(crate) fn merge_documents(documents: &[Document]) -> Result<Document> {
-    if documents.is_empty() {
+///
+/// Pages are appended in input order. Each copied page keeps its own
+/// resources, so identically named resources in different inputs don't clash.
+/// Fails with [`ImposeError::NoPages`] when there are no pages to merge.
+pub fn merge_documents(documents: &[Document]) -> Result<Document> {
+    if documents.iter().all(|doc| doc.get_pages().is_empty()) {
         return Err(ImposeError::NoPages);
     }
 
@@ -44,6 +264,232 @@ pub(crate) fn merge_documents(documents: &[Document]) -> Result<Document> {
         return Ok(documents[0].clone());
     }
 
-    // TODO: Properly merge all pages with resources
-    Ok(documents[0].clone())
+    merge_pages(documents, usize::MAX)
+}
+
+/// Number of pages `ranges` selects from each document
+pub(crate) fn selected_page_counts(
+    documents: &[Document],
+    ranges: &[Option<PageRange>],
+) -> Vec<usize> {
+    documents
+        .iter()
+        .enumerate()
+        .map(|(idx, doc)| {
+            let total = doc.get_pages().len();
+            match ranges.get(idx).and_then(Option::as_ref) {
+                Some(range) => range.count(total),
+                None => total,
+            }
+        })
+        .collect()
+}
+
+/// Merge documents, keeping only the pages selected by `ranges`
+///
+/// Ranges are matched to documents by position; a missing or `None` entry
+/// keeps every page of that document.
+fn merge_selected_pages(documents: &[Document], ranges: &[Option<PageRange>]) -> Result<Document> {
+    if ranges.iter().all(Option::is_none) {
+        return merge_documents(documents);
+    }
+
+    let selections = documents
+        .iter()
+        .enumerate()
+        .map(|(idx, doc)| {
+            let page_ids: Vec<_> = doc.get_pages().into_values().collect();
+            let selected = match ranges.get(idx).and_then(Option::as_ref) {
+                Some(range) => range
+                    .select(page_ids.len())
+                    .into_iter()
+                    .map(|page| page_ids[page])
+                    .collect(),
+                None => page_ids,
+            };
+            (doc, selected)
+        })
+        .collect();
+
+    copy_page_lists(selections)
+}
+
+/// Merge the selected pages of all documents into one source document,
+/// reversed when `reverse` is set
+pub(crate) fn merge_source_pages(
+    documents: &[Document],
+    ranges: &[Option<PageRange>],
+    reverse: bool,
+) -> Result<Document> {
+    let merged = merge_selected_pages(documents, ranges)?;
+    if !reverse {
+        return Ok(merged);
+    }
+
+    let page_ids: Vec<_> = merged.get_pages().into_values().rev().collect();
+    extract_pages(&merged, &page_ids)
+}
+
+/// Turn every page of `doc` clockwise by `rotation`, on top of its own /Rotate
+///
+/// The rotation is written to each page's /Rotate, so the XObject form
+/// matrix and the effective page dimensions both pick it up.
+pub(crate) fn rotate_pages(doc: &mut Document, rotation: Rotation) -> Result<()> {
+    if rotation == Rotation::None {
+        return Ok(());
+    }
+
+    for page_id in doc.get_pages().into_values() {
+        let current = page_rotation(doc, doc.get_dictionary(page_id)?);
+        let rotate = (current + i64::from(rotation.degrees())) % 360;
+        doc.get_dictionary_mut(page_id)?
+            .set("Rotate", Object::Integer(rotate));
+    }
+    Ok(())
+}
+
+/// Page boxes replaced when a page has no usable size
+const PAGE_BOX_KEYS: [&[u8]; 5] = [b"MediaBox", b"CropBox", b"TrimBox", b"BleedBox", b"ArtBox"];
+
+/// Give each page whose `page_box` has no usable size a page of
+/// `fallback` size, returning the 0-based indices of the pages changed
+///
+/// All of the page's boxes are replaced, including inherited ones, so
+/// whichever box is placed the page is imposed at the fallback size.
+pub(crate) fn replace_invalid_page_boxes(
+    doc: &mut Document,
+    page_box: PageBox,
+    fallback: PaperSize,
+) -> Result<Vec<usize>> {
+    let (width, height) = fallback.dimensions_pt();
+    let rect = Object::Array(vec![
+        Object::Integer(0),
+        Object::Integer(0),
+        Object::Real(width),
+        Object::Real(height),
+    ]);
+
+    let mut replaced = Vec::new();
+    for (idx, page_id) in doc.get_pages().into_values().enumerate() {
+        match get_page_dimensions(doc, page_id, page_box) {
+            Err(ImposeError::InvalidPageBox(_)) => {}
+            other => {
+                other?;
+                continue;
+            }
+        }
+        let page = doc.get_dictionary(page_id)?;
+        let keys: Vec<&[u8]> = PAGE_BOX_KEYS
+            .into_iter()
+            .filter(|&key| key == b"MediaBox" || inherited_attribute(doc, page, key).is_some())
+            .collect();
+        let page = doc.get_dictionary_mut(page_id)?;
+        for key in keys {
+            page.set(key, rect.clone());
+        }
+        replaced.push(idx);
+    }
+    Ok(replaced)
+}
+
+/// Copy at most `max_pages` pages, in order across all documents, into a new document
+pub(crate) fn merge_pages(documents: &[Document], max_pages: usize) -> Result<Document> {
+    let mut remaining = max_pages;
+    let selections = documents
+        .iter()
+        .map(|doc| {
+            let page_ids: Vec<_> = doc.get_pages().into_values().take(remaining).collect();
+            remaining -= page_ids.len();
+            (doc, page_ids)
+        })
+        .collect();
+
+    copy_page_lists(selections)
+}
+
+/// Copy the given pages of `source`, in order, into a new document
+pub(crate) fn extract_pages(source: &Document, page_ids: &[ObjectId]) -> Result<Document> {
+    copy_page_lists(vec![(source, page_ids.to_vec())])
+}
+
+/// Copy lists of pages from several documents, in order, into a new document
+fn copy_page_lists(selections: Vec<(&Document, Vec<ObjectId>)>) -> Result<Document> {
+    let version = selections
+        .first()
+        .map(|(doc, _)| doc.version.as_str())
+        .unwrap_or("1.7");
+    let mut dest = Document::with_version(version);
+    let pages_id = dest.new_object_id();
+    let mut kids = Vec::new();
+
+    for (source, page_ids) in selections {
+        // Object IDs are only meaningful within one document, so each gets its own cache
+        let mut cache = HashMap::new();
+        for page_id in page_ids {
+            let new_page_id = copy_page(&mut dest, source, page_id, pages_id, &mut cache)?;
+            kids.push(Object::Reference(new_page_id));
+        }
+    }
+
+    finish_page_tree(&mut dest, pages_id, kids);
+    Ok(dest)
+}
+
+/// Write the Pages node and Catalog for a document built from copied pages
+pub(super) fn finish_page_tree(doc: &mut Document, pages_id: ObjectId, kids: Vec<Object>) {
+    let count = kids.len() as i64;
+    doc.objects.insert(
+        pages_id,
+        Object::Dictionary(Dictionary::from_iter(vec![
+            ("Type", Object::Name(b"Pages".to_vec())),
+            ("Kids", Object::Array(kids)),
+            ("Count", Object::Integer(count)),
+        ])),
+    );
+
+    let catalog_id = doc.add_object(Dictionary::from_iter(vec![
+        ("Type", Object::Name(b"Catalog".to_vec())),
+        ("Pages", Object::Reference(pages_id)),
+    ]));
+    doc.trailer.set("Root", catalog_id);
+}
+
+/// Copy a single page into `dest`, attaching it to `parent_id`.
+///
+/// Inherited attributes are resolved and written onto the copied page so it
+/// no longer depends on the source page tree.
+fn copy_page(
+    dest: &mut Document,
+    source: &Document,
+    page_id: ObjectId,
+    parent_id: ObjectId,
+    cache: &mut HashMap<ObjectId, ObjectId>,
+) -> Result<ObjectId> {
+    let page_dict = source.get_dictionary(page_id)?;
+
+    // Reserve the ID up front so back-references (e.g. annotation /P) resolve to the copy
+    let new_page_id = dest.new_object_id();
+    cache.insert(page_id, new_page_id);
+
+    let mut new_page = Dictionary::new();
+    for (key, value) in page_dict.iter() {
+        if key == b"Parent" {
+            continue;
+        }
+        new_page.set(key.clone(), copy_object_deep(dest, source, value, cache)?);
+    }
+
+    for key in INHERITABLE_PAGE_ATTRIBUTES {
+        if !new_page.has(key)
+            && let Some(value) = inherited_attribute(source, page_dict, key)
+        {
+            new_page.set(key, copy_object_deep(dest, source, value, cache)?);
+        }
+    }
+
+    new_page.set("Parent", Object::Reference(parent_id));
+    dest.objects
+        .insert(new_page_id, Object::Dictionary(new_page));
+
+    Ok(new_page_id)
 }
@@ -0,0 +1,118 @@
+//! Document metadata for imposed output
+//!
+//! The output takes the first input's document information (`/Info`) and
+//! its catalog's language and viewer preferences, and names pdf-impose as
+//! its producer. `ModDate` is stamped when the file is saved.
+
+use crate::options::ImpositionOptions;
+use crate::types::*;
+use lopdf::{Dictionary, Document, Object, StringFormat};
+
+/// Catalog entries carried over from the first input
+const CATALOG_KEYS: [&[u8]; 2] = [b"Lang", b"ViewerPreferences"];
+
+/// Deepest nesting of references followed when inlining a copied value
+const MAX_INLINE_DEPTH: usize = 8;
+
+/// Metadata written to each imposed output document
+pub(crate) struct OutputMetadata {
+    info: Dictionary,
+    catalog: Vec<(&'static [u8], Object)>,
+}
+
+impl OutputMetadata {
+    /// Collect the metadata for the output of `documents`
+    ///
+    /// String and name entries of the first input's `/Info` are kept, except
+    /// `Producer` and `ModDate`, which describe this tool and this file. The
+    /// title is replaced by `title_override` when set.
+    pub(crate) fn new(documents: &[Document], options: &ImpositionOptions) -> Self {
+        let mut info = Dictionary::new();
+        let mut catalog = Vec::new();
+
+        if let Some(doc) = documents.first() {
+            if let Some(source) = document_info(doc) {
+                for (key, value) in source.iter() {
+                    if key == b"Producer" || key == b"ModDate" {
+                        continue;
+                    }
+                    if let Ok((_, value)) = doc.dereference(value)
+                        && matches!(value, Object::String(..) | Object::Name(_))
+                    {
+                        info.set(key.clone(), value.clone());
+                    }
+                }
+            }
+
+            if let Ok(source) = doc.catalog() {
+                for key in CATALOG_KEYS {
+                    if let Ok(value) = source.get(key) {
+                        catalog.push((key, inline(doc, value, MAX_INLINE_DEPTH)));
+                    }
+                }
+            }
+        }
+
+        info.set(
+            "Producer",
+            Object::string_literal(concat!("pdf-impose ", env!("CARGO_PKG_VERSION"))),
+        );
+        if let Some(title) = &options.title_override {
+            info.set("Title", text_string(title));
+        }
+        Self { info, catalog }
+    }
+
+    /// Write the metadata into `doc`
+    pub(crate) fn apply(&self, doc: &mut Document) -> Result<()> {
+        let info_id = doc.add_object(self.info.clone());
+        doc.trailer.set("Info", Object::Reference(info_id));
+
+        let catalog = doc.catalog_mut()?;
+        for (key, value) in &self.catalog {
+            catalog.set(key.to_vec(), value.clone());
+        }
+        Ok(())
+    }
+}
+
+/// The `/Info` dictionary of `doc`, if it has one
+fn document_info(doc: &Document) -> Option<&Dictionary> {
+    let info = doc.trailer.get(b"Info").ok()?;
+    doc.dereference(info).ok()?.1.as_dict().ok()
+}
+
+/// `value` with every reference inside it replaced by its target, so it can
+/// be written into another document
+fn inline(doc: &Document, value: &Object, depth: usize) -> Object {
+    let value = match value {
+        Object::Reference(id) => match (depth.checked_sub(1), doc.get_object(*id)) {
+            (Some(depth), Ok(target)) => return inline(doc, target, depth),
+            _ => return Object::Null,
+        },
+        other => other,
+    };
+    match value {
+        Object::Array(items) => {
+            Object::Array(items.iter().map(|item| inline(doc, item, depth)).collect())
+        }
+        Object::Dictionary(dict) => Object::Dictionary(Dictionary::from_iter(
+            dict.iter()
+                .map(|(key, item)| (key.clone(), inline(doc, item, depth))),
+        )),
+        other => other.clone(),
+    }
+}
+
+/// Encode `text` as a PDF text string: ASCII as is, anything else as
+/// UTF-16BE with a byte order mark
+pub(super) fn text_string(text: &str) -> Object {
+    if text.is_ascii() {
+        return Object::string_literal(text);
+    }
+    let bytes = [0xFE, 0xFF]
+        .into_iter()
+        .chain(text.encode_utf16().flat_map(u16::to_be_bytes))
+        .collect();
+    Object::String(bytes, StringFormat::Hexadecimal)
+}
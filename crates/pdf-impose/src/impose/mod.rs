@@ -5,21 +5,53 @@
 //! 2. Calculate signature layouts
 //! 3. Place pages with margins and alignment
 //! 4. Render to output PDF with printer's marks
+//!
+//! Steps 2 and 3 make up a plan, available on its own from [`plan`], or
+//! from page dimensions alone with [`plan_imposition`].
 
+mod annotations;
+mod cover;
 mod flyleaves;
+#[cfg(feature = "images")]
+mod images;
 mod io;
+mod metadata;
+mod outline;
+mod plan;
 mod sheet;
 mod signature;
 mod simple;
+mod split;
 
-pub use io::{load_multiple_pdfs, load_pdf, save_pdf};
+use annotations::copy_link_annotations;
+#[cfg(feature = "images")]
+pub use images::{is_image_path, load_image_folder, load_images, load_inputs};
+use io::decrypt_inputs;
+pub use io::{
+    load_multiple_pdfs, load_multiple_pdfs_with_password, load_pdf, load_pdf_with_password,
+    merge_documents, save_pdf, save_split_pdfs,
+};
+pub(crate) use io::{merge_pages, merge_source_pages, selected_page_counts};
+use io::{replace_invalid_page_boxes, rotate_pages};
+use metadata::OutputMetadata;
+use outline::{book_page_sources, selected_page_sources, write_outline};
+pub(crate) use plan::plan_sheets;
+pub use plan::{ImpositionPlan, PlannedSheet, SheetSignature, plan, plan_imposition};
+use sheet::render_plan;
+pub use split::{impose_split, impose_split_cancellable, impose_split_with_progress};
 
-use crate::constants::mm_to_pt;
+use crate::constants::{PAGES_PER_LEAF, mm_to_pt};
+use crate::layout::{Rect, SheetSide};
 use crate::options::ImpositionOptions;
+use crate::render::get_page_dimensions;
+use crate::stats::calculate_statistics;
 use crate::types::*;
+use cover::impose_cover;
+pub(crate) use cover::split_cover;
 use flyleaves::add_flyleaves;
-use io::merge_documents;
-use lopdf::{Document, ObjectId};
+pub(crate) use flyleaves::{blank_positions, blank_positions_with_file_starts, insert_blanks};
+use lopdf::{Document, Object, ObjectId};
+use std::collections::{HashMap, HashSet};
 
 // =============================================================================
 // Main Entry Point
@@ -28,45 +60,413 @@ use lopdf::{Document, ObjectId};
 /// Main imposition function
 ///
 /// Takes source documents and options, returns an imposed output document.
+/// For [`OutputFormat::TwoSided`] that document holds all fronts followed by
+/// all backs, in [`BackOrder`]; use [`impose_split`] to get them as separate
+/// documents. The same goes for a cover imposed with
+/// [`CoverMode::SeparateDocument`], which only [`impose_split`] returns.
 pub async fn impose(documents: &[Document], options: &ImpositionOptions) -> Result<Document> {
+    impose_with_progress(documents, options, |_| {}).await
+}
+
+/// [`impose`], calling `progress` as the imposition moves through its stages
+///
+/// The callback runs on the blocking thread doing the work, so it should
+/// return quickly, e.g. by forwarding the update over a channel.
+pub async fn impose_with_progress(
+    documents: &[Document],
+    options: &ImpositionOptions,
+    progress: impl Fn(ImposeProgress) + Send + 'static,
+) -> Result<Document> {
+    impose_cancellable(documents, options, progress, CancellationToken::new()).await
+}
+
+/// [`impose_with_progress`], stopping with [`ImposeError::Cancelled`] once
+/// `cancel` is cancelled
+pub async fn impose_cancellable(
+    documents: &[Document],
+    options: &ImpositionOptions,
+    progress: impl Fn(ImposeProgress) + Send + 'static,
+    cancel: CancellationToken,
+) -> Result<Document> {
     options.validate()?;
 
     let documents = documents.to_vec();
     let options = options.clone();
 
-    tokio::task::spawn_blocking(move || impose_sync(&documents, &options)).await?
+    tokio::task::spawn_blocking(move || {
+        let documents = decrypt_inputs(documents, options.password.as_deref())?;
+        let report = reporter(&progress, &cancel);
+        impose_sync(&documents, &options, &report).map(|out| out.document)
+    })
+    .await?
+}
+
+/// Impose a folding dummy of `page_count` pages, with no source document
+///
+/// Each page is drawn as in [`ImpositionOptions::dummy_mode`], at
+/// [`ImpositionOptions::fallback_page_size`]. As with [`plan_imposition`],
+/// options that edit the page sequence need documents and are not applied,
+/// and the input files are not checked.
+pub fn impose_dummy(page_count: usize, options: &ImpositionOptions) -> Result<Document> {
+    options.validate_layout()?;
+    if page_count == 0 {
+        return Err(ImposeError::NoPages);
+    }
+
+    let options = ImpositionOptions {
+        dummy_mode: true,
+        ..options.clone()
+    };
+    let dimensions = vec![options.fallback_page_size.dimensions_pt(); page_count];
+    let plan = plan_sheets(&dimensions, &options)?;
+    let mut imposed = render_plan(
+        &Document::new(),
+        &[],
+        &HashSet::new(),
+        &plan,
+        &options,
+        &|_| Ok(()),
+    )?;
+
+    let ordered = print_order(&imposed.sheets, &options);
+    set_page_order(&mut imposed.document, &ordered)?;
+    OutputMetadata::new(&[], &options).apply(&mut imposed.document)?;
+    if options.compress_output {
+        imposed.document.compress();
+    }
+    Ok(imposed.document)
+}
+
+/// Wrap `progress` so each report first checks `cancel`
+pub(crate) fn reporter<'a>(
+    progress: &'a dyn Fn(ImposeProgress),
+    cancel: &'a CancellationToken,
+) -> impl Fn(ImposeProgress) -> Result<()> + 'a {
+    move |stage| {
+        if cancel.is_cancelled() {
+            return Err(ImposeError::Cancelled);
+        }
+        progress(stage);
+        Ok(())
+    }
+}
+
+/// Pages that [`ImpositionOptions::auto_rotate_to_fit`] turns 90°
+///
+/// Pages are 0-based positions in the book, counting flyleaves and inserted
+/// blanks. This runs the full imposition, so it is meant for verbose
+/// reporting rather than routine use.
+pub async fn auto_rotated_pages(
+    documents: &[Document],
+    options: &ImpositionOptions,
+) -> Result<Vec<usize>> {
+    if !options.auto_rotate_to_fit {
+        return Ok(Vec::new());
+    }
+    options.validate()?;
+
+    let documents = documents.to_vec();
+    let options = options.clone();
+
+    tokio::task::spawn_blocking(move || {
+        let documents = decrypt_inputs(documents, options.password.as_deref())?;
+        impose_sync(&documents, &options, &|_| Ok(())).map(|out| {
+            let mut pages = out.turned_pages;
+            pages.sort_unstable();
+            pages.dedup();
+            pages
+        })
+    })
+    .await?
+}
+
+/// An imposed document together with the output pages that make up each sheet
+pub(crate) struct ImposedOutput {
+    pub document: Document,
+    pub sheets: Vec<ImposedSheet>,
+    /// Source pages given a quarter turn by `auto_rotate_to_fit`
+    pub turned_pages: Vec<usize>,
+    /// Where each source page is first placed
+    pub placed_on: HashMap<usize, PlacedOn>,
+    /// The wraparound cover, when it is imposed separately
+    pub cover: Option<Document>,
+}
+
+/// The output pages printed on one physical sheet
+pub(crate) struct ImposedSheet {
+    /// Signature index, for bindings that use signatures
+    pub signature: Option<usize>,
+    /// Output page IDs, front side first
+    pub pages: Vec<ObjectId>,
+}
+
+/// Where a source page is placed in the output
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PlacedOn {
+    /// The output page
+    pub page: ObjectId,
+    /// 0-based sheet index, in book order
+    pub sheet: usize,
+    /// The side of the sheet; `None` for sheets printed on one side only
+    pub side: Option<SheetSide>,
+    /// 0-based index of the cell on the side, in reading order
+    pub cell: usize,
+}
+
+impl PlacedOn {
+    /// A description for people, e.g. "sheet 8 back, slot 3"
+    pub fn describe(&self) -> String {
+        let side = match self.side {
+            Some(SheetSide::Front) => " front",
+            Some(SheetSide::Back) => " back",
+            None => "",
+        };
+        format!("sheet {}{side}, slot {}", self.sheet + 1, self.cell + 1)
+    }
+}
+
+/// Impose pages of `selected` whose box has no usable size at
+/// [`ImpositionOptions::fallback_page_size`], reporting a warning for each
+fn check_page_boxes(
+    selected: &mut Document,
+    documents: &[Document],
+    options: &ImpositionOptions,
+    report: &dyn Fn(ImposeProgress) -> Result<()>,
+) -> Result<()> {
+    let fallback = options.fallback_page_size;
+    let replaced = replace_invalid_page_boxes(selected, options.source_box, fallback)?;
+    if replaced.is_empty() {
+        return Ok(());
+    }
+
+    let sources = selected_page_sources(documents, options);
+    for idx in replaced {
+        let Some(&(input, page_id)) = sources.get(idx) else {
+            continue;
+        };
+        let page = documents[input]
+            .get_pages()
+            .into_iter()
+            .find_map(|(number, id)| (id == page_id).then_some(number))
+            .unwrap_or_default();
+        report(ImposeProgress::Warning(ImposeWarning::InvalidPageBox {
+            input,
+            page,
+            substituted: fallback,
+        }))?;
+    }
+    Ok(())
 }
 
-fn impose_sync(documents: &[Document], options: &ImpositionOptions) -> Result<Document> {
-    // Merge all input documents into a single source
-    let mut merged = merge_documents(documents)?;
+/// The book's pages, merged from the inputs and ready to lay out
+pub(crate) struct BookPages {
+    /// The selected pages with blanks and flyleaves inserted
+    pub merged: Document,
+    /// Page IDs of `merged`, in book order
+    pub page_ids: Vec<ObjectId>,
+    /// (width, height) of each page in points
+    pub dimensions: Vec<(f32, f32)>,
+    /// Cover pages split off the body
+    pub cover: Option<Document>,
+}
+
+/// Apply the options that edit the page sequence to `documents`
+pub(crate) fn prepare_pages(
+    documents: &[Document],
+    options: &ImpositionOptions,
+    report: &dyn Fn(ImposeProgress) -> Result<()>,
+) -> Result<BookPages> {
+    // Merge the selected pages of all input documents into a single source,
+    // reversing them before the flyleaves go on
+    let mut selected =
+        merge_source_pages(documents, &options.input_ranges, options.reverse_source)?;
+    check_page_boxes(&mut selected, documents, options, report)?;
+    let (body, cover) = split_cover(selected, &options.cover)?;
+    let mut merged = insert_blanks(body, &blank_positions(documents, options))?;
 
     // Add flyleaves (each flyleaf = 1 leaf = 2 pages)
     if options.front_flyleaves > 0 || options.back_flyleaves > 0 {
         merged = add_flyleaves(merged, options.front_flyleaves, options.back_flyleaves)?;
     }
+    rotate_pages(&mut merged, options.source_rotation)?;
 
-    // Get source page info
-    let pages = merged.get_pages();
-    let page_ids: Vec<ObjectId> = pages.values().copied().collect();
-    let total_pages = page_ids.len();
-
-    if total_pages == 0 {
+    let page_ids: Vec<ObjectId> = merged.get_pages().values().copied().collect();
+    if page_ids.is_empty() {
         return Err(ImposeError::NoPages);
     }
 
-    // Dispatch based on binding type
-    if options.binding_type.uses_signatures() {
-        signature::impose_signature_binding(&merged, &page_ids, options)
-    } else {
-        simple::impose_simple_binding(&merged, &page_ids, options)
+    let dimensions = page_ids
+        .iter()
+        .map(|&id| {
+            get_page_dimensions(&merged, id, options.source_box)
+                .unwrap_or(crate::constants::DEFAULT_PAGE_DIMENSIONS)
+        })
+        .collect();
+
+    Ok(BookPages {
+        merged,
+        page_ids,
+        dimensions,
+        cover,
+    })
+}
+
+fn impose_sync(
+    documents: &[Document],
+    options: &ImpositionOptions,
+    report: &dyn Fn(ImposeProgress) -> Result<()>,
+) -> Result<ImposedOutput> {
+    report(ImposeProgress::Merging)?;
+    let BookPages {
+        merged,
+        page_ids,
+        dimensions,
+        cover,
+    } = prepare_pages(documents, options, report)?;
+
+    // Lay out the sheets for the binding type, then render them
+    let plan = plan_sheets(&dimensions, options)?;
+    // Inserted blanks and flyleaves count towards the folios but show none
+    let unnumbered: HashSet<usize> = book_page_sources(documents, options)
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, source)| source.is_none().then_some(idx))
+        .collect();
+    let mut imposed = render_plan(&merged, &page_ids, &unnumbered, &plan, options, report)?;
+
+    let ordered = print_order(&imposed.sheets, options);
+    set_page_order(&mut imposed.document, &ordered)?;
+    write_outline(
+        &mut imposed.document,
+        &imposed.sheets,
+        documents,
+        options,
+        &imposed.placed_on,
+    )?;
+    if !options.dummy_mode {
+        copy_link_annotations(&mut imposed, &merged, &page_ids, &plan, documents, options)?;
+    }
+
+    // The spine is as thick as the body's leaves once padded out
+    if let Some(cover) = cover {
+        let stats = calculate_statistics(documents, options)?;
+        let leaves = stats.padded_pages().div_ceil(PAGES_PER_LEAF);
+        let spine_pt = mm_to_pt(options.cover.spine_width_mm(leaves));
+        imposed.cover = Some(impose_cover(&cover, options, spine_pt)?);
+    }
+
+    let metadata = OutputMetadata::new(documents, options);
+    metadata.apply(&mut imposed.document)?;
+    if let Some(cover) = &mut imposed.cover {
+        metadata.apply(cover)?;
     }
+
+    // Streams that already carry a /Filter (e.g. copied images) are left as-is
+    if options.compress_output {
+        report(ImposeProgress::Compressing)?;
+        imposed.document.compress();
+        if let Some(cover) = &mut imposed.cover {
+            cover.compress();
+        }
+    }
+
+    Ok(imposed)
+}
+
+// =============================================================================
+// Output Ordering
+// =============================================================================
+
+/// Order the sheets' output pages for the requested output format
+pub(crate) fn print_order(sheets: &[ImposedSheet], options: &ImpositionOptions) -> Vec<ObjectId> {
+    match options.output_format {
+        OutputFormat::DoubleSided => sheets
+            .iter()
+            .flat_map(|sheet| sheet.pages.iter().copied())
+            .collect(),
+        OutputFormat::SingleSidedSequence | OutputFormat::TwoSided => {
+            let (mut fronts, backs) = sides(sheets, options.single_sided_back_order);
+            fronts.extend(backs);
+            fronts
+        }
+    }
+}
+
+/// Front and back output pages of `sheets`, the fronts in sheet order and
+/// the backs in `back_order`
+///
+/// Reversed, the printed stack of fronts is turned over and fed straight
+/// back in, so the last front comes round first to meet the last back.
+pub(crate) fn sides(
+    sheets: &[ImposedSheet],
+    back_order: BackOrder,
+) -> (Vec<ObjectId>, Vec<ObjectId>) {
+    let fronts = sheets.iter().filter_map(|sheet| sheet.pages.first());
+    let mut backs: Vec<_> = sheets
+        .iter()
+        .filter_map(|sheet| sheet.pages.get(1))
+        .copied()
+        .collect();
+    if back_order == BackOrder::Reversed {
+        backs.reverse();
+    }
+    (fronts.copied().collect(), backs)
+}
+
+/// Replace the root page tree's kids with `page_ids`, in order
+fn set_page_order(doc: &mut Document, page_ids: &[ObjectId]) -> Result<()> {
+    let pages_id = doc.catalog()?.get(b"Pages")?.as_reference()?;
+    let kids = page_ids.iter().map(|&id| Object::Reference(id)).collect();
+    doc.get_dictionary_mut(pages_id)?
+        .set("Kids", Object::Array(kids));
+    Ok(())
 }
 
 // =============================================================================
 // Shared Utilities
 // =============================================================================
 
+/// Calculate the leaf area bounds (inside sheet margins) on one side of a
+/// sheet
+///
+/// Back sides are mirrored left to right for duplex, so their left and right
+/// margins swap to keep both sides' leaf areas back to back; turned half a
+/// turn for short-edge duplex, their top and bottom margins swap instead.
+pub(crate) fn calculate_leaf_bounds(
+    options: &ImpositionOptions,
+    width_pt: f32,
+    height_pt: f32,
+    side: SheetSide,
+) -> Rect {
+    let margins = &options.margins.sheet;
+    let (left_mm, right_mm, top_mm, bottom_mm) = match (side, options.duplex_flip) {
+        (SheetSide::Front, _) => (
+            margins.left_mm,
+            margins.right_mm,
+            margins.top_mm,
+            margins.bottom_mm,
+        ),
+        (SheetSide::Back, FlipEdge::LongEdge) => (
+            margins.right_mm,
+            margins.left_mm,
+            margins.top_mm,
+            margins.bottom_mm,
+        ),
+        (SheetSide::Back, FlipEdge::ShortEdge) => (
+            margins.left_mm,
+            margins.right_mm,
+            margins.bottom_mm,
+            margins.top_mm,
+        ),
+    };
+    Rect::new(
+        mm_to_pt(left_mm),
+        mm_to_pt(bottom_mm),
+        width_pt - mm_to_pt(left_mm) - mm_to_pt(right_mm),
+        height_pt - mm_to_pt(top_mm) - mm_to_pt(bottom_mm),
+    )
+}
+
 /// Calculate output sheet dimensions in points
 pub(crate) fn sheet_dimensions_pt(options: &ImpositionOptions) -> (f32, f32) {
     let (width_mm, height_mm) = options
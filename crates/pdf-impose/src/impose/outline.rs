@@ -0,0 +1,405 @@
+//! The imposed output's outline (bookmarks)
+//!
+//! With [`ImpositionOptions::generate_outline`] the outline opens with an
+//! entry per signature, holding one per sheet side, so a sheet can be found
+//! when proofing on screen.
+//!
+//! The inputs' outlines follow. Every bookmark is pointed at the output
+//! sheet side where its page is first placed, and with
+//! [`ImpositionOptions::bookmark_locations`] its title says where, e.g.
+//! "Chapter 3 (p. 57 → sheet 8 back, slot 3)". Bookmarks whose page is not
+//! imposed, or whose destination can't be resolved, are dropped and their
+//! children move up in their place.
+
+use super::metadata::text_string;
+use super::{ImposedSheet, PlacedOn, blank_positions};
+use crate::constants::PAGES_PER_LEAF;
+use crate::options::ImpositionOptions;
+use crate::types::*;
+use lopdf::{Dictionary, Document, Object, ObjectId, decode_text_string};
+use std::collections::{HashMap, HashSet};
+
+/// Deepest name tree searched for a named destination
+const MAX_NAME_TREE_DEPTH: usize = 32;
+
+/// The selected input pages, as (input index, page ID), in the order the
+/// imposition takes them before the cover is split off
+pub(crate) fn selected_page_sources(
+    documents: &[Document],
+    options: &ImpositionOptions,
+) -> Vec<(usize, ObjectId)> {
+    let mut selected: Vec<(usize, ObjectId)> = documents
+        .iter()
+        .enumerate()
+        .flat_map(|(idx, doc)| {
+            let page_ids: Vec<ObjectId> = doc.get_pages().into_values().collect();
+            let pages = match options.input_ranges.get(idx).and_then(Option::as_ref) {
+                Some(range) => range
+                    .select(page_ids.len())
+                    .into_iter()
+                    .map(|page| page_ids[page])
+                    .collect(),
+                None => page_ids,
+            };
+            pages.into_iter().map(move |page_id| (idx, page_id))
+        })
+        .collect();
+    if options.reverse_source {
+        selected.reverse();
+    }
+    selected
+}
+
+/// The input page behind each page of the book, as (input index, page ID);
+/// `None` for inserted blanks and flyleaves
+///
+/// Follows the same steps as the imposition: page selection, reversal,
+/// cover split, blank inserts and flyleaves.
+pub(crate) fn book_page_sources(
+    documents: &[Document],
+    options: &ImpositionOptions,
+) -> Vec<Option<(usize, ObjectId)>> {
+    let selected: Vec<Option<(usize, ObjectId)>> = selected_page_sources(documents, options)
+        .into_iter()
+        .map(Some)
+        .collect();
+
+    // Cover pages are not part of the book block
+    let front = options.cover.front_pages.min(selected.len());
+    let back = options.cover.back_pages.min(selected.len() - front);
+    let body = &selected[front..selected.len() - back];
+    if body.is_empty() {
+        return Vec::new();
+    }
+
+    let mut positions = blank_positions(documents, options);
+    positions.sort_unstable();
+    let mut pending = positions.iter().peekable();
+    let mut book = vec![None; options.front_flyleaves * PAGES_PER_LEAF];
+    for (idx, &page) in body.iter().enumerate() {
+        while pending.next_if(|&&position| position <= idx).is_some() {
+            book.push(None);
+        }
+        book.push(page);
+    }
+    book.extend(pending.map(|_| None));
+    book.extend(vec![None; options.back_flyleaves * PAGES_PER_LEAF]);
+    book
+}
+
+/// Where each input page first appears in the output, keyed by
+/// (input index, page ID)
+///
+/// `placed_on` maps book page indices to where they are placed.
+pub(super) fn page_targets(
+    documents: &[Document],
+    options: &ImpositionOptions,
+    placed_on: &HashMap<usize, PlacedOn>,
+) -> HashMap<(usize, ObjectId), PlacedOn> {
+    book_page_sources(documents, options)
+        .into_iter()
+        .enumerate()
+        .filter_map(|(idx, source)| Some((source?, *placed_on.get(&idx)?)))
+        .collect()
+}
+
+/// A bookmark read from an input, already pointing at its output page
+struct Bookmark {
+    title: Object,
+    page: ObjectId,
+    children: Vec<Bookmark>,
+}
+
+/// Write the outline of `output`: the sheet entries when
+/// [`ImpositionOptions::generate_outline`] is set, then the outlines of
+/// `documents`
+///
+/// `placed_on` maps book page indices to where they first appear.
+/// Destinations become `/Fit` views of that page, since the source
+/// page's own coordinates no longer apply once it is scaled onto a sheet.
+pub(crate) fn write_outline(
+    output: &mut Document,
+    sheets: &[ImposedSheet],
+    documents: &[Document],
+    options: &ImpositionOptions,
+    placed_on: &HashMap<usize, PlacedOn>,
+) -> Result<()> {
+    let mut bookmarks = if options.generate_outline {
+        sheet_bookmarks(sheets)
+    } else {
+        Vec::new()
+    };
+    bookmarks.extend(source_bookmarks(documents, options, placed_on));
+    if bookmarks.is_empty() {
+        return Ok(());
+    }
+
+    let outlines_id = output.new_object_id();
+    let (first, last) = write_bookmarks(output, &bookmarks, outlines_id);
+    output.objects.insert(
+        outlines_id,
+        Object::Dictionary(Dictionary::from_iter(vec![
+            ("Type", Object::Name(b"Outlines".to_vec())),
+            ("First", Object::Reference(first)),
+            ("Last", Object::Reference(last)),
+            ("Count", Object::Integer(bookmarks.len() as i64)),
+        ])),
+    );
+    output
+        .catalog_mut()?
+        .set("Outlines", Object::Reference(outlines_id));
+    Ok(())
+}
+
+/// An entry per sheet side, numbered through the book and grouped under
+/// their signatures for bindings that use them
+fn sheet_bookmarks(sheets: &[ImposedSheet]) -> Vec<Bookmark> {
+    let mut bookmarks: Vec<Bookmark> = Vec::new();
+    let mut current_signature = None;
+    for (idx, sheet) in sheets.iter().enumerate() {
+        let Some(&front) = sheet.pages.first() else {
+            continue;
+        };
+        // Sheets printed on one side only have nothing to tell apart
+        let names: &[&str] = if sheet.pages.len() == 1 {
+            &[""]
+        } else {
+            &[" front", " back"]
+        };
+        let sides = sheet.pages.iter().zip(names).map(|(&page, name)| Bookmark {
+            title: Object::string_literal(format!("Sheet {}{name}", idx + 1)),
+            page,
+            children: Vec::new(),
+        });
+
+        match sheet.signature {
+            Some(signature) => {
+                if current_signature != Some(signature) {
+                    current_signature = Some(signature);
+                    bookmarks.push(Bookmark {
+                        title: Object::string_literal(format!("Signature {}", signature + 1)),
+                        page: front,
+                        children: Vec::new(),
+                    });
+                }
+                if let Some(group) = bookmarks.last_mut() {
+                    group.children.extend(sides);
+                }
+            }
+            None => bookmarks.extend(sides),
+        }
+    }
+    bookmarks
+}
+
+/// The bookmarks of `documents`, pointed at the output pages their pages
+/// first appear on
+fn source_bookmarks(
+    documents: &[Document],
+    options: &ImpositionOptions,
+    placed_on: &HashMap<usize, PlacedOn>,
+) -> Vec<Bookmark> {
+    let targets = page_targets(documents, options, placed_on);
+
+    let mut bookmarks = Vec::new();
+    for (doc_idx, doc) in documents.iter().enumerate() {
+        let Some(first) = doc
+            .catalog()
+            .ok()
+            .and_then(|catalog| catalog.get(b"Outlines").ok())
+            .and_then(|outlines| doc.dereference(outlines).ok())
+            .and_then(|(_, outlines)| outlines.as_dict().ok())
+            .and_then(|outlines| outlines.get(b"First").ok())
+        else {
+            continue;
+        };
+        let page_numbers: HashMap<ObjectId, u32> = doc
+            .get_pages()
+            .into_iter()
+            .map(|(number, page_id)| (page_id, number))
+            .collect();
+        let target = |page_id| {
+            let placed = targets.get(&(doc_idx, page_id))?;
+            let note = options.bookmark_locations.then(|| {
+                let number = page_numbers.get(&page_id).copied().unwrap_or_default();
+                format!("p. {number} → {}", placed.describe())
+            });
+            Some((placed.page, note))
+        };
+        let mut visited = HashSet::new();
+        read_bookmarks(doc, first, &target, &mut visited, &mut bookmarks);
+    }
+    bookmarks
+}
+
+/// Read the outline items from `first` along their `/Next` chain into
+/// `bookmarks`, promoting the children of items that don't resolve
+///
+/// `target` gives the output page for an input page, with a note to add to
+/// the title when wanted.
+fn read_bookmarks(
+    doc: &Document,
+    first: &Object,
+    target: &dyn Fn(ObjectId) -> Option<(ObjectId, Option<String>)>,
+    visited: &mut HashSet<ObjectId>,
+    bookmarks: &mut Vec<Bookmark>,
+) {
+    let mut next = first.as_reference().ok();
+    while let Some(item_id) = next {
+        // Malformed outlines can loop back on themselves
+        if !visited.insert(item_id) {
+            break;
+        }
+        let Ok(item) = doc.get_dictionary(item_id) else {
+            break;
+        };
+
+        let mut children = Vec::new();
+        if let Ok(child) = item.get(b"First") {
+            read_bookmarks(doc, child, target, visited, &mut children);
+        }
+
+        let title = item
+            .get(b"Title")
+            .ok()
+            .and_then(|title| doc.dereference(title).ok())
+            .map(|(_, title)| title.clone());
+        let page = destination_page(doc, item).and_then(target);
+        match (title, page) {
+            (Some(title), Some((page, note))) => bookmarks.push(Bookmark {
+                title: match note {
+                    Some(note) => noted_title(&title, &note),
+                    None => title,
+                },
+                page,
+                children,
+            }),
+            _ => bookmarks.extend(children),
+        }
+
+        next = item.get(b"Next").and_then(Object::as_reference).ok();
+    }
+}
+
+/// `title` with `note` added in parentheses; titles that can't be decoded
+/// are kept as they are
+fn noted_title(title: &Object, note: &str) -> Object {
+    match decode_text_string(title) {
+        Ok(text) => text_string(&format!("{text} ({note})")),
+        Err(_) => title.clone(),
+    }
+}
+
+/// The page an outline item's or link's `/Dest` or GoTo action points at
+pub(super) fn destination_page(doc: &Document, item: &Dictionary) -> Option<ObjectId> {
+    let dest = match item.get(b"Dest") {
+        Ok(dest) => dest,
+        Err(_) => {
+            let (_, action) = doc.dereference(item.get(b"A").ok()?).ok()?;
+            let action = action.as_dict().ok()?;
+            if action.get(b"S").and_then(Object::as_name).ok()? != b"GoTo" {
+                return None;
+            }
+            action.get(b"D").ok()?
+        }
+    };
+    explicit_destination_page(doc, dest)
+}
+
+/// The page of a destination array, looking up named destinations first
+fn explicit_destination_page(doc: &Document, dest: &Object) -> Option<ObjectId> {
+    let (_, dest) = doc.dereference(dest).ok()?;
+    let dest = match dest {
+        Object::Name(name) | Object::String(name, _) => {
+            let (_, named) = doc.dereference(named_destination(doc, name)?).ok()?;
+            // A named destination is an array, or a dictionary holding it in /D
+            match named {
+                Object::Dictionary(dict) => doc.dereference(dict.get(b"D").ok()?).ok()?.1,
+                other => other,
+            }
+        }
+        other => other,
+    };
+    dest.as_array().ok()?.first()?.as_reference().ok()
+}
+
+/// Look up a named destination in the catalog's `/Dests` dictionary or its
+/// `/Names` tree
+fn named_destination<'a>(doc: &'a Document, name: &[u8]) -> Option<&'a Object> {
+    let catalog = doc.catalog().ok()?;
+    if let Some(dest) = catalog
+        .get(b"Dests")
+        .ok()
+        .and_then(|dests| doc.dereference(dests).ok())
+        .and_then(|(_, dests)| dests.as_dict().ok())
+        .and_then(|dests| dests.get(name).ok())
+    {
+        return Some(dest);
+    }
+
+    let (_, names) = doc.dereference(catalog.get(b"Names").ok()?).ok()?;
+    let (_, tree) = doc
+        .dereference(names.as_dict().ok()?.get(b"Dests").ok()?)
+        .ok()?;
+    name_tree_lookup(doc, tree.as_dict().ok()?, name, MAX_NAME_TREE_DEPTH)
+}
+
+/// Find `name` in a name tree node or its descendants
+fn name_tree_lookup<'a>(
+    doc: &'a Document,
+    node: &'a Dictionary,
+    name: &[u8],
+    depth: usize,
+) -> Option<&'a Object> {
+    if let Ok(names) = node.get(b"Names").and_then(Object::as_array) {
+        return names
+            .chunks_exact(2)
+            .find(|pair| matches!(&pair[0], Object::String(key, _) if key == name))
+            .map(|pair| &pair[1]);
+    }
+
+    let kids = node.get(b"Kids").and_then(Object::as_array).ok()?;
+    kids.iter().find_map(|kid| {
+        let kid = doc.get_dictionary(kid.as_reference().ok()?).ok()?;
+        name_tree_lookup(doc, kid, name, depth.checked_sub(1)?)
+    })
+}
+
+/// Write `bookmarks` as children of `parent`, returning the first and last
+/// item IDs; items with children start closed
+fn write_bookmarks(
+    output: &mut Document,
+    bookmarks: &[Bookmark],
+    parent: ObjectId,
+) -> (ObjectId, ObjectId) {
+    let ids: Vec<ObjectId> = bookmarks.iter().map(|_| output.new_object_id()).collect();
+
+    for (idx, bookmark) in bookmarks.iter().enumerate() {
+        let mut item = Dictionary::from_iter(vec![
+            ("Title", bookmark.title.clone()),
+            ("Parent", Object::Reference(parent)),
+            (
+                "Dest",
+                Object::Array(vec![
+                    Object::Reference(bookmark.page),
+                    Object::Name(b"Fit".to_vec()),
+                ]),
+            ),
+        ]);
+        if idx > 0 {
+            item.set("Prev", Object::Reference(ids[idx - 1]));
+        }
+        if let Some(&next) = ids.get(idx + 1) {
+            item.set("Next", Object::Reference(next));
+        }
+        if !bookmark.children.is_empty() {
+            let (first, last) = write_bookmarks(output, &bookmark.children, ids[idx]);
+            item.set("First", Object::Reference(first));
+            item.set("Last", Object::Reference(last));
+            item.set("Count", Object::Integer(-(bookmark.children.len() as i64)));
+        }
+        output.objects.insert(ids[idx], Object::Dictionary(item));
+    }
+
+    (ids[0], ids[ids.len() - 1])
+}
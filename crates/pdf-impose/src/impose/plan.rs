@@ -0,0 +1,128 @@
+//! Layout-only imposition planning
+//!
+//! Works out where every page lands on every side of every sheet from page
+//! dimensions alone. Rendering then turns the plan into a PDF, but the plan
+//! is also public for callers with renderers of their own.
+
+use super::{decrypt_inputs, prepare_pages, signature, simple};
+use crate::layout::{GridLayout, SheetLayout};
+use crate::options::ImpositionOptions;
+use crate::types::*;
+use lopdf::Document;
+
+/// Every sheet of an imposition, laid out on a common grid
+///
+/// Sheets are in book order, as they come off the press before the output
+/// format reorders their sides.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ImpositionPlan {
+    /// Output sheet (width, height) in points
+    pub sheet_size_pt: (f32, f32),
+    /// The grid every sheet side is divided into
+    pub grid: GridLayout,
+    /// The physical sheets, in book order
+    pub sheets: Vec<PlannedSheet>,
+}
+
+impl ImpositionPlan {
+    /// Every sheet side in order, each sheet's front before its back
+    pub fn sides(&self) -> impl Iterator<Item = &SheetLayout> {
+        self.sheets.iter().flat_map(|sheet| &sheet.sides)
+    }
+}
+
+/// The sides printed on one physical sheet
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlannedSheet {
+    /// The signature the sheet belongs to, for bindings that use signatures
+    pub signature: Option<SheetSignature>,
+    /// Layouts of the sheet's sides, front first
+    pub sides: Vec<SheetLayout>,
+}
+
+/// The signature a sheet belongs to, for the marks on its spine
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SheetSignature {
+    /// 0-based index of the signature in the book
+    pub index: usize,
+    /// Total number of signatures
+    pub count: usize,
+    /// Arrangement of this signature, which may be smaller than the
+    /// configured one for a short last signature
+    pub arrangement: PageArrangement,
+}
+
+/// Plan the sheets for pages of the given dimensions, in book order
+pub(crate) fn plan_sheets(
+    source_dimensions: &[(f32, f32)],
+    options: &ImpositionOptions,
+) -> Result<ImpositionPlan> {
+    if let Some(repeat) = options.step_and_repeat {
+        Ok(simple::plan_step_and_repeat(
+            source_dimensions,
+            options,
+            repeat,
+        ))
+    } else if let BindingType::NUp { rows, cols } = options.binding_type {
+        Ok(simple::plan_n_up(source_dimensions, options, cols, rows))
+    } else if options.binding_type.uses_signatures() {
+        signature::plan_signature_binding(source_dimensions, options)
+    } else if options.binding_type == BindingType::CutAndStack {
+        Ok(simple::plan_cut_and_stack(source_dimensions, options))
+    } else {
+        Ok(simple::plan_simple_binding(source_dimensions, options))
+    }
+}
+
+/// Lay out an imposition without rendering it
+///
+/// `source_page_dims` holds the (width, height) of each page of the book in
+/// reading order, as displayed. The options that edit the page sequence
+/// (input ranges, inserted blanks, flyleaves, cover pages, reversal and
+/// source rotation) work on documents and are not applied here, so the
+/// dimensions should describe the pages after those steps.
+///
+/// Returns the layout of every sheet side in sheet order, each sheet's
+/// front before its back. All geometry is in PDF points with the origin at
+/// the bottom-left corner of the output sheet.
+pub fn plan_imposition(
+    source_page_dims: &[(f32, f32)],
+    options: &ImpositionOptions,
+) -> Result<Vec<SheetLayout>> {
+    options.validate_layout()?;
+    if source_page_dims.is_empty() {
+        return Err(ImposeError::NoPages);
+    }
+
+    let plan = plan_sheets(source_page_dims, options)?;
+    Ok(plan
+        .sheets
+        .into_iter()
+        .flat_map(|sheet| sheet.sides)
+        .collect())
+}
+
+/// Work out the imposition of `documents` without rendering it
+///
+/// Applies every option that edits the page sequence, just as [`impose`]
+/// does, so each placement's `source_page` is a 0-based position in the
+/// book: the selected pages with blanks and flyleaves inserted. A cover
+/// imposed separately is not part of the plan.
+///
+/// [`impose`]: super::impose
+pub async fn plan(documents: &[Document], options: &ImpositionOptions) -> Result<ImpositionPlan> {
+    options.validate()?;
+
+    let documents = documents.to_vec();
+    let options = options.clone();
+
+    tokio::task::spawn_blocking(move || {
+        let documents = decrypt_inputs(documents, options.password.as_deref())?;
+        let pages = prepare_pages(&documents, &options, &|_| Ok(()))?;
+        plan_sheets(&pages.dimensions, &options)
+    })
+    .await?
+}
@@ -1,32 +1,49 @@
 //! Sheet rendering for imposition
 
+use super::plan::{ImpositionPlan, SheetSignature};
+use super::{ImposedOutput, ImposedSheet, PlacedOn, sheet_dimensions_pt};
 use crate::constants::{
-    DEFAULT_PAGE_DIMENSIONS, HELVETICA_CHAR_WIDTH_RATIO, PAGE_NUMBER_FONT_SIZE, PAGE_NUMBER_OFFSET,
+    DEFAULT_PAGE_DIMENSIONS, HELVETICA_CHAR_WIDTH_RATIO, PAGE_NUMBER_OFFSET, mm_to_pt, pt_to_mm,
 };
 use crate::layout::{
-    GridLayout, PagePlacement, SheetLayout, SignatureSlot, calculate_content_area, cell_bounds,
-    place_page,
+    GridLayout, PagePlacement, SheetLayout, SheetSide, SignatureSlot, apply_creep,
+    calculate_content_area, cell_bounds, cell_edge_info, outer_spine_slots, place_page_with_bleed,
+    turn_improves_fit,
+};
+use crate::marks::{
+    BoundEdge, ContentBounds, EdgeSide, FoldDirection, MARKS_FONT, MarksConfig, OuterSpine,
+    SlotLabel, color_bar_origin, generate_cell_borders, generate_marks, marks_color_spaces,
+    marks_use_font,
 };
-use crate::marks::{ContentBounds, MarksConfig, generate_marks};
 use crate::options::ImpositionOptions;
-use crate::render::create_page_xobject;
+use crate::render::{create_page_xobject, create_page_xobjects};
 use crate::types::*;
 use lopdf::{Dictionary, Document, Object, ObjectId, Stream};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 // =============================================================================
 // Placement Calculation
 // =============================================================================
 
 /// Calculate page placements for one side of a sheet
+///
+/// Content is extended by `bleed_pt` past the trim line on trimmed edges.
+/// With `auto_rotate`, pages that fit their cell larger when turned get a
+/// quarter turn. `creep_shifts` holds the creep compensation in points,
+/// indexed by `slot_index`; missing entries mean no shift.
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn calculate_sheet_placements(
     grid: &GridLayout,
     slots: &[&SignatureSlot],
     page_mapping: &[Option<usize>],
     source_dimensions: &[(f32, f32)],
     leaf_margins: &LeafMargins,
+    bleed_pt: f32,
     scaling_mode: ScalingMode,
+    alignment: ContentAlignment,
+    auto_rotate: bool,
     leaf_origin: (f32, f32),
+    creep_shifts: &[f32],
 ) -> Vec<PagePlacement> {
     slots
         .iter()
@@ -38,100 +55,299 @@ pub(crate) fn calculate_sheet_placements(
             let (src_width, src_height) = source_page
                 .and_then(|idx| source_dimensions.get(idx).copied())
                 .unwrap_or(DEFAULT_PAGE_DIMENSIONS);
+            let quarter_turn = auto_rotate
+                && source_page.is_some()
+                && turn_improves_fit(&content_area, src_width, src_height);
+            let (src_width, src_height) = if quarter_turn {
+                (src_height, src_width)
+            } else {
+                (src_width, src_height)
+            };
 
-            let mut placement = place_page(
+            let mut placement = place_page_with_bleed(
                 &content_area,
+                &cell,
                 src_width,
                 src_height,
                 scaling_mode,
+                alignment,
                 slot,
                 grid,
+                bleed_pt,
             );
             placement.source_page = source_page;
+            placement.quarter_turn = quarter_turn;
+            if let Some(&shift) = creep_shifts.get(slot.slot_index) {
+                apply_creep(&mut placement, &cell, shift, grid);
+            }
             placement
         })
         .collect()
 }
 
+/// Source pages among `placements` that got a quarter turn
+pub(crate) fn turned_sources(placements: &[PagePlacement]) -> impl Iterator<Item = usize> + '_ {
+    placements
+        .iter()
+        .filter(|placement| placement.quarter_turn)
+        .filter_map(|placement| placement.source_page)
+}
+
 // =============================================================================
 // Sheet Rendering
 // =============================================================================
 
+/// Form XObjects already created for source pages during one imposition run
+///
+/// Shared by every sheet so a source page that appears on several sheets is
+/// copied into the output once and referenced from each content stream.
+#[derive(Default)]
+pub(crate) struct XObjectCache {
+    /// Source page ID -> Form XObject ID
+    xobjects: HashMap<ObjectId, ObjectId>,
+    /// Source object ID -> copied object ID, for resources shared between pages
+    objects: HashMap<ObjectId, ObjectId>,
+}
+
+impl XObjectCache {
+    /// Return the XObject for `page_id`, creating it on first use
+    fn get_or_create(
+        &mut self,
+        output: &mut Document,
+        source: &Document,
+        page_id: ObjectId,
+        page_box: PageBox,
+    ) -> Result<ObjectId> {
+        if let Some(&xobject_id) = self.xobjects.get(&page_id) {
+            return Ok(xobject_id);
+        }
+        let xobject_id = create_page_xobject(output, source, page_id, page_box, &mut self.objects)?;
+        self.xobjects.insert(page_id, xobject_id);
+        Ok(xobject_id)
+    }
+
+    /// Create the XObjects for `page_ids` that don't exist yet in one batch,
+    /// copying them in parallel
+    fn prepare(
+        &mut self,
+        output: &mut Document,
+        source: &Document,
+        page_ids: &[ObjectId],
+        page_box: PageBox,
+    ) -> Result<()> {
+        let mut seen = HashSet::new();
+        let missing: Vec<ObjectId> = page_ids
+            .iter()
+            .copied()
+            .filter(|page_id| !self.xobjects.contains_key(page_id) && seen.insert(*page_id))
+            .collect();
+        let created = create_page_xobjects(output, source, &missing, page_box, &mut self.objects)?;
+        self.xobjects.extend(missing.into_iter().zip(created));
+        Ok(())
+    }
+}
+
+/// Spine-side edge of every leaf on the sheet
+fn bound_edges(layout: &SheetLayout, grid: &GridLayout) -> Vec<BoundEdge> {
+    let leaf_origin = (layout.leaf_bounds.x, layout.leaf_bounds.y);
+    layout
+        .placements
+        .iter()
+        .filter_map(|placement| {
+            let pos = placement.slot.grid_pos;
+            let edges = cell_edge_info(grid, pos);
+            let side = if edges.is_spine_left() {
+                EdgeSide::Left
+            } else if edges.is_spine_right() {
+                EdgeSide::Right
+            } else if edges.is_spine_top() {
+                EdgeSide::Top
+            } else if edges.is_spine_bottom() {
+                EdgeSide::Bottom
+            } else {
+                return None;
+            };
+            let cell = cell_bounds(grid, pos, leaf_origin);
+            Some(BoundEdge {
+                cell: ContentBounds {
+                    x: cell.x,
+                    y: cell.y,
+                    width: cell.width,
+                    height: cell.height,
+                },
+                side,
+            })
+        })
+        .collect()
+}
+
+/// Every cell on the sheet with the page it holds
+fn slot_labels(layout: &SheetLayout, grid: &GridLayout) -> Vec<SlotLabel> {
+    let leaf_origin = (layout.leaf_bounds.x, layout.leaf_bounds.y);
+    layout
+        .placements
+        .iter()
+        .map(|placement| {
+            let cell = cell_bounds(grid, placement.slot.grid_pos, leaf_origin);
+            SlotLabel {
+                cell: ContentBounds {
+                    x: cell.x,
+                    y: cell.y,
+                    width: cell.width,
+                    height: cell.height,
+                },
+                rotation_degrees: placement.total_rotation_degrees(),
+                page: placement.source_page,
+            }
+        })
+        .collect()
+}
+
 /// Render one side of a sheet to the output document
+///
+/// `signature` is `None` outside signature binding. Back sides are shifted
+/// by the options' back side offset. Source indices in `unnumbered` get no
+/// page number. `helvetica` is the font shared by every text drawn on the
+/// sheet, `None` when the options draw no text.
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn render_sheet(
     output: &mut Document,
     source: &Document,
     source_page_ids: &[ObjectId],
+    unnumbered: &HashSet<usize>,
     layout: &SheetLayout,
     sheet_width_pt: f32,
     sheet_height_pt: f32,
     parent_pages_id: ObjectId,
     grid: &GridLayout,
     options: &ImpositionOptions,
+    xobject_cache: &mut XObjectCache,
+    signature: Option<SheetSignature>,
+    helvetica: Option<ObjectId>,
 ) -> Result<ObjectId> {
     let mut page_dict = create_page_dict(parent_pages_id, sheet_width_pt, sheet_height_pt);
 
     let mut content_ops = Vec::new();
     let mut xobjects = Dictionary::new();
     let mut fonts = Dictionary::new();
-    let mut xobject_cache: HashMap<ObjectId, ObjectId> = HashMap::new();
     let mut content_bounds: Vec<ContentBounds> = Vec::new();
 
-    // Render each page placement
+    // Render each page placement, or its dummy
     for (idx, placement) in layout.placements.iter().enumerate() {
-        if let Some(source_idx) = placement.source_page {
-            if source_idx < source_page_ids.len() {
-                let source_page_id = source_page_ids[source_idx];
-                let xobject_name = format!("P{}", idx);
-
-                // Create XObject
-                let xobject_id =
-                    create_page_xobject(output, source, source_page_id, &mut xobject_cache)?;
-                xobjects.set(xobject_name.as_bytes(), Object::Reference(xobject_id));
-
-                // Generate placement command
-                content_ops.push(generate_placement_cmd(&xobject_name, placement));
-
-                // Record bounds for marks
-                content_bounds.push(ContentBounds {
-                    x: placement.content_rect.x,
-                    y: placement.content_rect.y,
-                    width: placement.content_rect.width,
-                    height: placement.content_rect.height,
-                });
-            }
+        if options.dummy_mode
+            && let Some(source_idx) = placement.source_page
+        {
+            content_ops.push(dummy_page_ops(placement, source_idx, signature));
+            content_bounds.push(ContentBounds {
+                x: placement.trim_rect.x,
+                y: placement.trim_rect.y,
+                width: placement.trim_rect.width,
+                height: placement.trim_rect.height,
+            });
+        } else if let Some(source_idx) = placement.source_page
+            && source_idx < source_page_ids.len()
+        {
+            let source_page_id = source_page_ids[source_idx];
+            let xobject_name = format!("P{}", idx);
+
+            // Create XObject
+            let xobject_id =
+                xobject_cache.get_or_create(output, source, source_page_id, options.source_box)?;
+            xobjects.set(xobject_name.as_bytes(), Object::Reference(xobject_id));
+
+            // Generate placement command
+            content_ops.push(generate_placement_cmd(&xobject_name, placement));
+
+            // Record the trim line for marks; bleed extends past it
+            content_bounds.push(ContentBounds {
+                x: placement.trim_rect.x,
+                y: placement.trim_rect.y,
+                width: placement.trim_rect.width,
+                height: placement.trim_rect.height,
+            });
         }
     }
 
+    // Frame each placed page
+    if options.cell_borders {
+        content_ops.push(generate_cell_borders(&content_bounds));
+    }
+
     // Generate printer's marks
     if options.marks.any_enabled() {
         let marks_config = MarksConfig {
-            cols: grid.cols,
-            rows: grid.rows,
-            cell_width: grid.cell_width_pt,
-            cell_height: grid.cell_height_pt,
+            sheet_width: sheet_width_pt,
+            sheet_height: sheet_height_pt,
+            cell_width: grid.col_pitch_pt(),
+            cell_height: grid.row_pitch_pt(),
+            vertical_folds: grid.vertical_folds.clone(),
+            horizontal_folds: grid.horizontal_folds.clone(),
+            vertical_fold_order: grid.fold_order(true),
+            horizontal_fold_order: grid.fold_order(false),
+            fold_direction: FoldDirection::seen_from(layout.side),
+            vertical_cuts: grid.vertical_cuts.clone(),
+            horizontal_cuts: grid.horizontal_cuts.clone(),
             leaf_left: layout.leaf_bounds.x,
             leaf_bottom: layout.leaf_bounds.y,
             leaf_right: layout.leaf_bounds.right(),
             leaf_top: layout.leaf_bounds.top(),
+            trim_box: ContentBounds::trim_box(
+                sheet_width_pt,
+                sheet_height_pt,
+                mm_to_pt(options.bleed_mm),
+            ),
             content_bounds,
+            outer_spine: signature
+                .and_then(|signature| outer_spine(layout, grid, options, signature)),
+            bound_edges: if options.marks.punch_guides.is_some() {
+                bound_edges(layout, grid)
+            } else {
+                Vec::new()
+            },
+            slot_labels: if options.marks.slot_labels {
+                slot_labels(layout, grid)
+            } else {
+                Vec::new()
+            },
         };
         content_ops.push(generate_marks(&options.marks, &marks_config));
+        if marks_use_font(&options.marks)
+            && let Some(font_id) = helvetica
+        {
+            fonts.set(MARKS_FONT, Object::Reference(font_id));
+        }
     }
 
     // Add page numbers
-    if options.add_page_numbers {
-        let (font_ops, font_id) = render_page_numbers(output, layout, grid, options);
-        content_ops.push(font_ops);
+    if options.add_page_numbers
+        && let Some(font_id) = helvetica
+    {
+        content_ops.push(render_page_numbers(layout, grid, options, unnumbered));
         fonts.set("F1", Object::Reference(font_id));
     }
 
+    if options.dummy_mode
+        && let Some(font_id) = helvetica
+    {
+        fonts.set(DUMMY_FONT, Object::Reference(font_id));
+    }
+
     // Build resources
     let mut resources = Dictionary::new();
     resources.set("XObject", Object::Dictionary(xobjects));
     if !fonts.is_empty() {
         resources.set("Font", Object::Dictionary(fonts));
     }
+    if let Some(color_spaces) = marks_color_spaces(&options.marks) {
+        resources.set("ColorSpace", Object::Dictionary(color_spaces));
+    }
+
+    // Shift the whole back side to compensate for duplex feed skew
+    let (offset_x, offset_y) = side_offset_pt(layout, options);
+    if offset_x != 0.0 || offset_y != 0.0 {
+        content_ops.insert(0, format!("q 1 0 0 1 {offset_x} {offset_y} cm\n"));
+        content_ops.push("Q\n".to_string());
+    }
 
     // Create content stream
     let content = content_ops.join("");
@@ -143,10 +359,210 @@ pub(crate) fn render_sheet(
     Ok(output.add_object(page_dict))
 }
 
+/// Render every sheet of a plan into a new document
+///
+/// `source_page_ids` are the pages the plan's source indices refer to, and
+/// `unnumbered` the source indices left without a page number. `report`
+/// hears about each output page as it is finished, and stops the rendering
+/// by returning an error.
+pub(crate) fn render_plan(
+    source: &Document,
+    source_page_ids: &[ObjectId],
+    unnumbered: &HashSet<usize>,
+    plan: &ImpositionPlan,
+    options: &ImpositionOptions,
+    report: &dyn Fn(ImposeProgress) -> Result<()>,
+) -> Result<ImposedOutput> {
+    let (sheet_width_pt, sheet_height_pt) = sheet_dimensions_pt(options);
+
+    let mut output = Document::with_version("1.7");
+    let pages_tree_id = output.new_object_id();
+    let mut page_refs = Vec::new();
+    let mut sheets = Vec::new();
+    let mut turned_pages = Vec::new();
+    let mut placed_on = HashMap::new();
+    let mut xobject_cache = XObjectCache::default();
+    // One font object serves every sheet's marks, page numbers and dummies
+    let helvetica =
+        (marks_use_font(&options.marks) || options.add_page_numbers || options.dummy_mode)
+            .then(|| helvetica_font(&mut output));
+    let total = plan.sheets.iter().map(|sheet| sheet.sides.len()).sum();
+
+    if options.marks.color_bars
+        && plan.sides().any(|layout| {
+            color_bar_origin(sheet_width_pt, layout.leaf_bounds.y, &options.marks.style).is_none()
+        })
+    {
+        report(ImposeProgress::Warning(ImposeWarning::NoRoomForColorBars))?;
+    }
+
+    // Marks around the leaf area need the narrowest sheet margin to hold them
+    let needed_mm = options.marks.min_sheet_margin_mm();
+    let margin_pt = plan
+        .sides()
+        .map(|layout| {
+            let leaf = &layout.leaf_bounds;
+            leaf.x
+                .min(leaf.y)
+                .min(sheet_width_pt - leaf.right())
+                .min(sheet_height_pt - leaf.top())
+        })
+        .fold(f32::INFINITY, f32::min);
+    let margin_mm = pt_to_mm(margin_pt);
+    if margin_mm < needed_mm - 0.01 {
+        report(ImposeProgress::Warning(
+            ImposeWarning::MarksExceedSheetMargin {
+                margin_mm,
+                needed_mm,
+            },
+        ))?;
+    }
+
+    // Copying the source pages is the bulk of the work, so it is done up
+    // front for every page the plan places, and in parallel; dummies copy
+    // nothing
+    if !options.dummy_mode {
+        let placed_ids: Vec<ObjectId> = plan
+            .sides()
+            .flat_map(|layout| &layout.placements)
+            .filter_map(|placement| source_page_ids.get(placement.source_page?).copied())
+            .collect();
+        xobject_cache.prepare(&mut output, source, &placed_ids, options.source_box)?;
+    }
+
+    for (sheet_idx, planned) in plan.sheets.iter().enumerate() {
+        let mut sheet_pages = Vec::with_capacity(planned.sides.len());
+        for layout in &planned.sides {
+            turned_pages.extend(turned_sources(&layout.placements));
+            let page_id = render_sheet(
+                &mut output,
+                source,
+                source_page_ids,
+                unnumbered,
+                layout,
+                sheet_width_pt,
+                sheet_height_pt,
+                pages_tree_id,
+                &plan.grid,
+                options,
+                &mut xobject_cache,
+                planned.signature,
+                helvetica,
+            )?;
+            for placement in &layout.placements {
+                let Some(source_idx) = placement.source_page else {
+                    continue;
+                };
+                let position = placement.slot.grid_pos;
+                placed_on.entry(source_idx).or_insert(PlacedOn {
+                    page: page_id,
+                    sheet: sheet_idx,
+                    side: (planned.sides.len() > 1).then_some(layout.side),
+                    cell: position.row * plan.grid.cols + position.col,
+                });
+            }
+            page_refs.push(Object::Reference(page_id));
+            sheet_pages.push(page_id);
+            report(ImposeProgress::Rendering {
+                current: page_refs.len(),
+                total,
+            })?;
+        }
+
+        sheets.push(ImposedSheet {
+            signature: planned.signature.map(|signature| signature.index),
+            pages: sheet_pages,
+        });
+    }
+
+    finalize_document(&mut output, pages_tree_id, page_refs);
+    Ok(ImposedOutput {
+        document: output,
+        sheets,
+        turned_pages,
+        placed_on,
+        cover: None,
+    })
+}
+
+/// Create pages tree and catalog, finalize document structure
+pub(super) fn finalize_document(
+    output: &mut Document,
+    pages_tree_id: ObjectId,
+    page_refs: Vec<Object>,
+) {
+    let count = page_refs.len() as i64;
+    let pages_dict = Dictionary::from_iter(vec![
+        ("Type", Object::Name(b"Pages".to_vec())),
+        ("Kids", Object::Array(page_refs)),
+        ("Count", Object::Integer(count)),
+    ]);
+    output
+        .objects
+        .insert(pages_tree_id, Object::Dictionary(pages_dict));
+
+    let catalog_id = output.add_object(Dictionary::from_iter(vec![
+        ("Type", Object::Name(b"Catalog".to_vec())),
+        ("Pages", Object::Reference(pages_tree_id)),
+    ]));
+
+    output.trailer.set("Root", catalog_id);
+}
+
 // =============================================================================
 // Helper Functions
 // =============================================================================
 
+/// Locate the spine fold on the outside of the signature
+///
+/// The spine runs between the first and last pages of the signature. Returns
+/// `None` when this side doesn't carry both pages or they don't sit side by
+/// side in the same row.
+fn outer_spine(
+    layout: &SheetLayout,
+    grid: &GridLayout,
+    options: &ImpositionOptions,
+    signature: SheetSignature,
+) -> Option<OuterSpine> {
+    let (first_slot, last_slot) = outer_spine_slots(signature.arrangement, options.binding_edge);
+    let find = |slot_index| {
+        layout
+            .placements
+            .iter()
+            .find(|p| p.slot.slot_index == slot_index && p.slot.sheet_side == layout.side)
+    };
+    let (first, last) = (find(first_slot)?, find(last_slot)?);
+    let (first_pos, last_pos) = (first.slot.grid_pos, last.slot.grid_pos);
+    if first_pos.row != last_pos.row || first_pos.col.abs_diff(last_pos.col) != 1 {
+        return None;
+    }
+
+    let cell = cell_bounds(
+        grid,
+        first_pos,
+        (layout.leaf_bounds.x, layout.leaf_bounds.y),
+    );
+    let spine_x = if last_pos.col < first_pos.col {
+        cell.x
+    } else {
+        cell.right()
+    };
+    // Rotated pages have their head at the bottom of the cell
+    let (head_y, tail_y) = if first.is_rotated() {
+        (cell.y, cell.top())
+    } else {
+        (cell.top(), cell.y)
+    };
+
+    Some(OuterSpine {
+        signature_index: signature.index,
+        signature_count: signature.count,
+        spine_x,
+        head_y,
+        tail_y,
+    })
+}
+
 /// Create a basic page dictionary
 fn create_page_dict(parent_id: ObjectId, width: f32, height: f32) -> Dictionary {
     let mut dict = Dictionary::new();
@@ -166,72 +582,196 @@ fn create_page_dict(parent_id: ObjectId, width: f32, height: f32) -> Dictionary
 
 /// Generate PDF command to place an XObject
 fn generate_placement_cmd(xobject_name: &str, placement: &PagePlacement) -> String {
+    let [a, b, c, d, e, f] = placement_matrix(placement);
+    format!("q {a} {b} {c} {d} {e} {f} cm /{xobject_name} Do Q\n")
+}
+
+/// The matrix that places a page's upright form in its content rect
+pub(super) fn placement_matrix(placement: &PagePlacement) -> [f32; 6] {
     let rect = &placement.content_rect;
+    let (scale_x, scale_y) = placement.scale;
 
-    if placement.is_rotated() {
-        // 180° rotation: matrix is [-scale 0 0 -scale tx ty]
-        let rot_x = rect.x + rect.width;
-        let rot_y = rect.y + rect.height;
-        format!(
-            "q {} 0 0 {} {} {} cm /{} Do Q\n",
-            -placement.scale, -placement.scale, rot_x, rot_y, xobject_name
-        )
+    // Turned pages have their source width along the cell's height, so the
+    // scale for each output axis stays with that axis
+    match placement.total_rotation_degrees() as i32 {
+        90 => [0.0, -scale_y, scale_x, 0.0, rect.x, rect.top()],
+        180 => [-scale_x, 0.0, 0.0, -scale_y, rect.right(), rect.top()],
+        270 => [0.0, scale_y, -scale_x, 0.0, rect.right(), rect.y],
+        _ => [scale_x, 0.0, 0.0, scale_y, rect.x, rect.y],
+    }
+}
+
+/// How far a sheet side is shifted, in points; back sides move by the
+/// options' back side offset to compensate for duplex feed skew
+pub(super) fn side_offset_pt(layout: &SheetLayout, options: &ImpositionOptions) -> (f32, f32) {
+    let (offset_x, offset_y) = options.back_side_offset_mm;
+    if layout.side == SheetSide::Back {
+        (mm_to_pt(offset_x), mm_to_pt(offset_y))
     } else {
+        (0.0, 0.0)
+    }
+}
+
+/// Add a Helvetica font to `output`
+fn helvetica_font(output: &mut Document) -> ObjectId {
+    let mut font_dict = Dictionary::new();
+    font_dict.set("Type", Object::Name(b"Font".to_vec()));
+    font_dict.set("Subtype", Object::Name(b"Type1".to_vec()));
+    font_dict.set("BaseFont", Object::Name(b"Helvetica".to_vec()));
+    output.add_object(font_dict)
+}
+
+/// Resource name of the font dummy pages are labelled in
+const DUMMY_FONT: &str = "FD";
+
+/// Draw a dummy page in place of source page `source_idx`
+///
+/// The page shows its 1-based book page number, its signature and an arrow
+/// pointing to its head, drawn in the page's upright frame so they turn with
+/// the slot.
+fn dummy_page_ops(
+    placement: &PagePlacement,
+    source_idx: usize,
+    signature: Option<SheetSignature>,
+) -> String {
+    let [a, b, c, d, e, f] = placement_matrix(placement);
+    let (scale_x, scale_y) = placement.scale;
+    let rect = &placement.content_rect;
+    let (width, height) = match placement.total_rotation_degrees() as i32 {
+        90 | 270 => (rect.height / scale_y, rect.width / scale_x),
+        _ => (rect.width / scale_x, rect.height / scale_y),
+    };
+    let center_x = width / 2.0;
+    let text_at = |text: &str, size: f32, y: f32| {
+        let text_width = text.len() as f32 * size * HELVETICA_CHAR_WIDTH_RATIO;
         format!(
-            "q {} 0 0 {} {} {} cm /{} Do Q\n",
-            placement.scale, placement.scale, rect.x, rect.y, xobject_name
+            "BT /{DUMMY_FONT} {size} Tf {} {y} Td ({text}) Tj ET\n",
+            center_x - text_width / 2.0
         )
+    };
+
+    let mut ops = format!("q {a} {b} {c} {d} {e} {f} cm\n");
+
+    // Frame the page so the dummy shows where it will be trimmed
+    ops.push_str(&format!("0.5 w 0 0 {width} {height} re S\n"));
+
+    let number_size = height.min(width) / 3.0;
+    let number = (source_idx + 1).to_string();
+    ops.push_str(&text_at(&number, number_size, height * 0.4));
+    if let Some(signature) = signature {
+        let label = format!("Sig {}", signature.index + 1);
+        ops.push_str(&text_at(&label, number_size / 4.0, height * 0.25));
     }
+
+    // Up arrow toward the head of the page
+    let (tip, base, head) = (height * 0.9, height * 0.72, width * 0.06);
+    ops.push_str(&format!(
+        "2 w {center_x} {base} m {center_x} {tip} l S \
+         {} {} m {center_x} {tip} l {} {} l S\n",
+        center_x - head,
+        tip - head,
+        center_x + head,
+        tip - head
+    ));
+
+    ops.push_str("Q\n");
+    ops
 }
 
-/// Render page numbers and return (content ops, font object id)
+/// Render page numbers and return their content ops
+///
+/// Each non-blank page outside `unnumbered` gets its book page number,
+/// counted from `page_number_start` in reading order, placed in the head or
+/// tail margin of its leaf per the page number options. Positions are worked
+/// out in the page's own upright frame, so pages in rotated cells get rotated
+/// numbers that read upright, and on the correct side, once the sheet is
+/// folded.
 fn render_page_numbers(
-    output: &mut Document,
     layout: &SheetLayout,
     grid: &GridLayout,
     options: &ImpositionOptions,
-) -> (String, ObjectId) {
-    // Create font
-    let mut font_dict = Dictionary::new();
-    font_dict.set("Type", Object::Name(b"Font".to_vec()));
-    font_dict.set("Subtype", Object::Name(b"Type1".to_vec()));
-    font_dict.set("BaseFont", Object::Name(b"Helvetica".to_vec()));
-    let font_id = output.add_object(font_dict);
-
+    unnumbered: &HashSet<usize>,
+) -> String {
+    let font_size = options.page_number_font_size;
+    let leaf_origin = (layout.leaf_bounds.x, layout.leaf_bounds.y);
     let mut ops = String::new();
 
-    for placement in &layout.placements {
-        if let Some(source_idx) = placement.source_page {
-            let page_num = options.page_number_start + source_idx;
-            let page_num_text = page_num.to_string();
-
-            // Calculate cell position
-            let cell_x =
-                layout.leaf_bounds.x + placement.slot.grid_pos.col as f32 * grid.cell_width_pt;
-            let cell_y = layout.leaf_bounds.y
-                + (grid.rows - placement.slot.grid_pos.row - 1) as f32 * grid.cell_height_pt;
-
-            if placement.is_rotated() {
-                // Rotated: position at top (appears at bottom after rotation)
-                let text_x = cell_x + grid.cell_width_pt / 2.0;
-                let text_y = cell_y + grid.cell_height_pt - PAGE_NUMBER_OFFSET;
-                ops.push_str(&format!(
-                    "q 1 0 0 1 {} {} cm -1 0 0 -1 0 0 cm BT /F1 {} Tf ({}) Tj ET Q\n",
-                    text_x, text_y, PAGE_NUMBER_FONT_SIZE, page_num_text
-                ));
-            } else {
-                // Normal: position at bottom center
-                let text_width =
-                    page_num_text.len() as f32 * PAGE_NUMBER_FONT_SIZE * HELVETICA_CHAR_WIDTH_RATIO;
-                let text_x = cell_x + grid.cell_width_pt / 2.0 - text_width / 2.0;
-                let text_y = cell_y + PAGE_NUMBER_OFFSET;
-                ops.push_str(&format!(
-                    "BT /F1 {} Tf {} {} Td ({}) Tj ET\n",
-                    PAGE_NUMBER_FONT_SIZE, text_x, text_y, page_num_text
-                ));
+    for placement in layout.non_blank_placements() {
+        let Some(source_idx) = placement.source_page else {
+            continue;
+        };
+        if unnumbered.contains(&source_idx) {
+            continue;
+        }
+        let page_num_text = (options.page_number_start + source_idx).to_string();
+        let text_width = page_num_text.len() as f32 * font_size * HELVETICA_CHAR_WIDTH_RATIO;
+
+        let cell = cell_bounds(grid, placement.slot.grid_pos, leaf_origin);
+        let content = calculate_content_area(&cell, &options.margins.leaf, &placement.slot, grid);
+
+        // Margins around the content as seen on the upright page
+        let (left, bottom, right, top) = (
+            content.x - cell.x,
+            content.y - cell.y,
+            cell.right() - content.right(),
+            cell.top() - content.top(),
+        );
+        let (left, bottom, right, top) = if placement.is_rotated() {
+            (right, top, left, bottom)
+        } else {
+            (left, bottom, right, top)
+        };
+
+        // Rectos have the fore-edge on the right unless the book binds on the right
+        let fore_edge_right =
+            placement.slot.page_side.is_recto() == (options.binding_edge == BindingEdge::Left);
+        let local_x = match (options.page_number_horizontal, fore_edge_right) {
+            (PageNumberHorizontal::Center, _) => {
+                (left + cell.width - right) / 2.0 - text_width / 2.0
             }
+            (PageNumberHorizontal::Outer, true) | (PageNumberHorizontal::Inner, false) => {
+                cell.width - right - text_width
+            }
+            (PageNumberHorizontal::Outer, false) | (PageNumberHorizontal::Inner, true) => left,
+        };
+        let local_y = match options.page_number_vertical {
+            PageNumberVertical::Footer => margin_baseline_offset(bottom, font_size),
+            PageNumberVertical::Header => {
+                cell.height - margin_baseline_offset(top, font_size) - font_size
+            }
+        };
+
+        if placement.is_rotated() {
+            // Turn the text with the page, about the cell's center
+            ops.push_str(&format!(
+                "q -1 0 0 -1 {} {} cm BT /F1 {} Tf 0 0 Td ({}) Tj ET Q\n",
+                cell.right() - local_x,
+                cell.top() - local_y,
+                font_size,
+                page_num_text
+            ));
+        } else {
+            ops.push_str(&format!(
+                "BT /F1 {} Tf {} {} Td ({}) Tj ET\n",
+                font_size,
+                cell.x + local_x,
+                cell.y + local_y,
+                page_num_text
+            ));
         }
     }
 
-    (ops, font_id)
+    ops
+}
+
+/// Distance from the cell edge to the near side of a page number's text
+///
+/// The number is centered in the margin when it fits there; otherwise it sits
+/// a fixed offset from the edge, over the page content.
+fn margin_baseline_offset(margin: f32, font_size: f32) -> f32 {
+    if margin >= font_size {
+        (margin - font_size) / 2.0
+    } else {
+        PAGE_NUMBER_OFFSET
+    }
 }
@@ -1,35 +1,27 @@
-//! Signature binding imposition (folded sheets)
+//! Signature binding layout (folded sheets)
 
-use super::sheet::{calculate_sheet_placements, render_sheet};
-use super::sheet_dimensions_pt;
+use super::plan::{ImpositionPlan, PlannedSheet, SheetSignature};
+use super::sheet::calculate_sheet_placements;
+use super::{calculate_leaf_bounds, sheet_dimensions_pt};
 use crate::constants::mm_to_pt;
 use crate::layout::{
-    Rect, SheetLayout, SheetSide, calculate_signature_slots, create_grid_layout, map_pages_to_slots,
+    SheetLayout, SheetSide, calculate_signature_slots, create_grid_layout, creep_steps,
+    map_pages_to_slots, turn_back_slots,
 };
 use crate::options::ImpositionOptions;
-use crate::render::get_page_dimensions;
 use crate::types::*;
-use lopdf::{Dictionary, Document, Object, ObjectId};
 
-/// Impose using signature binding (folded sheets)
-pub(crate) fn impose_signature_binding(
-    source: &Document,
-    page_ids: &[ObjectId],
+/// Plan signature binding (folded sheets)
+pub(crate) fn plan_signature_binding(
+    source_dimensions: &[(f32, f32)],
     options: &ImpositionOptions,
-) -> Result<Document> {
-    let total_pages = page_ids.len();
-
-    // Get source page dimensions
-    let source_dimensions: Vec<(f32, f32)> = page_ids
-        .iter()
-        .map(|&id| {
-            get_page_dimensions(source, id).unwrap_or(crate::constants::DEFAULT_PAGE_DIMENSIONS)
-        })
-        .collect();
+) -> Result<ImpositionPlan> {
+    let total_pages = source_dimensions.len();
 
     // Calculate output dimensions and leaf area
     let (output_width_pt, output_height_pt) = sheet_dimensions_pt(options);
-    let leaf_bounds = calculate_leaf_bounds(options, output_width_pt, output_height_pt);
+    let leaf_bounds =
+        calculate_leaf_bounds(options, output_width_pt, output_height_pt, SheetSide::Front);
 
     // Create grid layout
     let grid = create_grid_layout(
@@ -38,128 +30,97 @@ pub(crate) fn impose_signature_binding(
         leaf_bounds.height,
         output_width_pt,
         output_height_pt,
+        options.spine_edge,
     );
 
-    // Calculate signature slots
-    let signatures = calculate_signature_slots(total_pages, options.page_arrangement);
+    // Calculate signature slots, and the arrangement each signature uses
+    let plan = options.signature_arrangements(total_pages)?;
+    let mut signatures =
+        calculate_signature_slots(&plan, options.page_arrangement, options.binding_edge);
+    if options.duplex_flip == FlipEdge::ShortEdge {
+        for sig_slots in &mut signatures {
+            turn_back_slots(sig_slots, grid.cols, grid.rows);
+        }
+    }
+    let creep_pt = mm_to_pt(options.creep_mm);
 
-    // Build output document
-    let mut output = Document::with_version("1.7");
-    let pages_tree_id = output.new_object_id();
-    let mut page_refs = Vec::new();
+    let mut sheets = Vec::new();
 
     // Process each signature
-    for (sig_num, sig_slots) in signatures.iter().enumerate() {
-        let sig_start = sig_num * options.page_arrangement.pages_per_signature();
+    let mut sig_start = 0;
+    for (sig_num, (sig_slots, &sig_arrangement)) in signatures.iter().zip(&plan).enumerate() {
+        let signature = SheetSignature {
+            index: sig_num,
+            count: signatures.len(),
+            arrangement: sig_arrangement,
+        };
 
         // Map source pages to slots
-        let page_mapping = map_pages_to_slots(options.page_arrangement, sig_start, total_pages);
-
-        // Split slots by sheet side
-        let front_slots: Vec<_> = sig_slots
-            .iter()
-            .filter(|s| s.sheet_side == SheetSide::Front)
-            .collect();
-        let back_slots: Vec<_> = sig_slots
-            .iter()
-            .filter(|s| s.sheet_side == SheetSide::Back)
-            .collect();
-
-        // Render front side
-        let front_placements = calculate_sheet_placements(
-            &grid,
-            &front_slots,
-            &page_mapping[..front_slots.len()],
-            &source_dimensions,
-            &options.margins.leaf,
-            options.scaling_mode,
-            (leaf_bounds.x, leaf_bounds.y),
+        let page_mapping = map_pages_to_slots(
+            sig_arrangement,
+            options.binding_edge,
+            sig_start,
+            total_pages,
         );
+        sig_start += sig_arrangement.pages_per_signature();
 
-        let front_layout = SheetLayout {
-            side: SheetSide::Front,
-            placements: front_placements,
-            leaf_bounds,
-        };
-
-        let front_page_id = render_sheet(
-            &mut output,
-            source,
-            page_ids,
-            &front_layout,
-            output_width_pt,
-            output_height_pt,
-            pages_tree_id,
-            &grid,
-            options,
-        )?;
-        page_refs.push(Object::Reference(front_page_id));
-
-        // Render back side
-        if !back_slots.is_empty() {
-            let back_placements = calculate_sheet_placements(
-                &grid,
-                &back_slots,
-                &page_mapping[front_slots.len()..],
-                &source_dimensions,
-                &options.margins.leaf,
-                options.scaling_mode,
-                (leaf_bounds.x, leaf_bounds.y),
-            );
-
-            let back_layout = SheetLayout {
-                side: SheetSide::Back,
-                placements: back_placements,
-                leaf_bounds,
-            };
+        // Creep compensation per slot
+        let creep_shifts: Vec<f32> = creep_steps(sig_arrangement, options.binding_edge)
+            .into_iter()
+            .map(|steps| steps as f32 * creep_pt)
+            .collect();
 
-            let back_page_id = render_sheet(
-                &mut output,
-                source,
-                page_ids,
-                &back_layout,
-                output_width_pt,
-                output_height_pt,
-                pages_tree_id,
-                &grid,
-                options,
-            )?;
-            page_refs.push(Object::Reference(back_page_id));
+        // Each physical sheet holds a full grid of slots on each side;
+        // custom signatures nest several folio sheets
+        let slots_per_sheet = sig_slots.len() / sig_arrangement.sheets_per_signature();
+        for (sheet_slots, sheet_mapping) in sig_slots
+            .chunks(slots_per_sheet)
+            .zip(page_mapping.chunks(slots_per_sheet))
+        {
+            let mut sides = Vec::new();
+            for side in [SheetSide::Front, SheetSide::Back] {
+                let (side_slots, side_mapping): (Vec<_>, Vec<_>) = sheet_slots
+                    .iter()
+                    .zip(sheet_mapping)
+                    .filter(|(slot, _)| slot.sheet_side == side)
+                    .unzip();
+                if side_slots.is_empty() {
+                    continue;
+                }
+                let side_bounds =
+                    calculate_leaf_bounds(options, output_width_pt, output_height_pt, side);
+
+                let placements = calculate_sheet_placements(
+                    &grid,
+                    &side_slots,
+                    &side_mapping,
+                    source_dimensions,
+                    &options.margins.leaf,
+                    mm_to_pt(options.bleed_mm),
+                    options.scaling_mode,
+                    options.content_alignment,
+                    options.auto_rotate_to_fit,
+                    (side_bounds.x, side_bounds.y),
+                    &creep_shifts,
+                );
+
+                sides.push(SheetLayout {
+                    side,
+                    placements,
+                    leaf_bounds: side_bounds,
+                });
+            }
+
+            sheets.push(PlannedSheet {
+                signature: Some(signature),
+                sides,
+            });
         }
     }
 
-    // Finalize document
-    finalize_document(&mut output, pages_tree_id, page_refs);
-    Ok(output)
-}
-
-/// Calculate the leaf area bounds (inside sheet margins)
-fn calculate_leaf_bounds(options: &ImpositionOptions, width_pt: f32, height_pt: f32) -> Rect {
-    let margins = &options.margins.sheet;
-    Rect::new(
-        mm_to_pt(margins.left_mm),
-        mm_to_pt(margins.bottom_mm),
-        width_pt - mm_to_pt(margins.left_mm) - mm_to_pt(margins.right_mm),
-        height_pt - mm_to_pt(margins.top_mm) - mm_to_pt(margins.bottom_mm),
-    )
-}
-
-/// Create pages tree and catalog, finalize document structure
-fn finalize_document(output: &mut Document, pages_tree_id: ObjectId, page_refs: Vec<Object>) {
-    let count = page_refs.len() as i64;
-    let pages_dict = Dictionary::from_iter(vec![
-        ("Type", Object::Name(b"Pages".to_vec())),
-        ("Kids", Object::Array(page_refs)),
-        ("Count", Object::Integer(count)),
-    ]);
-    output
-        .objects
-        .insert(pages_tree_id, Object::Dictionary(pages_dict));
-
-    let catalog_id = output.add_object(Dictionary::from_iter(vec![
-        ("Type", Object::Name(b"Catalog".to_vec())),
-        ("Pages", Object::Reference(pages_tree_id)),
-    ]));
-
-    output.trailer.set("Root", catalog_id);
+    Ok(ImpositionPlan {
+        sheet_size_pt: (output_width_pt, output_height_pt),
+        grid,
+        sheets,
+    })
 }
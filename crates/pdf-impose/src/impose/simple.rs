@@ -1,35 +1,32 @@
-//! Simple 2-up binding imposition (perfect binding, side stitch, spiral)
+//! Simple 2-up binding layout (perfect binding, side stitch, spiral)
+//! plus cut-and-stack, step-and-repeat and N-up layout
 
-use super::sheet::{calculate_sheet_placements, render_sheet};
-use super::sheet_dimensions_pt;
+use super::plan::{ImpositionPlan, PlannedSheet};
+use super::sheet::calculate_sheet_placements;
+use super::{calculate_leaf_bounds, sheet_dimensions_pt};
 use crate::constants::mm_to_pt;
-use crate::layout::{PageSide, Rect, SheetLayout, SheetSide, SignatureSlot, create_grid_layout};
+use crate::layout::{
+    PageSide, SheetLayout, SheetSide, SignatureSlot, create_cut_grid_layout, create_grid_layout,
+    cut_and_stack_sheet_count, cut_and_stack_slots, map_cut_and_stack_pages, slots_for_side,
+    turn_back_slots,
+};
 use crate::options::ImpositionOptions;
-use crate::render::get_page_dimensions;
 use crate::types::*;
-use lopdf::{Dictionary, Document, Object, ObjectId};
 
-/// Impose using simple 2-up binding (perfect binding, side stitch, spiral)
+/// Plan simple 2-up binding (perfect binding, side stitch, spiral)
 ///
-/// Each output page has 2 source pages side by side.
-pub(crate) fn impose_simple_binding(
-    source: &Document,
-    page_ids: &[ObjectId],
+/// Each output page has 2 source pages side by side. Every second page is
+/// the back of the one before when printed duplex.
+pub(crate) fn plan_simple_binding(
+    source_dimensions: &[(f32, f32)],
     options: &ImpositionOptions,
-) -> Result<Document> {
-    let total_pages = page_ids.len();
-
-    // Get source page dimensions
-    let source_dimensions: Vec<(f32, f32)> = page_ids
-        .iter()
-        .map(|&id| {
-            get_page_dimensions(source, id).unwrap_or(crate::constants::DEFAULT_PAGE_DIMENSIONS)
-        })
-        .collect();
+) -> ImpositionPlan {
+    let total_pages = source_dimensions.len();
 
     // Calculate output dimensions and leaf area
     let (output_width_pt, output_height_pt) = sheet_dimensions_pt(options);
-    let leaf_bounds = calculate_leaf_bounds(options, output_width_pt, output_height_pt);
+    let leaf_bounds =
+        calculate_leaf_bounds(options, output_width_pt, output_height_pt, SheetSide::Front);
 
     // Simple 2-up grid (use folio layout)
     let grid = create_grid_layout(
@@ -38,18 +35,16 @@ pub(crate) fn impose_simple_binding(
         leaf_bounds.height,
         output_width_pt,
         output_height_pt,
+        options.spine_edge,
     );
 
-    // Build output document
-    let mut output = Document::with_version("1.7");
-    let pages_tree_id = output.new_object_id();
-    let mut page_refs = Vec::new();
+    let mut sheets = Vec::new();
 
     // Pad to even number
-    let padded_count = (total_pages + 1) / 2 * 2;
+    let padded_count = total_pages.div_ceil(2) * 2;
 
     // Process pages in pairs
-    for chunk_start in (0..padded_count).step_by(2) {
+    for (sheet_idx, chunk_start) in (0..padded_count).step_by(2).enumerate() {
         let left_page = if chunk_start < total_pages {
             Some(chunk_start)
         } else {
@@ -61,21 +56,178 @@ pub(crate) fn impose_simple_binding(
             None
         };
 
-        // Create simple slots for 2-up layout
-        let left_slot = SignatureSlot::new(0, SheetSide::Front, 0, 0, false, PageSide::Verso);
-        let right_slot = SignatureSlot::new(1, SheetSide::Front, 0, 1, false, PageSide::Recto);
+        let side = if sheet_idx.is_multiple_of(2) {
+            SheetSide::Front
+        } else {
+            SheetSide::Back
+        };
+
+        // Create simple slots for 2-up layout, turned on backs for
+        // short-edge duplex
+        let mut left_slot = SignatureSlot::new(0, SheetSide::Front, 0, 0, false, PageSide::Verso);
+        let mut right_slot = SignatureSlot::new(1, SheetSide::Front, 0, 1, false, PageSide::Recto);
+        if side == SheetSide::Back && options.duplex_flip == FlipEdge::ShortEdge {
+            left_slot.turn_half(grid.cols, grid.rows);
+            right_slot.turn_half(grid.cols, grid.rows);
+        }
 
         let slots = vec![&left_slot, &right_slot];
         let page_mapping = vec![left_page, right_page];
+        let side_bounds = calculate_leaf_bounds(options, output_width_pt, output_height_pt, side);
 
         let placements = calculate_sheet_placements(
             &grid,
             &slots,
             &page_mapping,
-            &source_dimensions,
+            source_dimensions,
+            &options.margins.leaf,
+            mm_to_pt(options.bleed_mm),
+            options.scaling_mode,
+            options.content_alignment,
+            options.auto_rotate_to_fit,
+            (side_bounds.x, side_bounds.y),
+            &[],
+        );
+
+        let layout = SheetLayout {
+            side,
+            placements,
+            leaf_bounds: side_bounds,
+        };
+
+        sheets.push(PlannedSheet {
+            signature: None,
+            sides: vec![layout],
+        });
+    }
+
+    ImpositionPlan {
+        sheet_size_pt: (output_width_pt, output_height_pt),
+        grid,
+        sheets,
+    }
+}
+
+/// Plan cut and stack (2-up, 4-up or 8-up, duplex)
+///
+/// Every cell position on the sheet is its own stack of leaves. After the
+/// printed pile is cut apart, the stacks are laid one on another in order
+/// to make up the book block.
+pub(crate) fn plan_cut_and_stack(
+    source_dimensions: &[(f32, f32)],
+    options: &ImpositionOptions,
+) -> ImpositionPlan {
+    let total_pages = source_dimensions.len();
+    let arrangement = options.page_arrangement;
+
+    // Calculate output dimensions and leaf area
+    let (output_width_pt, output_height_pt) = sheet_dimensions_pt(options);
+    let leaf_bounds =
+        calculate_leaf_bounds(options, output_width_pt, output_height_pt, SheetSide::Front);
+    let (cols, rows) = arrangement.grid_dimensions();
+    let grid = create_cut_grid_layout(
+        cols,
+        rows,
+        leaf_bounds.width,
+        leaf_bounds.height,
+        mm_to_pt(options.cell_gap_mm),
+    );
+
+    let mut slots = cut_and_stack_slots(arrangement);
+    if options.duplex_flip == FlipEdge::ShortEdge {
+        turn_back_slots(&mut slots, cols, rows);
+    }
+    let front_slots = slots_for_side(&slots, SheetSide::Front);
+    let back_slots = slots_for_side(&slots, SheetSide::Back);
+    let sheet_count = cut_and_stack_sheet_count(total_pages, arrangement);
+
+    let mut sheets = Vec::new();
+
+    for sheet in 0..sheet_count {
+        let page_mapping = map_cut_and_stack_pages(arrangement, sheet, sheet_count, total_pages);
+        let (front_mapping, back_mapping) = page_mapping.split_at(front_slots.len());
+
+        let mut sides = Vec::with_capacity(2);
+        for (side, side_slots, mapping) in [
+            (SheetSide::Front, &front_slots, front_mapping),
+            (SheetSide::Back, &back_slots, back_mapping),
+        ] {
+            let side_bounds =
+                calculate_leaf_bounds(options, output_width_pt, output_height_pt, side);
+            let placements = calculate_sheet_placements(
+                &grid,
+                side_slots,
+                mapping,
+                source_dimensions,
+                &options.margins.leaf,
+                mm_to_pt(options.bleed_mm),
+                options.scaling_mode,
+                options.content_alignment,
+                options.auto_rotate_to_fit,
+                (side_bounds.x, side_bounds.y),
+                &[],
+            );
+
+            sides.push(SheetLayout {
+                side,
+                placements,
+                leaf_bounds: side_bounds,
+            });
+        }
+
+        sheets.push(PlannedSheet {
+            signature: None,
+            sides,
+        });
+    }
+
+    ImpositionPlan {
+        sheet_size_pt: (output_width_pt, output_height_pt),
+        grid,
+        sheets,
+    }
+}
+
+/// Plan step and repeat
+///
+/// Every cell of a sheet gets the same source page, and each page fills
+/// `repeat.sheets_per_page()` sheets before the next one starts.
+pub(crate) fn plan_step_and_repeat(
+    source_dimensions: &[(f32, f32)],
+    options: &ImpositionOptions,
+    repeat: StepAndRepeat,
+) -> ImpositionPlan {
+    // Calculate output dimensions and leaf area
+    let (output_width_pt, output_height_pt) = sheet_dimensions_pt(options);
+    let leaf_bounds =
+        calculate_leaf_bounds(options, output_width_pt, output_height_pt, SheetSide::Front);
+    let grid = create_cut_grid_layout(
+        repeat.cols,
+        repeat.rows,
+        leaf_bounds.width,
+        leaf_bounds.height,
+        mm_to_pt(options.cell_gap_mm),
+    );
+
+    let slots = front_slots(repeat.cols, repeat.rows, NUpOrder::RowMajor);
+    let slot_refs: Vec<&SignatureSlot> = slots.iter().collect();
+
+    let mut sheets = Vec::new();
+
+    for source_page in 0..source_dimensions.len() {
+        let page_mapping = vec![Some(source_page); slots.len()];
+        let placements = calculate_sheet_placements(
+            &grid,
+            &slot_refs,
+            &page_mapping,
+            source_dimensions,
             &options.margins.leaf,
+            mm_to_pt(options.bleed_mm),
             options.scaling_mode,
+            options.content_alignment,
+            options.auto_rotate_to_fit,
             (leaf_bounds.x, leaf_bounds.y),
+            &[],
         );
 
         let layout = SheetLayout {
@@ -84,52 +236,110 @@ pub(crate) fn impose_simple_binding(
             leaf_bounds,
         };
 
-        let page_id = render_sheet(
-            &mut output,
-            source,
-            page_ids,
-            &layout,
-            output_width_pt,
-            output_height_pt,
-            pages_tree_id,
-            &grid,
-            options,
-        )?;
-        page_refs.push(Object::Reference(page_id));
+        // Print as many identical sheets as the wanted copies need
+        for _ in 0..repeat.sheets_per_page() {
+            sheets.push(PlannedSheet {
+                signature: None,
+                sides: vec![layout.clone()],
+            });
+        }
     }
 
-    // Finalize document
-    finalize_document(&mut output, pages_tree_id, page_refs);
-    Ok(output)
+    ImpositionPlan {
+        sheet_size_pt: (output_width_pt, output_height_pt),
+        grid,
+        sheets,
+    }
 }
 
-/// Calculate the leaf area bounds (inside sheet margins)
-fn calculate_leaf_bounds(options: &ImpositionOptions, width_pt: f32, height_pt: f32) -> Rect {
-    let margins = &options.margins.sheet;
-    Rect::new(
-        mm_to_pt(margins.left_mm),
-        mm_to_pt(margins.bottom_mm),
-        width_pt - mm_to_pt(margins.left_mm) - mm_to_pt(margins.right_mm),
-        height_pt - mm_to_pt(margins.top_mm) - mm_to_pt(margins.bottom_mm),
-    )
+/// Plan plain N-up
+///
+/// Pages fill the cells of single-sided sheets in the options' N-up order,
+/// with no reordering or mirroring for a back side. Every cell
+/// is inset by the leaf cut margin on all sides, so the cells match.
+pub(crate) fn plan_n_up(
+    source_dimensions: &[(f32, f32)],
+    options: &ImpositionOptions,
+    cols: usize,
+    rows: usize,
+) -> ImpositionPlan {
+    let total_pages = source_dimensions.len();
+
+    // Calculate output dimensions and leaf area
+    let (output_width_pt, output_height_pt) = sheet_dimensions_pt(options);
+    let leaf_bounds =
+        calculate_leaf_bounds(options, output_width_pt, output_height_pt, SheetSide::Front);
+    let grid = create_cut_grid_layout(
+        cols,
+        rows,
+        leaf_bounds.width,
+        leaf_bounds.height,
+        mm_to_pt(options.cell_gap_mm),
+    );
+
+    let slots = front_slots(cols, rows, options.n_up_order);
+    let slot_refs: Vec<&SignatureSlot> = slots.iter().collect();
+    let cut_mm = options.margins.leaf.cut_mm;
+    let margins = LeafMargins {
+        top_mm: cut_mm,
+        bottom_mm: cut_mm,
+        fore_edge_mm: cut_mm,
+        spine_mm: cut_mm,
+        cut_mm,
+    };
+
+    let mut sheets = Vec::new();
+
+    for sheet_start in (0..total_pages).step_by(slots.len()) {
+        let page_mapping: Vec<Option<usize>> = (sheet_start..sheet_start + slots.len())
+            .map(|page| (page < total_pages).then_some(page))
+            .collect();
+        let placements = calculate_sheet_placements(
+            &grid,
+            &slot_refs,
+            &page_mapping,
+            source_dimensions,
+            &margins,
+            mm_to_pt(options.bleed_mm),
+            options.scaling_mode,
+            options.content_alignment,
+            options.auto_rotate_to_fit,
+            (leaf_bounds.x, leaf_bounds.y),
+            &[],
+        );
+
+        sheets.push(PlannedSheet {
+            signature: None,
+            sides: vec![SheetLayout {
+                side: SheetSide::Front,
+                placements,
+                leaf_bounds,
+            }],
+        });
+    }
+
+    ImpositionPlan {
+        sheet_size_pt: (output_width_pt, output_height_pt),
+        grid,
+        sheets,
+    }
 }
 
-/// Create pages tree and catalog, finalize document structure
-fn finalize_document(output: &mut Document, pages_tree_id: ObjectId, page_refs: Vec<Object>) {
-    let count = page_refs.len() as i64;
-    let pages_dict = Dictionary::from_iter(vec![
-        ("Type", Object::Name(b"Pages".to_vec())),
-        ("Kids", Object::Array(page_refs)),
-        ("Count", Object::Integer(count)),
-    ]);
-    output
-        .objects
-        .insert(pages_tree_id, Object::Dictionary(pages_dict));
-
-    let catalog_id = output.add_object(Dictionary::from_iter(vec![
-        ("Type", Object::Name(b"Catalog".to_vec())),
-        ("Pages", Object::Reference(pages_tree_id)),
-    ]));
-
-    output.trailer.set("Root", catalog_id);
+/// One front-side slot per cell of a `cols` x `rows` grid, in `order`
+fn front_slots(cols: usize, rows: usize, order: NUpOrder) -> Vec<SignatureSlot> {
+    let cells: Vec<(usize, usize)> = match order {
+        NUpOrder::RowMajor => (0..rows)
+            .flat_map(|row| (0..cols).map(move |col| (row, col)))
+            .collect(),
+        NUpOrder::ColumnMajor => (0..cols)
+            .flat_map(|col| (0..rows).map(move |row| (row, col)))
+            .collect(),
+    };
+    cells
+        .into_iter()
+        .enumerate()
+        .map(|(idx, (row, col))| {
+            SignatureSlot::new(idx, SheetSide::Front, row, col, false, PageSide::Recto)
+        })
+        .collect()
 }
@@ -0,0 +1,147 @@
+//! Splitting imposed output into multiple documents
+
+use super::io::{decrypt_inputs, extract_pages};
+use super::metadata::OutputMetadata;
+use super::{ImposedOutput, ImposedSheet, impose_sync, print_order, reporter, sides};
+use crate::options::ImpositionOptions;
+use crate::types::*;
+use lopdf::Document;
+use std::ops::Range;
+
+/// Impose and split the output according to `options.split_mode`.
+///
+/// Returns `(suffix, document)` pairs in output order. Splits always fall
+/// on sheet boundaries so a front is never separated from its back, and the
+/// last part holds whatever remains. With `SplitMode::None` a single document
+/// with an empty suffix is returned.
+///
+/// With [`OutputFormat::TwoSided`] every part is further divided into a
+/// `_front` and a `_back` document, e.g. `_sig01_front` and `_sig01_back`.
+/// A separately imposed cover comes last, with the suffix `_cover`.
+pub async fn impose_split(
+    documents: &[Document],
+    options: &ImpositionOptions,
+) -> Result<Vec<(String, Document)>> {
+    impose_split_with_progress(documents, options, |_| {}).await
+}
+
+/// [`impose_split`], calling `progress` as the imposition moves through its
+/// stages, like [`impose_with_progress`](super::impose_with_progress)
+pub async fn impose_split_with_progress(
+    documents: &[Document],
+    options: &ImpositionOptions,
+    progress: impl Fn(ImposeProgress) + Send + 'static,
+) -> Result<Vec<(String, Document)>> {
+    impose_split_cancellable(documents, options, progress, CancellationToken::new()).await
+}
+
+/// [`impose_split`], reporting progress and stopping when `cancel` is
+/// cancelled, like [`impose_cancellable`](super::impose_cancellable)
+pub async fn impose_split_cancellable(
+    documents: &[Document],
+    options: &ImpositionOptions,
+    progress: impl Fn(ImposeProgress) + Send + 'static,
+    cancel: CancellationToken,
+) -> Result<Vec<(String, Document)>> {
+    options.validate()?;
+
+    let documents = documents.to_vec();
+    let options = options.clone();
+
+    tokio::task::spawn_blocking(move || {
+        let documents = decrypt_inputs(documents, options.password.as_deref())?;
+        let report = reporter(&progress, &cancel);
+        let mut imposed = impose_sync(&documents, &options, &report)?;
+        let cover = imposed.cover.take();
+        let mut parts = split_output(imposed, &options)?;
+        // Split parts are new documents, so they need the metadata again
+        let metadata = OutputMetadata::new(&documents, &options);
+        for (_, part) in &mut parts {
+            if !part.trailer.has(b"Info") {
+                metadata.apply(part)?;
+            }
+        }
+        parts.extend(cover.map(|cover| ("_cover".to_string(), cover)));
+        Ok(parts)
+    })
+    .await?
+}
+
+fn split_output(
+    imposed: ImposedOutput,
+    options: &ImpositionOptions,
+) -> Result<Vec<(String, Document)>> {
+    let mode = options.split_mode;
+    let two_sided = options.output_format == OutputFormat::TwoSided;
+    // Signature files are labelled (`_sig01`); page/sheet splits are plain numbers (`_001`)
+    let (label, min_width) = match mode {
+        SplitMode::None if !two_sided => return Ok(vec![(String::new(), imposed.document)]),
+        SplitMode::None => ("", 0),
+        SplitMode::BySignatures(_) => ("sig", 2),
+        SplitMode::ByPages(_) | SplitMode::BySheets(_) => ("", 3),
+    };
+
+    let chunks = chunk_sheets(&imposed.sheets, mode);
+    let width = chunks.len().to_string().len().max(min_width);
+
+    let mut parts = Vec::new();
+    for (idx, range) in chunks.into_iter().enumerate() {
+        let suffix = if mode == SplitMode::None {
+            String::new()
+        } else {
+            format!("_{label}{:0width$}", idx + 1)
+        };
+        let sheets = &imposed.sheets[range];
+
+        if two_sided {
+            let (fronts, backs) = sides(sheets, options.single_sided_back_order);
+            parts.push((
+                format!("{suffix}_front"),
+                extract_pages(&imposed.document, &fronts)?,
+            ));
+            parts.push((
+                format!("{suffix}_back"),
+                extract_pages(&imposed.document, &backs)?,
+            ));
+        } else {
+            let page_ids = print_order(sheets, options);
+            parts.push((suffix, extract_pages(&imposed.document, &page_ids)?));
+        }
+    }
+
+    Ok(parts)
+}
+
+/// Group sheets into consecutive ranges according to the split mode
+fn chunk_sheets(sheets: &[ImposedSheet], mode: SplitMode) -> Vec<Range<usize>> {
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    let mut pages_in_chunk = 0;
+
+    for (idx, sheet) in sheets.iter().enumerate() {
+        let starts_new_chunk = idx > start
+            && match mode {
+                SplitMode::None => false,
+                SplitMode::ByPages(n) => pages_in_chunk + sheet.pages.len() > n,
+                SplitMode::BySheets(n) => idx - start >= n,
+                SplitMode::BySignatures(n) => {
+                    // Bindings without signatures treat each sheet as its own group
+                    let group = |s: &ImposedSheet, i: usize| s.signature.unwrap_or(i);
+                    group(sheet, idx) - group(&sheets[start], start) >= n
+                }
+            };
+
+        if starts_new_chunk {
+            chunks.push(start..idx);
+            start = idx;
+            pages_in_chunk = 0;
+        }
+        pages_in_chunk += sheet.pages.len();
+    }
+
+    if start < sheets.len() {
+        chunks.push(start..sheets.len());
+    }
+
+    chunks
+}
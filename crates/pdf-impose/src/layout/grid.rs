@@ -3,7 +3,7 @@
 //! This module handles the geometric layout of the page grid on a sheet,
 //! including cell dimensions and fold/cut positions.
 
-use crate::types::PageArrangement;
+use crate::types::{PageArrangement, SpineEdge};
 
 use super::{GridLayout, GridPosition, Rect};
 
@@ -14,17 +14,21 @@ use super::{GridLayout, GridPosition, Rect};
 /// Create a grid layout for the given page arrangement.
 ///
 /// # Arguments
-/// * `arrangement` - The page arrangement (folio, quarto, octavo, custom)
+/// * `arrangement` - The page arrangement (folio, quarto, octavo, sexto,
+///   duodecimo, custom)
 /// * `leaf_width_pt` - Width of the leaf area in points (after sheet margins)
 /// * `leaf_height_pt` - Height of the leaf area in points (after sheet margins)
 /// * `output_width_pt` - Total output sheet width in points
 /// * `output_height_pt` - Total output sheet height in points
+/// * `spine_edge` - Which way the spine runs; only a quarto can have it
+///   either way, other arrangements always have a vertical spine
 pub fn create_grid_layout(
     arrangement: PageArrangement,
     leaf_width_pt: f32,
     leaf_height_pt: f32,
     output_width_pt: f32,
     output_height_pt: f32,
+    spine_edge: SpineEdge,
 ) -> GridLayout {
     let (cols, rows) = arrangement.grid_dimensions();
 
@@ -37,21 +41,50 @@ pub fn create_grid_layout(
         vertical_folds,
         horizontal_folds,
         vertical_cuts,
+        horizontal_cuts,
         horizontal_spine,
-    } = calculate_fold_cut_config(arrangement, is_landscape);
+    } = calculate_fold_cut_config(arrangement, is_landscape, spine_edge);
 
     GridLayout {
         cols,
         rows,
         cell_width_pt,
         cell_height_pt,
+        cell_gap_pt: 0.0,
         vertical_folds,
         horizontal_folds,
         vertical_cuts,
+        horizontal_cuts,
         horizontal_spine,
     }
 }
 
+/// Create a `cols` x `rows` grid where every line between cells is a cut
+/// and nothing is folded, for cut and stack, step and repeat or N-up.
+///
+/// Each cell is inset by half of `cell_gap_pt` on every side, so it is
+/// `cell_gap_pt` narrower and shorter than an even share of the leaf area.
+pub fn create_cut_grid_layout(
+    cols: usize,
+    rows: usize,
+    leaf_width_pt: f32,
+    leaf_height_pt: f32,
+    cell_gap_pt: f32,
+) -> GridLayout {
+    GridLayout {
+        cols,
+        rows,
+        cell_width_pt: leaf_width_pt / cols as f32 - cell_gap_pt,
+        cell_height_pt: leaf_height_pt / rows as f32 - cell_gap_pt,
+        cell_gap_pt,
+        vertical_folds: vec![],
+        horizontal_folds: vec![],
+        vertical_cuts: (0..cols - 1).collect(),
+        horizontal_cuts: (0..rows - 1).collect(),
+        horizontal_spine: false,
+    }
+}
+
 // =============================================================================
 // Fold/Cut Configuration
 // =============================================================================
@@ -61,36 +94,39 @@ struct FoldCutConfig {
     vertical_folds: Vec<usize>,
     horizontal_folds: Vec<usize>,
     vertical_cuts: Vec<usize>,
+    horizontal_cuts: Vec<usize>,
     horizontal_spine: bool,
 }
 
 /// Calculate fold and cut positions for an arrangement.
-fn calculate_fold_cut_config(arrangement: PageArrangement, is_landscape: bool) -> FoldCutConfig {
+fn calculate_fold_cut_config(
+    arrangement: PageArrangement,
+    is_landscape: bool,
+    spine_edge: SpineEdge,
+) -> FoldCutConfig {
     match arrangement {
         PageArrangement::Folio => FoldCutConfig {
             // Folio: single vertical fold in the center
             vertical_folds: vec![0],
             horizontal_folds: vec![],
             vertical_cuts: vec![],
+            horizontal_cuts: vec![],
             horizontal_spine: false,
         },
         PageArrangement::Quarto => {
-            if is_landscape {
-                // Landscape quarto: spine is horizontal (between rows)
-                FoldCutConfig {
-                    vertical_folds: vec![0],
-                    horizontal_folds: vec![0],
-                    vertical_cuts: vec![],
-                    horizontal_spine: true,
-                }
-            } else {
-                // Portrait quarto: spine is vertical (between columns)
-                FoldCutConfig {
-                    vertical_folds: vec![0],
-                    horizontal_folds: vec![0],
-                    vertical_cuts: vec![],
-                    horizontal_spine: false,
-                }
+            // The spine is horizontal (between rows) on a landscape sheet
+            // unless forced, vertical (between columns) otherwise
+            let horizontal_spine = match spine_edge {
+                SpineEdge::Auto => is_landscape,
+                SpineEdge::Vertical => false,
+                SpineEdge::Horizontal => true,
+            };
+            FoldCutConfig {
+                vertical_folds: vec![0],
+                horizontal_folds: vec![0],
+                vertical_cuts: vec![],
+                horizontal_cuts: vec![],
+                horizontal_spine,
             }
         }
         PageArrangement::Octavo => {
@@ -101,6 +137,31 @@ fn calculate_fold_cut_config(arrangement: PageArrangement, is_landscape: bool) -
                 vertical_folds: vec![0, 2],
                 horizontal_folds: vec![0],
                 vertical_cuts: vec![1],
+                horizontal_cuts: vec![],
+                horizontal_spine: false,
+            }
+        }
+        PageArrangement::Sexto => {
+            // Sexto: 2 cols x 3 rows
+            // A quarto in the top two rows; the bottom row is cut off and
+            // folded as a folio that nests inside it
+            FoldCutConfig {
+                vertical_folds: vec![0],
+                horizontal_folds: vec![0],
+                vertical_cuts: vec![],
+                horizontal_cuts: vec![1],
+                horizontal_spine: false,
+            }
+        }
+        PageArrangement::Duodecimo => {
+            // Duodecimo: 4 cols x 3 rows
+            // An octavo in the top two rows; the bottom row is cut off and
+            // split at the center into two folios that nest inside it
+            FoldCutConfig {
+                vertical_folds: vec![0, 2],
+                horizontal_folds: vec![0],
+                vertical_cuts: vec![1],
+                horizontal_cuts: vec![1],
                 horizontal_spine: false,
             }
         }
@@ -110,6 +171,7 @@ fn calculate_fold_cut_config(arrangement: PageArrangement, is_landscape: bool) -
                 vertical_folds: vec![0],
                 horizontal_folds: vec![],
                 vertical_cuts: vec![],
+                horizontal_cuts: vec![],
                 horizontal_spine: false,
             }
         }
@@ -132,10 +194,11 @@ fn calculate_fold_cut_config(arrangement: PageArrangement, is_landscape: bool) -
 pub fn cell_bounds(grid: &GridLayout, pos: GridPosition, leaf_origin: (f32, f32)) -> Rect {
     let (leaf_x, leaf_y) = leaf_origin;
 
-    // Calculate cell position
+    // Calculate cell position, half a gap in from its share of the leaf
     // Row 0 is at the top, so we need to invert the y calculation
-    let cell_x = leaf_x + pos.col as f32 * grid.cell_width_pt;
-    let cell_y = leaf_y + (grid.rows - pos.row - 1) as f32 * grid.cell_height_pt;
+    let inset = grid.cell_gap_pt / 2.0;
+    let cell_x = leaf_x + pos.col as f32 * grid.col_pitch_pt() + inset;
+    let cell_y = leaf_y + (grid.rows - pos.row - 1) as f32 * grid.row_pitch_pt() + inset;
 
     Rect::new(cell_x, cell_y, grid.cell_width_pt, grid.cell_height_pt)
 }
@@ -230,8 +293,8 @@ pub fn cell_edge_info(grid: &GridLayout, pos: GridPosition) -> CellEdgeInfo {
 
         cut_left: grid.has_cut_left(pos.col),
         cut_right: grid.has_cut_right(pos.col),
-        cut_top: false, // No horizontal cuts currently supported
-        cut_bottom: false,
+        cut_top: grid.has_cut_top(pos.row),
+        cut_bottom: grid.has_cut_bottom(pos.row),
 
         outer_left: grid.is_outer_left(pos.col),
         outer_right: grid.is_outer_right(pos.col),
@@ -252,7 +315,14 @@ mod tests {
 
     #[test]
     fn test_folio_grid() {
-        let grid = create_grid_layout(PageArrangement::Folio, 800.0, 600.0, 850.0, 650.0);
+        let grid = create_grid_layout(
+            PageArrangement::Folio,
+            800.0,
+            600.0,
+            850.0,
+            650.0,
+            SpineEdge::Auto,
+        );
 
         assert_eq!(grid.cols, 2);
         assert_eq!(grid.rows, 1);
@@ -264,7 +334,14 @@ mod tests {
 
     #[test]
     fn test_quarto_grid() {
-        let grid = create_grid_layout(PageArrangement::Quarto, 800.0, 600.0, 850.0, 650.0);
+        let grid = create_grid_layout(
+            PageArrangement::Quarto,
+            800.0,
+            600.0,
+            850.0,
+            650.0,
+            SpineEdge::Auto,
+        );
 
         assert_eq!(grid.cols, 2);
         assert_eq!(grid.rows, 2);
@@ -272,9 +349,91 @@ mod tests {
         assert_eq!(grid.cell_height_pt, 300.0);
     }
 
+    #[test]
+    fn test_fold_order_folds_the_spine_last() {
+        // Portrait quarto: the head fold, then the vertical spine fold
+        let grid = create_grid_layout(
+            PageArrangement::Quarto,
+            600.0,
+            800.0,
+            650.0,
+            850.0,
+            SpineEdge::Auto,
+        );
+        assert_eq!((grid.fold_order(false), grid.fold_order(true)), (1, 2));
+
+        // Landscape quarto turns the spine horizontal
+        let grid = create_grid_layout(
+            PageArrangement::Quarto,
+            800.0,
+            600.0,
+            850.0,
+            650.0,
+            SpineEdge::Auto,
+        );
+        assert_eq!((grid.fold_order(true), grid.fold_order(false)), (1, 2));
+
+        // A folio's only fold is its spine
+        let grid = create_grid_layout(
+            PageArrangement::Folio,
+            800.0,
+            600.0,
+            850.0,
+            650.0,
+            SpineEdge::Auto,
+        );
+        assert_eq!(grid.fold_order(true), 1);
+    }
+
+    #[test]
+    fn test_quarto_spine_edge_overrides_orientation() {
+        let quarto = |spine_edge, landscape: bool| {
+            let (width, height) = if landscape {
+                (800.0, 600.0)
+            } else {
+                (600.0, 800.0)
+            };
+            let grid = create_grid_layout(
+                PageArrangement::Quarto,
+                width,
+                height,
+                width + 50.0,
+                height + 50.0,
+                spine_edge,
+            );
+            grid.horizontal_spine
+        };
+        assert!(!quarto(SpineEdge::Auto, false));
+        assert!(quarto(SpineEdge::Auto, true));
+        for landscape in [false, true] {
+            assert!(!quarto(SpineEdge::Vertical, landscape));
+            assert!(quarto(SpineEdge::Horizontal, landscape));
+        }
+
+        // A forced vertical spine is the left edge of the top-right cell
+        let grid = create_grid_layout(
+            PageArrangement::Quarto,
+            800.0,
+            600.0,
+            850.0,
+            650.0,
+            SpineEdge::Vertical,
+        );
+        let info = cell_edge_info(&grid, GridPosition::new(0, 1));
+        assert!(info.is_spine_left() && !info.is_spine_bottom());
+        assert_eq!((grid.fold_order(false), grid.fold_order(true)), (1, 2));
+    }
+
     #[test]
     fn test_octavo_grid() {
-        let grid = create_grid_layout(PageArrangement::Octavo, 800.0, 600.0, 850.0, 650.0);
+        let grid = create_grid_layout(
+            PageArrangement::Octavo,
+            800.0,
+            600.0,
+            850.0,
+            650.0,
+            SpineEdge::Auto,
+        );
 
         assert_eq!(grid.cols, 4);
         assert_eq!(grid.rows, 2);
@@ -285,9 +444,68 @@ mod tests {
         assert_eq!(grid.vertical_cuts, vec![1]);
     }
 
+    #[test]
+    fn test_sexto_grid() {
+        let grid = create_grid_layout(
+            PageArrangement::Sexto,
+            600.0,
+            900.0,
+            650.0,
+            950.0,
+            SpineEdge::Auto,
+        );
+
+        assert_eq!(grid.cols, 2);
+        assert_eq!(grid.rows, 3);
+        assert_eq!(grid.cell_height_pt, 300.0);
+        // Quarto folds in the top two rows, offcut cut below them
+        assert_eq!(grid.vertical_folds, vec![0]);
+        assert_eq!(grid.horizontal_folds, vec![0]);
+        assert_eq!(grid.horizontal_cuts, vec![1]);
+
+        // The offcut's spine is still the center fold
+        let offcut = cell_edge_info(&grid, GridPosition::new(2, 0));
+        assert!(offcut.is_spine_right());
+        assert!(offcut.cut_top && !offcut.fold_top);
+    }
+
+    #[test]
+    fn test_duodecimo_grid() {
+        let grid = create_grid_layout(
+            PageArrangement::Duodecimo,
+            800.0,
+            600.0,
+            850.0,
+            650.0,
+            SpineEdge::Auto,
+        );
+
+        assert_eq!(grid.cols, 4);
+        assert_eq!(grid.rows, 3);
+        assert_eq!(grid.cell_width_pt, 200.0);
+        assert_eq!(grid.cell_height_pt, 200.0);
+        assert_eq!(grid.vertical_folds, vec![0, 2]);
+        assert_eq!(grid.vertical_cuts, vec![1]);
+        assert_eq!(grid.horizontal_cuts, vec![1]);
+
+        // Both offcut folios have their spine at their own center fold
+        let right_offcut = cell_edge_info(&grid, GridPosition::new(2, 3));
+        assert!(right_offcut.is_spine_left());
+        assert!(right_offcut.cut_top);
+        let inner = cell_edge_info(&grid, GridPosition::new(2, 1));
+        assert!(inner.is_spine_left() && inner.cut_right);
+    }
+
     #[test]
     fn test_cell_bounds() {
-        let grid = create_grid_layout(PageArrangement::Quarto, 800.0, 600.0, 850.0, 650.0);
+        let grid = create_grid_layout(
+            PageArrangement::Quarto,
+            800.0,
+            600.0,
+            850.0,
+            650.0,
+            SpineEdge::Auto,
+        );
 
         // Bottom-left cell (row 1, col 0)
         let bounds = cell_bounds(&grid, GridPosition::new(1, 0), (25.0, 25.0));
@@ -304,7 +522,14 @@ mod tests {
 
     #[test]
     fn test_cell_fold_edges() {
-        let grid = create_grid_layout(PageArrangement::Quarto, 800.0, 600.0, 850.0, 650.0);
+        let grid = create_grid_layout(
+            PageArrangement::Quarto,
+            800.0,
+            600.0,
+            850.0,
+            650.0,
+            SpineEdge::Auto,
+        );
 
         // Top-left cell (row 0, col 0): fold on right and bottom
         let edges = cell_fold_edges(&grid, GridPosition::new(0, 0));
@@ -330,7 +555,14 @@ mod tests {
 
     #[test]
     fn test_cell_edge_info_outer_edges() {
-        let grid = create_grid_layout(PageArrangement::Quarto, 800.0, 600.0, 850.0, 650.0);
+        let grid = create_grid_layout(
+            PageArrangement::Quarto,
+            800.0,
+            600.0,
+            850.0,
+            650.0,
+            SpineEdge::Auto,
+        );
 
         // Top-left is outer top and left
         let info = cell_edge_info(&grid, GridPosition::new(0, 0));
@@ -346,4 +578,34 @@ mod tests {
         assert!(info.outer_right);
         assert!(info.outer_bottom);
     }
+
+    #[test]
+    fn test_cut_grid_edges() {
+        let grid = create_cut_grid_layout(2, 3, 600.0, 900.0, 0.0);
+        assert_eq!(grid.cell_width_pt, 300.0);
+        assert_eq!(grid.cell_height_pt, 300.0);
+
+        // Middle-left cell: cut on the right, above and below, nothing folded
+        let info = cell_edge_info(&grid, GridPosition::new(1, 0));
+        assert!(info.cut_right && info.cut_top && info.cut_bottom);
+        assert!(!info.cut_left && info.outer_left);
+        assert!(!cell_fold_edges(&grid, GridPosition::new(1, 0)).any());
+    }
+
+    #[test]
+    fn test_cut_grid_gap_insets_cells() {
+        use crate::constants::mm_to_pt;
+
+        let gap = mm_to_pt(10.0);
+        let grid = create_cut_grid_layout(2, 3, 600.0, 900.0, gap);
+        assert!((grid.cell_width_pt - (300.0 - gap)).abs() < 0.001);
+        assert!((grid.cell_height_pt - (300.0 - gap)).abs() < 0.001);
+
+        // Half the gap on each side of every cell, a full gap between them
+        let left = cell_bounds(&grid, GridPosition::new(0, 0), (0.0, 0.0));
+        let right = cell_bounds(&grid, GridPosition::new(0, 1), (0.0, 0.0));
+        assert!((left.x - gap / 2.0).abs() < 0.001);
+        assert!((right.left() - left.right() - gap).abs() < 0.001);
+        assert!((right.right() - (600.0 - gap / 2.0)).abs() < 0.001);
+    }
 }
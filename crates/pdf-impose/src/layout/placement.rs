@@ -3,11 +3,13 @@
 //! This module handles calculating the final position of page content
 //! within grid cells, accounting for:
 //! - Leaf margins (spine, fore-edge, top, bottom, cut)
-//! - Content alignment toward folds
+//! - Content alignment (toward folds by default)
 //! - Scaling
 
 use crate::constants::{DEFAULT_PAGE_DIMENSIONS, mm_to_pt};
-use crate::types::{LeafMargins, ScalingMode};
+use crate::types::{
+    ContentAlignment, HorizontalAlignment, LeafMargins, ScalingMode, VerticalAlignment,
+};
 
 use super::{GridLayout, PagePlacement, Rect, SignatureSlot, cell_bounds, cell_edge_info};
 
@@ -108,13 +110,14 @@ fn calculate_edge_margin(
 ///
 /// This handles:
 /// - Scaling the source page to fit the content area
-/// - Aligning content toward fold edges
+/// - Aligning content within the area, toward fold edges by default
 ///
 /// # Arguments
 /// * `content_area` - The available area for content (after margins)
 /// * `source_width` - Width of the source page in points
 /// * `source_height` - Height of the source page in points
 /// * `scaling_mode` - How to scale the source page
+/// * `alignment` - Where the scaled page sits in the content area
 /// * `slot` - The signature slot
 /// * `grid` - The grid layout
 pub fn place_page(
@@ -122,6 +125,7 @@ pub fn place_page(
     source_width: f32,
     source_height: f32,
     scaling_mode: ScalingMode,
+    alignment: ContentAlignment,
     slot: &SignatureSlot,
     grid: &GridLayout,
 ) -> PagePlacement {
@@ -133,29 +137,158 @@ pub fn place_page(
         scaling_mode,
     );
 
-    let scaled_width = source_width * scale;
-    let scaled_height = source_height * scale;
+    let scaled_width = source_width * scale.0;
+    let scaled_height = source_height * scale.1;
 
-    // Determine alignment based on fold positions
-    let (x, y) = calculate_alignment(content_area, scaled_width, scaled_height, slot, grid);
+    let (x, y) = calculate_alignment(
+        content_area,
+        scaled_width,
+        scaled_height,
+        alignment,
+        slot,
+        grid,
+    );
+
+    let content_rect = Rect::new(x, y, scaled_width, scaled_height);
 
     PagePlacement {
         source_page: None, // Will be filled in by caller
-        content_rect: Rect::new(x, y, scaled_width, scaled_height),
+        content_rect,
+        trim_rect: content_rect,
         rotation_degrees: slot.rotation_degrees(),
+        quarter_turn: false,
         scale,
         slot: slot.clone(),
     }
 }
 
-/// Calculate content alignment based on fold positions.
+/// Place a page whose content extends `bleed` points past the trim line.
+///
+/// Bleed is added only on edges that get trimmed (outer and cut edges), never
+/// across a fold, so `content_area` is grown on those edges before placing and
+/// the result's `trim_rect` is the placed content minus the bleed.
+///
+/// Each edge's bleed is clamped to the room between `content_area` and the
+/// edge of `cell` (plus half of any cutting gap), so bleed never reaches into
+/// a neighboring cell's content when cells abut.
+#[allow(clippy::too_many_arguments)]
+pub fn place_page_with_bleed(
+    content_area: &Rect,
+    cell: &Rect,
+    source_width: f32,
+    source_height: f32,
+    scaling_mode: ScalingMode,
+    alignment: ContentAlignment,
+    slot: &SignatureSlot,
+    grid: &GridLayout,
+    bleed: f32,
+) -> PagePlacement {
+    let (left, bottom, right, top) = bleed_edges(content_area, cell, slot, grid, bleed);
+    let bleed_area = content_area.inset(-left, -bottom, -right, -top);
+
+    let mut placement = place_page(
+        &bleed_area,
+        source_width,
+        source_height,
+        scaling_mode,
+        alignment,
+        slot,
+        grid,
+    );
+    placement.trim_rect = placement.content_rect.inset(left, bottom, right, top);
+    placement
+}
+
+/// Bleed for each edge of a slot (left, bottom, right, top)
+fn bleed_edges(
+    content_area: &Rect,
+    cell: &Rect,
+    slot: &SignatureSlot,
+    grid: &GridLayout,
+    bleed: f32,
+) -> (f32, f32, f32, f32) {
+    if bleed <= 0.0 {
+        return (0.0, 0.0, 0.0, 0.0);
+    }
+
+    let edges = cell_edge_info(grid, slot.grid_pos);
+    let half_gap = grid.cell_gap_pt / 2.0;
+    let trimmed = |cut: bool, outer: bool, fold: bool, room: f32| {
+        if (cut || outer) && !fold {
+            bleed.min((room + half_gap).max(0.0))
+        } else {
+            0.0
+        }
+    };
+
+    (
+        trimmed(
+            edges.cut_left,
+            edges.outer_left,
+            edges.fold_left,
+            content_area.x - cell.x,
+        ),
+        trimmed(
+            edges.cut_bottom,
+            edges.outer_bottom,
+            edges.fold_bottom,
+            content_area.y - cell.y,
+        ),
+        trimmed(
+            edges.cut_right,
+            edges.outer_right,
+            edges.fold_right,
+            cell.right() - content_area.right(),
+        ),
+        trimmed(
+            edges.cut_top,
+            edges.outer_top,
+            edges.fold_top,
+            cell.top() - content_area.top(),
+        ),
+    )
+}
+
+/// Shift a placement toward the spine by `shift` points to compensate for creep.
 ///
-/// Content is pushed toward folds (where pages meet after folding)
-/// for proper alignment in the bound book.
+/// The content never moves past the cell's spine edge, so the spine margin is
+/// the room available for the shift.
+pub fn apply_creep(placement: &mut PagePlacement, cell: &Rect, shift: f32, grid: &GridLayout) {
+    if shift <= 0.0 {
+        return;
+    }
+
+    let edges = cell_edge_info(grid, placement.slot.grid_pos);
+    let rect = &placement.content_rect;
+
+    let (dx, dy) = if edges.is_spine_left() {
+        (-shift.min(rect.x - cell.x).max(0.0), 0.0)
+    } else if edges.is_spine_right() {
+        (shift.min(cell.right() - rect.right()).max(0.0), 0.0)
+    } else if edges.is_spine_top() {
+        (0.0, shift.min(cell.top() - rect.top()).max(0.0))
+    } else if edges.is_spine_bottom() {
+        (0.0, -shift.min(rect.y - cell.y).max(0.0))
+    } else {
+        (0.0, 0.0)
+    };
+
+    for rect in [&mut placement.content_rect, &mut placement.trim_rect] {
+        rect.x += dx;
+        rect.y += dy;
+    }
+}
+
+/// Calculate where the scaled content sits within the content area.
+///
+/// By default content is pushed toward folds (where pages meet after
+/// folding) for proper alignment in the bound book. Top and bottom refer to
+/// the upright page, so they swap for rotated slots.
 fn calculate_alignment(
     content_area: &Rect,
     scaled_width: f32,
     scaled_height: f32,
+    alignment: ContentAlignment,
     slot: &SignatureSlot,
     grid: &GridLayout,
 ) -> (f32, f32) {
@@ -164,28 +297,34 @@ fn calculate_alignment(
     let fold_bottom = grid.has_fold_bottom(slot.grid_pos.row);
     let fold_top = grid.has_fold_top(slot.grid_pos.row);
 
-    // Horizontal alignment
-    let x = if fold_right && !fold_left {
-        // Fold on right only: push content right
-        content_area.right() - scaled_width
-    } else if fold_left && !fold_right {
-        // Fold on left only: push content left
-        content_area.x
-    } else {
-        // No fold preference or both sides: center
-        content_area.x + (content_area.width - scaled_width) / 2.0
+    let left = content_area.x;
+    let right = content_area.right() - scaled_width;
+    let bottom = content_area.y;
+    let top = content_area.top() - scaled_height;
+    let center_x = content_area.x + (content_area.width - scaled_width) / 2.0;
+    let center_y = content_area.y + (content_area.height - scaled_height) / 2.0;
+
+    // Horizontal alignment; with no single fold to work from, center
+    let x = match (alignment.horizontal, fold_left, fold_right) {
+        (HorizontalAlignment::TowardSpine, false, true)
+        | (HorizontalAlignment::TowardForeEdge, true, false) => right,
+        (HorizontalAlignment::TowardSpine, true, false)
+        | (HorizontalAlignment::TowardForeEdge, false, true) => left,
+        _ => center_x,
     };
 
     // Vertical alignment
-    let y = if fold_bottom && !fold_top {
-        // Fold at bottom only: push content down
-        content_area.y
-    } else if fold_top && !fold_bottom {
-        // Fold at top only: push content up
-        content_area.top() - scaled_height
+    let (head, tail) = if slot.rotated {
+        (bottom, top)
     } else {
-        // No fold preference or both: center
-        content_area.y + (content_area.height - scaled_height) / 2.0
+        (top, bottom)
+    };
+    let y = match (alignment.vertical, fold_bottom, fold_top) {
+        (VerticalAlignment::TowardFold, true, false) => bottom,
+        (VerticalAlignment::TowardFold, false, true) => top,
+        (VerticalAlignment::Top, _, _) => head,
+        (VerticalAlignment::Bottom, _, _) => tail,
+        _ => center_y,
     };
 
     (x, y)
@@ -200,7 +339,9 @@ fn calculate_alignment(
 /// * `source_dimensions` - (width, height) in points for each source page
 /// * `leaf_margins` - Margin configuration
 /// * `scaling_mode` - How to scale pages
+/// * `alignment` - Where pages sit within their content areas
 /// * `leaf_origin` - Bottom-left corner of the leaf area
+#[allow(clippy::too_many_arguments)]
 pub fn calculate_placements(
     grid: &GridLayout,
     slots: &[&SignatureSlot],
@@ -208,6 +349,7 @@ pub fn calculate_placements(
     source_dimensions: &[(f32, f32)],
     leaf_margins: &LeafMargins,
     scaling_mode: ScalingMode,
+    alignment: ContentAlignment,
     leaf_origin: (f32, f32),
 ) -> Vec<PagePlacement> {
     slots
@@ -227,6 +369,7 @@ pub fn calculate_placements(
                 src_width,
                 src_height,
                 scaling_mode,
+                alignment,
                 slot,
                 grid,
             );
@@ -240,23 +383,48 @@ pub fn calculate_placements(
 // Scaling
 // =============================================================================
 
-/// Calculate scale factor for fitting source to target dimensions.
+/// Whether turning a page 90° lets it fit `content_area` at a larger scale
+pub fn turn_improves_fit(content_area: &Rect, source_width: f32, source_height: f32) -> bool {
+    let fit = |width: f32, height: f32| {
+        calculate_scale(
+            width,
+            height,
+            content_area.width,
+            content_area.height,
+            ScalingMode::Fit,
+        )
+        .0
+    };
+    fit(source_height, source_width) > fit(source_width, source_height)
+}
+
+/// Calculate (x, y) scale factors for fitting source to target dimensions.
+///
+/// Only `Stretch` scales the axes differently. A source with no area is
+/// left unscaled, and a target with none scales it to nothing, so the
+/// result is always finite.
 fn calculate_scale(
     src_width: f32,
     src_height: f32,
     target_width: f32,
     target_height: f32,
     mode: ScalingMode,
-) -> f32 {
-    let scale_w = target_width / src_width;
-    let scale_h = target_height / src_height;
+) -> (f32, f32) {
+    if !(src_width > 0.0 && src_height > 0.0) {
+        return (1.0, 1.0);
+    }
+    let scale_w = target_width.max(0.0) / src_width;
+    let scale_h = target_height.max(0.0) / src_height;
 
-    match mode {
+    let uniform = match mode {
+        ScalingMode::Stretch => return (scale_w, scale_h),
         ScalingMode::Fit => scale_w.min(scale_h),
+        ScalingMode::FitNoUpscale => scale_w.min(scale_h).min(1.0),
+        ScalingMode::Percent(percent) => percent / 100.0,
         ScalingMode::Fill => scale_w.max(scale_h),
         ScalingMode::None => 1.0,
-        ScalingMode::Stretch => scale_w, // Use width scaling, ignore height
-    }
+    };
+    (uniform, uniform)
 }
 
 // =============================================================================
@@ -267,7 +435,7 @@ fn calculate_scale(
 mod tests {
     use super::*;
     use crate::layout::{GridPosition, PageSide, SheetSide};
-    use crate::types::PageArrangement;
+    use crate::types::{PageArrangement, SpineEdge};
 
     fn make_slot(row: usize, col: usize, rotated: bool) -> SignatureSlot {
         SignatureSlot {
@@ -280,7 +448,7 @@ mod tests {
     }
 
     fn make_grid(arrangement: PageArrangement) -> GridLayout {
-        super::super::create_grid_layout(arrangement, 800.0, 600.0, 850.0, 650.0)
+        super::super::create_grid_layout(arrangement, 800.0, 600.0, 850.0, 650.0, SpineEdge::Auto)
     }
 
     #[test]
@@ -322,8 +490,14 @@ mod tests {
         };
 
         // Use portrait dimensions (height > width) so spine is vertical
-        let grid =
-            super::super::create_grid_layout(PageArrangement::Quarto, 600.0, 800.0, 650.0, 850.0);
+        let grid = super::super::create_grid_layout(
+            PageArrangement::Quarto,
+            600.0,
+            800.0,
+            650.0,
+            850.0,
+            SpineEdge::Auto,
+        );
 
         // Top-left cell, not rotated
         let slot_normal = make_slot(0, 0, false);
@@ -353,12 +527,70 @@ mod tests {
         // Source is 800x600, target is 400x400
         // To fit, we need to scale by 0.5 (width-limited)
         let scale = calculate_scale(800.0, 600.0, 400.0, 400.0, ScalingMode::Fit);
-        assert!((scale - 0.5).abs() < 0.001);
+        assert_eq!(scale.0, scale.1);
+        assert!((scale.0 - 0.5).abs() < 0.001);
 
         // Source is 400x800, target is 400x400
         // To fit, we need to scale by 0.5 (height-limited)
         let scale = calculate_scale(400.0, 800.0, 400.0, 400.0, ScalingMode::Fit);
-        assert!((scale - 0.5).abs() < 0.001);
+        assert_eq!(scale.0, scale.1);
+        assert!((scale.0 - 0.5).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_scale_fit_no_upscale_and_percent() {
+        // Source is smaller than the target: Fit enlarges, FitNoUpscale doesn't
+        let scale = calculate_scale(400.0, 600.0, 520.0, 780.0, ScalingMode::Fit);
+        assert_eq!(scale.0, scale.1);
+        assert!((scale.0 - 1.3).abs() < 0.001);
+        let scale = calculate_scale(400.0, 600.0, 520.0, 780.0, ScalingMode::FitNoUpscale);
+        assert_eq!(scale.0, scale.1);
+        assert!((scale.0 - 1.0).abs() < 0.001);
+
+        // Larger sources still shrink to fit
+        let scale = calculate_scale(800.0, 600.0, 400.0, 400.0, ScalingMode::FitNoUpscale);
+        assert_eq!(scale.0, scale.1);
+        assert!((scale.0 - 0.5).abs() < 0.001);
+
+        let scale = calculate_scale(800.0, 600.0, 400.0, 400.0, ScalingMode::Percent(85.0));
+        assert_eq!(scale.0, scale.1);
+        assert!((scale.0 - 0.85).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_scale_stays_finite_for_degenerate_sizes() {
+        for mode in [ScalingMode::Fit, ScalingMode::Fill, ScalingMode::Stretch] {
+            assert_eq!(calculate_scale(0.0, 0.0, 400.0, 300.0, mode), (1.0, 1.0));
+            assert_eq!(
+                calculate_scale(-612.0, 792.0, 400.0, 300.0, mode),
+                (1.0, 1.0)
+            );
+            assert_eq!(
+                calculate_scale(612.0, 792.0, -10.0, -10.0, mode),
+                (0.0, 0.0)
+            );
+        }
+    }
+
+    #[test]
+    fn test_scale_stretch_scales_axes_separately() {
+        let (scale_x, scale_y) = calculate_scale(612.0, 792.0, 400.0, 300.0, ScalingMode::Stretch);
+        assert!((scale_x - 400.0 / 612.0).abs() < 0.001);
+        assert!((scale_y - 300.0 / 792.0).abs() < 0.001);
+
+        // The stretched page fills the content area exactly
+        let content_area = Rect::new(10.0, 20.0, 400.0, 300.0);
+        let grid = make_grid(PageArrangement::Folio);
+        let placement = place_page(
+            &content_area,
+            612.0,
+            792.0,
+            ScalingMode::Stretch,
+            ContentAlignment::default(),
+            &make_slot(0, 0, false),
+            &grid,
+        );
+        assert_eq!(placement.content_rect, content_area);
     }
 
     #[test]
@@ -366,7 +598,8 @@ mod tests {
         // Source is 800x600, target is 400x400
         // To fill, we need to scale by 0.667 (height-limited, will crop width)
         let scale = calculate_scale(800.0, 600.0, 400.0, 400.0, ScalingMode::Fill);
-        assert!((scale - 400.0 / 600.0).abs() < 0.001);
+        assert_eq!(scale.0, scale.1);
+        assert!((scale.0 - 400.0 / 600.0).abs() < 0.001);
     }
 
     #[test]
@@ -381,6 +614,7 @@ mod tests {
             300.0, // Smaller than content area
             500.0,
             ScalingMode::None,
+            ContentAlignment::default(),
             &slot_left,
             &grid,
         );
@@ -396,6 +630,7 @@ mod tests {
             300.0,
             500.0,
             ScalingMode::None,
+            ContentAlignment::default(),
             &slot_right,
             &grid,
         );
@@ -403,4 +638,158 @@ mod tests {
         // Content should be at the left edge of content area
         assert!((placement.content_rect.x - content_area.x).abs() < 0.01);
     }
+
+    #[test]
+    fn test_center_alignment_is_equidistant_from_content_edges() {
+        let cell = Rect::new(0.0, 0.0, 400.0, 600.0);
+        let margins = LeafMargins {
+            top_mm: 5.0,
+            bottom_mm: 15.0,
+            fore_edge_mm: 5.0,
+            spine_mm: 10.0,
+            cut_mm: 0.0,
+        };
+        let grid = make_grid(PageArrangement::Folio);
+        let alignment = ContentAlignment {
+            horizontal: HorizontalAlignment::Center,
+            vertical: VerticalAlignment::Center,
+        };
+
+        for col in [0, 1] {
+            let slot = make_slot(0, col, false);
+            let area = calculate_content_area(&cell, &margins, &slot, &grid);
+            let placement = place_page(
+                &area,
+                200.0,
+                300.0,
+                ScalingMode::None,
+                alignment,
+                &slot,
+                &grid,
+            );
+
+            let rect = &placement.content_rect;
+            assert!(((rect.x - area.x) - (area.right() - rect.right())).abs() < 0.01);
+            assert!(((rect.y - area.y) - (area.top() - rect.top())).abs() < 0.01);
+        }
+    }
+
+    #[test]
+    fn test_fore_edge_and_head_alignment() {
+        let content_area = Rect::new(10.0, 10.0, 400.0, 600.0);
+        let grid = make_grid(PageArrangement::Folio);
+        let alignment = ContentAlignment {
+            horizontal: HorizontalAlignment::TowardForeEdge,
+            vertical: VerticalAlignment::Top,
+        };
+        let place = |slot: &SignatureSlot| {
+            place_page(
+                &content_area,
+                300.0,
+                500.0,
+                ScalingMode::None,
+                alignment,
+                slot,
+                &grid,
+            )
+            .content_rect
+        };
+
+        // Left cell: fold on the right, so the fore-edge is on the left
+        let rect = place(&make_slot(0, 0, false));
+        assert!((rect.x - content_area.x).abs() < 0.01);
+        assert!((rect.top() - content_area.top()).abs() < 0.01);
+
+        // A rotated page has its head at the bottom of the cell
+        let rect = place(&make_slot(0, 1, true));
+        assert!((rect.right() - content_area.right()).abs() < 0.01);
+        assert!((rect.y - content_area.y).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_creep_moves_toward_spine_within_margin() {
+        let cell = Rect::new(0.0, 0.0, 400.0, 600.0);
+        let margins = LeafMargins {
+            top_mm: 0.0,
+            bottom_mm: 0.0,
+            fore_edge_mm: 0.0,
+            spine_mm: 10.0,
+            cut_mm: 0.0,
+        };
+        let grid = make_grid(PageArrangement::Folio);
+        let spine_pt = mm_to_pt(10.0);
+
+        // Left cell: spine on the right, so creep moves content right
+        let slot = make_slot(0, 0, false);
+        let area = calculate_content_area(&cell, &margins, &slot, &grid);
+        let mut placement = place_page(
+            &area,
+            400.0,
+            600.0,
+            ScalingMode::Fit,
+            ContentAlignment::default(),
+            &slot,
+            &grid,
+        );
+        let before = placement.content_rect.x;
+        apply_creep(&mut placement, &cell, 5.0, &grid);
+        assert!((placement.content_rect.x - (before + 5.0)).abs() < 0.01);
+
+        // A shift larger than the spine margin stops at the fold
+        apply_creep(&mut placement, &cell, 1000.0, &grid);
+        assert!((placement.content_rect.right() - cell.right()).abs() < 0.01);
+        assert!((placement.content_rect.x - (before + spine_pt)).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_bleed_extends_outer_edges_only() {
+        let cell = Rect::new(0.0, 0.0, 400.0, 600.0);
+        let margins = LeafMargins {
+            top_mm: 5.0,
+            bottom_mm: 5.0,
+            fore_edge_mm: 5.0,
+            spine_mm: 10.0,
+            cut_mm: 0.0,
+        };
+        let grid = make_grid(PageArrangement::Folio);
+        let bleed = mm_to_pt(3.0);
+
+        // Left cell: fore-edge on the left, spine (fold) on the right
+        let slot = make_slot(0, 0, false);
+        let area = calculate_content_area(&cell, &margins, &slot, &grid);
+        // Source sized to exactly fill the content area plus its bleed
+        let placement = place_page_with_bleed(
+            &area,
+            &cell,
+            area.width + bleed,
+            area.height + 2.0 * bleed,
+            ScalingMode::Fit,
+            ContentAlignment::default(),
+            &slot,
+            &grid,
+            bleed,
+        );
+
+        let content = &placement.content_rect;
+        assert!((content.x - (area.x - bleed)).abs() < 0.01);
+        assert!((content.y - (area.y - bleed)).abs() < 0.01);
+        assert!((content.top() - (area.top() + bleed)).abs() < 0.01);
+        assert!((content.right() - area.right()).abs() < 0.01);
+
+        // The trim line is the content area itself
+        let trim = &placement.trim_rect;
+        assert!((trim.x - area.x).abs() < 0.01);
+        assert!((trim.y - area.y).abs() < 0.01);
+        assert!((trim.width - area.width).abs() < 0.01);
+        assert!((trim.height - area.height).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_turn_improves_fit_only_across_orientations() {
+        let portrait_cell = Rect::new(0.0, 0.0, 396.0, 612.0);
+        assert!(turn_improves_fit(&portrait_cell, 792.0, 612.0));
+        assert!(!turn_improves_fit(&portrait_cell, 612.0, 792.0));
+        // A square page fits the same either way
+        assert!(!turn_improves_fit(&portrait_cell, 500.0, 500.0));
+    }
 }
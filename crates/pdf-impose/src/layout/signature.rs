@@ -1,7 +1,7 @@
 //! Signature slot calculation
 //!
 //! This module calculates how pages are ordered within signatures for
-//! traditional bookbinding layouts (folio, quarto, octavo).
+//! traditional bookbinding layouts (folio, quarto, octavo, sexto, duodecimo).
 //!
 //! ## Traditional Bookbinding Layouts
 //!
@@ -19,50 +19,240 @@
 //! - Side A: Top [5↓, 12↓, 9↓, 8↓], Bottom [4, 13, 16, 1]
 //! - Side B: Top [6↓, 11↓, 10↓, 7↓], Bottom [3, 14, 15, 2]
 //! - Top row rotated 180°
+//!
+//! **Sexto (12 pages, quarto plus a folio offcut):**
+//! - Side A: Top [9↓, 4↓], Middle [12, 1], Bottom [8, 5]
+//! - Side B: Top [3↓, 10↓], Middle [2, 11], Bottom [6, 7]
+//! - The bottom row is cut off, folded, and nested inside the quarto
+//!
+//! **Duodecimo (24 pages, octavo plus two folio offcuts):**
+//! - Side A: Top [5↓, 20↓, 17↓, 8↓], Middle [4, 21, 24, 1], Bottom [14, 11, 16, 9]
+//! - Side B: Top [6↓, 19↓, 18↓, 7↓], Middle [3, 22, 23, 2], Bottom [10, 15, 12, 13]
+//! - The bottom row is cut off and split at the center; the right folio
+//!   nests inside the octavo and the left one inside that
+//!
+//! ## Short-Edge Duplex
+//!
+//! The back sides above are laid out for printers that turn the sheet over
+//! on its long edge. Printers that flip on the short edge need every back
+//! side turned half a turn (see [`turn_back_slots`]), so quarto Side B
+//! prints as Top [7↓, 2↓], Bottom [6, 3].
+//!
+//! ## Right-to-Left Binding
+//!
+//! Books bound on the right mirror every row, so folio Side A becomes
+//! [1, 4] and page 1 ends up as the right-hand page of the folded sheet
+//! with the spine on its right.
 
-use crate::types::PageArrangement;
+use crate::constants::PAGES_PER_LEAF;
+use crate::types::{BindingEdge, PageArrangement, SignaturePlan};
 
-use super::{PageSide, SheetSide, SignatureSlot};
+use super::{GridPosition, PageSide, SheetSide, SignatureSlot};
 
 // =============================================================================
 // Signature Calculation
 // =============================================================================
 
-/// Calculate signature slots for all signatures needed to hold the given pages.
+/// Calculate signature slots for each signature of a plan.
+///
+/// `plan` gives the arrangement of each signature (see [`plan_signatures`]
+/// and [`resolve_signature_plan`]) and `arrangement` the sheet grid they are
+/// printed on. Returns a vector of signatures, where each signature contains
+/// all its slots in the order they appear (front side first, then back side).
 ///
-/// Returns a vector of signatures, where each signature contains all its slots
-/// in the order they appear (front side first, then back side).
+/// Signatures smaller than the grid keep their pages the same size as the
+/// rest: ones with a head fold stay in the top rows so it lines up with the
+/// grid's, and single-row ones move to the bottom row. Their fronts sit in
+/// the left columns, so their backs move to the right columns to land behind
+/// them once the sheet is turned over.
 pub fn calculate_signature_slots(
-    total_pages: usize,
+    plan: &[PageArrangement],
     arrangement: PageArrangement,
+    binding_edge: BindingEdge,
 ) -> Vec<Vec<SignatureSlot>> {
+    let (grid_cols, grid_rows) = arrangement.grid_dimensions();
+
+    plan.iter()
+        .map(|&sig_arrangement| {
+            let mut slots = create_signature_slots(sig_arrangement, binding_edge);
+            if sig_arrangement == arrangement {
+                return slots;
+            }
+            if !slots.iter().any(|s| s.rotated) {
+                let rows = slots.iter().map(|s| s.grid_pos.row + 1).max().unwrap_or(0);
+                let row_offset = grid_rows.saturating_sub(rows);
+                for slot in &mut slots {
+                    slot.grid_pos.row += row_offset;
+                }
+            }
+            // Back column c lies behind front column cols - 1 - c of the
+            // whole grid, not of the signature's own narrower one
+            let cols = slots.iter().map(|s| s.grid_pos.col + 1).max().unwrap_or(0);
+            let col_offset = grid_cols.saturating_sub(cols);
+            for slot in slots
+                .iter_mut()
+                .filter(|slot| slot.sheet_side == SheetSide::Back)
+            {
+                slot.grid_pos.col += col_offset;
+            }
+            slots
+        })
+        .collect()
+}
+
+/// Arrangement of each signature needed to hold `total_pages`.
+///
+/// Normally every signature uses `arrangement` and the last one is padded
+/// with blanks. With `allow_short_last`, a remainder that exactly fills a
+/// smaller arrangement (a 4-page folio, an 8-page quarto, or a shorter custom
+/// signature) gets that arrangement instead, e.g. 52 pages in quarto become
+/// six quartos and a folio.
+pub fn plan_signatures(
+    total_pages: usize,
+    arrangement: PageArrangement,
+    allow_short_last: bool,
+) -> Vec<PageArrangement> {
     let pages_per_sig = arrangement.pages_per_signature();
+    let full_signatures = total_pages / pages_per_sig;
+    let remainder = total_pages % pages_per_sig;
 
-    // Pad to multiple of pages_per_signature
-    let padded_count = ((total_pages + pages_per_sig - 1) / pages_per_sig) * pages_per_sig;
-    let num_signatures = padded_count / pages_per_sig;
+    let mut plan = vec![arrangement; full_signatures];
+    if remainder > 0 {
+        let short = allow_short_last
+            .then(|| short_arrangement(arrangement, remainder.div_ceil(4) * 4))
+            .flatten();
+        plan.push(short.unwrap_or(arrangement));
+    }
+    plan
+}
+
+/// Like [`plan_signatures`] with a short last signature, but a remainder no
+/// smaller standard arrangement holds becomes a custom signature of nested
+/// folios, so the job is padded by at most three blanks.
+///
+/// For example 44 pages in duodecimo become a duodecimo and a 20-page
+/// signature instead of two duodecimos.
+pub fn plan_signatures_min_blanks(
+    total_pages: usize,
+    arrangement: PageArrangement,
+) -> Vec<PageArrangement> {
+    let mut plan = plan_signatures(total_pages, arrangement, true);
+    let needed = total_pages.div_ceil(4) * 4;
+    let planned: usize = plan.iter().map(|sig| sig.pages_per_signature()).sum();
+    if planned > needed
+        && let Some(last) = plan.last_mut()
+    {
+        *last = PageArrangement::Custom {
+            pages_per_signature: last.pages_per_signature() - (planned - needed),
+        };
+    }
+    plan
+}
 
-    (0..num_signatures)
-        .map(|_| create_signature_slots(arrangement))
+/// Arrangement of each signature of an explicit plan, printed on the grid
+/// of `arrangement`.
+///
+/// Counts matching a standard arrangement that fits the grid use it, and
+/// anything else becomes a custom signature. Signatures past the ones needed
+/// for `total_pages` are dropped rather than printed blank.
+pub fn resolve_signature_plan(
+    plan: &SignaturePlan,
+    arrangement: PageArrangement,
+    total_pages: usize,
+) -> Vec<PageArrangement> {
+    let mut covered = 0;
+    plan.pages_per_signature
+        .iter()
+        .take_while(|&&pages| {
+            let needed = covered < total_pages;
+            covered += pages;
+            needed
+        })
+        .map(|&pages| {
+            standard_arrangement(arrangement, pages).unwrap_or(PageArrangement::Custom {
+                pages_per_signature: pages,
+            })
+        })
         .collect()
 }
 
+/// `arrangement` itself if it holds `pages`, otherwise a standard
+/// arrangement of that size whose grid fits inside its grid
+fn standard_arrangement(arrangement: PageArrangement, pages: usize) -> Option<PageArrangement> {
+    let (grid_cols, grid_rows) = arrangement.grid_dimensions();
+    [
+        arrangement,
+        PageArrangement::Folio,
+        PageArrangement::Quarto,
+        PageArrangement::Octavo,
+        PageArrangement::Sexto,
+        PageArrangement::Duodecimo,
+    ]
+    .into_iter()
+    .find(|&candidate| {
+        let (cols, rows) = candidate.grid_dimensions();
+        candidate.pages_per_signature() == pages && cols <= grid_cols && rows <= grid_rows
+    })
+}
+
+/// A smaller arrangement holding exactly `pages` pages, if there is one
+fn short_arrangement(arrangement: PageArrangement, pages: usize) -> Option<PageArrangement> {
+    if pages >= arrangement.pages_per_signature() {
+        return None;
+    }
+    match arrangement {
+        PageArrangement::Custom { .. } => Some(PageArrangement::Custom {
+            pages_per_signature: pages,
+        }),
+        _ => standard_arrangement(arrangement, pages),
+    }
+}
+
 /// Create the slot layout for a single signature.
 ///
 /// The slots are returned in sheet order: all front-side slots first,
 /// then all back-side slots. Within each side, slots are in row-major order
 /// (top-left to bottom-right).
-fn create_signature_slots(arrangement: PageArrangement) -> Vec<SignatureSlot> {
-    match arrangement {
+fn create_signature_slots(
+    arrangement: PageArrangement,
+    binding_edge: BindingEdge,
+) -> Vec<SignatureSlot> {
+    let slots = match arrangement {
         PageArrangement::Folio => create_folio_slots(),
         PageArrangement::Quarto => create_quarto_slots(),
         PageArrangement::Octavo => create_octavo_slots(),
+        PageArrangement::Sexto => create_sexto_slots(),
+        PageArrangement::Duodecimo => create_duodecimo_slots(),
         PageArrangement::Custom {
             pages_per_signature,
         } => create_custom_slots(pages_per_signature),
+    };
+
+    match binding_edge {
+        BindingEdge::Left => slots,
+        BindingEdge::Right => {
+            let cols = arrangement.grid_dimensions().0;
+            mirror_rows(&slots, cols)
+                .into_iter()
+                .enumerate()
+                .map(|(slot_index, mut slot)| {
+                    slot.slot_index = slot_index;
+                    slot.grid_pos.col = cols - 1 - slot.grid_pos.col;
+                    slot
+                })
+                .collect()
+        }
     }
 }
 
+/// Reverse each row of a row-major, per-slot list
+fn mirror_rows<T: Clone>(items: &[T], cols: usize) -> Vec<T> {
+    items
+        .chunks(cols)
+        .flat_map(|row| row.iter().rev().cloned())
+        .collect()
+}
+
 // =============================================================================
 // Page Ordering
 // =============================================================================
@@ -75,8 +265,10 @@ fn create_signature_slots(arrangement: PageArrangement) -> Vec<SignatureSlot> {
 /// - Slot 1 gets page 1 (index 0)
 /// - Slot 2 gets page 2 (index 1)
 /// - Slot 3 gets page 3 (index 2)
-fn calculate_page_order(arrangement: PageArrangement) -> Vec<usize> {
-    match arrangement {
+///
+/// Bound on the right, each row is reversed, so folio returns [0, 3, 2, 1].
+fn calculate_page_order(arrangement: PageArrangement, binding_edge: BindingEdge) -> Vec<usize> {
+    let order = match arrangement {
         PageArrangement::Folio => vec![3, 0, 1, 2],
         PageArrangement::Quarto => vec![
             4, 3, // Side A top: pages 5, 4
@@ -91,6 +283,23 @@ fn calculate_page_order(arrangement: PageArrangement) -> Vec<usize> {
             5, 10, 9, 6, // Side B - bottom row (mirrored)
             2, 13, 14, 1,
         ],
+        PageArrangement::Sexto => vec![
+            8, 3, // Side A top: pages 9, 4
+            11, 0, // Side A middle: pages 12, 1
+            7, 4, // Side A bottom (offcut): pages 8, 5
+            2, 9, // Side B top: pages 3, 10 (mirrored)
+            1, 10, // Side B middle: pages 2, 11 (mirrored)
+            5, 6, // Side B bottom (offcut): pages 6, 7 (mirrored)
+        ],
+        PageArrangement::Duodecimo => vec![
+            // Side A - top row
+            4, 19, 16, 7, // Side A - middle row
+            3, 20, 23, 0, // Side A - bottom row (offcut)
+            13, 10, 15, 8, // Side B - top row (mirrored)
+            5, 18, 17, 6, // Side B - middle row (mirrored)
+            2, 21, 22, 1, // Side B - bottom row (offcut, mirrored)
+            9, 14, 11, 12,
+        ],
         PageArrangement::Custom {
             pages_per_signature,
         } => {
@@ -107,6 +316,11 @@ fn calculate_page_order(arrangement: PageArrangement) -> Vec<usize> {
             }
             order
         }
+    };
+
+    match binding_edge {
+        BindingEdge::Left => order,
+        BindingEdge::Right => mirror_rows(&order, arrangement.grid_dimensions().0),
     }
 }
 
@@ -116,10 +330,11 @@ fn calculate_page_order(arrangement: PageArrangement) -> Vec<usize> {
 /// returns which source page goes in each slot (or None for blank padding).
 pub fn map_pages_to_slots(
     arrangement: PageArrangement,
+    binding_edge: BindingEdge,
     sig_start: usize,
     total_source_pages: usize,
 ) -> Vec<Option<usize>> {
-    calculate_page_order(arrangement)
+    calculate_page_order(arrangement, binding_edge)
         .into_iter()
         .map(|relative_idx| {
             let absolute_idx = sig_start + relative_idx;
@@ -132,6 +347,52 @@ pub fn map_pages_to_slots(
         .collect()
 }
 
+/// Creep steps for each slot of a signature, in slot order.
+///
+/// After folding, the leaves of a signature nest inside one another. The
+/// step counts how many leaves lie inside the slot's leaf: 0 for the
+/// innermost fold, up to `pages_per_signature / 4 - 1` for the outermost.
+pub fn creep_steps(arrangement: PageArrangement, binding_edge: BindingEdge) -> Vec<usize> {
+    let pages_per_sig = arrangement.pages_per_signature();
+    let innermost = (pages_per_sig / 4).saturating_sub(1);
+
+    calculate_page_order(arrangement, binding_edge)
+        .into_iter()
+        .map(|relative_idx| {
+            // Pages 1-2 and their conjugates are on the outermost leaf, and so on inward
+            let depth = relative_idx.min(pages_per_sig - 1 - relative_idx) / 2;
+            innermost.saturating_sub(depth)
+        })
+        .collect()
+}
+
+/// Slot indices holding the first and last pages of a signature.
+///
+/// These two pages face each other across the spine fold on the outside of
+/// the folded signature.
+pub fn outer_spine_slots(
+    arrangement: PageArrangement,
+    binding_edge: BindingEdge,
+) -> (usize, usize) {
+    let last_page = arrangement.pages_per_signature() - 1;
+    let order = calculate_page_order(arrangement, binding_edge);
+    let slot_of = |page| order.iter().position(|&p| p == page).unwrap_or(0);
+    (slot_of(0), slot_of(last_page))
+}
+
+/// Turn the back-side slots of a sheet half a turn on its `cols` x `rows`
+/// grid, for duplex printers that flip the sheet on its short edge
+///
+/// Front-side slots are left alone.
+pub fn turn_back_slots(slots: &mut [SignatureSlot], cols: usize, rows: usize) {
+    for slot in slots
+        .iter_mut()
+        .filter(|slot| slot.sheet_side == SheetSide::Back)
+    {
+        slot.turn_half(cols, rows);
+    }
+}
+
 /// Get slots for a specific sheet side
 pub fn slots_for_side(slots: &[SignatureSlot], side: SheetSide) -> Vec<&SignatureSlot> {
     slots.iter().filter(|s| s.sheet_side == side).collect()
@@ -233,11 +494,103 @@ fn create_octavo_slots() -> Vec<SignatureSlot> {
     ]
 }
 
+// =============================================================================
+// Slot Creation - Sexto
+// =============================================================================
+
+/// Create slots for sexto arrangement (12 pages, 2x3 grid, one cut)
+///
+/// The top two rows fold as a quarto. The bottom row is cut off, folded
+/// once, and nested inside the quarto as its center pages.
+///
+/// Printed sheets:
+/// - Side A: Top row [9↓, 4↓], Middle row [12, 1], Bottom row [8, 5]
+/// - Side B (mirrored): Top row [3↓, 10↓], Middle row [2, 11], Bottom row [6, 7]
+fn create_sexto_slots() -> Vec<SignatureSlot> {
+    vec![
+        // Side A (front) - 2 cols x 3 rows
+        // Top row (rotated 180°)
+        SignatureSlot::new(0, SheetSide::Front, 0, 0, true, PageSide::Recto), // page 9
+        SignatureSlot::new(1, SheetSide::Front, 0, 1, true, PageSide::Verso), // page 4
+        // Middle row (not rotated)
+        SignatureSlot::new(2, SheetSide::Front, 1, 0, false, PageSide::Verso), // page 12
+        SignatureSlot::new(3, SheetSide::Front, 1, 1, false, PageSide::Recto), // page 1
+        // Bottom row, the offcut (not rotated)
+        SignatureSlot::new(4, SheetSide::Front, 2, 0, false, PageSide::Verso), // page 8
+        SignatureSlot::new(5, SheetSide::Front, 2, 1, false, PageSide::Recto), // page 5
+        // Side B (back) - mirrored for duplex
+        // Top row (rotated 180°)
+        SignatureSlot::new(6, SheetSide::Back, 0, 0, true, PageSide::Recto), // page 3
+        SignatureSlot::new(7, SheetSide::Back, 0, 1, true, PageSide::Verso), // page 10
+        // Middle row (not rotated)
+        SignatureSlot::new(8, SheetSide::Back, 1, 0, false, PageSide::Verso), // page 2
+        SignatureSlot::new(9, SheetSide::Back, 1, 1, false, PageSide::Recto), // page 11
+        // Bottom row, the offcut (not rotated)
+        SignatureSlot::new(10, SheetSide::Back, 2, 0, false, PageSide::Verso), // page 6
+        SignatureSlot::new(11, SheetSide::Back, 2, 1, false, PageSide::Recto), // page 7
+    ]
+}
+
+// =============================================================================
+// Slot Creation - Duodecimo
+// =============================================================================
+
+/// Create slots for duodecimo arrangement (24 pages, 4x3 grid, two cuts)
+///
+/// The top two rows fold as an octavo. The bottom row is cut off and split
+/// at the center into two folios; the right one nests inside the octavo
+/// and the left one inside that.
+///
+/// Printed sheets:
+/// - Side A: Top row [5↓, 20↓, 17↓, 8↓], Middle row [4, 21, 24, 1],
+///   Bottom row [14, 11, 16, 9]
+/// - Side B (mirrored): Top row [6↓, 19↓, 18↓, 7↓], Middle row [3, 22, 23, 2],
+///   Bottom row [10, 15, 12, 13]
+fn create_duodecimo_slots() -> Vec<SignatureSlot> {
+    vec![
+        // Side A (front) - 4 cols x 3 rows
+        // Top row (rotated 180°)
+        SignatureSlot::new(0, SheetSide::Front, 0, 0, true, PageSide::Recto), // page 5
+        SignatureSlot::new(1, SheetSide::Front, 0, 1, true, PageSide::Verso), // page 20
+        SignatureSlot::new(2, SheetSide::Front, 0, 2, true, PageSide::Recto), // page 17
+        SignatureSlot::new(3, SheetSide::Front, 0, 3, true, PageSide::Verso), // page 8
+        // Middle row (not rotated)
+        SignatureSlot::new(4, SheetSide::Front, 1, 0, false, PageSide::Verso), // page 4
+        SignatureSlot::new(5, SheetSide::Front, 1, 1, false, PageSide::Recto), // page 21
+        SignatureSlot::new(6, SheetSide::Front, 1, 2, false, PageSide::Verso), // page 24
+        SignatureSlot::new(7, SheetSide::Front, 1, 3, false, PageSide::Recto), // page 1
+        // Bottom row, the offcuts (not rotated)
+        SignatureSlot::new(8, SheetSide::Front, 2, 0, false, PageSide::Verso), // page 14
+        SignatureSlot::new(9, SheetSide::Front, 2, 1, false, PageSide::Recto), // page 11
+        SignatureSlot::new(10, SheetSide::Front, 2, 2, false, PageSide::Verso), // page 16
+        SignatureSlot::new(11, SheetSide::Front, 2, 3, false, PageSide::Recto), // page 9
+        // Side B (back) - mirrored for duplex
+        // Top row (rotated 180°)
+        SignatureSlot::new(12, SheetSide::Back, 0, 0, true, PageSide::Verso), // page 6
+        SignatureSlot::new(13, SheetSide::Back, 0, 1, true, PageSide::Recto), // page 19
+        SignatureSlot::new(14, SheetSide::Back, 0, 2, true, PageSide::Verso), // page 18
+        SignatureSlot::new(15, SheetSide::Back, 0, 3, true, PageSide::Recto), // page 7
+        // Middle row (not rotated)
+        SignatureSlot::new(16, SheetSide::Back, 1, 0, false, PageSide::Recto), // page 3
+        SignatureSlot::new(17, SheetSide::Back, 1, 1, false, PageSide::Verso), // page 22
+        SignatureSlot::new(18, SheetSide::Back, 1, 2, false, PageSide::Recto), // page 23
+        SignatureSlot::new(19, SheetSide::Back, 1, 3, false, PageSide::Verso), // page 2
+        // Bottom row, the offcuts (not rotated)
+        SignatureSlot::new(20, SheetSide::Back, 2, 0, false, PageSide::Verso), // page 10
+        SignatureSlot::new(21, SheetSide::Back, 2, 1, false, PageSide::Recto), // page 15
+        SignatureSlot::new(22, SheetSide::Back, 2, 2, false, PageSide::Verso), // page 12
+        SignatureSlot::new(23, SheetSide::Back, 2, 3, false, PageSide::Recto), // page 13
+    ]
+}
+
 // =============================================================================
 // Slot Creation - Custom
 // =============================================================================
 
 /// Create slots for custom page count using generic saddle-stitch pattern
+///
+/// Each group of 4 pages is its own folio sheet, front then back, and the
+/// sheets nest inside one another with the outermost first.
 fn create_custom_slots(pages_per_signature: usize) -> Vec<SignatureSlot> {
     let sheets = pages_per_signature / 4;
     let mut slots = Vec::with_capacity(pages_per_signature);
@@ -285,6 +638,70 @@ fn create_custom_slots(pages_per_signature: usize) -> Vec<SignatureSlot> {
     slots
 }
 
+// =============================================================================
+// Cut and Stack
+// =============================================================================
+
+/// Create slots for one cut-and-stack sheet (2-up folio, 4-up quarto or
+/// 8-up octavo grid)
+///
+/// Each cell holds one leaf of its own stack, nothing rotated. The front
+/// side carries the rectos; the back carries the versos, mirrored
+/// horizontally for duplex so each lands behind its recto.
+pub fn cut_and_stack_slots(arrangement: PageArrangement) -> Vec<SignatureSlot> {
+    let (cols, rows) = arrangement.grid_dimensions();
+    let cells = cols * rows;
+
+    let mut slots = Vec::with_capacity(cells * PAGES_PER_LEAF);
+    for (side, page_side, first_index) in [
+        (SheetSide::Front, PageSide::Recto, 0),
+        (SheetSide::Back, PageSide::Verso, cells),
+    ] {
+        for row in 0..rows {
+            for col in 0..cols {
+                let index = first_index + row * cols + col;
+                slots.push(SignatureSlot::new(index, side, row, col, false, page_side));
+            }
+        }
+    }
+    slots
+}
+
+/// Number of sheets needed to cut and stack `total_pages`; also the
+/// number of leaves in each stack
+pub fn cut_and_stack_sheet_count(total_pages: usize, arrangement: PageArrangement) -> usize {
+    let (cols, rows) = arrangement.grid_dimensions();
+    total_pages.div_ceil(cols * rows * PAGES_PER_LEAF)
+}
+
+/// Map source pages to the slots of one cut-and-stack sheet.
+///
+/// Stacks are numbered in reading order across the front side, and stack
+/// `n` holds leaves `n * sheet_count` onward, so piling the cut stacks in
+/// order gives the whole book. Padding falls at the end of the last stack.
+pub fn map_cut_and_stack_pages(
+    arrangement: PageArrangement,
+    sheet: usize,
+    sheet_count: usize,
+    total_source_pages: usize,
+) -> Vec<Option<usize>> {
+    let (cols, _) = arrangement.grid_dimensions();
+
+    cut_and_stack_slots(arrangement)
+        .into_iter()
+        .map(|slot| {
+            let GridPosition { row, col } = slot.grid_pos;
+            let (stack, side_offset) = match slot.sheet_side {
+                SheetSide::Front => (row * cols + col, 0),
+                SheetSide::Back => (row * cols + (cols - 1 - col), 1),
+            };
+            let leaf = stack * sheet_count + sheet;
+            let page = leaf * PAGES_PER_LEAF + side_offset;
+            (page < total_source_pages).then_some(page)
+        })
+        .collect()
+}
+
 // =============================================================================
 // Tests
 // =============================================================================
@@ -295,10 +712,75 @@ mod tests {
 
     #[test]
     fn test_folio_page_order() {
-        let order = calculate_page_order(PageArrangement::Folio);
+        let order = calculate_page_order(PageArrangement::Folio, BindingEdge::Left);
         assert_eq!(order, vec![3, 0, 1, 2]);
     }
 
+    #[test]
+    fn test_right_bound_folio_mirrors_columns() {
+        // Page 1 moves to slot 0 (left column) instead of page 4
+        let order = calculate_page_order(PageArrangement::Folio, BindingEdge::Right);
+        assert_eq!(order, vec![0, 3, 2, 1]);
+
+        let slots = create_signature_slots(PageArrangement::Folio, BindingEdge::Right);
+        assert_eq!(slots[0].grid_pos.col, 0);
+        assert_eq!(slots[0].page_side, PageSide::Recto);
+        assert_eq!(slots[1].page_side, PageSide::Verso);
+        assert!(
+            slots
+                .iter()
+                .enumerate()
+                .all(|(idx, slot)| slot.slot_index == idx)
+        );
+
+        assert_eq!(
+            outer_spine_slots(PageArrangement::Folio, BindingEdge::Right),
+            (0, 1)
+        );
+    }
+
+    #[test]
+    fn test_short_edge_flip_turns_quarto_back_side() {
+        let order = calculate_page_order(PageArrangement::Quarto, BindingEdge::Left);
+        let mut slots = create_signature_slots(PageArrangement::Quarto, BindingEdge::Left);
+        turn_back_slots(&mut slots, 2, 2);
+
+        // Back side, in row-major order of the printed sheet, as
+        // (1-based page, rotated)
+        let mut back: Vec<_> = slots
+            .iter()
+            .filter(|slot| slot.sheet_side == SheetSide::Back)
+            .map(|slot| (slot.grid_pos, order[slot.slot_index] + 1, slot.rotated))
+            .collect();
+        back.sort_by_key(|&(pos, _, _)| (pos.row, pos.col));
+        let back: Vec<_> = back
+            .into_iter()
+            .map(|(_, page, rotated)| (page, rotated))
+            .collect();
+
+        // Long edge prints Top [3↓, 6↓], Bottom [2, 7]; short edge turns it
+        assert_eq!(back, [(7, true), (2, true), (6, false), (3, false)]);
+
+        // The front is untouched
+        let front = create_signature_slots(PageArrangement::Quarto, BindingEdge::Left);
+        assert_eq!(slots[..4], front[..4]);
+    }
+
+    #[test]
+    fn test_right_bound_quarto_keeps_rotation() {
+        let order = calculate_page_order(PageArrangement::Quarto, BindingEdge::Right);
+        assert_eq!(order, vec![3, 4, 0, 7, 5, 2, 6, 1]);
+
+        // Rows stay put, so the top row is still the rotated one
+        let slots = create_signature_slots(PageArrangement::Quarto, BindingEdge::Right);
+        assert!(slots[0].rotated && slots[1].rotated);
+        assert!(!slots[2].rotated && !slots[3].rotated);
+        assert_eq!(
+            creep_steps(PageArrangement::Quarto, BindingEdge::Right),
+            vec![0, 0, 1, 1, 0, 0, 1, 1]
+        );
+    }
+
     #[test]
     fn test_folio_slots() {
         let slots = create_folio_slots();
@@ -338,7 +820,7 @@ mod tests {
     #[test]
     fn test_page_mapping_with_padding() {
         // 6 source pages, folio needs 8 (2 signatures)
-        let mapped = map_pages_to_slots(PageArrangement::Folio, 4, 6);
+        let mapped = map_pages_to_slots(PageArrangement::Folio, BindingEdge::Left, 4, 6);
 
         // Second signature: pages 5, 6 exist, 7, 8 are blank
         assert_eq!(mapped[0], None); // page 8 (index 7) - blank
@@ -347,6 +829,320 @@ mod tests {
         assert_eq!(mapped[3], None); // page 7 (index 6) - blank
     }
 
+    #[test]
+    fn test_creep_steps() {
+        // Folio has a single leaf, so there's nothing to compensate
+        assert_eq!(
+            creep_steps(PageArrangement::Folio, BindingEdge::Left),
+            vec![0, 0, 0, 0]
+        );
+
+        // Quarto: pages 1, 2, 7, 8 are on the outer leaf
+        assert_eq!(
+            creep_steps(PageArrangement::Quarto, BindingEdge::Left),
+            vec![0, 0, 1, 1, 0, 0, 1, 1]
+        );
+
+        // Custom 16: four nested sheets, outermost first
+        assert_eq!(
+            creep_steps(
+                PageArrangement::Custom {
+                    pages_per_signature: 16
+                },
+                BindingEdge::Left
+            ),
+            vec![3, 3, 3, 3, 2, 2, 2, 2, 1, 1, 1, 1, 0, 0, 0, 0]
+        );
+    }
+
+    #[test]
+    fn test_outer_spine_slots() {
+        // Pages 1 and 4 / 8 / 16 share the front side's spine fold
+        assert_eq!(
+            outer_spine_slots(PageArrangement::Folio, BindingEdge::Left),
+            (1, 0)
+        );
+        assert_eq!(
+            outer_spine_slots(PageArrangement::Quarto, BindingEdge::Left),
+            (3, 2)
+        );
+        assert_eq!(
+            outer_spine_slots(PageArrangement::Octavo, BindingEdge::Left),
+            (7, 6)
+        );
+    }
+
+    #[test]
+    fn test_cut_and_stack_pages() {
+        // 10 pages 2-up: 3 sheets, stacks of leaves 0-2 and 3-5
+        let sheets = cut_and_stack_sheet_count(10, PageArrangement::Folio);
+        assert_eq!(sheets, 3);
+
+        // Front [1, 7], back mirrored [8, 2]
+        let first = map_cut_and_stack_pages(PageArrangement::Folio, 0, sheets, 10);
+        assert_eq!(first, vec![Some(0), Some(6), Some(7), Some(1)]);
+
+        // Padding is the last leaf of the second stack
+        let last = map_cut_and_stack_pages(PageArrangement::Folio, 2, sheets, 10);
+        assert_eq!(last, vec![Some(4), None, None, Some(5)]);
+    }
+
+    #[test]
+    fn test_cut_and_stack_quarto_back_is_mirrored() {
+        let slots = cut_and_stack_slots(PageArrangement::Quarto);
+        assert_eq!(slots.len(), 8);
+        assert!(slots.iter().all(|s| !s.rotated));
+
+        // One sheet holds four stacks of one leaf each
+        let mapped = map_cut_and_stack_pages(PageArrangement::Quarto, 0, 1, 8);
+        let pages: Vec<_> = mapped.into_iter().map(Option::unwrap).collect();
+        assert_eq!(pages, vec![0, 2, 4, 6, 3, 1, 7, 5]);
+    }
+
+    #[test]
+    fn test_short_last_signature_plan() {
+        // 52 pages: six quartos and a folio instead of a quarto with 4 blanks
+        let plan = plan_signatures(52, PageArrangement::Quarto, true);
+        assert_eq!(plan.len(), 7);
+        assert_eq!(plan[5], PageArrangement::Quarto);
+        assert_eq!(plan[6], PageArrangement::Folio);
+
+        // Without the option, or when no smaller arrangement fits, pad instead
+        assert_eq!(
+            plan_signatures(52, PageArrangement::Quarto, false)[6],
+            PageArrangement::Quarto
+        );
+        assert_eq!(
+            plan_signatures(28, PageArrangement::Octavo, true)[1],
+            PageArrangement::Octavo
+        );
+        assert_eq!(
+            plan_signatures(24, PageArrangement::Octavo, true)[1],
+            PageArrangement::Quarto
+        );
+    }
+
+    #[test]
+    fn test_short_last_signature_slots_sit_in_bottom_row() {
+        let plan = plan_signatures(12, PageArrangement::Quarto, true);
+        let sigs = calculate_signature_slots(&plan, PageArrangement::Quarto, BindingEdge::Left);
+        assert_eq!(sigs.len(), 2);
+        assert_eq!(sigs[1].len(), 4);
+        assert!(sigs[1].iter().all(|s| s.grid_pos.row == 1 && !s.rotated));
+    }
+
+    #[test]
+    fn test_short_last_signature_backs_line_up_with_fronts() {
+        // An octavo and a closing folio, then an octavo and a closing quarto
+        for (total_pages, short) in [(20, PageArrangement::Folio), (24, PageArrangement::Quarto)] {
+            for binding_edge in [BindingEdge::Left, BindingEdge::Right] {
+                let plan = plan_signatures(total_pages, PageArrangement::Octavo, true);
+                assert_eq!(plan[1], short);
+                let sigs = calculate_signature_slots(&plan, PageArrangement::Octavo, binding_edge);
+                let (grid_cols, _) = PageArrangement::Octavo.grid_dimensions();
+                let pages = calculate_page_order(short, binding_edge);
+
+                // Turned over on its long edge, front column c lands on back
+                // column grid_cols - 1 - c, which must hold the other side
+                // of the same leaf
+                let slots = &sigs[1];
+                for (front, &front_page) in slots.iter().zip(&pages) {
+                    if front.sheet_side != SheetSide::Front {
+                        continue;
+                    }
+                    let behind = GridPosition {
+                        row: front.grid_pos.row,
+                        col: grid_cols - 1 - front.grid_pos.col,
+                    };
+                    let back_page = slots
+                        .iter()
+                        .zip(&pages)
+                        .find(|(slot, _)| {
+                            slot.sheet_side == SheetSide::Back && slot.grid_pos == behind
+                        })
+                        .map(|(_, &page)| page);
+                    assert_eq!(
+                        back_page.map(|page| page / 2),
+                        Some(front_page / 2),
+                        "page {} of a {:?} bound {:?} has the wrong page behind it",
+                        front_page + 1,
+                        short,
+                        binding_edge
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_resolve_signature_plan() {
+        let plan = SignaturePlan {
+            pages_per_signature: vec![12, 12, 12, 12, 12, 4],
+        };
+        let custom = PageArrangement::Custom {
+            pages_per_signature: 12,
+        };
+
+        let resolved = resolve_signature_plan(&plan, PageArrangement::Quarto, 62);
+        assert_eq!(resolved.len(), 6);
+        assert!(resolved[..5].iter().all(|&a| a == custom));
+        assert_eq!(resolved[5], PageArrangement::Folio);
+
+        // Unneeded trailing signatures are dropped
+        assert_eq!(
+            resolve_signature_plan(&plan, PageArrangement::Quarto, 20).len(),
+            2
+        );
+
+        // An octavo does not fit a quarto grid, so 16 pages print as custom
+        let plan = SignaturePlan {
+            pages_per_signature: vec![16, 8],
+        };
+        assert_eq!(
+            resolve_signature_plan(&plan, PageArrangement::Quarto, 24),
+            vec![
+                PageArrangement::Custom {
+                    pages_per_signature: 16
+                },
+                PageArrangement::Quarto,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sexto_slots_match_page_order() {
+        let slots = create_sexto_slots();
+        assert_eq!(slots.len(), 12);
+
+        // Only the quarto's top row is rotated; the offcut row is upright
+        let rotated: Vec<_> = slots
+            .iter()
+            .filter(|s| s.rotated)
+            .map(|s| s.slot_index)
+            .collect();
+        assert_eq!(rotated, vec![0, 1, 6, 7]);
+
+        // Page sides follow the page numbers
+        let order = calculate_page_order(PageArrangement::Sexto, BindingEdge::Left);
+        for (slot, page) in slots.iter().zip(order) {
+            assert_eq!(slot.page_side, PageSide::from_page_number(page + 1));
+        }
+    }
+
+    #[test]
+    fn test_duodecimo_slots_match_page_order() {
+        let slots = create_duodecimo_slots();
+        assert_eq!(slots.len(), 24);
+        assert!(slots.iter().all(|s| s.rotated == (s.grid_pos.row == 0)));
+
+        let order = calculate_page_order(PageArrangement::Duodecimo, BindingEdge::Left);
+        for (slot, page) in slots.iter().zip(order) {
+            assert_eq!(slot.page_side, PageSide::from_page_number(page + 1));
+        }
+    }
+
+    #[test]
+    fn test_offcut_arrangements_nest_innermost() {
+        // Sexto: the quarto's two leaves wrap the folio offcut (pages 5-8)
+        assert_eq!(
+            creep_steps(PageArrangement::Sexto, BindingEdge::Left),
+            vec![1, 1, 2, 2, 0, 0, 1, 1, 2, 2, 0, 0]
+        );
+        assert_eq!(
+            outer_spine_slots(PageArrangement::Sexto, BindingEdge::Left),
+            (3, 2)
+        );
+        assert_eq!(
+            outer_spine_slots(PageArrangement::Duodecimo, BindingEdge::Left),
+            (7, 6)
+        );
+    }
+
+    #[test]
+    fn test_plan_signatures_min_blanks() {
+        // 20 and 33 pages in octavo end on a folio, as with a short last signature
+        assert_eq!(
+            plan_signatures_min_blanks(20, PageArrangement::Octavo),
+            [PageArrangement::Octavo, PageArrangement::Folio]
+        );
+        assert_eq!(
+            plan_signatures_min_blanks(33, PageArrangement::Octavo),
+            [
+                PageArrangement::Octavo,
+                PageArrangement::Octavo,
+                PageArrangement::Folio
+            ]
+        );
+
+        // 12 left over on an octavo grid has no standard arrangement
+        assert_eq!(
+            plan_signatures_min_blanks(28, PageArrangement::Octavo)[1],
+            PageArrangement::Custom {
+                pages_per_signature: 12
+            }
+        );
+        // Whole signatures are left alone
+        assert_eq!(
+            plan_signatures_min_blanks(32, PageArrangement::Octavo),
+            [PageArrangement::Octavo; 2]
+        );
+    }
+
+    #[test]
+    fn test_short_signatures_on_offcut_grids() {
+        // A quarto left over on a sexto grid keeps the sexto's head fold
+        let plan = plan_signatures(20, PageArrangement::Sexto, true);
+        assert_eq!(plan, vec![PageArrangement::Sexto, PageArrangement::Quarto]);
+        let sigs = calculate_signature_slots(&plan, PageArrangement::Sexto, BindingEdge::Left);
+        assert!(sigs[1].iter().all(|s| s.grid_pos.row < 2));
+
+        // A folio lands in the offcut row
+        let plan = plan_signatures(16, PageArrangement::Sexto, true);
+        let sigs = calculate_signature_slots(&plan, PageArrangement::Sexto, BindingEdge::Left);
+        assert!(sigs[1].iter().all(|s| s.grid_pos.row == 2));
+
+        // Duodecimo can end on a sexto or an octavo; octavo can't end on a sexto
+        assert_eq!(
+            plan_signatures(36, PageArrangement::Duodecimo, true)[1],
+            PageArrangement::Sexto
+        );
+        assert_eq!(
+            plan_signatures(40, PageArrangement::Duodecimo, true)[1],
+            PageArrangement::Octavo
+        );
+        assert_eq!(
+            plan_signatures(28, PageArrangement::Octavo, true)[1],
+            PageArrangement::Octavo
+        );
+    }
+
+    #[test]
+    fn test_custom_slots_fill_their_grid() {
+        for pages_per_signature in [12, 32] {
+            let arrangement = PageArrangement::Custom {
+                pages_per_signature,
+            };
+            let (cols, rows) = arrangement.grid_dimensions();
+            let cells = cols * rows;
+            let slots = create_signature_slots(arrangement, BindingEdge::Left);
+            assert_eq!(slots.len(), pages_per_signature);
+            assert_eq!(slots.len(), arrangement.sheets_per_signature() * 2 * cells);
+
+            // Every sheet covers each cell of the grid exactly once per side
+            for sheet in slots.chunks(2 * cells) {
+                for side in [SheetSide::Front, SheetSide::Back] {
+                    let mut cells_used: Vec<_> = slots_for_side(sheet, side)
+                        .iter()
+                        .map(|s| s.grid_pos.to_index(cols))
+                        .collect();
+                    cells_used.sort_unstable();
+                    assert_eq!(cells_used, (0..cells).collect::<Vec<_>>());
+                }
+            }
+            assert!(slots.iter().all(|s| !s.rotated));
+        }
+    }
+
     #[test]
     fn test_slots_for_side() {
         let slots = create_quarto_slots();
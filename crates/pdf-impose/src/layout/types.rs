@@ -13,6 +13,7 @@
 
 /// Which side of a bound book this page appears on after folding
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PageSide {
     /// Right-hand page (odd page numbers in final book: 1, 3, 5, ...)
     /// The spine edge is on the left
@@ -49,6 +50,7 @@ impl PageSide {
 
 /// Which physical side of the printed sheet
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SheetSide {
     /// Front of the sheet (printed first in duplex)
     #[default]
@@ -80,6 +82,7 @@ impl SheetSide {
 ///
 /// Row 0 is the top row, column 0 is the leftmost column.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GridPosition {
     /// Row index (0 = top row)
     pub row: usize,
@@ -118,6 +121,7 @@ impl GridPosition {
 /// - Whether it needs rotation
 /// - Which side of the book it will be on after folding
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SignatureSlot {
     /// Index in the flat signature order (0..pages_per_sig)
     pub slot_index: usize,
@@ -154,6 +158,14 @@ impl SignatureSlot {
     pub fn rotation_degrees(&self) -> f32 {
         if self.rotated { 180.0 } else { 0.0 }
     }
+
+    /// Turn the slot half a turn about the center of a `cols` x `rows` grid,
+    /// moving it to the opposite cell and flipping its rotation
+    pub fn turn_half(&mut self, cols: usize, rows: usize) {
+        self.grid_pos =
+            GridPosition::new(rows - 1 - self.grid_pos.row, cols - 1 - self.grid_pos.col);
+        self.rotated = !self.rotated;
+    }
 }
 
 // =============================================================================
@@ -165,6 +177,7 @@ impl SignatureSlot {
 /// Describes the physical layout of pages on a sheet, including
 /// where folds and cuts occur.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GridLayout {
     /// Number of columns in the page grid
     pub cols: usize,
@@ -174,6 +187,10 @@ pub struct GridLayout {
     pub cell_width_pt: f32,
     /// Height of each cell in points
     pub cell_height_pt: f32,
+    /// Gap between neighboring cells in points; each cell is inset by half
+    /// of it on every side
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub cell_gap_pt: f32,
     /// Column indices that have a fold on their right edge
     /// (e.g., for 2 cols: [0] means fold between col 0 and col 1)
     pub vertical_folds: Vec<usize>,
@@ -183,6 +200,8 @@ pub struct GridLayout {
     /// Column indices where vertical cuts occur
     /// (used in octavo where center is cut, not folded)
     pub vertical_cuts: Vec<usize>,
+    /// Row indices that have a cut on their bottom edge
+    pub horizontal_cuts: Vec<usize>,
     /// Whether the spine runs horizontally (true for landscape quarto)
     pub horizontal_spine: bool,
 }
@@ -218,6 +237,44 @@ impl GridLayout {
         col > 0 && self.vertical_cuts.contains(&(col - 1))
     }
 
+    /// Check if a row has a cut on its bottom edge
+    pub fn has_cut_bottom(&self, row: usize) -> bool {
+        self.horizontal_cuts.contains(&row)
+    }
+
+    /// Check if a row has a cut on its top edge
+    pub fn has_cut_top(&self, row: usize) -> bool {
+        row > 0 && self.horizontal_cuts.contains(&(row - 1))
+    }
+
+    /// Which pass of the folding makes the folds running one way (vertical
+    /// or horizontal), 1 for the first
+    ///
+    /// Folds across the spine are made first and the spine fold last.
+    pub fn fold_order(&self, vertical: bool) -> usize {
+        let across_spine = if self.horizontal_spine {
+            &self.vertical_folds
+        } else {
+            &self.horizontal_folds
+        };
+        let along_spine = vertical != self.horizontal_spine;
+        if along_spine && !across_spine.is_empty() {
+            2
+        } else {
+            1
+        }
+    }
+
+    /// Distance in points from one column's left edge to the next one's
+    pub fn col_pitch_pt(&self) -> f32 {
+        self.cell_width_pt + self.cell_gap_pt
+    }
+
+    /// Distance in points from one row's bottom edge to the next one's
+    pub fn row_pitch_pt(&self) -> f32 {
+        self.cell_height_pt + self.cell_gap_pt
+    }
+
     /// Total number of cells in the grid
     pub fn cell_count(&self) -> usize {
         self.cols * self.rows
@@ -253,6 +310,7 @@ impl GridLayout {
 /// Used for cell bounds, content areas, and page placements.
 /// Coordinates are in PDF space (origin at bottom-left).
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Rect {
     /// X position (left edge)
     pub x: f32,
@@ -355,15 +413,22 @@ impl Rect {
 /// This is the result of all layout calculations and contains
 /// everything needed to render the page.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PagePlacement {
     /// Source page index (None = blank page)
     pub source_page: Option<usize>,
     /// Position and size of the page content in points
     pub content_rect: Rect,
-    /// Rotation to apply in degrees (0.0 or 180.0)
+    /// The trimmed page within `content_rect`; smaller only when bleed is added
+    pub trim_rect: Rect,
+    /// Rotation of the slot in degrees (0.0 or 180.0)
     pub rotation_degrees: f32,
-    /// Scale factor applied to the source page
-    pub scale: f32,
+    /// Whether the page is turned a further 90° clockwise to make better use
+    /// of a cell whose orientation differs from its own
+    pub quarter_turn: bool,
+    /// Scale factors (x, y) applied to the source page; they differ only
+    /// when stretching
+    pub scale: (f32, f32),
     /// The signature slot this placement corresponds to
     pub slot: SignatureSlot,
 }
@@ -378,6 +443,15 @@ impl PagePlacement {
     pub fn is_rotated(&self) -> bool {
         self.rotation_degrees.abs() > 0.1
     }
+
+    /// Total clockwise rotation of the page content: 0, 90, 180 or 270
+    pub fn total_rotation_degrees(&self) -> f32 {
+        if self.quarter_turn {
+            (self.rotation_degrees + 90.0) % 360.0
+        } else {
+            self.rotation_degrees
+        }
+    }
 }
 
 // =============================================================================
@@ -389,6 +463,7 @@ impl PagePlacement {
 /// Contains all the page placements and bounds for rendering one side
 /// of a physical sheet.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SheetLayout {
     /// Which side of the physical sheet
     pub side: SheetSide,
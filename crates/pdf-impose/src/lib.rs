@@ -3,17 +3,28 @@ pub mod impose;
 pub mod layout;
 mod marks;
 mod options;
+#[cfg(feature = "presets")]
+mod presets;
 mod preview;
 mod render;
 mod stats;
 mod types;
 
-pub use impose::{impose, load_multiple_pdfs, load_pdf, save_pdf};
+pub use impose::{
+    ImpositionPlan, PlannedSheet, SheetSignature, auto_rotated_pages, impose, impose_cancellable,
+    impose_dummy, impose_split, impose_split_cancellable, impose_split_with_progress,
+    impose_with_progress, load_multiple_pdfs, load_multiple_pdfs_with_password, load_pdf,
+    load_pdf_with_password, merge_documents, plan, plan_imposition, save_pdf, save_split_pdfs,
+};
+#[cfg(feature = "images")]
+pub use impose::{is_image_path, load_image_folder, load_images, load_inputs};
 pub use layout::{
     GridLayout, GridPosition, PagePlacement, PageSide, Rect, SheetLayout, SheetSide, SignatureSlot,
 };
 pub use options::*;
+#[cfg(feature = "presets")]
+pub use presets::{PresetStore, builtin_presets};
 pub use preview::generate_preview;
 pub use render::{create_page_xobject, get_page_dimensions, render_imposed_page};
-pub use stats::calculate_statistics;
+pub use stats::{calculate_statistics, calculate_statistics_for_pages};
 pub use types::*;
@@ -6,10 +6,15 @@
 //! Marks are rendered per-leaf (the folded/trimmed unit), not per-page.
 
 use crate::constants::{
-    BEZIER_CIRCLE_FACTOR, CROP_MARK_GAP, CROP_MARK_LENGTH, CROP_MARK_WIDTH, CUT_LINE_WIDTH,
-    FOLD_LINE_WIDTH, REGISTRATION_MARK_SIZE, REGISTRATION_MARK_WIDTH, SCISSORS_SIZE,
+    BEZIER_CIRCLE_FACTOR, CELL_BORDER_WIDTH, COLLATION_MARK_LENGTH, COLLATION_MARK_WIDTH,
+    COLOR_BAR_CLEARANCE, COLOR_BAR_PATCH_SIZE, CUT_LINE_WIDTH, FOLD_LABEL_FONT_SIZE,
+    FOLD_LABEL_OFFSET, FOLD_LINE_WIDTH, HELVETICA_CHAR_WIDTH_RATIO, PUNCH_GUIDE_WIDTH,
+    REGISTRATION_MARK_SIZE, SCISSORS_SIZE, SEWING_MARK_LENGTH, SEWING_MARK_WIDTH,
+    SLOT_LABEL_FONT_SIZE, SLOT_LABEL_GRAY, SPINE_MARK_LENGTH, SPINE_MARK_WIDTH, mm_to_pt,
 };
-use crate::types::PrinterMarks;
+use crate::layout::SheetSide;
+use crate::types::{HoleSpacing, MarkStyle, PrinterMarks, PunchGuides, SewingMarks};
+use lopdf::{Dictionary, Object};
 
 // =============================================================================
 // Configuration
@@ -21,15 +26,33 @@ use crate::types::PrinterMarks;
 /// - Sheet: The entire output page (e.g., Letter, A3)
 /// - Leaf area: The region inside sheet margins where content is placed
 /// - Cells: Individual page positions within the leaf area (arranged in grid)
+///
+/// The trim box is the sheet as it's trimmed after printing, for crop marks.
 pub struct MarksConfig {
-    /// Number of columns in the page grid
-    pub cols: usize,
-    /// Number of rows in the page grid
-    pub rows: usize,
-    /// Width of each cell (page position) in points
+    /// Width of the sheet in points
+    pub sheet_width: f32,
+    /// Height of the sheet in points
+    pub sheet_height: f32,
+    /// Width of each cell (page position) in points, plus any gap between
+    /// cells
     pub cell_width: f32,
-    /// Height of each cell (page position) in points
+    /// Height of each cell (page position) in points, plus any gap between
+    /// cells
     pub cell_height: f32,
+    /// Column indices with a fold on their right edge
+    pub vertical_folds: Vec<usize>,
+    /// Row indices (from the top) with a fold on their bottom edge
+    pub horizontal_folds: Vec<usize>,
+    /// Folding pass (1 for the first) of the vertical folds
+    pub vertical_fold_order: usize,
+    /// Folding pass (1 for the first) of the horizontal folds
+    pub horizontal_fold_order: usize,
+    /// How this sheet side sees its folds
+    pub fold_direction: FoldDirection,
+    /// Column indices with a cut on their right edge
+    pub vertical_cuts: Vec<usize>,
+    /// Row indices (from the top) with a cut on their bottom edge
+    pub horizontal_cuts: Vec<usize>,
     /// Left edge of the leaf area in points (after sheet margins)
     pub leaf_left: f32,
     /// Bottom edge of the leaf area in points (after sheet margins)
@@ -38,8 +61,97 @@ pub struct MarksConfig {
     pub leaf_right: f32,
     /// Top edge of the leaf area in points
     pub leaf_top: f32,
+    /// The trimmed sheet (the sheet less the bleed), marked by crop marks
+    pub trim_box: ContentBounds,
     /// Content boundaries for each cell (for trim marks)
     pub content_bounds: Vec<ContentBounds>,
+    /// Spine fold on the outside of a signature, for collation and sewing
+    /// marks; only set on the sheet side that forms that outside
+    pub outer_spine: Option<OuterSpine>,
+    /// Binding edge of each leaf, for punch guides
+    pub bound_edges: Vec<BoundEdge>,
+    /// Every cell on the side with the page it holds, for slot labels
+    pub slot_labels: Vec<SlotLabel>,
+}
+
+impl MarksConfig {
+    /// X position of the right edge of column `col`
+    fn column_edge_x(&self, col: usize) -> f32 {
+        self.leaf_left + (col + 1) as f32 * self.cell_width
+    }
+
+    /// Y position of the bottom edge of row `row`, counted from the top
+    fn row_edge_y(&self, row: usize) -> f32 {
+        self.leaf_top - (row + 1) as f32 * self.cell_height
+    }
+}
+
+/// Which way a fold bends, as seen from one side of the sheet
+///
+/// Every fold is made with the front of the sheet (the outer forme, with the
+/// signature's first page) outward, so the front sees mountain folds and the
+/// back valley folds.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum FoldDirection {
+    /// The fold's ridge points toward the viewer
+    Mountain,
+    /// The fold's ridge points away from the viewer
+    Valley,
+}
+
+impl FoldDirection {
+    /// The direction of every fold as seen from `side` of the sheet
+    pub fn seen_from(side: SheetSide) -> Self {
+        if side.is_front() {
+            FoldDirection::Mountain
+        } else {
+            FoldDirection::Valley
+        }
+    }
+}
+
+/// The spine fold as it lies on the outside of a folded signature
+#[derive(Clone, Copy)]
+pub struct OuterSpine {
+    /// 0-based index of this signature in the book
+    pub signature_index: usize,
+    /// Total number of signatures
+    pub signature_count: usize,
+    /// X position of the spine fold line
+    pub spine_x: f32,
+    /// Y position of the spine's head end
+    pub head_y: f32,
+    /// Y position of the spine's tail end
+    pub tail_y: f32,
+}
+
+/// The edge of a leaf that gets bound
+#[derive(Clone, Copy)]
+pub struct BoundEdge {
+    /// Bounds of the leaf's cell
+    pub cell: ContentBounds,
+    /// Which side of the cell is bound
+    pub side: EdgeSide,
+}
+
+/// A cell labelled with the page it holds, for proofing
+#[derive(Clone, Copy)]
+pub struct SlotLabel {
+    /// Bounds of the cell
+    pub cell: ContentBounds,
+    /// Clockwise rotation of the page in the cell: 0, 90, 180 or 270
+    pub rotation_degrees: f32,
+    /// 0-based page in reading order, or `None` for a blank
+    pub page: Option<usize>,
+}
+
+/// A side of a cell
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum EdgeSide {
+    Left,
+    Right,
+    Top,
+    Bottom,
 }
 
 /// Bounds of actual content within a cell
@@ -52,6 +164,16 @@ pub struct ContentBounds {
 }
 
 impl ContentBounds {
+    /// The trim box of a sheet: the sheet inset by `bleed` on every side
+    pub fn trim_box(sheet_width: f32, sheet_height: f32, bleed: f32) -> Self {
+        Self {
+            x: bleed,
+            y: bleed,
+            width: sheet_width - 2.0 * bleed,
+            height: sheet_height - 2.0 * bleed,
+        }
+    }
+
     /// Check if bounds are valid (positive area)
     pub fn is_valid(&self) -> bool {
         self.width > 0.0 && self.height > 0.0
@@ -74,11 +196,15 @@ impl ContentBounds {
 pub fn generate_marks(marks: &PrinterMarks, config: &MarksConfig) -> String {
     let mut ops = String::new();
 
-    // Save graphics state and set default stroke color
-    ops.push_str("q\n0 0 0 RG\n");
+    // Save graphics state and set the stroke color
+    let [r, g, b] = marks.style.color_rgb;
+    ops.push_str(&format!("q\n{} {} {} RG\n", r, g, b));
 
     if marks.fold_lines {
-        ops.push_str(&generate_fold_lines(config));
+        ops.push_str(&generate_fold_lines(config, marks.fold_labels));
+        if marks.fold_labels {
+            ops.push_str(&generate_fold_labels(config, &marks.style));
+        }
     }
 
     if marks.cut_lines {
@@ -86,15 +212,39 @@ pub fn generate_marks(marks: &PrinterMarks, config: &MarksConfig) -> String {
     }
 
     if marks.trim_marks {
-        ops.push_str(&generate_trim_marks(config));
+        ops.push_str(&generate_trim_marks(config, &marks.style));
     }
 
     if marks.crop_marks {
-        ops.push_str(&generate_crop_marks(config));
+        ops.push_str(&generate_crop_marks(config, &marks.style));
     }
 
     if marks.registration_marks {
-        ops.push_str(&generate_registration_marks(config));
+        ops.push_str(&generate_registration_marks(config, &marks.style));
+    }
+
+    if let Some(spine) = &config.outer_spine {
+        if marks.collation_marks {
+            ops.push_str(&generate_collation_mark(spine));
+        }
+        if let Some(sewing) = &marks.sewing_marks {
+            ops.push_str(&generate_sewing_marks(sewing, spine));
+        }
+        if marks.spine_marks {
+            ops.push_str(&generate_spine_marks(spine, &marks.style));
+        }
+    }
+
+    if let Some(punch) = &marks.punch_guides {
+        ops.push_str(&generate_punch_guides(punch, &config.bound_edges));
+    }
+
+    if marks.color_bars {
+        ops.push_str(&generate_color_bars(config, &marks.style));
+    }
+
+    if marks.slot_labels {
+        ops.push_str(&generate_slot_labels(&config.slot_labels));
     }
 
     // Restore graphics state
@@ -108,28 +258,86 @@ pub fn generate_marks(marks: &PrinterMarks, config: &MarksConfig) -> String {
 // =============================================================================
 
 /// Generate fold lines (dashed lines at fold positions)
-fn generate_fold_lines(config: &MarksConfig) -> String {
+///
+/// With `labelled` folds, mountain folds are dash-dotted to tell them from
+/// the dashed valley folds.
+fn generate_fold_lines(config: &MarksConfig, labelled: bool) -> String {
     let mut ops = String::new();
 
     // Set line properties for fold lines (dashed)
-    ops.push_str(&format!("{} w\n[6 3] 0 d\n", FOLD_LINE_WIDTH));
-
-    // Vertical fold lines (between columns)
-    // For 4-column layouts (octavo), the center line (col 2) is a cut, not a fold
-    for col in 1..config.cols {
-        if config.cols == 4 && col == 2 {
-            continue; // Skip center line for octavo - it's a cut line
-        }
-        let x = config.leaf_left + col as f32 * config.cell_width;
+    let dash = match config.fold_direction {
+        FoldDirection::Mountain if labelled => "[6 3 1 3]",
+        _ => "[6 3]",
+    };
+    ops.push_str(&format!("{} w\n{} 0 d\n", FOLD_LINE_WIDTH, dash));
+
+    for &col in &config.vertical_folds {
+        let x = config.column_edge_x(col);
         ops.push_str(&draw_line(x, config.leaf_bottom, x, config.leaf_top));
     }
 
+    for &row in &config.horizontal_folds {
+        let y = config.row_edge_y(row);
+        ops.push_str(&draw_line(config.leaf_left, y, config.leaf_right, y));
+    }
+
     // Reset to solid line
     ops.push_str("[] 0 d\n");
 
     ops
 }
 
+/// Resource name of the font the marks are labelled in
+pub(crate) const MARKS_FONT: &str = "FM";
+
+/// Whether the marks need [`MARKS_FONT`] (Helvetica) in the page's resources
+pub(crate) fn marks_use_font(marks: &PrinterMarks) -> bool {
+    (marks.fold_lines && marks.fold_labels) || marks.slot_labels
+}
+
+/// Label each fold with its folding pass and direction, e.g. "1 mountain"
+///
+/// Vertical folds are labelled at the head of the sheet and horizontal ones
+/// at its left, in the sheet margin where there's room and just inside the
+/// leaf area otherwise.
+fn generate_fold_labels(config: &MarksConfig, style: &MarkStyle) -> String {
+    let direction = match config.fold_direction {
+        FoldDirection::Mountain => "mountain",
+        FoldDirection::Valley => "valley",
+    };
+    let size = FOLD_LABEL_FONT_SIZE;
+    let offset = FOLD_LABEL_OFFSET;
+    let label = |x: f32, y: f32, text: &str| {
+        format!("BT /{MARKS_FONT} {size} Tf {x} {y} Td ({text}) Tj ET\n")
+    };
+
+    let [r, g, b] = style.color_rgb;
+    let mut ops = format!("{} {} {} rg\n", r, g, b);
+
+    let text = format!("{} {}", config.vertical_fold_order, direction);
+    let y = if config.sheet_height - config.leaf_top >= size + 2.0 * offset {
+        config.leaf_top + offset
+    } else {
+        config.leaf_top - offset - size
+    };
+    for &col in &config.vertical_folds {
+        ops.push_str(&label(config.column_edge_x(col) + offset, y, &text));
+    }
+
+    let text = format!("{} {}", config.horizontal_fold_order, direction);
+    let width = text.len() as f32 * size * HELVETICA_CHAR_WIDTH_RATIO;
+    let x = if config.leaf_left >= width + 2.0 * offset {
+        config.leaf_left - offset - width
+    } else {
+        config.leaf_left + offset
+    };
+    for &row in &config.horizontal_folds {
+        ops.push_str(&label(x, config.row_edge_y(row) + offset, &text));
+    }
+
+    ops
+}
+
 // =============================================================================
 // Cut Lines
 // =============================================================================
@@ -141,9 +349,9 @@ fn generate_cut_lines(config: &MarksConfig) -> String {
     // Set line properties for cut lines (solid)
     ops.push_str(&format!("{} w\n[] 0 d\n", CUT_LINE_WIDTH));
 
-    // Horizontal cut lines (between rows)
-    for row in 1..config.rows {
-        let y = config.leaf_bottom + row as f32 * config.cell_height;
+    // Horizontal cuts (e.g. the sexto's bottom row)
+    for &row in &config.horizontal_cuts {
+        let y = config.row_edge_y(row);
         ops.push_str(&draw_line(config.leaf_left, y, config.leaf_right, y));
         ops.push_str(&draw_scissors_horizontal(
             config.leaf_left - SCISSORS_SIZE - 3.0,
@@ -151,9 +359,9 @@ fn generate_cut_lines(config: &MarksConfig) -> String {
         ));
     }
 
-    // Vertical center cut for 4-column layouts (octavo)
-    if config.cols == 4 {
-        let x = config.leaf_left + 2.0 * config.cell_width;
+    // Vertical cuts (e.g. the octavo center)
+    for &col in &config.vertical_cuts {
+        let x = config.column_edge_x(col);
         ops.push_str(&draw_line(x, config.leaf_bottom, x, config.leaf_top));
         ops.push_str(&draw_scissors_vertical(
             x,
@@ -169,13 +377,13 @@ fn generate_cut_lines(config: &MarksConfig) -> String {
 // =============================================================================
 
 /// Generate trim marks (L-shaped marks at corners of each content area)
-fn generate_trim_marks(config: &MarksConfig) -> String {
+fn generate_trim_marks(config: &MarksConfig, style: &MarkStyle) -> String {
     if config.content_bounds.is_empty() {
         return String::new();
     }
 
     let mut ops = String::new();
-    ops.push_str(&format!("{} w\n[] 0 d\n", CROP_MARK_WIDTH));
+    ops.push_str(&format!("{} w\n[] 0 d\n", style.line_width_pt));
 
     for bounds in &config.content_bounds {
         if !bounds.is_valid() {
@@ -186,6 +394,7 @@ fn generate_trim_marks(config: &MarksConfig) -> String {
             bounds.y,
             bounds.right(),
             bounds.top(),
+            style,
         ));
     }
 
@@ -196,76 +405,52 @@ fn generate_trim_marks(config: &MarksConfig) -> String {
 // Crop Marks
 // =============================================================================
 
-/// Generate crop marks (L-shaped marks at corners of the leaf area)
-fn generate_crop_marks(config: &MarksConfig) -> String {
+/// Generate crop marks (L-shaped marks at corners of the trim box)
+///
+/// They sit outside the trimmed sheet, in the bleed around it, so the cut
+/// takes them off.
+fn generate_crop_marks(config: &MarksConfig, style: &MarkStyle) -> String {
+    let trim = &config.trim_box;
     let mut ops = String::new();
-    ops.push_str(&format!("{} w\n[] 0 d\n", CROP_MARK_WIDTH));
+    ops.push_str(&format!("{} w\n[] 0 d\n", style.line_width_pt));
     ops.push_str(&draw_corner_marks(
-        config.leaf_left,
-        config.leaf_bottom,
-        config.leaf_right,
-        config.leaf_top,
+        trim.x,
+        trim.y,
+        trim.right(),
+        trim.top(),
+        style,
     ));
     ops
 }
 
 /// Draw L-shaped corner marks at all four corners of a rectangle
-fn draw_corner_marks(left: f32, bottom: f32, right: f32, top: f32) -> String {
+fn draw_corner_marks(left: f32, bottom: f32, right: f32, top: f32, style: &MarkStyle) -> String {
+    let (gap, length) = (style.gap_pt, style.mark_length_pt);
     let mut ops = String::new();
 
     // Top-left corner
-    ops.push_str(&draw_line(
-        left,
-        top + CROP_MARK_GAP,
-        left,
-        top + CROP_MARK_GAP + CROP_MARK_LENGTH,
-    ));
-    ops.push_str(&draw_line(
-        left - CROP_MARK_GAP,
-        top,
-        left - CROP_MARK_GAP - CROP_MARK_LENGTH,
-        top,
-    ));
+    ops.push_str(&draw_line(left, top + gap, left, top + gap + length));
+    ops.push_str(&draw_line(left - gap, top, left - gap - length, top));
 
     // Top-right corner
-    ops.push_str(&draw_line(
-        right,
-        top + CROP_MARK_GAP,
-        right,
-        top + CROP_MARK_GAP + CROP_MARK_LENGTH,
-    ));
-    ops.push_str(&draw_line(
-        right + CROP_MARK_GAP,
-        top,
-        right + CROP_MARK_GAP + CROP_MARK_LENGTH,
-        top,
-    ));
+    ops.push_str(&draw_line(right, top + gap, right, top + gap + length));
+    ops.push_str(&draw_line(right + gap, top, right + gap + length, top));
 
     // Bottom-left corner
-    ops.push_str(&draw_line(
-        left,
-        bottom - CROP_MARK_GAP,
-        left,
-        bottom - CROP_MARK_GAP - CROP_MARK_LENGTH,
-    ));
-    ops.push_str(&draw_line(
-        left - CROP_MARK_GAP,
-        bottom,
-        left - CROP_MARK_GAP - CROP_MARK_LENGTH,
-        bottom,
-    ));
+    ops.push_str(&draw_line(left, bottom - gap, left, bottom - gap - length));
+    ops.push_str(&draw_line(left - gap, bottom, left - gap - length, bottom));
 
     // Bottom-right corner
     ops.push_str(&draw_line(
         right,
-        bottom - CROP_MARK_GAP,
+        bottom - gap,
         right,
-        bottom - CROP_MARK_GAP - CROP_MARK_LENGTH,
+        bottom - gap - length,
     ));
     ops.push_str(&draw_line(
-        right + CROP_MARK_GAP,
+        right + gap,
         bottom,
-        right + CROP_MARK_GAP + CROP_MARK_LENGTH,
+        right + gap + length,
         bottom,
     ));
 
@@ -276,12 +461,51 @@ fn draw_corner_marks(left: f32, bottom: f32, right: f32, top: f32) -> String {
 // Registration Marks
 // =============================================================================
 
-/// Generate registration marks (crosshair circles at midpoints of leaf edges)
-fn generate_registration_marks(config: &MarksConfig) -> String {
+/// Resource name of the registration color space on pages with marks
+pub(crate) const REGISTRATION_COLOR_SPACE: &str = "CSReg";
+
+/// Color spaces the marks need in the page's resources, if any
+///
+/// Registration marks are stroked in the `All` separation, so they print on
+/// every plate. Viewers and composite printers without that separation draw
+/// it through the tint transform as 100% of each process ink.
+pub(crate) fn marks_color_spaces(marks: &PrinterMarks) -> Option<Dictionary> {
+    if !marks.registration_marks {
+        return None;
+    }
+
+    let mut tint_transform = Dictionary::new();
+    tint_transform.set("FunctionType", Object::Integer(2));
+    tint_transform.set("Domain", vec![0.into(), 1.into()]);
+    tint_transform.set("C0", vec![0.into(), 0.into(), 0.into(), 0.into()]);
+    tint_transform.set("C1", vec![1.into(), 1.into(), 1.into(), 1.into()]);
+    tint_transform.set("N", Object::Integer(1));
+
+    let mut color_spaces = Dictionary::new();
+    color_spaces.set(
+        REGISTRATION_COLOR_SPACE,
+        Object::Array(vec![
+            Object::Name(b"Separation".to_vec()),
+            Object::Name(b"All".to_vec()),
+            Object::Name(b"DeviceCMYK".to_vec()),
+            Object::Dictionary(tint_transform),
+        ]),
+    );
+    Some(color_spaces)
+}
+
+/// Generate registration marks (crosshair circles at midpoints of leaf
+/// edges) in registration color
+///
+/// The page's resources need the color spaces from [`marks_color_spaces`].
+fn generate_registration_marks(config: &MarksConfig, style: &MarkStyle) -> String {
     let mut ops = String::new();
-    ops.push_str(&format!("{} w\n", REGISTRATION_MARK_WIDTH));
+    ops.push_str(&format!(
+        "q\n/{} CS\n1 SCN\n{} w\n",
+        REGISTRATION_COLOR_SPACE, style.line_width_pt
+    ));
 
-    let offset = CROP_MARK_GAP + REGISTRATION_MARK_SIZE;
+    let offset = style.gap_pt + REGISTRATION_MARK_SIZE;
     let half_size = REGISTRATION_MARK_SIZE / 2.0;
 
     let mid_x = (config.leaf_left + config.leaf_right) / 2.0;
@@ -299,6 +523,7 @@ fn generate_registration_marks(config: &MarksConfig) -> String {
         ops.push_str(&draw_registration_mark(x, y, half_size));
     }
 
+    ops.push_str("Q\n");
     ops
 }
 
@@ -316,6 +541,265 @@ fn draw_registration_mark(cx: f32, cy: f32, half_size: f32) -> String {
     ops
 }
 
+// =============================================================================
+// Color Bars
+// =============================================================================
+
+/// Fill colors of the color bar patches, in order: the process inks, black
+/// tints, then the RGB primaries
+const COLOR_BAR_PATCHES: [&str; 10] = [
+    "1 0 0 0 k",    // Cyan
+    "0 1 0 0 k",    // Magenta
+    "0 0 1 0 k",    // Yellow
+    "0 0 0 1 k",    // Black
+    "0 0 0 0.75 k", // 75% black
+    "0 0 0 0.5 k",  // 50% black
+    "0 0 0 0.25 k", // 25% black
+    "1 0 0 rg",     // Red
+    "0 1 0 rg",     // Green
+    "0 0 1 rg",     // Blue
+];
+
+/// Lower-left corner of the color bar on a sheet `sheet_width` wide whose
+/// leaf area starts `leaf_bottom` up from the bottom edge
+///
+/// The bar is centered on the sheet and sits below the reach of the trim
+/// and registration marks. `None` if the bottom margin or the sheet width
+/// can't fit it.
+pub(crate) fn color_bar_origin(
+    sheet_width: f32,
+    leaf_bottom: f32,
+    style: &MarkStyle,
+) -> Option<(f32, f32)> {
+    let mark_reach = style.gap_pt + style.mark_length_pt.max(1.5 * REGISTRATION_MARK_SIZE);
+    let bottom = leaf_bottom - mark_reach - COLOR_BAR_CLEARANCE - COLOR_BAR_PATCH_SIZE;
+    let width = COLOR_BAR_PATCHES.len() as f32 * COLOR_BAR_PATCH_SIZE;
+    if bottom < 0.0 || width > sheet_width - 2.0 * COLOR_BAR_CLEARANCE {
+        return None;
+    }
+    Some(((sheet_width - width) / 2.0, bottom))
+}
+
+/// Generate a color bar (a row of solid patches) in the bottom sheet margin,
+/// if it fits there
+fn generate_color_bars(config: &MarksConfig, style: &MarkStyle) -> String {
+    let Some((left, bottom)) = color_bar_origin(config.sheet_width, config.leaf_bottom, style)
+    else {
+        return String::new();
+    };
+
+    let mut ops = String::new();
+    for (i, color) in COLOR_BAR_PATCHES.iter().enumerate() {
+        ops.push_str(&format!(
+            "{}\n{} {} {} {} re f\n",
+            color,
+            left + i as f32 * COLOR_BAR_PATCH_SIZE,
+            bottom,
+            COLOR_BAR_PATCH_SIZE,
+            COLOR_BAR_PATCH_SIZE
+        ));
+    }
+    ops
+}
+
+// =============================================================================
+// Collation Marks
+// =============================================================================
+
+/// Generate a collation mark (filled block across the spine fold)
+///
+/// Each signature's block sits one step further from the head than the
+/// previous one, so the gathered book block shows a diagonal staircase that
+/// breaks wherever a signature is missing or out of order.
+fn generate_collation_mark(spine: &OuterSpine) -> String {
+    let spine_length = (spine.tail_y - spine.head_y).abs();
+    let direction = (spine.tail_y - spine.head_y).signum();
+
+    // Step by a full block, squeezing the steps if they'd run off the tail
+    let step = if spine.signature_count > 1 {
+        let room = (spine_length - COLLATION_MARK_LENGTH).max(0.0);
+        COLLATION_MARK_LENGTH.min(room / (spine.signature_count - 1) as f32)
+    } else {
+        0.0
+    };
+
+    let start = spine.head_y + direction * spine.signature_index as f32 * step;
+    let end = start + direction * COLLATION_MARK_LENGTH;
+
+    format!(
+        "0 0 0 rg\n{} {} {} {} re f\n",
+        spine.spine_x - COLLATION_MARK_WIDTH / 2.0,
+        start.min(end),
+        COLLATION_MARK_WIDTH,
+        COLLATION_MARK_LENGTH
+    )
+}
+
+// =============================================================================
+// Sewing Marks
+// =============================================================================
+
+/// Generate sewing station ticks (short lines across the spine fold)
+fn generate_sewing_marks(sewing: &SewingMarks, spine: &OuterSpine) -> String {
+    let mut ops = String::new();
+    ops.push_str(&format!("{} w\n[] 0 d\n", SEWING_MARK_WIDTH));
+
+    let half = SEWING_MARK_LENGTH / 2.0;
+    for y in sewing_station_positions(sewing, spine.head_y, spine.tail_y) {
+        ops.push_str(&draw_line(spine.spine_x - half, y, spine.spine_x + half, y));
+    }
+
+    ops
+}
+
+/// Y positions of the sewing stations between `head_y` and `tail_y`
+fn sewing_station_positions(sewing: &SewingMarks, head_y: f32, tail_y: f32) -> Vec<f32> {
+    let direction = (tail_y - head_y).signum();
+    let margin = mm_to_pt(sewing.head_tail_margin_mm);
+    let first = head_y + direction * margin;
+    let span = ((tail_y - head_y).abs() - 2.0 * margin).max(0.0);
+
+    match sewing.stations {
+        0 => Vec::new(),
+        1 => vec![(head_y + tail_y) / 2.0],
+        n => {
+            let gap = span / (n - 1) as f32;
+            (0..n).map(|i| first + direction * i as f32 * gap).collect()
+        }
+    }
+}
+
+// =============================================================================
+// Spine Marks
+// =============================================================================
+
+/// Generate spine ticks (short lines continuing the spine fold past its
+/// head and tail, clear of the leaf by the mark gap)
+fn generate_spine_marks(spine: &OuterSpine, style: &MarkStyle) -> String {
+    let mut ops = String::new();
+    ops.push_str(&format!("{} w\n[] 0 d\n", SPINE_MARK_WIDTH));
+
+    let direction = (spine.tail_y - spine.head_y).signum();
+    let (gap, length) = (style.gap_pt, SPINE_MARK_LENGTH);
+    let x = spine.spine_x;
+    let head = spine.head_y - direction * gap;
+    let tail = spine.tail_y + direction * gap;
+    ops.push_str(&draw_line(x, head, x, head - direction * length));
+    ops.push_str(&draw_line(x, tail, x, tail + direction * length));
+
+    ops
+}
+
+// =============================================================================
+// Punch Guides
+// =============================================================================
+
+/// Generate punch-hole guides (unfilled circles inset from each binding edge)
+fn generate_punch_guides(punch: &PunchGuides, edges: &[BoundEdge]) -> String {
+    let mut ops = String::new();
+    ops.push_str(&format!("{} w\n[] 0 d\n", PUNCH_GUIDE_WIDTH));
+
+    let offset = mm_to_pt(punch.edge_offset_mm);
+    let diameter = mm_to_pt(punch.hole_diameter_mm);
+    let radius = diameter / 2.0;
+
+    for edge in edges {
+        let cell = &edge.cell;
+        let length = match edge.side {
+            EdgeSide::Left | EdgeSide::Right => cell.height,
+            EdgeSide::Top | EdgeSide::Bottom => cell.width,
+        };
+
+        for along in hole_positions(punch.spacing, length, diameter) {
+            let (cx, cy) = match edge.side {
+                EdgeSide::Left => (cell.x + offset, cell.top() - along),
+                EdgeSide::Right => (cell.right() - offset, cell.top() - along),
+                EdgeSide::Top => (cell.x + along, cell.top() - offset),
+                EdgeSide::Bottom => (cell.x + along, cell.y + offset),
+            };
+            ops.push_str(&draw_circle(cx, cy, radius));
+        }
+    }
+
+    ops
+}
+
+/// Hole centers as distances from the start of an edge `length` long
+fn hole_positions(spacing: HoleSpacing, length: f32, diameter: f32) -> Vec<f32> {
+    match spacing {
+        HoleSpacing::Count(count) => {
+            let share = length / count as f32;
+            (0..count).map(|i| (i as f32 + 0.5) * share).collect()
+        }
+        HoleSpacing::PitchMm(pitch_mm) => {
+            let pitch = mm_to_pt(pitch_mm);
+            if length < diameter || pitch <= 0.0 {
+                return Vec::new();
+            }
+            let count = ((length - diameter) / pitch).floor() as usize + 1;
+            let start = (length - (count - 1) as f32 * pitch) / 2.0;
+            (0..count).map(|i| start + i as f32 * pitch).collect()
+        }
+    }
+}
+
+// =============================================================================
+// Cell Borders
+// =============================================================================
+
+/// Frame each placed page with a thin black rectangle at its trim line
+pub(crate) fn generate_cell_borders(bounds: &[ContentBounds]) -> String {
+    let mut ops = format!("q\n0 0 0 RG\n{} w\n[] 0 d\n", CELL_BORDER_WIDTH);
+    for cell in bounds.iter().filter(|cell| cell.is_valid()) {
+        ops.push_str(&format!(
+            "{} {} {} {} re S\n",
+            cell.x, cell.y, cell.width, cell.height
+        ));
+    }
+    ops.push_str("Q\n");
+    ops
+}
+
+// =============================================================================
+// Slot Labels
+// =============================================================================
+
+/// Label each cell with its 1-based page number, or "BLANK", in light gray
+/// at the cell's center
+///
+/// Labels turn with the page in the cell so they read the same way as it,
+/// and shrink to fit cells too narrow for the full font size.
+fn generate_slot_labels(labels: &[SlotLabel]) -> String {
+    let mut ops = format!("q\n{} g\n", SLOT_LABEL_GRAY);
+    for label in labels.iter().filter(|label| label.cell.is_valid()) {
+        let text = match label.page {
+            Some(page) => (page + 1).to_string(),
+            None => "BLANK".to_string(),
+        };
+        let cell = &label.cell;
+        // Cell width and height as seen along the turned text
+        let ([a, b, c, d], across, along) = match label.rotation_degrees as i32 {
+            90 => ([0, -1, 1, 0], cell.height, cell.width),
+            180 => ([-1, 0, 0, -1], cell.width, cell.height),
+            270 => ([0, 1, -1, 0], cell.height, cell.width),
+            _ => ([1, 0, 0, 1], cell.width, cell.height),
+        };
+        let char_width = text.len() as f32 * HELVETICA_CHAR_WIDTH_RATIO;
+        let size = SLOT_LABEL_FONT_SIZE
+            .min(across * 0.8 / char_width)
+            .min(along * 0.5);
+        ops.push_str(&format!(
+            "q {a} {b} {c} {d} {} {} cm BT /{MARKS_FONT} {size} Tf {} {} Td ({text}) Tj ET Q\n",
+            cell.x + cell.width / 2.0,
+            cell.y + cell.height / 2.0,
+            -char_width * size / 2.0,
+            -size / 3.0,
+        ));
+    }
+    ops.push_str("Q\n");
+    ops
+}
+
 // =============================================================================
 // Scissors Symbol
 // =============================================================================
@@ -1,3 +1,5 @@
+use crate::constants::PAGE_NUMBER_FONT_SIZE;
+use crate::layout::{plan_signatures, plan_signatures_min_blanks, resolve_signature_plan};
 use crate::types::*;
 use std::path::PathBuf;
 
@@ -7,22 +9,116 @@ use serde::{Deserialize, Serialize};
 /// Comprehensive imposition configuration
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
 pub struct ImpositionOptions {
     // Input
     pub input_files: Vec<PathBuf>,
+    /// Optional page selection per input file, matched by position
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub input_ranges: Vec<Option<PageRange>>,
+    /// Password for encrypted inputs; never written to a saved configuration
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub password: Option<String>,
+    /// Reverse the selected pages, for sources scanned last page first;
+    /// flyleaves stay at the front and back
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub reverse_source: bool,
+    /// Insert a blank page before each of these 0-based positions in the
+    /// selected pages (not counting cover pages), e.g. to start a chapter on
+    /// a recto
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub blank_inserts: Vec<usize>,
+    /// Cover pages to keep out of the body
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub cover: CoverOptions,
+    /// Start each input file on a recto, inserting a blank before any file
+    /// that would otherwise begin on a verso
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub chapter_recto: bool,
+    /// 0-based positions in the selected pages (like `blank_inserts`) of
+    /// pages that must start on a recto; a blank goes before any that would
+    /// otherwise fall on a verso
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub chapter_starts_recto: Vec<usize>,
 
     // Binding and arrangement
     pub binding_type: BindingType,
+    /// Side of the spine; `Right` mirrors the signatures for right-to-left books
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub binding_edge: BindingEdge,
+    /// Which way the spine fold runs across the sheet; only a quarto
+    /// signature can force a horizontal one
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub spine_edge: SpineEdge,
     pub page_arrangement: PageArrangement,
+    /// Let the last signature use a smaller arrangement instead of padding
+    /// it with blanks, when the leftover pages fill one exactly
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub allow_short_last_signature: bool,
+    /// Like `allow_short_last_signature`, but a remainder that no smaller
+    /// arrangement holds exactly becomes a custom signature of nested folios,
+    /// so no more than three blanks are added
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub variable_last_signature: bool,
+    /// Explicit pages per signature; overrides the uniform arrangement,
+    /// `allow_short_last_signature` and `variable_last_signature`
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub signature_plan: Option<SignaturePlan>,
+    /// Repeat each source page across the whole sheet instead of binding;
+    /// overrides the binding type and arrangement
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub step_and_repeat: Option<StepAndRepeat>,
+    /// Edge the duplex printer flips sheets on; `ShortEdge` turns every back
+    /// side half a turn
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub duplex_flip: FlipEdge,
+    /// Order in which pages fill the cells with N-up binding
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub n_up_order: NUpOrder,
 
     // Output configuration
     pub output_paper_size: PaperSize,
     pub output_orientation: Orientation,
     pub output_format: OutputFormat,
+    /// Order of the backs in single-sided and two-sided output
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub single_sided_back_order: BackOrder,
     pub scaling_mode: ScalingMode,
+    /// Where content sits in its leaf when it doesn't fill it
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub content_alignment: ContentAlignment,
+    /// Turn pages 90° when that lets them fit their cell at a larger scale,
+    /// e.g. landscape pages in a portrait book
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub auto_rotate_to_fit: bool,
+    /// Page boundary of the source pages to place
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub source_box: PageBox,
+    /// Size to impose source pages at when their page box has no usable
+    /// size, e.g. a `[0 0 0 0]` MediaBox
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub fallback_page_size: PaperSize,
 
     // Margins
     pub margins: Margins,
+    /// Creep compensation per nested leaf in mm (roughly the paper thickness)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub creep_mm: f32,
+    /// Bleed in mm added past the trim line on outer and cut edges
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub bleed_mm: f32,
+    /// Gutter in mm between the cells of grids that are cut apart (N-up,
+    /// cut and stack, step and repeat); folded layouts ignore it
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub cell_gap_mm: f32,
+    /// Stroke a thin frame around each placed page, e.g. to set the slides
+    /// of an N-up handout apart
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub cell_borders: bool,
+    /// Shift in mm (x, y) applied to everything printed on back sides, to
+    /// line them up with the fronts on printers whose duplex feed drifts
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub back_side_offset_mm: (f32, f32),
 
     // Printer's marks
     pub marks: PrinterMarks,
@@ -30,6 +126,19 @@ pub struct ImpositionOptions {
     // Page numbering
     pub add_page_numbers: bool,
     pub page_number_start: usize,
+    /// Page number font size in points
+    #[cfg_attr(feature = "serde", serde(default = "default_page_number_font_size"))]
+    pub page_number_font_size: f32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub page_number_vertical: PageNumberVertical,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub page_number_horizontal: PageNumberHorizontal,
+
+    // Folding dummy
+    /// Draw each page's book page number, signature and an up arrow instead
+    /// of its content, for folding a dummy to check the imposition
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub dummy_mode: bool,
 
     // Flyleaves
     pub front_flyleaves: usize,
@@ -38,32 +147,99 @@ pub struct ImpositionOptions {
     // Output splitting
     pub split_mode: SplitMode,
 
+    // Output compression
+    /// Flate-compress output streams that don't already have a filter
+    #[cfg_attr(feature = "serde", serde(default = "default_compress_output"))]
+    pub compress_output: bool,
+
     // Rotation for source pages
     pub source_rotation: Rotation,
+
+    // Output metadata
+    /// Title for the output's document information, instead of the first
+    /// input's title
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub title_override: Option<String>,
+    /// Add an outline to the output with an entry per signature and one
+    /// per sheet side beneath it, for finding sheets when proofing on screen
+    #[cfg_attr(feature = "serde", serde(default = "default_generate_outline"))]
+    pub generate_outline: bool,
+    /// Add where each input bookmark's page landed to its title, e.g.
+    /// "Chapter 3 (p. 57 → sheet 8 back, slot 3)", for proofing
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub bookmark_locations: bool,
 }
 
 impl Default for ImpositionOptions {
     fn default() -> Self {
         Self {
             input_files: Vec::new(),
+            input_ranges: Vec::new(),
+            password: None,
+            reverse_source: false,
+            blank_inserts: Vec::new(),
+            cover: CoverOptions::default(),
+            chapter_recto: false,
+            chapter_starts_recto: Vec::new(),
             binding_type: BindingType::Signature,
+            binding_edge: BindingEdge::Left,
+            spine_edge: SpineEdge::Auto,
+            allow_short_last_signature: false,
+            variable_last_signature: false,
+            signature_plan: None,
+            step_and_repeat: None,
+            n_up_order: NUpOrder::RowMajor,
+            duplex_flip: FlipEdge::LongEdge,
             page_arrangement: PageArrangement::Quarto,
             output_paper_size: PaperSize::Letter,
             output_orientation: Orientation::Portrait,
             output_format: OutputFormat::DoubleSided,
+            single_sided_back_order: BackOrder::Reversed,
             scaling_mode: ScalingMode::Fit,
+            content_alignment: ContentAlignment::default(),
+            auto_rotate_to_fit: false,
+            source_box: PageBox::CropBox,
+            fallback_page_size: PaperSize::Letter,
             margins: Margins::default(),
+            creep_mm: 0.0,
+            bleed_mm: 0.0,
+            cell_gap_mm: 0.0,
+            cell_borders: false,
+            back_side_offset_mm: (0.0, 0.0),
             marks: PrinterMarks::default(),
             add_page_numbers: false,
             page_number_start: 1,
+            page_number_font_size: PAGE_NUMBER_FONT_SIZE,
+            page_number_vertical: PageNumberVertical::Footer,
+            page_number_horizontal: PageNumberHorizontal::Outer,
+            dummy_mode: false,
             front_flyleaves: 0,
             back_flyleaves: 0,
             split_mode: SplitMode::None,
+            compress_output: true,
             source_rotation: Rotation::None,
+            title_override: None,
+            generate_outline: true,
+            bookmark_locations: false,
         }
     }
 }
 
+#[cfg(feature = "serde")]
+fn default_compress_output() -> bool {
+    true
+}
+
+#[cfg(feature = "serde")]
+fn default_generate_outline() -> bool {
+    true
+}
+
+#[cfg(feature = "serde")]
+fn default_page_number_font_size() -> f32 {
+    PAGE_NUMBER_FONT_SIZE
+}
+
 impl ImpositionOptions {
     /// Load options from JSON file
     #[cfg(feature = "serde")]
@@ -83,14 +259,87 @@ impl ImpositionOptions {
         Ok(())
     }
 
+    /// Whether the output is written as several files, through
+    /// [`impose_split`](crate::impose_split) and
+    /// [`save_split_pdfs`](crate::save_split_pdfs)
+    pub fn writes_multiple_files(&self) -> bool {
+        self.split_mode != SplitMode::None
+            || self.output_format == OutputFormat::TwoSided
+            || self.cover.separate()
+    }
+
+    /// Whether `cell_gap_mm` applies: the sheet is a grid of cells that are
+    /// cut apart with nothing folded
+    pub fn uses_cell_gap(&self) -> bool {
+        self.step_and_repeat.is_some()
+            || matches!(
+                self.binding_type,
+                BindingType::NUp { .. } | BindingType::CutAndStack
+            )
+    }
+
+    /// How to save the output, following `compress_output`
+    pub fn save_options(&self) -> SaveOptions {
+        SaveOptions {
+            compress_streams: self.compress_output,
+            ..Default::default()
+        }
+    }
+
+    /// Arrangement of each signature needed for `total_pages`, from the
+    /// signature plan if there is one
+    pub(crate) fn signature_arrangements(
+        &self,
+        total_pages: usize,
+    ) -> Result<Vec<PageArrangement>> {
+        let Some(plan) = &self.signature_plan else {
+            if self.variable_last_signature {
+                return Ok(plan_signatures_min_blanks(
+                    total_pages,
+                    self.page_arrangement,
+                ));
+            }
+            return Ok(plan_signatures(
+                total_pages,
+                self.page_arrangement,
+                self.allow_short_last_signature,
+            ));
+        };
+
+        let padded_pages = total_pages.div_ceil(4) * 4;
+        if plan.total_pages() < padded_pages {
+            return Err(ImposeError::Config(format!(
+                "Signature plan holds {} pages but {} are needed",
+                plan.total_pages(),
+                padded_pages
+            )));
+        }
+        Ok(resolve_signature_plan(
+            plan,
+            self.page_arrangement,
+            total_pages,
+        ))
+    }
+
     /// Validate the options
     pub fn validate(&self) -> Result<()> {
         if self.input_files.is_empty() {
             return Err(ImposeError::Config("No input files specified".to_string()));
         }
 
+        if self.input_ranges.len() > self.input_files.len() {
+            return Err(ImposeError::Config(
+                "More page ranges than input files".to_string(),
+            ));
+        }
+
+        self.validate_layout()
+    }
+
+    /// Validate the options that shape the layout, leaving out the input files
+    pub fn validate_layout(&self) -> Result<()> {
         let pages_per_sig = self.page_arrangement.pages_per_signature();
-        if pages_per_sig == 0 || pages_per_sig % 4 != 0 {
+        if pages_per_sig == 0 || !pages_per_sig.is_multiple_of(4) {
             return Err(ImposeError::Config(
                 "Pages per signature must be a multiple of 4".to_string(),
             ));
@@ -114,10 +363,315 @@ impl ImpositionOptions {
             _ => {}
         }
 
+        if let BindingType::NUp { rows, cols } = self.binding_type {
+            if rows == 0 || cols == 0 {
+                return Err(ImposeError::Config(
+                    "N-up needs at least one row and one column".to_string(),
+                ));
+            }
+            if self.output_format == OutputFormat::TwoSided {
+                return Err(ImposeError::Config(
+                    "N-up prints one side per sheet and does not support TwoSided output"
+                        .to_string(),
+                ));
+            }
+        }
+
+        if self.spine_edge == SpineEdge::Horizontal
+            && (!self.binding_type.uses_signatures()
+                || self.page_arrangement != PageArrangement::Quarto
+                || self.step_and_repeat.is_some())
+        {
+            return Err(ImposeError::Config(
+                "A horizontal spine needs a Quarto signature; other layouts fold or bind \
+                 along a vertical spine"
+                    .to_string(),
+            ));
+        }
+
+        if let Some(repeat) = &self.step_and_repeat {
+            if repeat.cols == 0 || repeat.rows == 0 || repeat.copies == Some(0) {
+                return Err(ImposeError::Config(
+                    "Step and repeat needs at least one row, one column and one copy".to_string(),
+                ));
+            }
+            if self.output_format == OutputFormat::TwoSided {
+                return Err(ImposeError::Config(
+                    "Step and repeat prints one side per sheet and does not support TwoSided output"
+                        .to_string(),
+                ));
+            }
+        }
+
+        if let Some(plan) = &self.signature_plan {
+            if !self.binding_type.uses_signatures() {
+                return Err(ImposeError::Config(format!(
+                    "A signature plan needs a signature binding, not {:?}",
+                    self.binding_type
+                )));
+            }
+            if plan.pages_per_signature.is_empty()
+                || plan
+                    .pages_per_signature
+                    .iter()
+                    .any(|&pages| pages == 0 || !pages.is_multiple_of(4))
+            {
+                return Err(ImposeError::Config(
+                    "Signature plan entries must be positive multiples of 4".to_string(),
+                ));
+            }
+        }
+
+        if self.binding_type == BindingType::CutAndStack
+            && !matches!(
+                self.page_arrangement,
+                PageArrangement::Folio | PageArrangement::Quarto | PageArrangement::Octavo
+            )
+        {
+            return Err(ImposeError::Config(
+                "Cut and stack needs a 2-up (Folio), 4-up (Quarto) or 8-up (Octavo) arrangement"
+                    .to_string(),
+            ));
+        }
+
+        if !self.creep_mm.is_finite() || self.creep_mm < 0.0 {
+            return Err(ImposeError::Config(
+                "Creep must be zero or a positive number of millimeters".to_string(),
+            ));
+        }
+
+        if let PaperSize::Custom {
+            width_mm,
+            height_mm,
+        } = self.output_paper_size
+            && !(width_mm.is_finite() && width_mm > 0.0 && height_mm.is_finite() && height_mm > 0.0)
+        {
+            return Err(ImposeError::Config(
+                "Custom paper width and height must be positive millimeters".to_string(),
+            ));
+        }
+
+        if let PaperSize::Custom {
+            width_mm,
+            height_mm,
+        } = self.fallback_page_size
+            && !(width_mm.is_finite() && width_mm > 0.0 && height_mm.is_finite() && height_mm > 0.0)
+        {
+            return Err(ImposeError::Config(
+                "Custom fallback page width and height must be positive millimeters".to_string(),
+            ));
+        }
+
+        // Margins that meet or cross leave cells of no size to place pages in
+        let sheet = &self.margins.sheet;
+        let edges = [sheet.top_mm, sheet.bottom_mm, sheet.left_mm, sheet.right_mm];
+        if edges.iter().any(|&mm| !mm.is_finite() || mm < 0.0) {
+            return Err(ImposeError::Config(
+                "Sheet margins must be zero or a positive number of millimeters".to_string(),
+            ));
+        }
+        let (width_mm, height_mm) = self
+            .output_paper_size
+            .dimensions_with_orientation(self.output_orientation);
+        if sheet.left_mm + sheet.right_mm >= width_mm || sheet.top_mm + sheet.bottom_mm >= height_mm
+        {
+            return Err(ImposeError::Config(format!(
+                "Sheet margins leave no room on the {width_mm} x {height_mm} mm sheet"
+            )));
+        }
+
+        if let ScalingMode::Percent(percent) = self.scaling_mode
+            && !(percent.is_finite() && percent > 0.0)
+        {
+            return Err(ImposeError::Config(
+                "Scale percentage must be a positive number".to_string(),
+            ));
+        }
+
+        if self.cover.mode == CoverMode::SeparateDocument {
+            if self.cover.front_pages > 2 || self.cover.back_pages > 2 {
+                return Err(ImposeError::Config(
+                    "A wraparound cover holds at most two pages at each end".to_string(),
+                ));
+            }
+            if !self.cover.paper_thickness_mm.is_finite() || self.cover.paper_thickness_mm < 0.0 {
+                return Err(ImposeError::Config(
+                    "Paper thickness must be zero or a positive number of millimeters".to_string(),
+                ));
+            }
+        }
+
+        if !self.bleed_mm.is_finite() || self.bleed_mm < 0.0 {
+            return Err(ImposeError::Config(
+                "Bleed must be zero or a positive number of millimeters".to_string(),
+            ));
+        }
+
+        if !self.cell_gap_mm.is_finite() || self.cell_gap_mm < 0.0 {
+            return Err(ImposeError::Config(
+                "Cell gap must be zero or a positive number of millimeters".to_string(),
+            ));
+        }
+
+        let (offset_x, offset_y) = self.back_side_offset_mm;
+        if !offset_x.is_finite() || !offset_y.is_finite() {
+            return Err(ImposeError::Config(
+                "Back side offset must be a finite number of millimeters".to_string(),
+            ));
+        }
+
+        if !self.page_number_font_size.is_finite() || self.page_number_font_size <= 0.0 {
+            return Err(ImposeError::Config(
+                "Page number font size must be a positive number of points".to_string(),
+            ));
+        }
+
+        let style = &self.marks.style;
+        if style
+            .color_rgb
+            .iter()
+            .any(|component| !(0.0..=1.0).contains(component))
+        {
+            return Err(ImposeError::Config(
+                "Mark color components must be between 0 and 1".to_string(),
+            ));
+        }
+        if [style.line_width_pt, style.mark_length_pt, style.gap_pt]
+            .iter()
+            .any(|value| !value.is_finite() || *value < 0.0)
+        {
+            return Err(ImposeError::Config(
+                "Mark weight, length and gap must be zero or a positive number of points"
+                    .to_string(),
+            ));
+        }
+
+        if let Some(punch) = &self.marks.punch_guides {
+            if !matches!(
+                self.binding_type,
+                BindingType::SideStitch | BindingType::Spiral
+            ) {
+                return Err(ImposeError::Config(format!(
+                    "Punch guides are only available for SideStitch and Spiral binding, not {:?}",
+                    self.binding_type
+                )));
+            }
+            let spacing_ok = match punch.spacing {
+                HoleSpacing::Count(count) => count > 0,
+                HoleSpacing::PitchMm(pitch) => pitch.is_finite() && pitch > 0.0,
+            };
+            if !spacing_ok {
+                return Err(ImposeError::Config(
+                    "Punch guides need at least one hole and a positive pitch".to_string(),
+                ));
+            }
+            if !punch.edge_offset_mm.is_finite()
+                || punch.edge_offset_mm < 0.0
+                || !punch.hole_diameter_mm.is_finite()
+                || punch.hole_diameter_mm <= 0.0
+            {
+                return Err(ImposeError::Config(
+                    "Punch hole offset and diameter must be positive millimeters".to_string(),
+                ));
+            }
+        }
+
+        if let SplitMode::ByPages(0) | SplitMode::BySheets(0) | SplitMode::BySignatures(0) =
+            self.split_mode
+        {
+            return Err(ImposeError::Config(
+                "Split size must be at least 1".to_string(),
+            ));
+        }
+
         Ok(())
     }
 }
 
+// =============================================================================
+// Builder
+// =============================================================================
+
+/// Fluent builder for [`ImpositionOptions`]
+///
+/// Starts from the defaults; fields without a builder method can still be
+/// set on the struct directly.
+///
+/// ```
+/// use pdf_impose::*;
+///
+/// let options = ImpositionOptions::builder()
+///     .input("book.pdf")
+///     .binding(BindingType::Signature)
+///     .arrangement(PageArrangement::Octavo)
+///     .paper(PaperSize::A3)
+///     .sheet_margin_mm(10.0)
+///     .build()
+///     .unwrap();
+/// assert_eq!(options.margins.sheet, SheetMargins::uniform(10.0));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ImpositionOptionsBuilder {
+    options: ImpositionOptions,
+}
+
+impl ImpositionOptions {
+    /// Start building options from the defaults
+    pub fn builder() -> ImpositionOptionsBuilder {
+        ImpositionOptionsBuilder::default()
+    }
+}
+
+impl ImpositionOptionsBuilder {
+    /// Add an input file; call once per file, in order
+    pub fn input(mut self, path: impl Into<PathBuf>) -> Self {
+        self.options.input_files.push(path.into());
+        self
+    }
+
+    /// Set the binding type
+    pub fn binding(mut self, binding_type: BindingType) -> Self {
+        self.options.binding_type = binding_type;
+        self
+    }
+
+    /// Set the page arrangement (folding scheme)
+    pub fn arrangement(mut self, arrangement: PageArrangement) -> Self {
+        self.options.page_arrangement = arrangement;
+        self
+    }
+
+    /// Set the output paper size
+    pub fn paper(mut self, paper_size: PaperSize) -> Self {
+        self.options.output_paper_size = paper_size;
+        self
+    }
+
+    /// Set the same sheet margin on all four sides
+    pub fn sheet_margin_mm(mut self, margin_mm: f32) -> Self {
+        self.options.margins.sheet = SheetMargins::uniform(margin_mm);
+        self
+    }
+
+    /// Set the margins of each leaf
+    pub fn leaf_margins(mut self, margins: LeafMargins) -> Self {
+        self.options.margins.leaf = margins;
+        self
+    }
+
+    /// Set the printer's marks
+    pub fn marks(mut self, marks: PrinterMarks) -> Self {
+        self.options.marks = marks;
+        self
+    }
+
+    /// Validate and return the options
+    pub fn build(self) -> Result<ImpositionOptions> {
+        self.options.validate()?;
+        Ok(self.options)
+    }
+}
+
 #[cfg(feature = "serde")]
 mod serde_impls {
     use super::*;
@@ -129,12 +683,20 @@ mod serde_impls {
         where
             S: serde::Serializer,
         {
+            use serde::ser::SerializeStruct;
             let s = match self {
                 BindingType::Signature => "Signature",
                 BindingType::PerfectBinding => "PerfectBinding",
                 BindingType::SideStitch => "SideStitch",
                 BindingType::Spiral => "Spiral",
                 BindingType::CaseBinding => "CaseBinding",
+                BindingType::CutAndStack => "CutAndStack",
+                BindingType::NUp { rows, cols } => {
+                    let mut s = serializer.serialize_struct("NUp", 2)?;
+                    s.serialize_field("rows", rows)?;
+                    s.serialize_field("cols", cols)?;
+                    return s.end();
+                }
             };
             serializer.serialize_str(s)
         }
@@ -142,18 +704,85 @@ mod serde_impls {
 
     #[cfg(feature = "serde")]
     impl<'de> Deserialize<'de> for BindingType {
+        fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            use serde::de::{self, MapAccess, Visitor};
+            use std::fmt;
+
+            struct BindingTypeVisitor;
+
+            impl<'de> Visitor<'de> for BindingTypeVisitor {
+                type Value = BindingType;
+
+                fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                    formatter.write_str("a binding type")
+                }
+
+                fn visit_str<E>(self, value: &str) -> std::result::Result<BindingType, E>
+                where
+                    E: de::Error,
+                {
+                    match value {
+                        "Signature" => Ok(BindingType::Signature),
+                        "PerfectBinding" => Ok(BindingType::PerfectBinding),
+                        "SideStitch" => Ok(BindingType::SideStitch),
+                        "Spiral" => Ok(BindingType::Spiral),
+                        "CaseBinding" => Ok(BindingType::CaseBinding),
+                        "CutAndStack" => Ok(BindingType::CutAndStack),
+                        _ => Err(serde::de::Error::custom("Unknown binding type")),
+                    }
+                }
+
+                fn visit_map<M>(self, mut map: M) -> std::result::Result<BindingType, M::Error>
+                where
+                    M: MapAccess<'de>,
+                {
+                    let (mut rows, mut cols) = (None, None);
+                    while let Some(key) = map.next_key::<String>()? {
+                        match key.as_str() {
+                            "rows" => rows = Some(map.next_value()?),
+                            "cols" => cols = Some(map.next_value()?),
+                            _ => {
+                                let _: serde::de::IgnoredAny = map.next_value()?;
+                            }
+                        }
+                    }
+
+                    Ok(BindingType::NUp {
+                        rows: rows.ok_or_else(|| de::Error::missing_field("rows"))?,
+                        cols: cols.ok_or_else(|| de::Error::missing_field("cols"))?,
+                    })
+                }
+            }
+
+            deserializer.deserialize_any(BindingTypeVisitor)
+        }
+    }
+
+    impl Serialize for BindingEdge {
+        fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            serializer.serialize_str(match self {
+                BindingEdge::Left => "Left",
+                BindingEdge::Right => "Right",
+            })
+        }
+    }
+
+    impl<'de> Deserialize<'de> for BindingEdge {
         fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
         where
             D: serde::Deserializer<'de>,
         {
             let s = String::deserialize(deserializer)?;
             match s.as_str() {
-                "Signature" => Ok(BindingType::Signature),
-                "PerfectBinding" => Ok(BindingType::PerfectBinding),
-                "SideStitch" => Ok(BindingType::SideStitch),
-                "Spiral" => Ok(BindingType::Spiral),
-                "CaseBinding" => Ok(BindingType::CaseBinding),
-                _ => Err(serde::de::Error::custom("Unknown binding type")),
+                "Left" => Ok(BindingEdge::Left),
+                "Right" => Ok(BindingEdge::Right),
+                _ => Err(serde::de::Error::custom("Unknown binding edge")),
             }
         }
     }
@@ -168,6 +797,8 @@ mod serde_impls {
                 PageArrangement::Folio => serializer.serialize_str("Folio"),
                 PageArrangement::Quarto => serializer.serialize_str("Quarto"),
                 PageArrangement::Octavo => serializer.serialize_str("Octavo"),
+                PageArrangement::Sexto => serializer.serialize_str("Sexto"),
+                PageArrangement::Duodecimo => serializer.serialize_str("Duodecimo"),
                 PageArrangement::Custom {
                     pages_per_signature,
                 } => {
@@ -204,9 +835,11 @@ mod serde_impls {
                         "Folio" => Ok(PageArrangement::Folio),
                         "Quarto" => Ok(PageArrangement::Quarto),
                         "Octavo" => Ok(PageArrangement::Octavo),
+                        "Sexto" => Ok(PageArrangement::Sexto),
+                        "Duodecimo" => Ok(PageArrangement::Duodecimo),
                         _ => Err(de::Error::unknown_variant(
                             value,
-                            &["Folio", "Quarto", "Octavo", "Custom"],
+                            &["Folio", "Quarto", "Octavo", "Sexto", "Duodecimo", "Custom"],
                         )),
                     }
                 }
@@ -363,35 +996,298 @@ mod serde_impls {
     }
 
     impl Serialize for ScalingMode {
+        fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            use serde::ser::SerializeStruct;
+            match self {
+                ScalingMode::Fit => serializer.serialize_str("Fit"),
+                ScalingMode::FitNoUpscale => serializer.serialize_str("FitNoUpscale"),
+                ScalingMode::Fill => serializer.serialize_str("Fill"),
+                ScalingMode::None => serializer.serialize_str("None"),
+                ScalingMode::Stretch => serializer.serialize_str("Stretch"),
+                ScalingMode::Percent(percent) => {
+                    let mut s = serializer.serialize_struct("Percent", 1)?;
+                    s.serialize_field("percent", percent)?;
+                    s.end()
+                }
+            }
+        }
+    }
+
+    impl<'de> Deserialize<'de> for ScalingMode {
+        fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            use serde::de::{self, MapAccess, Visitor};
+            use std::fmt;
+
+            struct ScalingModeVisitor;
+
+            impl<'de> Visitor<'de> for ScalingModeVisitor {
+                type Value = ScalingMode;
+
+                fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                    formatter.write_str("a scaling mode")
+                }
+
+                fn visit_str<E>(self, value: &str) -> std::result::Result<ScalingMode, E>
+                where
+                    E: de::Error,
+                {
+                    match value {
+                        "Fit" => Ok(ScalingMode::Fit),
+                        "FitNoUpscale" => Ok(ScalingMode::FitNoUpscale),
+                        "Fill" => Ok(ScalingMode::Fill),
+                        "None" => Ok(ScalingMode::None),
+                        "Stretch" => Ok(ScalingMode::Stretch),
+                        _ => Err(de::Error::unknown_variant(
+                            value,
+                            &["Fit", "FitNoUpscale", "Fill", "None", "Stretch", "Percent"],
+                        )),
+                    }
+                }
+
+                fn visit_map<M>(self, mut map: M) -> std::result::Result<ScalingMode, M::Error>
+                where
+                    M: MapAccess<'de>,
+                {
+                    let mut percent = None;
+
+                    while let Some(key) = map.next_key::<String>()? {
+                        match key.as_str() {
+                            "percent" => percent = Some(map.next_value()?),
+                            _ => {
+                                let _: serde::de::IgnoredAny = map.next_value()?;
+                            }
+                        }
+                    }
+
+                    percent
+                        .map(ScalingMode::Percent)
+                        .ok_or_else(|| de::Error::missing_field("percent"))
+                }
+            }
+
+            deserializer.deserialize_any(ScalingModeVisitor)
+        }
+    }
+
+    impl Serialize for BackOrder {
         fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
         where
             S: serde::Serializer,
         {
             serializer.serialize_str(match self {
-                ScalingMode::Fit => "Fit",
-                ScalingMode::Fill => "Fill",
-                ScalingMode::None => "None",
-                ScalingMode::Stretch => "Stretch",
+                BackOrder::Normal => "Normal",
+                BackOrder::Reversed => "Reversed",
             })
         }
     }
 
-    impl<'de> Deserialize<'de> for ScalingMode {
+    impl<'de> Deserialize<'de> for BackOrder {
         fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
         where
             D: serde::Deserializer<'de>,
         {
             let s = String::deserialize(deserializer)?;
             match s.as_str() {
-                "Fit" => Ok(ScalingMode::Fit),
-                "Fill" => Ok(ScalingMode::Fill),
-                "None" => Ok(ScalingMode::None),
-                "Stretch" => Ok(ScalingMode::Stretch),
-                _ => Err(serde::de::Error::custom("Unknown scaling mode")),
+                "Normal" => Ok(BackOrder::Normal),
+                "Reversed" => Ok(BackOrder::Reversed),
+                _ => Err(serde::de::Error::custom("Unknown back order")),
             }
         }
     }
 
+    impl Serialize for CoverMode {
+        fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            serializer.serialize_str(match self {
+                CoverMode::Skip => "Skip",
+                CoverMode::SeparateDocument => "SeparateDocument",
+            })
+        }
+    }
+
+    impl<'de> Deserialize<'de> for CoverMode {
+        fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            let s = String::deserialize(deserializer)?;
+            match s.as_str() {
+                "Skip" => Ok(CoverMode::Skip),
+                "SeparateDocument" => Ok(CoverMode::SeparateDocument),
+                _ => Err(serde::de::Error::custom("Unknown cover mode")),
+            }
+        }
+    }
+
+    impl Serialize for PageBox {
+        fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            serializer.serialize_str(match self {
+                PageBox::MediaBox => "MediaBox",
+                PageBox::CropBox => "CropBox",
+                PageBox::TrimBox => "TrimBox",
+                PageBox::BleedBox => "BleedBox",
+            })
+        }
+    }
+
+    impl<'de> Deserialize<'de> for PageBox {
+        fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            let s = String::deserialize(deserializer)?;
+            match s.as_str() {
+                "MediaBox" => Ok(PageBox::MediaBox),
+                "CropBox" => Ok(PageBox::CropBox),
+                "TrimBox" => Ok(PageBox::TrimBox),
+                "BleedBox" => Ok(PageBox::BleedBox),
+                _ => Err(serde::de::Error::custom("Unknown page box")),
+            }
+        }
+    }
+
+    impl Serialize for PageNumberVertical {
+        fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            serializer.serialize_str(match self {
+                PageNumberVertical::Header => "Header",
+                PageNumberVertical::Footer => "Footer",
+            })
+        }
+    }
+
+    impl<'de> Deserialize<'de> for PageNumberVertical {
+        fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            let s = String::deserialize(deserializer)?;
+            match s.as_str() {
+                "Header" => Ok(PageNumberVertical::Header),
+                "Footer" => Ok(PageNumberVertical::Footer),
+                _ => Err(serde::de::Error::custom("Unknown page number position")),
+            }
+        }
+    }
+
+    impl Serialize for PageNumberHorizontal {
+        fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            serializer.serialize_str(match self {
+                PageNumberHorizontal::Inner => "Inner",
+                PageNumberHorizontal::Outer => "Outer",
+                PageNumberHorizontal::Center => "Center",
+            })
+        }
+    }
+
+    impl<'de> Deserialize<'de> for PageNumberHorizontal {
+        fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            let s = String::deserialize(deserializer)?;
+            match s.as_str() {
+                "Inner" => Ok(PageNumberHorizontal::Inner),
+                "Outer" => Ok(PageNumberHorizontal::Outer),
+                "Center" => Ok(PageNumberHorizontal::Center),
+                _ => Err(serde::de::Error::custom("Unknown page number alignment")),
+            }
+        }
+    }
+
+    impl Serialize for HorizontalAlignment {
+        fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            serializer.serialize_str(match self {
+                HorizontalAlignment::TowardSpine => "TowardSpine",
+                HorizontalAlignment::Center => "Center",
+                HorizontalAlignment::TowardForeEdge => "TowardForeEdge",
+            })
+        }
+    }
+
+    impl<'de> Deserialize<'de> for HorizontalAlignment {
+        fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            let s = String::deserialize(deserializer)?;
+            match s.as_str() {
+                "TowardSpine" => Ok(HorizontalAlignment::TowardSpine),
+                "Center" => Ok(HorizontalAlignment::Center),
+                "TowardForeEdge" => Ok(HorizontalAlignment::TowardForeEdge),
+                _ => Err(serde::de::Error::custom("Unknown horizontal alignment")),
+            }
+        }
+    }
+
+    impl Serialize for VerticalAlignment {
+        fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            serializer.serialize_str(match self {
+                VerticalAlignment::TowardFold => "TowardFold",
+                VerticalAlignment::Top => "Top",
+                VerticalAlignment::Center => "Center",
+                VerticalAlignment::Bottom => "Bottom",
+            })
+        }
+    }
+
+    impl<'de> Deserialize<'de> for VerticalAlignment {
+        fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            let s = String::deserialize(deserializer)?;
+            match s.as_str() {
+                "TowardFold" => Ok(VerticalAlignment::TowardFold),
+                "Top" => Ok(VerticalAlignment::Top),
+                "Center" => Ok(VerticalAlignment::Center),
+                "Bottom" => Ok(VerticalAlignment::Bottom),
+                _ => Err(serde::de::Error::custom("Unknown vertical alignment")),
+            }
+        }
+    }
+
+    impl Serialize for PageRange {
+        fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            serializer.serialize_str(&self.to_string())
+        }
+    }
+
+    impl<'de> Deserialize<'de> for PageRange {
+        fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            let s = String::deserialize(deserializer)?;
+            s.parse().map_err(serde::de::Error::custom)
+        }
+    }
+
     impl Serialize for Rotation {
         fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
         where
@@ -0,0 +1,171 @@
+//! Named imposition presets
+//!
+//! A handful of presets for common jobs come bundled; the user's own are
+//! saved as JSON files in a directory of their own, by default under the
+//! platform's config directory. Presets describe a layout, so they never
+//! carry input files or their page ranges.
+
+use crate::options::ImpositionOptions;
+use crate::types::*;
+use std::path::{Path, PathBuf};
+
+/// The presets bundled with pdf-impose, as (name, options)
+pub fn builtin_presets() -> Vec<(String, ImpositionOptions)> {
+    let zine = ImpositionOptions {
+        binding_type: BindingType::Signature,
+        page_arrangement: PageArrangement::Folio,
+        output_paper_size: PaperSize::Letter,
+        output_orientation: Orientation::Landscape,
+        marks: PrinterMarks {
+            fold_lines: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let mut octavo = ImpositionOptions {
+        binding_type: BindingType::CaseBinding,
+        page_arrangement: PageArrangement::Octavo,
+        output_paper_size: PaperSize::A3,
+        output_orientation: Orientation::Landscape,
+        marks: PrinterMarks {
+            fold_lines: true,
+            cut_lines: true,
+            trim_marks: true,
+            collation_marks: true,
+            sewing_marks: Some(SewingMarks::default()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    octavo.margins.leaf = LeafMargins {
+        top_mm: 5.0,
+        bottom_mm: 8.0,
+        fore_edge_mm: 8.0,
+        spine_mm: 5.0,
+        cut_mm: 3.0,
+    };
+
+    let perfect = ImpositionOptions {
+        binding_type: BindingType::PerfectBinding,
+        page_arrangement: PageArrangement::Folio,
+        output_paper_size: PaperSize::Letter,
+        output_orientation: Orientation::Landscape,
+        marks: PrinterMarks {
+            crop_marks: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    vec![
+        ("Zine – A5 booklet on Letter".to_string(), zine),
+        ("Octavo hardcover on A3".to_string(), octavo),
+        ("Perfect-bound 2-up".to_string(), perfect),
+    ]
+}
+
+/// User presets, one JSON file per preset named after it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PresetStore {
+    dir: PathBuf,
+}
+
+impl PresetStore {
+    /// A store keeping its presets in `dir`, which is created on first save
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// The store in the platform's config directory (e.g.
+    /// `~/.config/pdf-tools/impose-presets` on Linux)
+    pub fn open_default() -> Result<Self> {
+        let dirs = directories::ProjectDirs::from("", "", "pdf-tools").ok_or_else(|| {
+            ImposeError::Config("No config directory to keep presets in".to_string())
+        })?;
+        Ok(Self::new(dirs.config_dir().join("impose-presets")))
+    }
+
+    /// Directory the presets are kept in
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Names of the saved presets, sorted; bundled presets aren't included
+    pub async fn list(&self) -> Result<Vec<String>> {
+        let mut entries = match tokio::fs::read_dir(&self.dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut names = Vec::new();
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "json")
+                && let Some(name) = path.file_stem().and_then(|stem| stem.to_str())
+            {
+                names.push(name.to_string());
+            }
+        }
+        names.sort();
+        Ok(names)
+    }
+
+    /// Save `options` as the preset `name`, replacing any saved under that
+    /// name; the input files and their page ranges are left out
+    pub async fn save(&self, name: &str, options: &ImpositionOptions) -> Result<()> {
+        let path = self.path(name)?;
+        let options = ImpositionOptions {
+            input_files: Vec::new(),
+            input_ranges: Vec::new(),
+            ..options.clone()
+        };
+        tokio::fs::create_dir_all(&self.dir).await?;
+        options.save(path).await
+    }
+
+    /// Delete the saved preset `name`
+    pub async fn delete(&self, name: &str) -> Result<()> {
+        match tokio::fs::remove_file(self.path(name)?).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Err(no_preset(name)),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Load the preset `name`: a saved one, or else the bundled one of that
+    /// name
+    pub async fn load(&self, name: &str) -> Result<ImpositionOptions> {
+        let path = self.path(name)?;
+        if tokio::fs::try_exists(&path).await? {
+            return ImpositionOptions::load(path).await;
+        }
+
+        builtin_presets()
+            .into_iter()
+            .find(|(builtin, _)| builtin == name)
+            .map(|(_, options)| options)
+            .ok_or_else(|| no_preset(name))
+    }
+
+    /// File of the preset `name`, rejecting names that aren't a plain file name
+    fn path(&self, name: &str) -> Result<PathBuf> {
+        if name.trim().is_empty()
+            || name.starts_with('.')
+            || name
+                .chars()
+                .any(|c| matches!(c, '/' | '\\') || c.is_control())
+        {
+            return Err(ImposeError::Config(format!(
+                "Invalid preset name \"{}\"",
+                name
+            )));
+        }
+        Ok(self.dir.join(format!("{}.json", name)))
+    }
+}
+
+fn no_preset(name: &str) -> ImposeError {
+    ImposeError::Config(format!("No preset named \"{}\"", name))
+}
@@ -2,12 +2,12 @@
 //!
 //! Generates a limited preview of the imposition for quick display.
 
-use crate::impose::impose;
+use crate::impose::{
+    blank_positions, impose, insert_blanks, merge_pages, merge_source_pages, split_cover,
+};
 use crate::options::ImpositionOptions;
-use crate::render::copy_object_deep;
 use crate::types::*;
-use lopdf::{Dictionary, Document, Object};
-use std::collections::HashMap;
+use lopdf::Document;
 
 /// Generate a preview of the imposition
 ///
@@ -17,9 +17,30 @@ pub async fn generate_preview(
     options: &ImpositionOptions,
     max_sheets: usize,
 ) -> Result<Document> {
+    // Every cut-and-stack sheet draws from every stack, so even the first
+    // sheets need the whole book; impose it all and keep the first few
+    if options.binding_type == BindingType::CutAndStack && options.step_and_repeat.is_none() {
+        let mut output = impose(documents, options).await?;
+        truncate_pages(&mut output, max_sheets * 2);
+        return Ok(output);
+    }
+
     // Calculate how many source pages we need for the preview
     let pages_per_sig = options.page_arrangement.pages_per_signature();
-    let source_pages_needed = if options.binding_type.uses_signatures() {
+    let source_pages_needed = if let Some(repeat) = options.step_and_repeat {
+        // Each page fills one or more sheets on its own
+        max_sheets.div_ceil(repeat.sheets_per_page())
+    } else if let BindingType::NUp { rows, cols } = options.binding_type {
+        // Each sheet holds one full grid
+        max_sheets * rows * cols
+    } else if let Some(plan) = options
+        .signature_plan
+        .as_ref()
+        .filter(|_| options.binding_type.uses_signatures())
+    {
+        // Show the first max_sheets signatures of the plan
+        plan.pages_per_signature.iter().take(max_sheets).sum()
+    } else if options.binding_type.uses_signatures() {
         // Show max_sheets signatures
         max_sheets * pages_per_sig
     } else {
@@ -27,11 +48,24 @@ pub async fn generate_preview(
         max_sheets * 2
     };
 
-    // Create preview documents with limited pages
-    let preview_docs = limit_document_pages(documents, source_pages_needed)?;
+    // Apply page ranges, reversal, the cover split and blank pages up front
+    // so the page limit keeps the pages that come first in the body
+    let selected = merge_source_pages(documents, &options.input_ranges, options.reverse_source)?;
+    let (body, _) = split_cover(selected, &options.cover)?;
+    let selected = insert_blanks(body, &blank_positions(documents, options))?;
+    let preview_docs = limit_document_pages(&[selected], source_pages_needed)?;
+    let options = ImpositionOptions {
+        input_ranges: Vec::new(),
+        reverse_source: false,
+        blank_inserts: Vec::new(),
+        chapter_recto: false,
+        chapter_starts_recto: Vec::new(),
+        cover: CoverOptions::default(),
+        ..options.clone()
+    };
 
     // Impose with limited pages
-    impose(&preview_docs, options).await
+    impose(&preview_docs, &options).await
 }
 
 /// Limit documents to a maximum number of pages
@@ -40,120 +74,21 @@ fn limit_document_pages(documents: &[Document], max_pages: usize) -> Result<Vec<
         return Err(ImposeError::NoPages);
     }
 
-    let doc = &documents[0];
-    let pages = doc.get_pages();
-    let total_pages = pages.len();
+    let total_pages: usize = documents.iter().map(|doc| doc.get_pages().len()).sum();
 
     if total_pages <= max_pages {
         return Ok(documents.to_vec());
     }
 
-    // Create a new document with limited pages
-    let page_ids: Vec<_> = pages.iter().take(max_pages).map(|(_, &id)| id).collect();
-    let limited_doc = copy_pages_to_new_document(doc, &page_ids)?;
-
-    Ok(vec![limited_doc])
-}
-
-/// Copy specified pages to a new document
-fn copy_pages_to_new_document(source: &Document, page_ids: &[lopdf::ObjectId]) -> Result<Document> {
-    let mut dest = Document::with_version(source.version.as_str());
-    let mut cache = HashMap::new();
-
-    // Create pages tree
-    let pages_tree_id = dest.new_object_id();
-    let mut kids = Vec::with_capacity(page_ids.len());
-
-    for &page_id in page_ids {
-        if let Ok(page_obj) = source.get_object(page_id) {
-            let new_page_id = copy_page_object(&mut dest, source, page_obj, &mut cache)?;
-            kids.push(Object::Reference(new_page_id));
-        }
-    }
-
-    // Create pages dictionary
-    let pages_dict = Dictionary::from_iter(vec![
-        ("Type", Object::Name(b"Pages".to_vec())),
-        ("Kids", Object::Array(kids)),
-        ("Count", Object::Integer(page_ids.len() as i64)),
-    ]);
-    dest.objects
-        .insert(pages_tree_id, Object::Dictionary(pages_dict));
-
-    // Create catalog
-    let catalog_id = dest.add_object(Dictionary::from_iter(vec![
-        ("Type", Object::Name(b"Catalog".to_vec())),
-        ("Pages", Object::Reference(pages_tree_id)),
-    ]));
-
-    dest.trailer.set("Root", catalog_id);
-
-    Ok(dest)
-}
-
-/// Copy a page object and its resources to a new document
-fn copy_page_object(
-    dest: &mut Document,
-    source: &Document,
-    obj: &Object,
-    cache: &mut HashMap<lopdf::ObjectId, lopdf::ObjectId>,
-) -> Result<lopdf::ObjectId> {
-    match obj {
-        Object::Reference(id) => {
-            if let Some(&new_id) = cache.get(id) {
-                Ok(new_id)
-            } else {
-                let referenced = source.get_object(*id)?;
-                let new_id = copy_page_object(dest, source, referenced, cache)?;
-                cache.insert(*id, new_id);
-                Ok(new_id)
-            }
-        }
-        Object::Dictionary(dict) => {
-            let mut new_dict = Dictionary::new();
-            for (key, value) in dict.iter() {
-                let new_value = copy_value_for_page(dest, source, value, cache)?;
-                new_dict.set(key.clone(), new_value);
-            }
-            Ok(dest.add_object(new_dict))
-        }
-        Object::Stream(stream) => {
-            let mut new_dict = Dictionary::new();
-            for (key, value) in stream.dict.iter() {
-                let new_value = copy_value_for_page(dest, source, value, cache)?;
-                new_dict.set(key.clone(), new_value);
-            }
-            let new_stream = lopdf::Stream {
-                dict: new_dict,
-                content: stream.content.clone(),
-                allows_compression: stream.allows_compression,
-                start_position: None,
-            };
-            Ok(dest.add_object(new_stream))
-        }
-        _ => Ok(dest.add_object(obj.clone())),
-    }
+    Ok(vec![merge_pages(documents, max_pages)?])
 }
 
-/// Copy a value, following references as needed
-fn copy_value_for_page(
-    dest: &mut Document,
-    source: &Document,
-    value: &Object,
-    cache: &mut HashMap<lopdf::ObjectId, lopdf::ObjectId>,
-) -> Result<Object> {
-    match value {
-        Object::Reference(id) => {
-            let new_id = if let Some(&cached_id) = cache.get(id) {
-                cached_id
-            } else {
-                let referenced = source.get_object(*id)?;
-                let new_id = copy_page_object(dest, source, referenced, cache)?;
-                cache.insert(*id, new_id);
-                new_id
-            };
-            Ok(Object::Reference(new_id))
-        }
-        _ => copy_object_deep(dest, source, value, cache),
+/// Drop output pages past the first `max_pages`
+fn truncate_pages(doc: &mut Document, max_pages: usize) {
+    let total_pages = doc.get_pages().len() as u32;
+    let extra: Vec<u32> = ((max_pages as u32 + 1)..=total_pages).collect();
+    if !extra.is_empty() {
+        doc.delete_pages(&extra);
+        doc.prune_objects();
     }
 }
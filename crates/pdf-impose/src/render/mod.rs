@@ -11,3 +11,6 @@ mod xobject;
 
 pub use page::*;
 pub use xobject::{copy_object_deep, create_page_xobject, get_page_dimensions};
+pub(crate) use xobject::{
+    create_page_xobjects, inherited_attribute, page_form_frame, page_rotation,
+};
@@ -5,9 +5,12 @@
 //! `impose/sheet.rs` internally.
 
 use crate::constants::{HELVETICA_CHAR_WIDTH_RATIO, PAGE_NUMBER_FONT_SIZE, PAGE_NUMBER_OFFSET};
-use crate::layout::{PagePlacement, Rect};
-use crate::marks::{ContentBounds, MarksConfig, generate_marks};
-use crate::types::{PrinterMarks, Result};
+use crate::layout::{GridLayout, PagePlacement, Rect, create_cut_grid_layout, create_grid_layout};
+use crate::marks::{
+    ContentBounds, FoldDirection, MARKS_FONT, MarksConfig, SlotLabel, generate_marks,
+    marks_color_spaces, marks_use_font,
+};
+use crate::types::{PageArrangement, PageBox, PrinterMarks, Result, SpineEdge};
 use lopdf::{Dictionary, Document, Object, ObjectId, Stream};
 use std::collections::HashMap;
 
@@ -38,6 +41,8 @@ use super::xobject::create_page_xobject;
 /// * `cell_height` - Height of each cell in points
 /// * `add_page_numbers` - Whether to add page numbers
 /// * `page_number_start` - Starting page number
+/// * `cache` - Copied source objects; pass the same map for every page of a
+///   document so shared fonts and images are copied into it only once
 #[allow(clippy::too_many_arguments)]
 pub fn render_imposed_page(
     output: &mut Document,
@@ -55,6 +60,7 @@ pub fn render_imposed_page(
     cell_height: f32,
     add_page_numbers: bool,
     page_number_start: usize,
+    cache: &mut HashMap<ObjectId, ObjectId>,
 ) -> Result<ObjectId> {
     // Create page dictionary
     let mut page_dict = Dictionary::new();
@@ -73,51 +79,94 @@ pub fn render_imposed_page(
     let mut content_ops = Vec::new();
     let mut xobjects = Dictionary::new();
     let mut fonts = Dictionary::new();
-    let mut xobject_cache: HashMap<ObjectId, ObjectId> = HashMap::new();
     let mut content_bounds: Vec<ContentBounds> = Vec::new();
 
     // Render each page placement
     for (idx, placement) in placements.iter().enumerate() {
-        if let Some(source_idx) = placement.source_page {
-            if source_idx < source_page_ids.len() {
-                let source_page_id = source_page_ids[source_idx];
-                let xobject_name = format!("P{}", idx);
-
-                let xobject_id =
-                    create_page_xobject(output, source, source_page_id, &mut xobject_cache)?;
-                xobjects.set(xobject_name.as_bytes(), Object::Reference(xobject_id));
-
-                content_ops.push(generate_placement_command(
-                    &xobject_name,
-                    &placement.content_rect,
-                    placement.scale,
-                    placement.rotation_degrees,
-                ));
+        if let Some(source_idx) = placement.source_page
+            && source_idx < source_page_ids.len()
+        {
+            let source_page_id = source_page_ids[source_idx];
+            let xobject_name = format!("P{}", idx);
 
-                content_bounds.push(ContentBounds {
-                    x: placement.content_rect.x,
-                    y: placement.content_rect.y,
-                    width: placement.content_rect.width,
-                    height: placement.content_rect.height,
-                });
-            }
+            let xobject_id =
+                create_page_xobject(output, source, source_page_id, PageBox::default(), cache)?;
+            xobjects.set(xobject_name.as_bytes(), Object::Reference(xobject_id));
+
+            content_ops.push(generate_placement_command(
+                &xobject_name,
+                &placement.content_rect,
+                placement.scale,
+                placement.rotation_degrees,
+            ));
+
+            // Record the trim line for marks; bleed extends past it
+            content_bounds.push(ContentBounds {
+                x: placement.trim_rect.x,
+                y: placement.trim_rect.y,
+                width: placement.trim_rect.width,
+                height: placement.trim_rect.height,
+            });
         }
     }
 
     // Generate printer's marks
     if marks.any_enabled() {
+        let grid = marks_grid(
+            grid_cols,
+            grid_rows,
+            leaf_bounds,
+            sheet_width_pt,
+            sheet_height_pt,
+        );
+        let (vertical_fold_order, horizontal_fold_order) =
+            (grid.fold_order(true), grid.fold_order(false));
         let marks_config = MarksConfig {
-            cols: grid_cols,
-            rows: grid_rows,
+            sheet_width: sheet_width_pt,
+            sheet_height: sheet_height_pt,
             cell_width,
             cell_height,
+            vertical_folds: grid.vertical_folds,
+            horizontal_folds: grid.horizontal_folds,
+            vertical_fold_order,
+            horizontal_fold_order,
+            // Placements don't say which side they're on; take the front
+            fold_direction: FoldDirection::Mountain,
+            vertical_cuts: grid.vertical_cuts,
+            horizontal_cuts: grid.horizontal_cuts,
             leaf_left: leaf_bounds.x,
             leaf_bottom: leaf_bounds.y,
             leaf_right: leaf_bounds.right(),
             leaf_top: leaf_bounds.top(),
+            trim_box: ContentBounds::trim_box(sheet_width_pt, sheet_height_pt, 0.0),
             content_bounds,
+            outer_spine: None,
+            bound_edges: Vec::new(),
+            slot_labels: placements
+                .iter()
+                .map(|placement| {
+                    let grid_pos = &placement.slot.grid_pos;
+                    SlotLabel {
+                        cell: ContentBounds {
+                            x: leaf_bounds.x + grid_pos.col as f32 * cell_width,
+                            y: leaf_bounds.y + (grid_rows - grid_pos.row - 1) as f32 * cell_height,
+                            width: cell_width,
+                            height: cell_height,
+                        },
+                        rotation_degrees: placement.total_rotation_degrees(),
+                        page: placement.source_page,
+                    }
+                })
+                .collect(),
         };
         content_ops.push(generate_marks(marks, &marks_config));
+        if marks_use_font(marks) {
+            let mut font_dict = Dictionary::new();
+            font_dict.set("Type", Object::Name(b"Font".to_vec()));
+            font_dict.set("Subtype", Object::Name(b"Type1".to_vec()));
+            font_dict.set("BaseFont", Object::Name(b"Helvetica".to_vec()));
+            fonts.set(MARKS_FONT, Object::Reference(output.add_object(font_dict)));
+        }
     }
 
     // Add page numbers
@@ -141,6 +190,9 @@ pub fn render_imposed_page(
     if !fonts.is_empty() {
         resources.set("Font", Object::Dictionary(fonts));
     }
+    if let Some(color_spaces) = marks_color_spaces(marks) {
+        resources.set("ColorSpace", Object::Dictionary(color_spaces));
+    }
 
     // Create content stream
     let content = content_ops.join("");
@@ -156,11 +208,45 @@ pub fn render_imposed_page(
 // Helper Functions
 // =============================================================================
 
+/// The folds and cuts of a `cols` x `rows` grid, for its marks
+///
+/// A grid the size of one of the folded arrangements folds and cuts like it;
+/// any other grid is cut apart along every line.
+fn marks_grid(
+    cols: usize,
+    rows: usize,
+    leaf_bounds: &Rect,
+    sheet_width_pt: f32,
+    sheet_height_pt: f32,
+) -> GridLayout {
+    let folded = [
+        PageArrangement::Folio,
+        PageArrangement::Quarto,
+        PageArrangement::Octavo,
+        PageArrangement::Sexto,
+        PageArrangement::Duodecimo,
+    ]
+    .into_iter()
+    .find(|arrangement| arrangement.grid_dimensions() == (cols, rows));
+
+    match folded {
+        Some(arrangement) => create_grid_layout(
+            arrangement,
+            leaf_bounds.width,
+            leaf_bounds.height,
+            sheet_width_pt,
+            sheet_height_pt,
+            SpineEdge::Auto,
+        ),
+        None => create_cut_grid_layout(cols, rows, leaf_bounds.width, leaf_bounds.height, 0.0),
+    }
+}
+
 /// Generate the PDF content stream command to place a page.
 fn generate_placement_command(
     xobject_name: &str,
     rect: &Rect,
-    scale: f32,
+    (scale_x, scale_y): (f32, f32),
     rotation_degrees: f32,
 ) -> String {
     if rotation_degrees.abs() > 0.1 {
@@ -169,12 +255,12 @@ fn generate_placement_command(
         let rot_y = rect.y + rect.height;
         format!(
             "q {} 0 0 {} {} {} cm /{} Do Q\n",
-            -scale, -scale, rot_x, rot_y, xobject_name
+            -scale_x, -scale_y, rot_x, rot_y, xobject_name
         )
     } else {
         format!(
             "q {} 0 0 {} {} {} cm /{} Do Q\n",
-            scale, scale, rect.x, rect.y, xobject_name
+            scale_x, scale_y, rect.x, rect.y, xobject_name
         )
     }
 }
@@ -4,8 +4,9 @@
 //! which are then placed onto output pages with transformations.
 
 use crate::constants::DEFAULT_PAGE_DIMENSIONS;
-use crate::types::Result;
+use crate::types::{ImposeError, PageBox, Result};
 use lopdf::{Dictionary, Document, Object, ObjectId, Stream};
+use rayon::prelude::*;
 use std::collections::HashMap;
 
 // =============================================================================
@@ -22,22 +23,89 @@ use std::collections::HashMap;
 /// * `output` - The output document to add the XObject to
 /// * `source` - The source document containing the page
 /// * `page_id` - The object ID of the source page
+/// * `page_box` - Which page boundary to place
 /// * `cache` - Cache to avoid copying the same object multiple times
 pub fn create_page_xobject(
     output: &mut Document,
     source: &Document,
     page_id: ObjectId,
+    page_box: PageBox,
     cache: &mut HashMap<ObjectId, ObjectId>,
 ) -> Result<ObjectId> {
+    let FormParts {
+        mut dict,
+        content,
+        resources,
+    } = form_parts(source, page_id, page_box)?;
+
+    if let Some(resources) = resources {
+        dict.set(
+            "Resources",
+            copy_object_deep(output, source, resources, cache)?,
+        );
+    }
+
+    // Create XObject with content stream
+    Ok(output.add_object(Stream::new(dict, content)))
+}
+
+/// Create XObjects for several source pages, like [`create_page_xobject`]
+///
+/// Reading the pages and copying their resources runs in parallel. New
+/// object IDs are still handed out in `page_ids` order, so the output is
+/// the same from run to run.
+pub(crate) fn create_page_xobjects(
+    output: &mut Document,
+    source: &Document,
+    page_ids: &[ObjectId],
+    page_box: PageBox,
+    cache: &mut HashMap<ObjectId, ObjectId>,
+) -> Result<Vec<ObjectId>> {
+    let parts = page_ids
+        .par_iter()
+        .map(|&page_id| form_parts(source, page_id, page_box))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut reserved = Vec::new();
+    for resources in parts.iter().filter_map(|part| part.resources) {
+        reserve_references(output, source, resources, cache, &mut reserved)?;
+    }
+    let copied = copy_reserved(source, &reserved, cache)?;
+    output.objects.extend(copied);
+
+    Ok(parts
+        .into_iter()
+        .map(|part| {
+            let FormParts {
+                mut dict,
+                content,
+                resources,
+            } = part;
+            if let Some(resources) = resources {
+                dict.set("Resources", remap_references(resources, cache));
+            }
+            output.add_object(Stream::new(dict, content))
+        })
+        .collect())
+}
+
+/// A page's Form XObject, short of the resources copied into the output
+struct FormParts<'a> {
+    dict: Dictionary,
+    content: Vec<u8>,
+    /// The page's resources in the source document
+    resources: Option<&'a Object>,
+}
+
+/// Read everything a page's Form XObject needs from the source
+fn form_parts(source: &Document, page_id: ObjectId, page_box: PageBox) -> Result<FormParts<'_>> {
     let page_dict = source.get_dictionary(page_id)?;
 
-    // Get page dimensions from MediaBox
-    let media_box = page_dict
-        .get(b"MediaBox")
-        .and_then(|obj| obj.as_array())
-        .ok()
+    // Only the region inside the chosen box is placed
+    let placed_box = page_box_rect(source, page_dict, page_box)
         .cloned()
         .unwrap_or_else(default_media_box);
+    let bounds = box_bounds(&placed_box);
 
     // Get page content
     let content_data = get_page_content(source, page_dict)?;
@@ -46,19 +114,54 @@ pub fn create_page_xobject(
     let mut xobject_dict = Dictionary::new();
     xobject_dict.set("Type", Object::Name(b"XObject".to_vec()));
     xobject_dict.set("Subtype", Object::Name(b"Form".to_vec()));
-    xobject_dict.set("BBox", Object::Array(media_box));
+    xobject_dict.set("BBox", Object::Array(placed_box));
     xobject_dict.set("FormType", Object::Integer(1));
 
-    // Copy resources if present
-    if let Ok(resources) = page_dict.get(b"Resources") {
+    // Move the page's origin to (0, 0) and bake in its /Rotate so the form
+    // appears upright, occupying (0, 0)-(effective width, effective height)
+    if let Some(matrix) = form_matrix(bounds, page_rotation(source, page_dict)) {
         xobject_dict.set(
-            "Resources",
-            copy_object_deep(output, source, resources, cache)?,
+            "Matrix",
+            Object::Array(matrix.iter().map(|&v| Object::Real(v)).collect()),
         );
     }
 
-    // Create XObject with content stream
-    Ok(output.add_object(Stream::new(xobject_dict, content_data)))
+    Ok(FormParts {
+        dict: xobject_dict,
+        content: content_data,
+        // Including resources inherited from the page tree
+        resources: inherited_attribute(source, page_dict, b"Resources"),
+    })
+}
+
+/// The part of a page its Form XObject shows, as `[x0 y0 x1 y1]` in page
+/// space, and the form matrix that sets that part upright at the origin
+pub(crate) fn page_form_frame(
+    source: &Document,
+    page_id: ObjectId,
+    page_box: PageBox,
+) -> Result<([f32; 4], [f32; 6])> {
+    let page_dict = source.get_dictionary(page_id)?;
+    let bounds = match page_box_rect(source, page_dict, page_box) {
+        Some(rect) => box_bounds(rect),
+        None => box_bounds(&default_media_box()),
+    };
+    let matrix = form_matrix(bounds, page_rotation(source, page_dict))
+        .unwrap_or([1.0, 0.0, 0.0, 1.0, 0.0, 0.0]);
+    Ok((bounds, matrix))
+}
+
+/// Form matrix that normalizes the origin and applies the page rotation,
+/// or `None` when the identity matrix would do
+fn form_matrix(bounds: [f32; 4], rotation: i64) -> Option<[f32; 6]> {
+    let [x0, y0, x1, y1] = bounds;
+    match rotation {
+        90 => Some([0.0, -1.0, 1.0, 0.0, -y0, x1]),
+        180 => Some([-1.0, 0.0, 0.0, -1.0, x1, y1]),
+        270 => Some([0.0, 1.0, -1.0, 0.0, y1, -x0]),
+        _ if x0 != 0.0 || y0 != 0.0 => Some([1.0, 0.0, 0.0, 1.0, -x0, -y0]),
+        _ => None,
+    }
 }
 
 /// Get default MediaBox for US Letter size
@@ -105,14 +208,14 @@ fn get_concatenated_content_streams(doc: &Document, refs: &[Object]) -> Result<V
     let mut result = Vec::new();
 
     for obj in refs {
-        if let Object::Reference(id) = obj {
-            if let Ok(stream) = doc.get_object(*id)?.as_stream() {
-                let content = stream
-                    .decompressed_content()
-                    .unwrap_or_else(|_| stream.content.clone());
-                result.extend_from_slice(&content);
-                result.push(b'\n');
-            }
+        if let Object::Reference(id) = obj
+            && let Ok(stream) = doc.get_object(*id)?.as_stream()
+        {
+            let content = stream
+                .decompressed_content()
+                .unwrap_or_else(|_| stream.content.clone());
+            result.extend_from_slice(&content);
+            result.push(b'\n');
         }
     }
 
@@ -132,51 +235,94 @@ pub fn copy_object_deep(
     obj: &Object,
     cache: &mut HashMap<ObjectId, ObjectId>,
 ) -> Result<Object> {
+    let mut reserved = Vec::new();
+    reserve_references(output, source, obj, cache, &mut reserved)?;
+    let copied = copy_reserved(source, &reserved, cache)?;
+    output.objects.extend(copied);
+    Ok(remap_references(obj, cache))
+}
+
+/// Give every source object reachable from `obj` and not yet in `cache` an
+/// ID in the output, recording the new `(source, output)` pairs in
+/// `reserved`
+fn reserve_references(
+    output: &mut Document,
+    source: &Document,
+    obj: &Object,
+    cache: &mut HashMap<ObjectId, ObjectId>,
+    reserved: &mut Vec<(ObjectId, ObjectId)>,
+) -> Result<()> {
     match obj {
         Object::Reference(id) => {
-            // Check cache first
-            if let Some(&new_id) = cache.get(id) {
-                return Ok(Object::Reference(new_id));
+            if cache.contains_key(id) {
+                return Ok(());
             }
 
-            // Get and copy the referenced object
-            let referenced = source.get_object(*id)?;
-            let copied = copy_object_deep(output, source, referenced, cache)?;
-
-            // Add to output and cache
-            let new_id = output.add_object(copied);
+            // Reserve the new ID before recursing so reference cycles terminate
+            let new_id = output.new_object_id();
             cache.insert(*id, new_id);
+            reserved.push((*id, new_id));
 
-            Ok(Object::Reference(new_id))
-        }
-        Object::Dictionary(dict) => {
-            let mut new_dict = Dictionary::new();
-            for (key, value) in dict.iter() {
-                new_dict.set(key.clone(), copy_object_deep(output, source, value, cache)?);
-            }
-            Ok(Object::Dictionary(new_dict))
-        }
-        Object::Array(arr) => {
-            let new_arr: Result<Vec<_>> = arr
-                .iter()
-                .map(|item| copy_object_deep(output, source, item, cache))
-                .collect();
-            Ok(Object::Array(new_arr?))
+            let referenced = source.get_object(*id)?;
+            reserve_references(output, source, referenced, cache, reserved)
         }
-        Object::Stream(stream) => {
-            let mut new_dict = Dictionary::new();
-            for (key, value) in stream.dict.iter() {
-                new_dict.set(key.clone(), copy_object_deep(output, source, value, cache)?);
-            }
-            Ok(Object::Stream(Stream {
-                dict: new_dict,
-                content: stream.content.clone(),
-                allows_compression: stream.allows_compression,
-                start_position: None,
-            }))
+        Object::Dictionary(dict) => dict
+            .iter()
+            .try_for_each(|(_, value)| reserve_references(output, source, value, cache, reserved)),
+        Object::Array(arr) => arr
+            .iter()
+            .try_for_each(|item| reserve_references(output, source, item, cache, reserved)),
+        Object::Stream(stream) => stream
+            .dict
+            .iter()
+            .try_for_each(|(_, value)| reserve_references(output, source, value, cache, reserved)),
+        // Primitive types hold no references
+        _ => Ok(()),
+    }
+}
+
+/// Copy reserved source objects, in parallel, pointing their references at
+/// the output IDs in `cache`
+fn copy_reserved(
+    source: &Document,
+    reserved: &[(ObjectId, ObjectId)],
+    cache: &HashMap<ObjectId, ObjectId>,
+) -> Result<Vec<(ObjectId, Object)>> {
+    reserved
+        .par_iter()
+        .map(|&(source_id, new_id)| {
+            let referenced = source.get_object(source_id)?;
+            Ok((new_id, remap_references(referenced, cache)))
+        })
+        .collect()
+}
+
+/// Clone `obj`, replacing source references with their output IDs
+fn remap_references(obj: &Object, cache: &HashMap<ObjectId, ObjectId>) -> Object {
+    let remap_dict = |dict: &Dictionary| {
+        let mut new_dict = Dictionary::new();
+        for (key, value) in dict.iter() {
+            new_dict.set(key.clone(), remap_references(value, cache));
         }
+        new_dict
+    };
+
+    match obj {
+        Object::Reference(id) => Object::Reference(cache.get(id).copied().unwrap_or(*id)),
+        Object::Dictionary(dict) => Object::Dictionary(remap_dict(dict)),
+        Object::Array(arr) => Object::Array(
+            arr.iter()
+                .map(|item| remap_references(item, cache))
+                .collect(),
+        ),
+        Object::Stream(stream) => Object::Stream(Stream {
+            dict: remap_dict(&stream.dict),
+            content: stream.content.clone(),
+            allows_compression: stream.allows_compression,
+            start_position: None,
+        }),
         // Primitive types: just clone
-        _ => Ok(obj.clone()),
+        _ => obj.clone(),
     }
 }
 
@@ -184,28 +330,95 @@ pub fn copy_object_deep(
 // Page Dimensions
 // =============================================================================
 
-/// Get source page dimensions (width, height) in points
-pub fn get_page_dimensions(doc: &Document, page_id: ObjectId) -> Result<(f32, f32)> {
+/// Get source page dimensions (width, height) in points, as displayed.
+///
+/// Measures `page_box`, falling back to the boxes it defaults to. Width and
+/// height are swapped for pages with `/Rotate 90` or `/Rotate 270`. A box
+/// with no area fails with [`ImposeError::InvalidPageBox`].
+pub fn get_page_dimensions(
+    doc: &Document,
+    page_id: ObjectId,
+    page_box: PageBox,
+) -> Result<(f32, f32)> {
     let page_dict = doc.get_dictionary(page_id)?;
 
-    if let Some(mb) = page_dict
-        .get(b"MediaBox")
-        .and_then(|obj| obj.as_array())
-        .ok()
-    {
-        let width = extract_number(&mb[2]).unwrap_or(DEFAULT_PAGE_DIMENSIONS.0);
-        let height = extract_number(&mb[3]).unwrap_or(DEFAULT_PAGE_DIMENSIONS.1);
-        Ok((width, height))
+    let (width, height) = if let Some(rect) = page_box_rect(doc, page_dict, page_box) {
+        let [x0, y0, x1, y1] = box_bounds(rect);
+        ((x1 - x0).abs(), (y1 - y0).abs())
     } else {
-        Ok(DEFAULT_PAGE_DIMENSIONS)
+        DEFAULT_PAGE_DIMENSIONS
+    };
+    if !(width.is_finite() && height.is_finite() && width > 0.0 && height > 0.0) {
+        return Err(ImposeError::InvalidPageBox(page_id));
+    }
+
+    match page_rotation(doc, page_dict) {
+        90 | 270 => Ok((height, width)),
+        _ => Ok((width, height)),
+    }
+}
+
+/// The rectangle for `page_box`, or the first box it falls back to, any of
+/// which may be inherited from an ancestor Pages node
+fn page_box_rect<'a>(
+    doc: &'a Document,
+    page_dict: &'a Dictionary,
+    page_box: PageBox,
+) -> Option<&'a Vec<Object>> {
+    page_box.lookup_keys().iter().find_map(|key| {
+        let value = inherited_attribute(doc, page_dict, key)?;
+        doc.dereference(value).ok()?.1.as_array().ok()
+    })
+}
+
+/// Look up `key` on a page dictionary, walking up its `/Parent` chain if needed
+pub(crate) fn inherited_attribute<'a>(
+    doc: &'a Document,
+    page_dict: &'a Dictionary,
+    key: &[u8],
+) -> Option<&'a Object> {
+    let mut current = page_dict;
+    let mut visited = Vec::new();
+
+    loop {
+        if let Ok(value) = current.get(key) {
+            return Some(value);
+        }
+        let parent_id = current.get(b"Parent").and_then(Object::as_reference).ok()?;
+        if visited.contains(&parent_id) {
+            return None;
+        }
+        visited.push(parent_id);
+        current = doc.get_dictionary(parent_id).ok()?;
     }
 }
 
-/// Extract numeric value from a PDF object
+/// Read a page's (possibly inherited) `/Rotate`, normalized to 0, 90, 180 or 270
+pub(crate) fn page_rotation(doc: &Document, page_dict: &Dictionary) -> i64 {
+    let rotate = inherited_attribute(doc, page_dict, b"Rotate")
+        .and_then(extract_number)
+        .unwrap_or(0.0);
+    let quarter_turns = (rotate / 90.0).round() as i64;
+    quarter_turns.rem_euclid(4) * 90
+}
+
+/// Read `[x0 y0 x1 y1]` from a PDF rectangle, falling back to the default page size
+fn box_bounds(rect: &[Object]) -> [f32; 4] {
+    let value =
+        |idx: usize, default: f32| rect.get(idx).and_then(extract_number).unwrap_or(default);
+    [
+        value(0, 0.0),
+        value(1, 0.0),
+        value(2, DEFAULT_PAGE_DIMENSIONS.0),
+        value(3, DEFAULT_PAGE_DIMENSIONS.1),
+    ]
+}
+
+/// Extract a finite numeric value from a PDF object
 fn extract_number(obj: &Object) -> Option<f32> {
     match obj {
         Object::Integer(i) => Some(*i as f32),
-        Object::Real(r) => Some(*r),
+        Object::Real(r) if r.is_finite() => Some(*r),
         _ => None,
     }
 }
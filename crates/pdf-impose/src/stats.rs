@@ -2,7 +2,11 @@
 //!
 //! Calculates output statistics without performing the actual imposition.
 
-use crate::constants::PAGES_PER_LEAF;
+use crate::constants::{PAGES_PER_LEAF, pt_to_mm};
+use crate::impose::{
+    blank_positions, blank_positions_with_file_starts, plan_sheets, selected_page_counts,
+};
+use crate::layout::cut_and_stack_sheet_count;
 use crate::options::ImpositionOptions;
 use crate::types::*;
 use lopdf::Document;
@@ -14,8 +18,39 @@ pub fn calculate_statistics(
     documents: &[Document],
     options: &ImpositionOptions,
 ) -> Result<ImpositionStatistics> {
-    // Count total source pages
-    let mut source_pages: usize = documents.iter().map(|doc| doc.get_pages().len()).sum();
+    // Count total source pages, honoring any per-file page ranges
+    let selected: usize = selected_page_counts(documents, &options.input_ranges)
+        .iter()
+        .sum();
+    statistics_for_pages(selected, blank_positions(documents, options), options)
+}
+
+/// Calculate statistics for `page_count` selected source pages, without
+/// loading any documents
+///
+/// `page_count` counts the pages after input ranges are applied, cover pages
+/// included. The pages are treated as one input, so `chapter_recto` adds no
+/// blanks; use [`calculate_statistics`] when the inputs are at hand.
+pub fn calculate_statistics_for_pages(
+    page_count: usize,
+    options: &ImpositionOptions,
+) -> Result<ImpositionStatistics> {
+    statistics_for_pages(
+        page_count,
+        blank_positions_with_file_starts(&[], options),
+        options,
+    )
+}
+
+/// Statistics for `selected` source pages with blanks inserted before the
+/// body positions in `blanks`
+fn statistics_for_pages(
+    selected: usize,
+    mut blanks: Vec<usize>,
+    options: &ImpositionOptions,
+) -> Result<ImpositionStatistics> {
+    // Cover pages are imposed apart from the body, if at all
+    let mut source_pages = selected.saturating_sub(options.cover.pages());
 
     // Add flyleaves (each flyleaf = 1 leaf = 2 pages)
     source_pages += (options.front_flyleaves + options.back_flyleaves) * PAGES_PER_LEAF;
@@ -24,11 +59,90 @@ pub fn calculate_statistics(
         return Err(ImposeError::NoPages);
     }
 
-    if options.binding_type.uses_signatures() {
-        calculate_signature_stats(source_pages, options)
+    // Inserted blanks are laid out like any other page but count as blanks
+    let inserted = blanks.len();
+    let total_pages = source_pages + inserted;
+
+    let mut stats = if let Some(repeat) = options.step_and_repeat {
+        calculate_step_repeat_stats(total_pages, options, repeat)
+    } else if let BindingType::NUp { rows, cols } = options.binding_type {
+        Ok(calculate_n_up_stats(total_pages, options, rows * cols))
+    } else if options.binding_type.uses_signatures() {
+        calculate_signature_stats(total_pages, options)
+    } else if options.binding_type == BindingType::CutAndStack {
+        calculate_cut_stack_stats(total_pages, options)
     } else {
-        calculate_simple_stats(source_pages)
+        calculate_simple_stats(total_pages, options)
+    }?;
+    stats.source_pages -= inserted;
+    stats.blank_pages_added += inserted;
+    stats.cover_sheets = usize::from(options.cover.separate());
+    stats.paper_sheets = stats.output_sheets + stats.cover_sheets;
+
+    let bound = options.step_and_repeat.is_none()
+        && !matches!(options.binding_type, BindingType::NUp { .. });
+    if bound {
+        let leaves = stats.padded_pages().div_ceil(PAGES_PER_LEAF);
+        stats.spine_thickness_mm = Some(options.cover.spine_width_mm(leaves));
+    }
+
+    if let Some(pages_per_signature) = &stats.pages_per_signature {
+        blanks.sort_unstable();
+        let blanks = book_blank_positions(&blanks, options, total_pages);
+        stats.blank_pages_per_signature = Some(blanks_per_signature(
+            pages_per_signature,
+            &blanks,
+            total_pages,
+        ));
     }
+
+    // The grid doesn't depend on the page sizes, only on how many there are
+    let grid = plan_sheets(&vec![(1.0, 1.0); total_pages], options)?.grid;
+    stats.trim_size_mm = (pt_to_mm(grid.cell_width_pt), pt_to_mm(grid.cell_height_pt));
+    Ok(stats)
+}
+
+/// Book positions of the inserted blanks, given sorted by body position,
+/// among the `total_pages` pages laid out before padding
+fn book_blank_positions(
+    blanks: &[usize],
+    options: &ImpositionOptions,
+    total_pages: usize,
+) -> Vec<usize> {
+    let front = options.front_flyleaves * PAGES_PER_LEAF;
+    let flyleaves = (options.front_flyleaves + options.back_flyleaves) * PAGES_PER_LEAF;
+    let body_pages = total_pages - blanks.len() - flyleaves;
+
+    // Each blank goes before the body page at its position, after the
+    // blanks ahead of it; blanks past the end follow the last body page
+    blanks
+        .iter()
+        .enumerate()
+        .map(|(earlier, &position)| front + position.min(body_pages) + earlier)
+        .collect()
+}
+
+/// Count the blanks in each signature: the inserted ones, plus the padding
+/// after the first `total_pages` pages
+fn blanks_per_signature(
+    pages_per_signature: &[usize],
+    inserted: &[usize],
+    total_pages: usize,
+) -> Vec<usize> {
+    let mut start = 0;
+    pages_per_signature
+        .iter()
+        .map(|&pages| {
+            let end = start + pages;
+            let inserted = inserted
+                .iter()
+                .filter(|&&position| (start..end).contains(&position))
+                .count();
+            let padding = end.saturating_sub(start.max(total_pages));
+            start = end;
+            inserted + padding
+        })
+        .collect()
 }
 
 /// Calculate statistics for signature binding
@@ -36,15 +150,21 @@ fn calculate_signature_stats(
     source_pages: usize,
     options: &ImpositionOptions,
 ) -> Result<ImpositionStatistics> {
-    let pages_per_sig = options.page_arrangement.pages_per_signature();
-    let sheets_per_sig = options.page_arrangement.sheets_per_signature();
+    let plan = options.signature_arrangements(source_pages)?;
+    let pages_per_signature: Vec<usize> = plan
+        .iter()
+        .map(|arrangement| arrangement.pages_per_signature())
+        .collect();
 
-    // Pad to multiple of pages_per_signature
-    let padded_count = round_up_to_multiple(source_pages, pages_per_sig);
+    // Pad the last signature to its full page count
+    let padded_count: usize = pages_per_signature.iter().sum();
     let blank_pages_added = padded_count - source_pages;
 
-    let num_signatures = padded_count / pages_per_sig;
-    let total_sheets = num_signatures * sheets_per_sig;
+    let num_signatures = plan.len();
+    let total_sheets: usize = plan
+        .iter()
+        .map(|arrangement| arrangement.sheets_per_signature())
+        .sum();
 
     // Output pages (front and back of each sheet)
     let output_pages = total_sheets * 2;
@@ -53,14 +173,24 @@ fn calculate_signature_stats(
         source_pages,
         output_sheets: total_sheets,
         signatures: Some(num_signatures),
-        pages_per_signature: Some(vec![pages_per_sig; num_signatures]),
+        pages_per_signature: Some(pages_per_signature),
         output_pages,
         blank_pages_added,
+        stack_height: None,
+        bleed_fits: bleed_fits(options),
+        cover_sheets: 0,
+        paper_sheets: 0,
+        spine_thickness_mm: None,
+        blank_pages_per_signature: None,
+        trim_size_mm: (0.0, 0.0),
     })
 }
 
 /// Calculate statistics for simple 2-up binding
-fn calculate_simple_stats(source_pages: usize) -> Result<ImpositionStatistics> {
+fn calculate_simple_stats(
+    source_pages: usize,
+    options: &ImpositionOptions,
+) -> Result<ImpositionStatistics> {
     // Perfect binding, side stitch, spiral: 2 pages per sheet
     let padded_count = round_up_to_multiple(source_pages, 2);
     let blank_pages_added = padded_count - source_pages;
@@ -75,10 +205,115 @@ fn calculate_simple_stats(source_pages: usize) -> Result<ImpositionStatistics> {
         pages_per_signature: None,
         output_pages,
         blank_pages_added,
+        stack_height: None,
+        bleed_fits: bleed_fits(options),
+        cover_sheets: 0,
+        paper_sheets: 0,
+        spine_thickness_mm: None,
+        blank_pages_per_signature: None,
+        trim_size_mm: (0.0, 0.0),
     })
 }
 
+/// Calculate statistics for cut and stack
+fn calculate_cut_stack_stats(
+    source_pages: usize,
+    options: &ImpositionOptions,
+) -> Result<ImpositionStatistics> {
+    let total_sheets = cut_and_stack_sheet_count(source_pages, options.page_arrangement);
+    let (cols, rows) = options.page_arrangement.grid_dimensions();
+
+    // Every stack is as tall as the number of sheets
+    let padded_count = total_sheets * cols * rows * PAGES_PER_LEAF;
+
+    Ok(ImpositionStatistics {
+        source_pages,
+        output_sheets: total_sheets,
+        signatures: None,
+        pages_per_signature: None,
+        output_pages: total_sheets * 2,
+        blank_pages_added: padded_count - source_pages,
+        stack_height: Some(total_sheets),
+        bleed_fits: bleed_fits(options),
+        cover_sheets: 0,
+        paper_sheets: 0,
+        spine_thickness_mm: None,
+        blank_pages_per_signature: None,
+        trim_size_mm: (0.0, 0.0),
+    })
+}
+
+/// Calculate statistics for step and repeat (single-sided sheets)
+fn calculate_step_repeat_stats(
+    source_pages: usize,
+    options: &ImpositionOptions,
+    repeat: StepAndRepeat,
+) -> Result<ImpositionStatistics> {
+    let total_sheets = source_pages * repeat.sheets_per_page();
+
+    Ok(ImpositionStatistics {
+        source_pages,
+        output_sheets: total_sheets,
+        signatures: None,
+        pages_per_signature: None,
+        output_pages: total_sheets,
+        blank_pages_added: 0,
+        stack_height: None,
+        bleed_fits: bleed_fits(options),
+        cover_sheets: 0,
+        paper_sheets: 0,
+        spine_thickness_mm: None,
+        blank_pages_per_signature: None,
+        trim_size_mm: (0.0, 0.0),
+    })
+}
+
+/// Calculate statistics for N-up (single-sided sheets filled in order)
+fn calculate_n_up_stats(
+    source_pages: usize,
+    options: &ImpositionOptions,
+    cells: usize,
+) -> ImpositionStatistics {
+    let total_sheets = source_pages.div_ceil(cells);
+
+    ImpositionStatistics {
+        source_pages,
+        output_sheets: total_sheets,
+        signatures: None,
+        pages_per_signature: None,
+        output_pages: total_sheets,
+        blank_pages_added: total_sheets * cells - source_pages,
+        stack_height: None,
+        bleed_fits: bleed_fits(options),
+        cover_sheets: 0,
+        paper_sheets: 0,
+        spine_thickness_mm: None,
+        blank_pages_per_signature: None,
+        trim_size_mm: (0.0, 0.0),
+    }
+}
+
+/// Check that bleed on outer edges stays inside the leaf area, and that bleed
+/// on cut edges doesn't reach the next page
+fn bleed_fits(options: &ImpositionOptions) -> bool {
+    let leaf = &options.margins.leaf;
+    // N-up cells use the cut margin on every edge
+    if matches!(options.binding_type, BindingType::NUp { .. }) {
+        return options.bleed_mm <= leaf.cut_mm;
+    }
+
+    let has_cuts = (options.binding_type.uses_signatures()
+        && matches!(
+            options.page_arrangement,
+            PageArrangement::Octavo | PageArrangement::Sexto | PageArrangement::Duodecimo
+        ))
+        || options.binding_type == BindingType::CutAndStack
+        || options.step_and_repeat.is_some();
+
+    options.bleed_mm <= leaf.fore_edge_mm && (!has_cuts || options.bleed_mm <= leaf.cut_mm)
+}
+
 /// Round up to the nearest multiple
 fn round_up_to_multiple(value: usize, multiple: usize) -> usize {
-    ((value + multiple - 1) / multiple) * multiple
+    value.div_ceil(multiple) * multiple
 }
@@ -6,7 +6,14 @@
 //! - Binding and arrangement options
 //! - Margin configurations
 //! - Printer's marks settings
+//! - Progress reporting and cancellation
 
+use crate::constants::{
+    CROP_MARK_GAP, CROP_MARK_LENGTH, CROP_MARK_WIDTH, REGISTRATION_MARK_SIZE, SCISSORS_SIZE,
+    SPINE_MARK_LENGTH, pt_to_mm,
+};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use thiserror::Error;
 
 // =============================================================================
@@ -16,8 +23,9 @@ use thiserror::Error;
 /// Errors that can occur during imposition
 #[derive(Error, Debug)]
 pub enum ImposeError {
+    /// A lopdf error not covered by a more specific variant
     #[error("PDF error: {0}")]
-    Pdf(#[from] lopdf::Error),
+    Pdf(#[source] lopdf::Error),
 
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
@@ -30,6 +38,48 @@ pub enum ImposeError {
 
     #[error("No pages to impose")]
     NoPages,
+
+    #[error("Imposition cancelled")]
+    Cancelled,
+
+    /// An input is encrypted and couldn't be opened; `needs_password` is set
+    /// when no password was given, and clear when the one given was wrong
+    #[error(
+        "PDF is encrypted; {}",
+        if *needs_password { "a password is needed to open it" } else { "the password is wrong" }
+    )]
+    Encrypted { needs_password: bool },
+
+    #[error("PDF version {0} is not supported")]
+    UnsupportedPdfVersion(String),
+
+    #[error("PDF page tree is damaged: {0}")]
+    CorruptPageTree(String),
+
+    #[error("Page object {} {} has no usable page box", .0.0, .0.1)]
+    InvalidPageBox(lopdf::ObjectId),
+
+    /// An image input that couldn't be decoded
+    #[error("Can't read image {}: {message}", .path.display())]
+    Image {
+        path: std::path::PathBuf,
+        message: String,
+    },
+}
+
+impl From<lopdf::Error> for ImposeError {
+    fn from(err: lopdf::Error) -> Self {
+        use lopdf::encryption::DecryptionError;
+        match err {
+            lopdf::Error::Decryption(DecryptionError::IncorrectPassword) => Self::Encrypted {
+                needs_password: false,
+            },
+            lopdf::Error::PageNumberNotFound(page) => {
+                Self::CorruptPageTree(format!("page {page} not found"))
+            }
+            err => Self::Pdf(err),
+        }
+    }
 }
 
 /// Result type alias for imposition operations
@@ -69,7 +119,7 @@ impl Orientation {
 ///
 /// All dimensions are stored in portrait orientation (width < height).
 /// Use `dimensions_with_orientation` to get landscape dimensions.
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub enum PaperSize {
     /// ISO A3 (297mm × 420mm)
     A3,
@@ -78,6 +128,7 @@ pub enum PaperSize {
     /// ISO A5 (148mm × 210mm)
     A5,
     /// US Letter (8.5" × 11")
+    #[default]
     Letter,
     /// US Legal (8.5" × 14")
     Legal,
@@ -87,12 +138,6 @@ pub enum PaperSize {
     Custom { width_mm: f32, height_mm: f32 },
 }
 
-impl Default for PaperSize {
-    fn default() -> Self {
-        PaperSize::Letter
-    }
-}
-
 impl PaperSize {
     /// Get base dimensions in millimeters (always portrait: width < height for standard sizes)
     pub fn dimensions_mm(self) -> (f32, f32) {
@@ -152,6 +197,49 @@ pub enum BindingType {
     Spiral,
     /// Case binding (sewn signatures in hardcover)
     CaseBinding,
+    /// Cut and stack (2-up, 4-up or 8-up sheets cut apart into stacks that are
+    /// piled in order, then perfect bound)
+    CutAndStack,
+    /// Plain N-up for handouts: `rows` × `cols` pages per sheet, one-sided,
+    /// filled in sequence (see `NUpOrder`) with no folding or spine
+    NUp { rows: usize, cols: usize },
+}
+
+/// Which side of the book the spine is on, seen from the front cover
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub enum BindingEdge {
+    /// Left-to-right reading; page 1 is a right-hand page
+    #[default]
+    Left,
+    /// Right-to-left reading (manga, Arabic, Hebrew); page 1 is a
+    /// left-hand page
+    Right,
+}
+
+/// Which way the spine fold runs across the sheet
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SpineEdge {
+    /// Follow the arrangement and sheet: horizontal for a quarto on a
+    /// landscape sheet, vertical otherwise
+    #[default]
+    Auto,
+    /// Spine between columns, whatever the sheet orientation
+    Vertical,
+    /// Spine between rows, whatever the sheet orientation; quarto only
+    Horizontal,
+}
+
+/// Which edge a duplex printer turns the sheet over on between its sides
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FlipEdge {
+    /// Backs are printed upright, mirrored left to right behind their fronts
+    #[default]
+    LongEdge,
+    /// Backs are printed turned half a turn, for printers that tumble the
+    /// sheet over its short edge
+    ShortEdge,
 }
 
 impl BindingType {
@@ -161,6 +249,17 @@ impl BindingType {
     }
 }
 
+/// Order in which pages fill the cells of an N-up sheet
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NUpOrder {
+    /// Left to right along each row, rows from top to bottom
+    #[default]
+    RowMajor,
+    /// Top to bottom down each column, columns from left to right
+    ColumnMajor,
+}
+
 /// Page arrangement within a signature
 ///
 /// Determines how many pages fit on each sheet and how they're folded.
@@ -176,7 +275,14 @@ pub enum PageArrangement {
     /// Octavo: 16 pages per sheet (3 folds)
     /// Grid: 4 columns × 2 rows
     Octavo,
+    /// Sexto: 12 pages per sheet (a quarto plus a folio offcut)
+    /// Grid: 2 columns × 3 rows, bottom row cut off and inset
+    Sexto,
+    /// Duodecimo: 24 pages per sheet (an octavo plus two folio offcuts)
+    /// Grid: 4 columns × 3 rows, bottom row cut off and inset
+    Duodecimo,
     /// Custom pages per signature (must be multiple of 4)
+    /// Grid: 2 columns × 1 row, one folio sheet per 4 pages nested together
     Custom { pages_per_signature: usize },
 }
 
@@ -187,15 +293,21 @@ impl PageArrangement {
             PageArrangement::Folio => 4,
             PageArrangement::Quarto => 8,
             PageArrangement::Octavo => 16,
+            PageArrangement::Sexto => 12,
+            PageArrangement::Duodecimo => 24,
             PageArrangement::Custom {
                 pages_per_signature,
             } => pages_per_signature,
         }
     }
 
-    /// Number of sheets per signature
+    /// Number of physical sheets per signature
+    ///
+    /// Each sheet carries a full grid of pages on both sides, so the common
+    /// arrangements fold from a single sheet.
     pub fn sheets_per_signature(self) -> usize {
-        self.pages_per_signature() / 4
+        let (cols, rows) = self.grid_dimensions();
+        self.pages_per_signature().div_ceil(2 * cols * rows)
     }
 
     /// Grid dimensions (columns, rows) for this arrangement
@@ -204,22 +316,62 @@ impl PageArrangement {
             PageArrangement::Folio => (2, 1),
             PageArrangement::Quarto => (2, 2),
             PageArrangement::Octavo => (4, 2),
-            PageArrangement::Custom {
-                pages_per_signature,
-            } => {
-                let pages_per_side = pages_per_signature / 2;
-                if pages_per_side <= 2 {
-                    (2, 1)
-                } else if pages_per_side <= 4 {
-                    (2, 2)
-                } else {
-                    (4, (pages_per_side + 3) / 4)
-                }
-            }
+            PageArrangement::Sexto => (2, 3),
+            PageArrangement::Duodecimo => (4, 3),
+            // Custom signatures are nested folio sheets
+            PageArrangement::Custom { .. } => (2, 1),
         }
     }
 }
 
+/// Explicit pages per signature, in binding order
+///
+/// Replaces the uniform run of signatures from the page arrangement, e.g.
+/// five 12-page signatures followed by a 4-page folio. Each count must be a
+/// multiple of 4.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
+pub struct SignaturePlan {
+    pub pages_per_signature: Vec<usize>,
+}
+
+impl SignaturePlan {
+    /// Total pages the plan holds
+    pub fn total_pages(&self) -> usize {
+        self.pages_per_signature.iter().sum()
+    }
+}
+
+/// Step and repeat: every cell of a sheet holds the same source page
+///
+/// Used for business cards, tickets and labels. Each source page fills
+/// whole sheets before moving on to the next page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
+pub struct StepAndRepeat {
+    /// Number of columns of copies
+    pub cols: usize,
+    /// Number of rows of copies
+    pub rows: usize,
+    /// Copies wanted of each source page; `None` prints one full sheet each
+    pub copies: Option<usize>,
+}
+
+impl StepAndRepeat {
+    /// Copies that fit on one sheet
+    pub fn cells(&self) -> usize {
+        self.cols * self.rows
+    }
+
+    /// Sheets printed for each source page, enough for the wanted copies
+    pub fn sheets_per_page(&self) -> usize {
+        self.copies
+            .map_or(1, |copies| copies.div_ceil(self.cells()).max(1))
+    }
+}
+
 // =============================================================================
 // Output Configuration
 // =============================================================================
@@ -230,18 +382,38 @@ pub enum OutputFormat {
     /// Single PDF with both sides interleaved (page 1 front, page 1 back, page 2 front, ...)
     #[default]
     DoubleSided,
-    /// Two separate PDFs (all fronts, then all backs)
+    /// Two separate PDFs, `_front` and `_back`, for manual duplexing: all
+    /// fronts, then all backs in the order given by [`BackOrder`], reversed
+    /// by default so the printed stack can be flipped and fed back in
     TwoSided,
     /// Single PDF with pages in print order for single-sided printing
+    /// (all fronts, then the backs in the order given by [`BackOrder`])
     SingleSidedSequence,
 }
 
-/// Page scaling behavior when source pages don't match output cell size
+/// Order of the back sides in a [`OutputFormat::SingleSidedSequence`] run
+/// and the `_back` file of [`OutputFormat::TwoSided`] output
+///
+/// Which one is right depends on whether the printer stacks face-up or face-down.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub enum BackOrder {
+    /// Backs in the same order as the fronts (back 1..N)
+    Normal,
+    /// Backs in reverse order (back N..1), for re-feeding the stack as it came out
+    #[default]
+    Reversed,
+}
+
+/// Page scaling behavior when source pages don't match output cell size
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub enum ScalingMode {
     /// Fit entire page within available space (preserve aspect ratio, may have margins)
     #[default]
     Fit,
+    /// Like `Fit`, but never enlarge pages past their original size
+    FitNoUpscale,
+    /// Scale by a fixed percentage of the original size, e.g. `Percent(85.0)`
+    Percent(f32),
     /// Fill available space (preserve aspect ratio, may crop)
     Fill,
     /// No scaling (center at original size)
@@ -250,6 +422,99 @@ pub enum ScalingMode {
     Stretch,
 }
 
+/// Which page boundary of the source pages is placed on the sheet
+///
+/// A missing box falls back as in the PDF spec: TrimBox and BleedBox to the
+/// CropBox, and the CropBox to the MediaBox.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub enum PageBox {
+    /// The full page, including any slug area
+    MediaBox,
+    /// The visible page region
+    #[default]
+    CropBox,
+    /// The finished page after trimming
+    TrimBox,
+    /// The page plus bleed
+    BleedBox,
+}
+
+impl PageBox {
+    /// Dictionary keys to try, in order, when reading this box from a page
+    pub(crate) fn lookup_keys(self) -> &'static [&'static [u8]] {
+        match self {
+            PageBox::MediaBox => &[b"MediaBox"],
+            PageBox::CropBox => &[b"CropBox", b"MediaBox"],
+            PageBox::TrimBox => &[b"TrimBox", b"CropBox", b"MediaBox"],
+            PageBox::BleedBox => &[b"BleedBox", b"CropBox", b"MediaBox"],
+        }
+    }
+}
+
+/// Whether page numbers go in the head or tail margin
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub enum PageNumberVertical {
+    /// Above the page content
+    Header,
+    /// Below the page content
+    #[default]
+    Footer,
+}
+
+/// Where page numbers sit across the width of the page
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub enum PageNumberHorizontal {
+    /// Toward the spine
+    Inner,
+    /// Toward the fore-edge (right on rectos, left on versos, swapped when
+    /// bound on the right)
+    #[default]
+    Outer,
+    /// Centered on the page content
+    Center,
+}
+
+/// Where scaled content sits in its leaf when it doesn't fill the space
+/// inside the margins
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
+pub struct ContentAlignment {
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub horizontal: HorizontalAlignment,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub vertical: VerticalAlignment,
+}
+
+/// Horizontal position of content within its leaf
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub enum HorizontalAlignment {
+    /// Against the fold at the side of the leaf, so facing pages meet at
+    /// the spine; centered when neither or both sides are folds
+    #[default]
+    TowardSpine,
+    /// Equal space on both sides
+    Center,
+    /// Against the edge opposite the fold; centered when neither or both
+    /// sides are folds
+    TowardForeEdge,
+}
+
+/// Vertical position of content within its leaf
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub enum VerticalAlignment {
+    /// Against the fold at the head or tail of the leaf; centered when
+    /// neither or both are folds
+    #[default]
+    TowardFold,
+    /// Against the head of the page
+    Top,
+    /// Equal space above and below
+    Center,
+    /// Against the tail of the page
+    Bottom,
+}
+
 /// Rotation to apply to source pages
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
 pub enum Rotation {
@@ -291,8 +556,11 @@ impl Rotation {
 ///
 /// These margins ensure content stays within the printer's printable area.
 /// Typical home printers need 5-10mm margins; commercial printers may print borderless.
+/// Left and right are the front side's edges; back sides swap them, as they are
+/// mirrored for duplex.
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
 pub struct SheetMargins {
     pub top_mm: f32,
     pub bottom_mm: f32,
@@ -341,6 +609,7 @@ impl SheetMargins {
 /// - Consistent page margins in the final book
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
 pub struct LeafMargins {
     /// Top margin (head) of each leaf
     pub top_mm: f32,
@@ -370,6 +639,7 @@ impl LeafMargins {
 /// Combined margins for imposition
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
 pub struct Margins {
     /// Printer-safe margins around the entire output sheet
     pub sheet: SheetMargins,
@@ -384,30 +654,181 @@ pub struct Margins {
 /// Printer's marks configuration
 ///
 /// These marks help with alignment, folding, and trimming during finishing.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
 pub struct PrinterMarks {
     /// Add fold lines (dashed) - where paper should be folded
     pub fold_lines: bool,
+    /// Label each fold line with its folding pass and direction, and
+    /// dash-dot mountain folds to tell them from valley folds
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub fold_labels: bool,
     /// Add cut lines (solid with scissors) - where paper should be cut after folding
     pub cut_lines: bool,
-    /// Add crop marks (L-shaped corner marks at sheet edges)
+    /// Add crop marks (L-shaped corner marks at the corners of the trimmed
+    /// sheet, which is the sheet less the bleed; they show in the bleed)
     pub crop_marks: bool,
     /// Add trim marks (L-shaped corner marks at each page boundary)
     pub trim_marks: bool,
     /// Add registration marks (crosshairs for alignment)
     pub registration_marks: bool,
+    /// Add collation marks (back-step blocks on the spine fold, stepping
+    /// down one position per signature)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub collation_marks: bool,
+    /// Add sewing station ticks across the spine fold
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub sewing_marks: Option<SewingMarks>,
+    /// Add short ticks continuing the spine fold past its head and tail
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub spine_marks: bool,
+    /// Add punch-hole guides along the binding edge of each leaf (side
+    /// stitch and spiral binding only)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub punch_guides: Option<PunchGuides>,
+    /// Add a row of CMYK, black tint and RGB solid patches in the bottom
+    /// sheet margin, where there's room below the other marks
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub color_bars: bool,
+    /// Print each cell's page number, or "BLANK" for padding, faintly at its
+    /// center for proofing the imposition; unlike page numbers these are
+    /// not meant for the finished book
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub slot_labels: bool,
+    /// Color, stroke weight and size of the crop, trim and registration marks
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub style: MarkStyle,
+}
+
+/// How the crop, trim and registration marks are drawn
+///
+/// The color strokes every mark but the registration marks, which are always
+/// in registration color; fold and cut lines keep their own weights.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
+pub struct MarkStyle {
+    /// Stroke color as RGB components from 0 to 1
+    pub color_rgb: [f32; 3],
+    /// Stroke weight of crop, trim and registration marks in points
+    pub line_width_pt: f32,
+    /// Length of each arm of a crop or trim mark in points
+    pub mark_length_pt: f32,
+    /// Gap between a mark and the edge it marks in points
+    pub gap_pt: f32,
+}
+
+impl Default for MarkStyle {
+    fn default() -> Self {
+        Self {
+            color_rgb: [0.0, 0.0, 0.0],
+            line_width_pt: CROP_MARK_WIDTH,
+            mark_length_pt: CROP_MARK_LENGTH,
+            gap_pt: CROP_MARK_GAP,
+        }
+    }
+}
+
+/// Sewing station positions along the spine fold
+///
+/// Stations are spread evenly between the head and tail margins; a single
+/// station sits at the middle of the spine.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
+pub struct SewingMarks {
+    /// Number of sewing stations
+    pub stations: u8,
+    /// Distance from the head and tail to the outermost stations in mm
+    pub head_tail_margin_mm: f32,
+}
+
+impl Default for SewingMarks {
+    fn default() -> Self {
+        Self {
+            stations: 4,
+            head_tail_margin_mm: 15.0,
+        }
+    }
+}
+
+/// Punch-hole guides along the binding edge of each leaf
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
+pub struct PunchGuides {
+    /// How the holes are spaced along the binding edge
+    pub spacing: HoleSpacing,
+    /// Distance from the binding edge to the hole centers in mm
+    pub edge_offset_mm: f32,
+    /// Hole diameter in mm
+    pub hole_diameter_mm: f32,
+}
+
+impl Default for PunchGuides {
+    fn default() -> Self {
+        Self {
+            spacing: HoleSpacing::PitchMm(8.47),
+            edge_offset_mm: 5.0,
+            hole_diameter_mm: 4.0,
+        }
+    }
+}
+
+/// Spacing of punch holes along the binding edge
+///
+/// Either way the row of holes is centered on the edge.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HoleSpacing {
+    /// A fixed number of holes, one in the middle of each equal share of the edge
+    Count(u8),
+    /// Center-to-center distance in mm, as many holes as fit
+    PitchMm(f32),
 }
 
 impl PrinterMarks {
     /// Enable all marks
+    ///
+    /// Punch guides are left off since only some binding types accept them,
+    /// and slot labels since they print over the pages.
     pub fn all() -> Self {
         Self {
             fold_lines: true,
+            fold_labels: true,
             cut_lines: true,
             crop_marks: true,
             trim_marks: true,
             registration_marks: true,
+            collation_marks: true,
+            sewing_marks: Some(SewingMarks::default()),
+            spine_marks: true,
+            punch_guides: None,
+            color_bars: true,
+            slot_labels: false,
+            style: MarkStyle::default(),
+        }
+    }
+
+    /// Fold and cut lines only, for folding and cutting sheets by hand
+    pub fn folding_guide() -> Self {
+        Self {
+            fold_lines: true,
+            cut_lines: true,
+            ..Default::default()
+        }
+    }
+
+    /// Crop, trim and registration marks with color bars, as a print shop
+    /// expects
+    pub fn print_shop() -> Self {
+        Self {
+            crop_marks: true,
+            trim_marks: true,
+            registration_marks: true,
+            color_bars: true,
+            ..Default::default()
         }
     }
 
@@ -418,6 +839,96 @@ impl PrinterMarks {
             || self.crop_marks
             || self.trim_marks
             || self.registration_marks
+            || self.collation_marks
+            || self.sewing_marks.is_some()
+            || self.spine_marks
+            || self.punch_guides.is_some()
+            || self.color_bars
+            || self.slot_labels
+    }
+
+    /// Smallest sheet margin in mm that keeps the marks drawn around the
+    /// leaf area on the sheet, or 0 if none are enabled
+    ///
+    /// Color bars and fold labels aren't counted, since they're left out or
+    /// moved inside the leaf area where they don't fit. Neither are crop
+    /// marks, which mark the trimmed sheet rather than the leaf area.
+    pub fn min_sheet_margin_mm(&self) -> f32 {
+        let gap = self.style.gap_pt;
+        let reaches = [
+            (self.trim_marks, gap + self.style.mark_length_pt),
+            (self.registration_marks, gap + 1.5 * REGISTRATION_MARK_SIZE),
+            // Scissors sit 3pt out from the leaf on each cut
+            (self.cut_lines, SCISSORS_SIZE + 3.0),
+            (self.spine_marks, gap + SPINE_MARK_LENGTH),
+        ];
+        let reach_pt = reaches
+            .into_iter()
+            .filter(|(enabled, _)| *enabled)
+            .map(|(_, reach)| reach)
+            .fold(0.0, f32::max);
+        pt_to_mm(reach_pt)
+    }
+}
+
+// =============================================================================
+// Cover
+// =============================================================================
+
+/// What to do with the cover pages at the front and back of the source
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub enum CoverMode {
+    /// Leave the cover pages out of the output
+    #[default]
+    Skip,
+    /// Leave them out of the body and impose them as a wraparound cover in
+    /// a document of their own
+    SeparateDocument,
+}
+
+/// Cover pages kept out of the body, for bindings with a separate cover
+///
+/// A wraparound cover puts the back cover and the front cover either side of
+/// the spine, so it holds at most two pages at each end: the outside and the
+/// inside of the cover.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
+pub struct CoverOptions {
+    /// Pages at the start of the source that belong to the front cover
+    pub front_pages: usize,
+    /// Pages at the end of the source that belong to the back cover
+    pub back_pages: usize,
+    pub mode: CoverMode,
+    /// Thickness of one leaf of the body paper, which sets the spine width
+    pub paper_thickness_mm: f32,
+}
+
+impl Default for CoverOptions {
+    fn default() -> Self {
+        Self {
+            front_pages: 0,
+            back_pages: 0,
+            mode: CoverMode::Skip,
+            paper_thickness_mm: 0.1,
+        }
+    }
+}
+
+impl CoverOptions {
+    /// Total pages taken out of the body for the cover
+    pub fn pages(&self) -> usize {
+        self.front_pages + self.back_pages
+    }
+
+    /// Whether the cover is imposed as its own document
+    pub fn separate(&self) -> bool {
+        self.mode == CoverMode::SeparateDocument && self.pages() > 0
+    }
+
+    /// Spine width in mm for a body of `leaves` leaves
+    pub fn spine_width_mm(&self, leaves: usize) -> f32 {
+        leaves as f32 * self.paper_thickness_mm
     }
 }
 
@@ -439,12 +950,131 @@ pub enum SplitMode {
     BySignatures(usize),
 }
 
+// =============================================================================
+// Saving
+// =============================================================================
+
+/// How [`save_pdf`](crate::save_pdf) writes a document
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SaveOptions {
+    /// Flate-compress streams that don't already have a filter
+    pub compress_streams: bool,
+    /// Write the cross-reference section as a stream (PDF 1.5) instead of a
+    /// classic table. lopdf can't pack objects into object streams yet, so
+    /// the objects themselves are still written one by one.
+    pub use_object_streams: bool,
+    /// Ask for a linearized ("fast web view") file. lopdf can't write one
+    /// yet, so this has no effect for now.
+    pub linearize_hint: bool,
+}
+
+impl Default for SaveOptions {
+    fn default() -> Self {
+        Self {
+            compress_streams: true,
+            use_object_streams: true,
+            linearize_hint: false,
+        }
+    }
+}
+
+// =============================================================================
+// Page Selection
+// =============================================================================
+
+/// A selection of pages from one input document
+///
+/// Parsed from strings like `1-4,7,10-`: page numbers are 1-based and
+/// inclusive, and an open-ended span runs to the last page. Pages are
+/// selected in the order written.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PageRange {
+    spans: Vec<(usize, Option<usize>)>,
+}
+
+impl PageRange {
+    /// Zero-based indices of the selected pages in a document of `total_pages`.
+    ///
+    /// Pages past the end of the document are skipped.
+    pub fn select(&self, total_pages: usize) -> Vec<usize> {
+        self.spans
+            .iter()
+            .flat_map(|&(start, end)| {
+                let end = end.unwrap_or(total_pages).min(total_pages);
+                (start..=end).map(|page| page - 1)
+            })
+            .collect()
+    }
+
+    /// Number of pages selected from a document of `total_pages`
+    pub fn count(&self, total_pages: usize) -> usize {
+        self.select(total_pages).len()
+    }
+
+    /// Whether the range names pages past the end of a document of
+    /// `total_pages`, which [`select`](Self::select) skips
+    pub fn exceeds(&self, total_pages: usize) -> bool {
+        self.spans
+            .iter()
+            .any(|&(start, end)| start > total_pages || end.is_some_and(|end| end > total_pages))
+    }
+}
+
+impl std::str::FromStr for PageRange {
+    type Err = ImposeError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || ImposeError::Config(format!("Invalid page range: {s:?}"));
+        let parse_page = |text: &str| match text.trim().parse::<usize>() {
+            Ok(0) | Err(_) => Err(invalid()),
+            Ok(page) => Ok(page),
+        };
+
+        let spans = s
+            .split(',')
+            .map(|part| match part.split_once('-') {
+                Some((start, end)) if end.trim().is_empty() => Ok((parse_page(start)?, None)),
+                Some((start, end)) => {
+                    let (start, end) = (parse_page(start)?, parse_page(end)?);
+                    if start > end {
+                        return Err(invalid());
+                    }
+                    Ok((start, Some(end)))
+                }
+                None => {
+                    let page = parse_page(part)?;
+                    Ok((page, Some(page)))
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self { spans })
+    }
+}
+
+impl std::fmt::Display for PageRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (idx, &(start, end)) in self.spans.iter().enumerate() {
+            if idx > 0 {
+                write!(f, ",")?;
+            }
+            match end {
+                Some(end) if end == start => write!(f, "{start}")?,
+                Some(end) => write!(f, "{start}-{end}")?,
+                None => write!(f, "{start}-")?,
+            }
+        }
+        Ok(())
+    }
+}
+
 // =============================================================================
 // Statistics
 // =============================================================================
 
 /// Statistics about an imposition job
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ImpositionStatistics {
     /// Total number of source pages (including flyleaves)
     pub source_pages: usize,
@@ -457,7 +1087,27 @@ pub struct ImpositionStatistics {
     /// Total output page count (usually output_sheets × 2)
     pub output_pages: usize,
     /// Number of blank pages added for padding
+    ///
+    /// With cut and stack these all come at the end of the last stack.
     pub blank_pages_added: usize,
+    /// Leaves in each stack (if using cut and stack)
+    pub stack_height: Option<usize>,
+    /// Sheets in the separately imposed cover (0 when there is none)
+    pub cover_sheets: usize,
+    /// Sheets of paper the whole job takes: the body's sheets plus the cover's
+    pub paper_sheets: usize,
+    /// Estimated spine thickness of the bound body, from the padded leaf
+    /// count and the cover's `paper_thickness_mm` (`None` for N-up and step
+    /// and repeat, which aren't bound)
+    pub spine_thickness_mm: Option<f32>,
+    /// Blank pages (padding and inserted blanks) in each signature, if using
+    /// signatures
+    pub blank_pages_per_signature: Option<Vec<usize>>,
+    /// (width, height) in mm of a finished leaf once folded and trimmed
+    pub trim_size_mm: (f32, f32),
+    /// Whether the requested bleed fits in the leaf margins; bleed that would run
+    /// into the sheet margins or a neighboring page is clamped short
+    pub bleed_fits: bool,
 }
 
 impl ImpositionStatistics {
@@ -465,4 +1115,110 @@ impl ImpositionStatistics {
     pub fn has_blank_pages(&self) -> bool {
         self.blank_pages_added > 0
     }
+
+    /// Page count after padding to whole sheets or signatures
+    pub fn padded_pages(&self) -> usize {
+        self.source_pages + self.blank_pages_added
+    }
+}
+
+// =============================================================================
+// Progress
+// =============================================================================
+
+/// A stage of a running imposition, or a warning raised along the way, as
+/// reported by [`impose_with_progress`](crate::impose_with_progress)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImposeProgress {
+    /// Merging the selected pages of the inputs, with blanks and flyleaves
+    Merging,
+    /// Output page `current` of `total` (1-based) has been rendered; source
+    /// pages are copied in as they are first placed
+    Rendering { current: usize, total: usize },
+    /// Compressing the output streams
+    Compressing,
+    /// A problem with the inputs was worked around
+    Warning(ImposeWarning),
+}
+
+/// A problem with the inputs that the imposition worked around
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImposeWarning {
+    /// A page's box has no usable size (e.g. `[0 0 0 0]`), so the page was
+    /// imposed at the size of `substituted`
+    InvalidPageBox {
+        /// 0-based index of the input file
+        input: usize,
+        /// 1-based page number in that input
+        page: u32,
+        substituted: PaperSize,
+    },
+    /// Color bars were asked for, but the bottom sheet margin of some sheet
+    /// sides has no room for them below the other marks, so they were left
+    /// off those sides
+    NoRoomForColorBars,
+    /// The narrowest sheet margin (`margin_mm`) is too narrow for the
+    /// printer's marks around the leaf area, which need `needed_mm`; they run
+    /// off the sheet there
+    MarksExceedSheetMargin { margin_mm: f32, needed_mm: f32 },
+}
+
+impl std::fmt::Display for ImposeWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            ImposeWarning::InvalidPageBox {
+                input,
+                page,
+                substituted,
+            } => {
+                write!(
+                    f,
+                    "page {page} of input {} had an invalid page box, substituted ",
+                    input + 1
+                )?;
+                match substituted {
+                    PaperSize::Custom {
+                        width_mm,
+                        height_mm,
+                    } => write!(f, "{width_mm} × {height_mm} mm"),
+                    named => write!(f, "{named:?}"),
+                }
+            }
+            ImposeWarning::NoRoomForColorBars => {
+                write!(f, "the bottom sheet margin has no room for color bars")
+            }
+            ImposeWarning::MarksExceedSheetMargin {
+                margin_mm,
+                needed_mm,
+            } => write!(
+                f,
+                "the printer's marks run off a {margin_mm:.1} mm sheet margin; \
+                 use a sheet margin of at least {needed_mm:.1} mm"
+            ),
+        }
+    }
+}
+
+/// Shared flag for stopping a running imposition
+///
+/// Clones share the flag. The imposition checks it between output pages and
+/// stops with [`ImposeError::Cancelled`] once it is set.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Create a token that has not been cancelled
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ask the imposition using this token to stop
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether [`cancel`](Self::cancel) has been called on any clone
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
 }
@@ -0,0 +1,82 @@
+//! PDF fixtures and content readers shared by the integration tests
+
+// Each test crate uses only some of these
+#![allow(dead_code)]
+
+use lopdf::{Dictionary, Document, Object, Stream};
+
+/// A PDF rectangle array
+pub fn rect(values: [i64; 4]) -> Object {
+    Object::Array(values.into_iter().map(Object::Integer).collect())
+}
+
+/// Create a document of `num_pages` pages with empty content, with
+/// `page_entries` added to every page and `pages_entries` to the Pages node
+pub fn create_pdf(
+    num_pages: usize,
+    page_entries: &[(&str, Object)],
+    pages_entries: &[(&str, Object)],
+) -> Document {
+    let mut doc = Document::with_version("1.7");
+    let pages_id = doc.new_object_id();
+
+    let mut kids = Vec::new();
+    for _ in 0..num_pages {
+        let content_id = doc.add_object(Stream::new(Dictionary::new(), b"q Q".to_vec()));
+        let mut page = Dictionary::from_iter(vec![
+            ("Type", Object::Name(b"Page".to_vec())),
+            ("Parent", Object::Reference(pages_id)),
+            ("Contents", Object::Reference(content_id)),
+        ]);
+        for (key, value) in page_entries {
+            page.set(*key, value.clone());
+        }
+        kids.push(Object::Reference(doc.add_object(page)));
+    }
+
+    let mut pages = Dictionary::from_iter(vec![
+        ("Type", Object::Name(b"Pages".to_vec())),
+        ("Kids", Object::Array(kids)),
+        ("Count", Object::Integer(num_pages as i64)),
+    ]);
+    for (key, value) in pages_entries {
+        pages.set(*key, value.clone());
+    }
+    doc.objects.insert(pages_id, Object::Dictionary(pages));
+
+    let catalog_id = doc.add_object(Dictionary::from_iter(vec![
+        ("Type", Object::Name(b"Catalog".to_vec())),
+        ("Pages", Object::Reference(pages_id)),
+    ]));
+    doc.trailer.set("Root", catalog_id);
+
+    doc
+}
+
+/// Create a document of `num_pages` blank 612x792 pages
+pub fn create_test_pdf(num_pages: usize) -> Document {
+    create_pdf(
+        num_pages,
+        &[
+            ("MediaBox", rect([0, 0, 612, 792])),
+            ("Resources", Object::Dictionary(Dictionary::new())),
+        ],
+        &[],
+    )
+}
+
+/// The XObject name and `cm` matrix of each page placed on `page_id`, in
+/// drawing order
+pub fn placed_matrices(doc: &Document, page_id: lopdf::ObjectId) -> Vec<(String, [f32; 6])> {
+    let content = String::from_utf8(doc.get_page_content(page_id).unwrap()).unwrap();
+    content
+        .lines()
+        .filter(|line| line.ends_with("Do Q"))
+        .map(|line| {
+            let ops: Vec<&str> = line.split_whitespace().collect();
+            let name = ops[ops.len() - 3].trim_start_matches('/').to_string();
+            let matrix: Vec<f32> = ops[1..7].iter().map(|op| op.parse().unwrap()).collect();
+            (name, matrix.try_into().unwrap())
+        })
+        .collect()
+}
@@ -2,6 +2,9 @@ use lopdf::{Dictionary, Document, Object, Stream};
 use pdf_impose::*;
 use std::path::PathBuf;
 
+mod common;
+use common::placed_matrices;
+
 fn create_test_pdf(num_pages: usize) -> Document {
     let mut doc = Document::with_version("1.7");
 
@@ -31,82 +34,3058 @@ fn create_test_pdf(num_pages: usize) -> Document {
         kids.push(Object::Reference(page_id));
     }
 
-    // Create pages dict
-    let pages_dict = Dictionary::from_iter(vec![
-        ("Type", Object::Name(b"Pages".to_vec())),
-        ("Kids", Object::Array(kids)),
-        ("Count", Object::Integer(num_pages as i64)),
-    ]);
-    doc.objects.insert(pages_id, Object::Dictionary(pages_dict));
+    // Create pages dict
+    let pages_dict = Dictionary::from_iter(vec![
+        ("Type", Object::Name(b"Pages".to_vec())),
+        ("Kids", Object::Array(kids)),
+        ("Count", Object::Integer(num_pages as i64)),
+    ]);
+    doc.objects.insert(pages_id, Object::Dictionary(pages_dict));
+
+    // Create catalog
+    let catalog_id = doc.add_object(Dictionary::from_iter(vec![
+        ("Type", Object::Name(b"Catalog".to_vec())),
+        ("Pages", Object::Reference(pages_id)),
+    ]));
+
+    doc.trailer.set("Root", catalog_id);
+
+    doc
+}
+
+#[tokio::test]
+async fn test_load_pdf() {
+    use tempfile::NamedTempFile;
+
+    let mut doc = create_test_pdf(5);
+    let temp = NamedTempFile::new().unwrap();
+    let path = temp.path();
+
+    // Save test PDF
+    let mut writer = Vec::new();
+    doc.save_to(&mut writer).unwrap();
+    std::fs::write(path, writer).unwrap();
+
+    // Load it back
+    let loaded = load_pdf(path).await.unwrap();
+    assert_eq!(loaded.get_pages().len(), 5);
+}
+
+#[tokio::test]
+async fn test_load_multiple_pdfs() {
+    use tempfile::NamedTempFile;
+
+    let mut doc1 = create_test_pdf(3);
+    let mut doc2 = create_test_pdf(4);
+
+    let temp1 = NamedTempFile::new().unwrap();
+    let temp2 = NamedTempFile::new().unwrap();
+
+    let mut writer = Vec::new();
+    doc1.save_to(&mut writer).unwrap();
+    std::fs::write(temp1.path(), &writer).unwrap();
+
+    writer.clear();
+    doc2.save_to(&mut writer).unwrap();
+    std::fs::write(temp2.path(), &writer).unwrap();
+
+    let paths = vec![temp1.path(), temp2.path()];
+    let docs = load_multiple_pdfs(&paths).await.unwrap();
+
+    assert_eq!(docs.len(), 2);
+    assert_eq!(docs[0].get_pages().len(), 3);
+    assert_eq!(docs[1].get_pages().len(), 4);
+}
+
+#[tokio::test]
+async fn test_save_pdf() {
+    use tempfile::NamedTempFile;
+
+    let doc = create_test_pdf(2);
+    let temp = NamedTempFile::new().unwrap();
+
+    save_pdf(doc, temp.path(), SaveOptions::default())
+        .await
+        .unwrap();
+
+    // Verify file was created and can be loaded
+    assert!(temp.path().exists());
+    let loaded = Document::load(temp.path()).unwrap();
+    assert_eq!(loaded.get_pages().len(), 2);
+}
+
+/// A document whose pages have long, uncompressed content streams
+fn create_text_heavy_pdf(num_pages: usize) -> Document {
+    let mut doc = create_test_pdf(num_pages);
+    for page_id in doc.get_pages().into_values().collect::<Vec<_>>() {
+        let content: String = (0..300)
+            .map(|line| format!("BT /F1 9 Tf 40 {} Td (Line {line}) Tj ET\n", 40 + line * 2))
+            .collect();
+        let content_id = doc.add_object(Stream::new(Dictionary::new(), content.into_bytes()));
+        doc.get_dictionary_mut(page_id)
+            .unwrap()
+            .set("Contents", Object::Reference(content_id));
+    }
+    doc
+}
+
+/// The bytes `save_pdf` writes for `doc` with `save_options`
+async fn saved_bytes(doc: Document, save_options: SaveOptions) -> Vec<u8> {
+    let temp = tempfile::NamedTempFile::new().unwrap();
+    save_pdf(doc, temp.path(), save_options).await.unwrap();
+    std::fs::read(temp.path()).unwrap()
+}
+
+#[tokio::test]
+async fn test_save_pdf_compressed_output_is_smaller() {
+    let doc = create_text_heavy_pdf(20);
+    let uncompressed = SaveOptions {
+        compress_streams: false,
+        ..Default::default()
+    };
+
+    let compressed_len = saved_bytes(doc.clone(), SaveOptions::default()).await.len();
+    let uncompressed_len = saved_bytes(doc, uncompressed).await.len();
+
+    assert!(
+        compressed_len * 4 < uncompressed_len,
+        "{compressed_len} bytes compressed vs {uncompressed_len} uncompressed"
+    );
+}
+
+#[tokio::test]
+async fn test_save_pdf_cross_reference_format() {
+    let table = SaveOptions {
+        use_object_streams: false,
+        ..Default::default()
+    };
+    let mut old = create_test_pdf(2);
+    old.version = "1.4".to_string();
+
+    let with_table = saved_bytes(create_test_pdf(2), table).await;
+    let with_stream = saved_bytes(old, SaveOptions::default()).await;
+
+    let contains = |bytes: &[u8], needle: &[u8]| bytes.windows(needle.len()).any(|w| w == needle);
+    assert!(contains(&with_table, b"\nxref\n"));
+    assert!(contains(&with_stream, b"/Type/XRef"));
+    assert!(with_stream.starts_with(b"%PDF-1.5"));
+    for bytes in [with_table, with_stream] {
+        assert_eq!(Document::load_mem(&bytes).unwrap().get_pages().len(), 2);
+    }
+}
+
+/// Write `doc` to a temporary file, RC4-encrypted with the user password
+/// `password`
+fn encrypted_pdf_file(mut doc: Document, password: &str) -> tempfile::NamedTempFile {
+    use lopdf::{EncryptionState, EncryptionVersion, Permissions};
+
+    doc.trailer.set(
+        "ID",
+        Object::Array(vec![
+            Object::string_literal("0123456789abcdef"),
+            Object::string_literal("0123456789abcdef"),
+        ]),
+    );
+    let state = EncryptionState::try_from(EncryptionVersion::V2 {
+        document: &doc,
+        owner_password: "owner",
+        user_password: password,
+        key_length: 128,
+        permissions: Permissions::all(),
+    })
+    .unwrap();
+    doc.encrypt(&state).unwrap();
+
+    let temp = tempfile::NamedTempFile::new().unwrap();
+    doc.save(temp.path()).unwrap();
+    temp
+}
+
+#[tokio::test]
+async fn test_load_pdf_with_password_decrypts_input() {
+    let file = encrypted_pdf_file(create_labeled_pdf("a", 2), "secret");
+
+    let doc = load_pdf_with_password(file.path(), Some("secret"))
+        .await
+        .unwrap();
+    assert!(!doc.is_encrypted());
+    let first_page = doc.page_iter().next().unwrap();
+    assert_eq!(doc.get_page_content(first_page).unwrap(), b"% a1");
+
+    let missing = load_pdf_with_password(file.path(), None).await;
+    assert!(matches!(
+        missing,
+        Err(ImposeError::Encrypted {
+            needs_password: true
+        })
+    ));
+    let wrong = load_pdf_with_password(file.path(), Some("guess")).await;
+    assert!(matches!(
+        wrong,
+        Err(ImposeError::Encrypted {
+            needs_password: false
+        })
+    ));
+}
+
+#[tokio::test]
+async fn test_load_multiple_pdfs_with_password_opens_plain_inputs_too() {
+    let locked = encrypted_pdf_file(create_labeled_pdf("a", 2), "secret");
+    let plain = tempfile::NamedTempFile::new().unwrap();
+    create_labeled_pdf("b", 3).save(plain.path()).unwrap();
+
+    let documents =
+        load_multiple_pdfs_with_password(&[locked.path(), plain.path()], Some("secret"))
+            .await
+            .unwrap();
+    let page_counts: Vec<usize> = documents.iter().map(|doc| doc.get_pages().len()).collect();
+    assert_eq!(page_counts, [2, 3]);
+}
+
+#[tokio::test]
+async fn test_impose_encrypted_input_needs_password() {
+    let file = encrypted_pdf_file(create_labeled_pdf("a", 4), "secret");
+    let documents = vec![load_pdf(file.path()).await.unwrap()];
+    let mut options = ImpositionOptions {
+        input_files: vec![file.path().to_owned()],
+        binding_type: BindingType::PerfectBinding,
+        compress_output: false,
+        ..Default::default()
+    };
+
+    let locked = impose(&documents, &options).await;
+    assert!(matches!(
+        locked,
+        Err(ImposeError::Encrypted {
+            needs_password: true
+        })
+    ));
+
+    options.password = Some("secret".to_string());
+    let output = impose(&documents, &options).await.unwrap();
+    let first_page = *output.get_pages().values().next().unwrap();
+    assert_eq!(placed_labels(&output, first_page), vec!["% a1", "% a2"]);
+}
+
+fn media_box_width(doc: &Document, page_id: lopdf::ObjectId) -> i64 {
+    let page = doc.get_dictionary(page_id).unwrap();
+    page.get(b"MediaBox").unwrap().as_array().unwrap()[2]
+        .as_i64()
+        .unwrap()
+}
+
+#[test]
+fn test_merge_documents_keeps_all_pages() {
+    let merged = merge_documents(&[create_test_pdf(3), create_test_pdf(3)]).unwrap();
+    assert_eq!(merged.get_pages().len(), 6);
+}
+
+#[test]
+fn test_merge_documents_without_pages_fails() {
+    assert!(matches!(merge_documents(&[]), Err(ImposeError::NoPages)));
+    let empty = merge_documents(&[create_test_pdf(0), create_test_pdf(0)]);
+    assert!(matches!(empty, Err(ImposeError::NoPages)));
+}
+
+#[tokio::test]
+async fn test_load_pdf_reports_unsupported_version() {
+    let mut doc = create_test_pdf(1);
+    doc.version = "3.1".to_string();
+    let temp = tempfile::NamedTempFile::new().unwrap();
+    doc.save(temp.path()).unwrap();
+
+    match load_pdf(temp.path()).await {
+        Err(ImposeError::UnsupportedPdfVersion(version)) => assert_eq!(version, "3.1"),
+        other => panic!("Expected UnsupportedPdfVersion, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_load_pdf_reports_corrupt_page_tree() {
+    let mut doc = create_test_pdf(2);
+    doc.catalog_mut().unwrap().remove(b"Pages");
+    let temp = tempfile::NamedTempFile::new().unwrap();
+    doc.save(temp.path()).unwrap();
+
+    let result = load_pdf(temp.path()).await;
+    assert!(matches!(result, Err(ImposeError::CorruptPageTree(_))));
+}
+
+/// The image XObject drawn on `page_id` of a document built from images
+#[cfg(feature = "images")]
+fn page_image(doc: &Document, page_id: lopdf::ObjectId) -> &Stream {
+    let resources = doc
+        .get_dictionary(page_id)
+        .unwrap()
+        .get(b"Resources")
+        .and_then(Object::as_dict)
+        .unwrap();
+    let image_id = resources
+        .get(b"XObject")
+        .and_then(Object::as_dict)
+        .and_then(|xobjects| xobjects.get(b"Im0"))
+        .and_then(Object::as_reference)
+        .unwrap();
+    doc.get_object(image_id)
+        .and_then(Object::as_stream)
+        .unwrap()
+}
+
+#[cfg(feature = "images")]
+#[tokio::test]
+async fn test_load_image_folder_makes_a_page_per_image_in_natural_order() {
+    let dir = tempfile::tempdir().unwrap();
+    let jpeg_path = dir.path().join("page10.jpg");
+    image::RgbImage::from_pixel(60, 40, image::Rgb([200, 30, 30]))
+        .save(&jpeg_path)
+        .unwrap();
+    image::RgbaImage::from_pixel(30, 40, image::Rgba([0, 0, 255, 128]))
+        .save(dir.path().join("page2.png"))
+        .unwrap();
+    std::fs::write(dir.path().join("notes.txt"), "not an image").unwrap();
+
+    let doc = load_image_folder(dir.path(), PaperSize::A4).await.unwrap();
+
+    // page2.png sorts first and stays upright; the wide JPEG gets a landscape page
+    let pages: Vec<_> = doc.get_pages().into_values().collect();
+    assert_eq!(pages.len(), 2);
+    let width = |page_id| {
+        let page = doc.get_dictionary(page_id).unwrap();
+        page.get(b"MediaBox").unwrap().as_array().unwrap()[2]
+            .as_float()
+            .unwrap()
+            .round()
+    };
+    assert_eq!(width(pages[0]), 595.0);
+    assert_eq!(width(pages[1]), 842.0);
+
+    let png = page_image(&doc, pages[0]);
+    assert_eq!(
+        png.dict.get(b"ColorSpace").unwrap().as_name().unwrap(),
+        b"DeviceRGB"
+    );
+    assert!(
+        png.dict.get(b"SMask").is_ok(),
+        "PNG alpha should become a soft mask"
+    );
+
+    let jpeg = page_image(&doc, pages[1]);
+    assert_eq!(
+        jpeg.dict.get(b"Filter").unwrap().as_name().unwrap(),
+        b"DCTDecode"
+    );
+    assert_eq!(jpeg.dict.get(b"Width").unwrap().as_i64().unwrap(), 60);
+    assert_eq!(jpeg.content, std::fs::read(&jpeg_path).unwrap());
+
+    let options = ImpositionOptions {
+        input_files: vec![dir.path().to_path_buf()],
+        page_arrangement: PageArrangement::Folio,
+        ..Default::default()
+    };
+    let imposed = impose(&[doc], &options).await.unwrap();
+    assert_eq!(imposed.get_pages().len(), 2);
+}
+
+#[cfg(feature = "images")]
+#[tokio::test]
+async fn test_load_images_reports_undecodable_image() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("broken.png");
+    std::fs::write(&path, b"not a png").unwrap();
+
+    match load_images(&[&path], PaperSize::Letter).await {
+        Err(ImposeError::Image { path: bad, .. }) => assert_eq!(bad, path),
+        other => panic!("Expected Image error, got {other:?}"),
+    }
+}
+
+/// Create a document whose page contents are `% <label><n>` comments
+fn create_labeled_pdf(label: &str, num_pages: usize) -> Document {
+    let mut doc = create_test_pdf(num_pages);
+    let page_ids: Vec<_> = doc.get_pages().into_values().collect();
+    for (idx, page_id) in page_ids.into_iter().enumerate() {
+        let content = format!("% {label}{}", idx + 1).into_bytes();
+        let content_id = doc.add_object(Stream::new(Dictionary::new(), content));
+        doc.get_dictionary_mut(page_id)
+            .unwrap()
+            .set("Contents", Object::Reference(content_id));
+    }
+    doc
+}
+
+#[test]
+fn test_merge_documents_three_and_four_pages() {
+    let merged =
+        merge_documents(&[create_labeled_pdf("a", 3), create_labeled_pdf("b", 4)]).unwrap();
+
+    let contents: Vec<_> = merged
+        .get_pages()
+        .into_values()
+        .map(|id| String::from_utf8(merged.get_page_content(id).unwrap()).unwrap())
+        .collect();
+
+    assert_eq!(
+        contents,
+        vec!["% a1", "% a2", "% a3", "% b1", "% b2", "% b3", "% b4"]
+    );
+}
+
+#[test]
+fn test_merge_documents_preserves_order_and_media_boxes() {
+    let first = create_test_pdf(2);
+    let mut second = create_test_pdf(2);
+
+    // Move the MediaBox of the second document onto its Pages node so it must be inherited
+    let page_ids: Vec<_> = second.get_pages().into_values().collect();
+    for id in &page_ids {
+        second.get_dictionary_mut(*id).unwrap().remove(b"MediaBox");
+    }
+    let pages_id = second
+        .catalog()
+        .unwrap()
+        .get(b"Pages")
+        .unwrap()
+        .as_reference()
+        .unwrap();
+    second.get_dictionary_mut(pages_id).unwrap().set(
+        "MediaBox",
+        Object::Array(vec![
+            Object::Integer(0),
+            Object::Integer(0),
+            Object::Integer(420),
+            Object::Integer(595),
+        ]),
+    );
+
+    let merged = merge_documents(&[first, second]).unwrap();
+    let widths: Vec<_> = merged
+        .get_pages()
+        .into_values()
+        .map(|id| media_box_width(&merged, id))
+        .collect();
+
+    assert_eq!(widths, vec![612, 612, 420, 420]);
+}
+
+#[tokio::test]
+async fn test_impose_multiple_documents() {
+    let docs = vec![create_test_pdf(3), create_test_pdf(3)];
+    let mut options = ImpositionOptions::default();
+    options.input_files.push(PathBuf::from("a.pdf"));
+    options.input_files.push(PathBuf::from("b.pdf"));
+    options.binding_type = BindingType::PerfectBinding;
+
+    let result = impose(&docs, &options).await.unwrap();
+    // 6 source pages, 2-up
+    assert_eq!(result.get_pages().len(), 3);
+}
+
+fn split_options(split_mode: SplitMode) -> ImpositionOptions {
+    let mut options = ImpositionOptions::default();
+    options.input_files.push(PathBuf::from("test.pdf"));
+    options.page_arrangement = PageArrangement::Folio;
+    options.split_mode = split_mode;
+    options
+}
+
+fn split_page_counts(parts: &[(String, Document)]) -> Vec<usize> {
+    parts.iter().map(|(_, doc)| doc.get_pages().len()).collect()
+}
+
+#[tokio::test]
+async fn test_impose_split_none_returns_single_document() {
+    let parts = impose_split(&[create_test_pdf(12)], &split_options(SplitMode::None))
+        .await
+        .unwrap();
+
+    assert_eq!(parts.len(), 1);
+    assert_eq!(parts[0].0, "");
+    assert_eq!(parts[0].1.get_pages().len(), 6);
+}
+
+#[tokio::test]
+async fn test_impose_split_by_signatures() {
+    // 12 pages in folio = 3 signatures of one sheet (front + back) each
+    let options = split_options(SplitMode::BySignatures(2));
+    let parts = impose_split(&[create_test_pdf(12)], &options)
+        .await
+        .unwrap();
+
+    let suffixes: Vec<_> = parts.iter().map(|(suffix, _)| suffix.as_str()).collect();
+    assert_eq!(suffixes, vec!["_sig01", "_sig02"]);
+    assert_eq!(split_page_counts(&parts), vec![4, 2]);
+}
+
+#[tokio::test]
+async fn test_impose_split_by_sheets_and_pages() {
+    let docs = [create_test_pdf(12)];
+
+    let by_sheets = impose_split(&docs, &split_options(SplitMode::BySheets(1)))
+        .await
+        .unwrap();
+    assert_eq!(split_page_counts(&by_sheets), vec![2, 2, 2]);
+
+    // Three pages per file would split a sheet, so each file stops at the sheet boundary
+    let by_pages = impose_split(&docs, &split_options(SplitMode::ByPages(3)))
+        .await
+        .unwrap();
+    assert_eq!(split_page_counts(&by_pages), vec![2, 2, 2]);
+    assert_eq!(by_pages[0].0, "_001");
+}
+
+#[tokio::test]
+async fn test_impose_split_last_chunk_holds_remainder() {
+    // 20 pages in folio = 5 sheets -> 2 + 2 + 1
+    let options = split_options(SplitMode::BySheets(2));
+    let parts = impose_split(&[create_test_pdf(20)], &options)
+        .await
+        .unwrap();
+
+    let suffixes: Vec<_> = parts.iter().map(|(suffix, _)| suffix.as_str()).collect();
+    assert_eq!(suffixes, vec!["_001", "_002", "_003"]);
+    assert_eq!(split_page_counts(&parts), vec![4, 4, 2]);
+}
+
+#[tokio::test]
+async fn test_impose_split_two_sided_front_and_back_files() {
+    let mut options = split_options(SplitMode::None);
+    options.output_format = OutputFormat::TwoSided;
+    options.add_page_numbers = true;
+    let docs = [create_test_pdf(12)];
+
+    let parts = impose_split(&docs, &options).await.unwrap();
+    let suffixes: Vec<_> = parts.iter().map(|(suffix, _)| suffix.as_str()).collect();
+    assert_eq!(suffixes, vec!["_front", "_back"]);
+    assert_eq!(split_page_counts(&parts), vec![3, 3]);
+
+    // The fronts come in sheet order and the backs reversed, so the printed
+    // stack of fronts can be turned over and fed back in
+    let double_sided = ImpositionOptions {
+        output_format: OutputFormat::DoubleSided,
+        ..options.clone()
+    };
+    let interleaved = all_page_numbers(&impose(&docs, &double_sided).await.unwrap());
+    let front = all_page_numbers(&parts[0].1);
+    let back = all_page_numbers(&parts[1].1);
+    assert_eq!(
+        front,
+        [
+            interleaved[0].clone(),
+            interleaved[2].clone(),
+            interleaved[4].clone()
+        ]
+    );
+    assert_eq!(
+        back,
+        [
+            interleaved[5].clone(),
+            interleaved[3].clone(),
+            interleaved[1].clone()
+        ]
+    );
+
+    // Backs in sheet order on request
+    options.single_sided_back_order = BackOrder::Normal;
+    let parts = impose_split(&docs, &options).await.unwrap();
+    assert_eq!(
+        all_page_numbers(&parts[1].1),
+        [
+            interleaved[1].clone(),
+            interleaved[3].clone(),
+            interleaved[5].clone()
+        ]
+    );
+    options.single_sided_back_order = BackOrder::Reversed;
+
+    // Each file matches the matching half of the single-document output
+    let combined = all_page_numbers(&impose(&docs, &options).await.unwrap());
+    assert_eq!(front, combined[..3]);
+    assert_eq!(back, combined[3..]);
+
+    // Split modes still apply, each part getting its own pair
+    options.split_mode = SplitMode::BySignatures(2);
+    let parts = impose_split(&docs, &options).await.unwrap();
+    let suffixes: Vec<_> = parts.iter().map(|(suffix, _)| suffix.as_str()).collect();
+    assert_eq!(
+        suffixes,
+        vec!["_sig01_front", "_sig01_back", "_sig02_front", "_sig02_back"]
+    );
+    assert_eq!(split_page_counts(&parts), vec![2, 2, 1, 1]);
+}
+
+#[tokio::test]
+async fn test_impose_split_rejects_zero() {
+    let result = impose_split(
+        &[create_test_pdf(4)],
+        &split_options(SplitMode::BySheets(0)),
+    )
+    .await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_save_split_pdfs() {
+    let dir = tempfile::tempdir().unwrap();
+    let base = dir.path().join("output.pdf");
+    let options = split_options(SplitMode::BySignatures(1));
+    let parts = impose_split(&[create_test_pdf(8)], &options).await.unwrap();
+
+    let paths = save_split_pdfs(parts, &base, SaveOptions::default())
+        .await
+        .unwrap();
+
+    assert_eq!(
+        paths,
+        vec![
+            dir.path().join("output_sig01.pdf"),
+            dir.path().join("output_sig02.pdf")
+        ]
+    );
+    for path in paths {
+        assert_eq!(Document::load(path).unwrap().get_pages().len(), 2);
+    }
+
+    let mut options = split_options(SplitMode::None);
+    options.output_format = OutputFormat::TwoSided;
+    assert!(options.writes_multiple_files());
+    let parts = impose_split(&[create_test_pdf(8)], &options).await.unwrap();
+    let paths = save_split_pdfs(parts, &base, SaveOptions::default())
+        .await
+        .unwrap();
+    assert_eq!(
+        paths,
+        vec![
+            dir.path().join("output_front.pdf"),
+            dir.path().join("output_back.pdf")
+        ]
+    );
+}
+
+/// Page numbers drawn on an output page, in drawing order
+fn page_numbers_on(doc: &Document, page_id: lopdf::ObjectId) -> Vec<usize> {
+    let content = String::from_utf8(doc.get_page_content(page_id).unwrap()).unwrap();
+    content
+        .split(") Tj")
+        .filter_map(|chunk| chunk.rsplit('(').next()?.parse().ok())
+        .collect()
+}
+
+fn all_page_numbers(doc: &Document) -> Vec<Vec<usize>> {
+    doc.get_pages()
+        .into_values()
+        .map(|id| page_numbers_on(doc, id))
+        .collect()
+}
+
+#[test]
+fn test_impose_dummy_labels_every_page() {
+    let options = ImpositionOptions {
+        binding_type: BindingType::Signature,
+        page_arrangement: PageArrangement::Folio,
+        ..Default::default()
+    };
+
+    let doc = impose_dummy(8, &options).unwrap();
+    assert_eq!(doc.get_pages().len(), 4);
+
+    let mut numbers = all_page_numbers(&doc).concat();
+    numbers.sort_unstable();
+    assert_eq!(numbers, (1..=8).collect::<Vec<_>>());
+
+    let first = *doc.get_pages().values().next().unwrap();
+    let content = String::from_utf8(doc.get_page_content(first).unwrap()).unwrap();
+    assert!(content.contains("(Sig 1) Tj"));
+}
+
+#[tokio::test]
+async fn test_dummy_mode_skips_source_content() {
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        dummy_mode: true,
+        ..Default::default()
+    };
+
+    let output = impose(&[create_test_pdf(8)], &options).await.unwrap();
+    assert!(all_page_numbers(&output).iter().all(|side| side.len() == 4));
+    for page_id in output.get_pages().into_values() {
+        let page = output.get_dictionary(page_id).unwrap();
+        let resources = page.get(b"Resources").unwrap().as_dict().unwrap();
+        assert!(
+            resources
+                .get(b"XObject")
+                .unwrap()
+                .as_dict()
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_impose_cut_and_stack_order() {
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        binding_type: BindingType::CutAndStack,
+        page_arrangement: PageArrangement::Folio,
+        add_page_numbers: true,
+        ..Default::default()
+    };
+
+    // 10 pages in two stacks of 3 leaves; the last leaf is blank
+    let output = impose(&[create_test_pdf(10)], &options).await.unwrap();
+    assert_eq!(
+        all_page_numbers(&output),
+        vec![
+            vec![1, 7],
+            vec![8, 2],
+            vec![3, 9],
+            vec![10, 4],
+            vec![5],
+            vec![6],
+        ]
+    );
+
+    // 4-up: four stacks of one leaf, backs mirrored
+    let options = ImpositionOptions {
+        page_arrangement: PageArrangement::Quarto,
+        ..options
+    };
+    let output = impose(&[create_test_pdf(8)], &options).await.unwrap();
+    assert_eq!(
+        all_page_numbers(&output),
+        vec![vec![1, 3, 5, 7], vec![4, 2, 8, 6]]
+    );
+
+    let options = ImpositionOptions {
+        page_arrangement: PageArrangement::Custom {
+            pages_per_signature: 32,
+        },
+        ..options
+    };
+    let result = impose(&[create_test_pdf(8)], &options).await;
+    assert!(matches!(result, Err(ImposeError::Config(_))));
+}
+
+/// Cut a duplex cut-and-stack output into its stacks: the front cells in
+/// reading order, each followed by the back cell behind it
+fn cut_into_stacks(sides: &[Vec<usize>], cols: usize) -> Vec<Vec<usize>> {
+    let cells = sides[0].len();
+    let mut stacks = vec![Vec::new(); cells];
+    for sheet in sides.chunks(2) {
+        for (cell, stack) in stacks.iter_mut().enumerate() {
+            let (row, col) = (cell / cols, cell % cols);
+            stack.push(sheet[0][cell]);
+            stack.push(sheet[1][row * cols + cols - 1 - col]);
+        }
+    }
+    stacks
+}
+
+#[tokio::test]
+async fn test_impose_cut_and_stack_stacks_are_sequential() {
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        binding_type: BindingType::CutAndStack,
+        page_arrangement: PageArrangement::Folio,
+        add_page_numbers: true,
+        ..Default::default()
+    };
+
+    // 8 pages 2-up: two sheets, each stack of two leaves runs in order
+    let output = impose(&[create_test_pdf(8)], &options).await.unwrap();
+    let stacks = cut_into_stacks(&all_page_numbers(&output), 2);
+    assert_eq!(stacks, vec![vec![1, 2, 3, 4], vec![5, 6, 7, 8]]);
+
+    // 8-up over 32 pages: eight stacks piled in order give the whole book
+    let options = ImpositionOptions {
+        page_arrangement: PageArrangement::Octavo,
+        ..options
+    };
+    let output = impose(&[create_test_pdf(32)], &options).await.unwrap();
+    let stacks = cut_into_stacks(&all_page_numbers(&output), 4);
+    assert_eq!(stacks.len(), 8);
+    assert_eq!(stacks.concat(), (1..=32).collect::<Vec<_>>());
+}
+
+#[tokio::test]
+async fn test_impose_step_and_repeat() {
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        step_and_repeat: Some(StepAndRepeat {
+            cols: 2,
+            rows: 3,
+            copies: Some(10),
+        }),
+        add_page_numbers: true,
+        ..Default::default()
+    };
+
+    // 10 copies of 6 per sheet: two sheets of each page, one side each
+    let output = impose(&[create_test_pdf(2)], &options).await.unwrap();
+    assert_eq!(
+        all_page_numbers(&output),
+        vec![vec![1; 6], vec![1; 6], vec![2; 6], vec![2; 6]]
+    );
+}
+
+#[tokio::test]
+async fn test_impose_short_last_signature_uses_folio_order() {
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        page_arrangement: PageArrangement::Quarto,
+        allow_short_last_signature: true,
+        add_page_numbers: true,
+        ..Default::default()
+    };
+
+    // 12 pages: one quarto, then pages 9-12 as a folio rather than a padded quarto
+    let output = impose(&[create_test_pdf(12)], &options).await.unwrap();
+    let numbers = all_page_numbers(&output);
+    assert_eq!(numbers.len(), 4);
+    assert_eq!(numbers[2], vec![12, 9]);
+    assert_eq!(numbers[3], vec![10, 11]);
+}
+
+#[tokio::test]
+async fn test_impose_signature_plan() {
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        page_arrangement: PageArrangement::Quarto,
+        signature_plan: Some(SignaturePlan {
+            pages_per_signature: vec![4, 8],
+        }),
+        add_page_numbers: true,
+        ..Default::default()
+    };
+
+    // A folio of pages 1-4 in the bottom row, then a quarto of pages 5-12
+    let output = impose(&[create_test_pdf(12)], &options).await.unwrap();
+    assert_eq!(
+        all_page_numbers(&output),
+        vec![
+            vec![4, 1],
+            vec![2, 3],
+            vec![9, 8, 12, 5],
+            vec![7, 10, 6, 11],
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_impose_two_sided_fronts_then_backs() {
+    let mut options = ImpositionOptions::default();
+    options.input_files.push(PathBuf::from("test.pdf"));
+    options.page_arrangement = PageArrangement::Folio;
+    options.add_page_numbers = true;
+
+    let double = impose(&[create_test_pdf(8)], &options).await.unwrap();
+    options.output_format = OutputFormat::TwoSided;
+    let two_sided = impose(&[create_test_pdf(8)], &options).await.unwrap();
+
+    // Same pages, regrouped: front 1, back 1, front 2, back 2 -> fronts, then
+    // the backs reversed for turning the printed stack over
+    let double = all_page_numbers(&double);
+    let two_sided = all_page_numbers(&two_sided);
+    assert_eq!(two_sided.len(), 4);
+    assert_eq!(
+        two_sided,
+        vec![
+            double[0].clone(),
+            double[2].clone(),
+            double[3].clone(),
+            double[1].clone()
+        ]
+    );
+
+    // Once the stack of fronts is reversed, back N must hold the other side
+    // of every leaf on front N
+    let sheets = two_sided.len() / 2;
+    let (fronts, backs) = two_sided.split_at(sheets);
+    for (front, back) in fronts.iter().rev().zip(backs) {
+        assert_eq!(front.len(), 2);
+        for leaf in front {
+            let other_side = if leaf % 2 == 1 { leaf + 1 } else { leaf - 1 };
+            assert!(
+                back.contains(&other_side),
+                "front {front:?} / back {back:?}"
+            );
+        }
+    }
+
+    // Backs in sheet order on request
+    options.single_sided_back_order = BackOrder::Normal;
+    let normal = impose(&[create_test_pdf(8)], &options).await.unwrap();
+    assert_eq!(
+        all_page_numbers(&normal),
+        vec![
+            double[0].clone(),
+            double[2].clone(),
+            double[1].clone(),
+            double[3].clone()
+        ]
+    );
+}
+
+async fn single_sided_page_numbers(pages: usize, back_order: BackOrder) -> Vec<Vec<usize>> {
+    let mut options = ImpositionOptions::default();
+    options.input_files.push(PathBuf::from("test.pdf"));
+    options.page_arrangement = PageArrangement::Quarto;
+    options.add_page_numbers = true;
+    options.output_format = OutputFormat::SingleSidedSequence;
+    options.single_sided_back_order = back_order;
+
+    let doc = impose(&[create_test_pdf(pages)], &options).await.unwrap();
+    all_page_numbers(&doc)
+}
+
+#[tokio::test]
+async fn test_impose_single_sided_sequence_quarto() {
+    let mut options = ImpositionOptions::default();
+    options.input_files.push(PathBuf::from("test.pdf"));
+    options.page_arrangement = PageArrangement::Quarto;
+    options.add_page_numbers = true;
+    let double = all_page_numbers(&impose(&[create_test_pdf(16)], &options).await.unwrap());
+    let (front1, back1, front2, back2) = (&double[0], &double[1], &double[2], &double[3]);
+
+    // A single 8-page quarto sheet is just front then back in either order
+    let single = single_sided_page_numbers(8, BackOrder::Reversed).await;
+    assert_eq!(single.len(), 2);
+    assert_eq!(&single[0], front1);
+    assert_eq!(&single[1], back1);
+
+    let reversed = single_sided_page_numbers(16, BackOrder::Reversed).await;
+    assert_eq!(
+        reversed,
+        vec![front1.clone(), front2.clone(), back2.clone(), back1.clone()]
+    );
+
+    let normal = single_sided_page_numbers(16, BackOrder::Normal).await;
+    assert_eq!(
+        normal,
+        vec![front1.clone(), front2.clone(), back1.clone(), back2.clone()]
+    );
+}
+
+#[tokio::test]
+async fn test_impose_with_page_ranges() {
+    let docs = [create_labeled_pdf("a", 10), create_labeled_pdf("b", 3)];
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("a.pdf"), PathBuf::from("b.pdf")],
+        input_ranges: vec![Some("2-3,9-".parse().unwrap()), Some("3".parse().unwrap())],
+        binding_type: BindingType::PerfectBinding,
+        ..Default::default()
+    };
+
+    let output = impose(&docs, &options).await.unwrap();
+    // a2, a3, a9, a10, b3 -> padded to 6 -> 3 sheets
+    assert_eq!(output.get_pages().len(), 3);
+
+    let mut contents: Vec<String> = output
+        .objects
+        .values()
+        .filter_map(|obj| obj.as_stream().ok())
+        .filter(|stream| stream.dict.has(b"BBox"))
+        .map(|stream| String::from_utf8(stream.content.clone()).unwrap())
+        .collect();
+    contents.sort();
+    assert_eq!(contents, vec!["% a10", "% a2", "% a3", "% a9", "% b3"]);
+}
+
+/// Labels of the source pages placed on an output page, in drawing order
+fn placed_labels(doc: &Document, page_id: lopdf::ObjectId) -> Vec<String> {
+    let page = doc.get_dictionary(page_id).unwrap();
+    let xobjects = page
+        .get(b"Resources")
+        .and_then(Object::as_dict)
+        .and_then(|resources| resources.get(b"XObject"))
+        .and_then(Object::as_dict)
+        .unwrap();
+    let content = String::from_utf8(doc.get_page_content(page_id).unwrap()).unwrap();
+    content
+        .lines()
+        .filter(|line| line.ends_with("Do Q"))
+        .map(|line| {
+            let ops: Vec<&str> = line.split_whitespace().collect();
+            let name = ops[ops.len() - 3].trim_start_matches('/');
+            let id = xobjects
+                .get(name.as_bytes())
+                .unwrap()
+                .as_reference()
+                .unwrap();
+            let form = doc.get_object(id).unwrap().as_stream().unwrap();
+            String::from_utf8(form.content.clone()).unwrap()
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_impose_reverse_source_keeps_flyleaves_in_place() {
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("a.pdf")],
+        binding_type: BindingType::PerfectBinding,
+        reverse_source: true,
+        front_flyleaves: 1,
+        compress_output: false,
+        ..Default::default()
+    };
+
+    let output = impose(&[create_labeled_pdf("a", 4)], &options)
+        .await
+        .unwrap();
+    let pages: Vec<Vec<String>> = output
+        .get_pages()
+        .values()
+        .map(|&id| placed_labels(&output, id))
+        .collect();
+
+    // The flyleaf's two blank pages come first, then the last source page
+    assert_eq!(pages.len(), 3);
+    assert_eq!(pages[0], vec!["", ""]);
+    assert_eq!(pages[1], vec!["% a4", "% a3"]);
+    assert_eq!(pages[2], vec!["% a2", "% a1"]);
+}
+
+#[tokio::test]
+async fn test_impose_blank_inserts_shift_later_pages() {
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("a.pdf")],
+        binding_type: BindingType::PerfectBinding,
+        blank_inserts: vec![1, 3, 9],
+        compress_output: false,
+        ..Default::default()
+    };
+
+    let output = impose(&[create_labeled_pdf("a", 4)], &options)
+        .await
+        .unwrap();
+    let pages: Vec<Vec<String>> = output
+        .get_pages()
+        .values()
+        .map(|&id| placed_labels(&output, id))
+        .collect();
+
+    // Blanks go before original pages 2 and 4; the out-of-range one goes last
+    assert_eq!(
+        pages,
+        [
+            vec!["% a1", ""],
+            vec!["% a2", "% a3"],
+            vec!["", "% a4"],
+            vec![""],
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_impose_page_numbers_skip_blanks_and_flyleaves() {
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        binding_type: BindingType::PerfectBinding,
+        blank_inserts: vec![1],
+        front_flyleaves: 1,
+        add_page_numbers: true,
+        ..Default::default()
+    };
+
+    // Flyleaf, flyleaf, 1, blank, 2, 3, 4: the blanks still count
+    let output = impose(&[create_test_pdf(4)], &options).await.unwrap();
+    let mut numbers = all_page_numbers(&output).concat();
+    numbers.sort_unstable();
+    assert_eq!(numbers, vec![3, 5, 6, 7]);
+}
+
+#[tokio::test]
+async fn test_impose_chapter_recto_starts_second_file_on_recto() {
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("a.pdf"), PathBuf::from("b.pdf")],
+        binding_type: BindingType::PerfectBinding,
+        chapter_recto: true,
+        compress_output: false,
+        ..Default::default()
+    };
+
+    let output = impose(
+        &[create_labeled_pdf("a", 3), create_labeled_pdf("b", 4)],
+        &options,
+    )
+    .await
+    .unwrap();
+    let reading_order: Vec<String> = output
+        .get_pages()
+        .values()
+        .flat_map(|&id| placed_labels(&output, id))
+        .collect();
+
+    // b1 lands at reading position 5 rather than on the verso at 4
+    assert_eq!(
+        reading_order,
+        ["% a1", "% a2", "% a3", "", "% b1", "% b2", "% b3", "% b4"]
+    );
+}
+
+#[tokio::test]
+async fn test_impose_chapter_starts_recto_inserts_needed_blanks() {
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("a.pdf")],
+        binding_type: BindingType::PerfectBinding,
+        chapter_starts_recto: vec![1, 4, 6],
+        compress_output: false,
+        ..Default::default()
+    };
+
+    let output = impose(&[create_labeled_pdf("a", 7)], &options)
+        .await
+        .unwrap();
+    let reading_order: Vec<String> = output
+        .get_pages()
+        .values()
+        .flat_map(|&id| placed_labels(&output, id))
+        .collect();
+
+    // a2 and a5 would fall on versos; a7 already lands on a recto after them
+    assert_eq!(
+        reading_order,
+        [
+            "% a1", "", "% a2", "% a3", "% a4", "", "% a5", "% a6", "% a7"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_impose_cover_skip_drops_cover_pages() {
+    let mut options = ImpositionOptions {
+        input_files: vec![PathBuf::from("a.pdf")],
+        binding_type: BindingType::PerfectBinding,
+        compress_output: false,
+        ..Default::default()
+    };
+    options.cover.front_pages = 1;
+    options.cover.back_pages = 1;
+
+    let output = impose(&[create_labeled_pdf("a", 6)], &options)
+        .await
+        .unwrap();
+    let reading_order: Vec<String> = output
+        .get_pages()
+        .values()
+        .flat_map(|&id| placed_labels(&output, id))
+        .collect();
+
+    assert_eq!(reading_order, ["% a2", "% a3", "% a4", "% a5"]);
+}
+
+#[tokio::test]
+async fn test_impose_split_writes_wraparound_cover_last() {
+    let mut options = ImpositionOptions {
+        input_files: vec![PathBuf::from("a.pdf")],
+        binding_type: BindingType::PerfectBinding,
+        compress_output: false,
+        ..Default::default()
+    };
+    options.cover = CoverOptions {
+        front_pages: 2,
+        back_pages: 2,
+        mode: CoverMode::SeparateDocument,
+        paper_thickness_mm: 0.1,
+    };
+    assert!(options.writes_multiple_files());
+
+    let parts = impose_split(&[create_labeled_pdf("a", 12)], &options)
+        .await
+        .unwrap();
+    let suffixes: Vec<&str> = parts.iter().map(|(suffix, _)| suffix.as_str()).collect();
+    assert_eq!(suffixes, ["", "_cover"]);
+
+    let (_, body) = &parts[0];
+    assert_eq!(body.get_pages().len(), 4);
+
+    // Outside: back cover, spine, front cover; inside mirrors it
+    let (_, cover) = &parts[1];
+    let sides: Vec<Vec<String>> = cover
+        .get_pages()
+        .values()
+        .map(|&id| placed_labels(cover, id))
+        .collect();
+    assert_eq!(sides, [vec!["% a12", "% a1"], vec!["% a2", "% a11"]]);
+
+    // Eight body pages make four leaves of 0.1 mm
+    let spine_pt = 0.4 * 72.0 / 25.4;
+    let first = *cover.get_pages().values().next().unwrap();
+    let media_box = cover
+        .get_dictionary(first)
+        .unwrap()
+        .get(b"MediaBox")
+        .unwrap()
+        .as_array()
+        .unwrap();
+    let width = media_box[2].as_float().unwrap();
+    assert!((width - (2.0 * 612.0 + spine_pt)).abs() < 0.01, "{width}");
+}
+
+#[tokio::test]
+async fn test_impose_reverse_source_first_slot_gets_last_page() {
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("a.pdf")],
+        page_arrangement: PageArrangement::Folio,
+        reverse_source: true,
+        compress_output: false,
+        ..Default::default()
+    };
+
+    let output = impose(&[create_labeled_pdf("a", 4)], &options)
+        .await
+        .unwrap();
+    let front = *output.get_pages().values().next().unwrap();
+
+    // Folio front holds book pages 4 and 1, which are source pages 1 and 4
+    assert_eq!(placed_labels(&output, front), vec!["% a1", "% a4"]);
+}
+
+/// Give `doc` a flat outline with one bookmark per (title, page index)
+fn add_outline(doc: &mut Document, bookmarks: &[(&str, usize)]) {
+    let page_ids: Vec<_> = doc.get_pages().into_values().collect();
+    let outlines_id = doc.new_object_id();
+    let item_ids: Vec<_> = bookmarks.iter().map(|_| doc.new_object_id()).collect();
+    for (idx, &(title, page)) in bookmarks.iter().enumerate() {
+        let mut item = Dictionary::from_iter(vec![
+            ("Title", Object::string_literal(title)),
+            ("Parent", Object::Reference(outlines_id)),
+            (
+                "Dest",
+                Object::Array(vec![
+                    Object::Reference(page_ids[page]),
+                    Object::Name(b"XYZ".to_vec()),
+                    Object::Null,
+                    Object::Null,
+                    Object::Null,
+                ]),
+            ),
+        ]);
+        if idx > 0 {
+            item.set("Prev", Object::Reference(item_ids[idx - 1]));
+        }
+        if let Some(&next) = item_ids.get(idx + 1) {
+            item.set("Next", Object::Reference(next));
+        }
+        doc.objects.insert(item_ids[idx], Object::Dictionary(item));
+    }
+    doc.objects.insert(
+        outlines_id,
+        Object::Dictionary(Dictionary::from_iter(vec![
+            ("Type", Object::Name(b"Outlines".to_vec())),
+            ("First", Object::Reference(item_ids[0])),
+            ("Last", Object::Reference(item_ids[item_ids.len() - 1])),
+            ("Count", Object::Integer(bookmarks.len() as i64)),
+        ])),
+    );
+    doc.catalog_mut()
+        .unwrap()
+        .set("Outlines", Object::Reference(outlines_id));
+}
+
+/// The top-level bookmarks of `doc`, as (title, destination page)
+fn top_level_bookmarks(doc: &Document) -> Vec<(String, lopdf::ObjectId)> {
+    let Ok(outlines) = doc.catalog().unwrap().get(b"Outlines") else {
+        return Vec::new();
+    };
+    let outlines = doc
+        .get_dictionary(outlines.as_reference().unwrap())
+        .unwrap();
+    let mut next = outlines.get(b"First").and_then(Object::as_reference).ok();
+    let mut bookmarks = Vec::new();
+    while let Some(item_id) = next {
+        let item = doc.get_dictionary(item_id).unwrap();
+        let title = lopdf::decode_text_string(item.get(b"Title").unwrap()).unwrap();
+        let dest = item.get(b"Dest").and_then(Object::as_array).unwrap();
+        bookmarks.push((title, dest[0].as_reference().unwrap()));
+        next = item.get(b"Next").and_then(Object::as_reference).ok();
+    }
+    bookmarks
+}
+
+#[tokio::test]
+async fn test_impose_remaps_outline_to_output_pages() {
+    let mut source = create_labeled_pdf("a", 8);
+    add_outline(&mut source, &[("One", 0), ("Four", 3), ("Seven", 6)]);
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("a.pdf")],
+        page_arrangement: PageArrangement::Quarto,
+        generate_outline: false,
+        ..Default::default()
+    };
+
+    let result = impose(&[source], &options).await.unwrap();
+
+    let output_pages: Vec<_> = result.get_pages().into_values().collect();
+    let bookmarks = top_level_bookmarks(&result);
+    assert_eq!(bookmarks.len(), 3);
+    for ((title, page_id), label) in bookmarks.iter().zip(["% a1", "% a4", "% a7"]) {
+        assert!(
+            output_pages.contains(page_id),
+            "{title} points outside the output"
+        );
+        assert!(
+            placed_labels(&result, *page_id)
+                .iter()
+                .any(|placed| placed == label),
+            "{title} does not point at the side holding {label}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_impose_drops_bookmarks_to_unselected_pages() {
+    let mut source = create_labeled_pdf("a", 8);
+    add_outline(&mut source, &[("One", 0), ("Four", 3), ("Seven", 6)]);
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("a.pdf")],
+        input_ranges: vec![Some("1-5".parse().unwrap())],
+        binding_type: BindingType::PerfectBinding,
+        generate_outline: false,
+        ..Default::default()
+    };
+
+    let result = impose(&[source], &options).await.unwrap();
+
+    let titles: Vec<String> = top_level_bookmarks(&result)
+        .into_iter()
+        .map(|(title, _)| title)
+        .collect();
+    assert_eq!(titles, vec!["One", "Four"]);
+}
+
+#[tokio::test]
+async fn test_impose_bookmark_locations_name_sheet_side_and_slot() {
+    let mut source = create_labeled_pdf("a", 8);
+    add_outline(&mut source, &[("One", 0), ("Six", 5)]);
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("a.pdf")],
+        page_arrangement: PageArrangement::Quarto,
+        generate_outline: false,
+        bookmark_locations: true,
+        ..Default::default()
+    };
+
+    let result = impose(&[source], &options).await.unwrap();
+
+    // A quarto prints page 1 bottom right of the front and page 6 top right
+    // of the back
+    let output_pages: Vec<_> = result.get_pages().into_values().collect();
+    assert_eq!(
+        top_level_bookmarks(&result),
+        vec![
+            (
+                "One (p. 1 → sheet 1 front, slot 4)".to_string(),
+                output_pages[0]
+            ),
+            (
+                "Six (p. 6 → sheet 1 back, slot 2)".to_string(),
+                output_pages[1]
+            ),
+        ]
+    );
+}
+
+/// The children of the outline item `item_id`, as (title, destination page)
+fn child_bookmarks(doc: &Document, item_id: lopdf::ObjectId) -> Vec<(String, lopdf::ObjectId)> {
+    let item = doc.get_dictionary(item_id).unwrap();
+    let mut next = item.get(b"First").and_then(Object::as_reference).ok();
+    let mut children = Vec::new();
+    while let Some(child_id) = next {
+        let child = doc.get_dictionary(child_id).unwrap();
+        assert_eq!(
+            child.get(b"Parent").and_then(Object::as_reference).unwrap(),
+            item_id
+        );
+        let title = lopdf::decode_text_string(child.get(b"Title").unwrap()).unwrap();
+        let dest = child.get(b"Dest").and_then(Object::as_array).unwrap();
+        children.push((title, dest[0].as_reference().unwrap()));
+        next = child.get(b"Next").and_then(Object::as_reference).ok();
+    }
+    children
+}
+
+#[tokio::test]
+async fn test_impose_outline_maps_signatures_to_sheet_sides() {
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("a.pdf")],
+        page_arrangement: PageArrangement::Quarto,
+        ..Default::default()
+    };
+
+    let result = impose(&[create_labeled_pdf("a", 32)], &options)
+        .await
+        .unwrap();
+
+    // 32 pages make four quarto signatures of one sheet each
+    let output_pages: Vec<_> = result.get_pages().into_values().collect();
+    assert_eq!(output_pages.len(), 8);
+    let outlines_id = result
+        .catalog()
+        .unwrap()
+        .get(b"Outlines")
+        .and_then(Object::as_reference)
+        .unwrap();
+    let signatures = child_bookmarks(&result, outlines_id);
+    let titles: Vec<&str> = signatures.iter().map(|(title, _)| title.as_str()).collect();
+    assert_eq!(
+        titles,
+        ["Signature 1", "Signature 2", "Signature 3", "Signature 4"]
+    );
+
+    let outlines = result.get_dictionary(outlines_id).unwrap();
+    let mut item = outlines.get(b"First").and_then(Object::as_reference).ok();
+    for (sheet, (_, signature_page)) in signatures.iter().enumerate() {
+        let item_id = item.unwrap();
+        let sides = child_bookmarks(&result, item_id);
+        assert_eq!(
+            sides,
+            vec![
+                (
+                    format!("Sheet {} front", sheet + 1),
+                    output_pages[2 * sheet]
+                ),
+                (
+                    format!("Sheet {} back", sheet + 1),
+                    output_pages[2 * sheet + 1]
+                ),
+            ]
+        );
+        assert_eq!(*signature_page, output_pages[2 * sheet]);
+        item = result
+            .get_dictionary(item_id)
+            .unwrap()
+            .get(b"Next")
+            .and_then(Object::as_reference)
+            .ok();
+    }
+}
+
+#[tokio::test]
+async fn test_impose_outline_can_be_turned_off() {
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("a.pdf")],
+        page_arrangement: PageArrangement::Quarto,
+        generate_outline: false,
+        ..Default::default()
+    };
+
+    let result = impose(&[create_labeled_pdf("a", 32)], &options)
+        .await
+        .unwrap();
+
+    assert!(result.catalog().unwrap().get(b"Outlines").is_err());
+}
+
+/// Add a link annotation over `rect` on page `page` (0-based) of `doc`,
+/// with `target` as its `/A` or `/Dest` entry
+fn add_link(doc: &mut Document, page: usize, rect: [f32; 4], target: (&str, Object)) {
+    let page_id = doc.get_pages().into_values().nth(page).unwrap();
+    let link_id = doc.add_object(Dictionary::from_iter(vec![
+        ("Type", Object::Name(b"Annot".to_vec())),
+        ("Subtype", Object::Name(b"Link".to_vec())),
+        (
+            "Rect",
+            Object::Array(rect.iter().map(|&v| Object::Real(v)).collect()),
+        ),
+        target,
+    ]));
+    doc.get_dictionary_mut(page_id)
+        .unwrap()
+        .set("Annots", Object::Array(vec![Object::Reference(link_id)]));
+}
+
+/// `rect` on an upright source page, as placed on the sheet by `matrix`
+fn placed_rect(matrix: [f32; 6], rect: [f32; 4]) -> [f32; 4] {
+    let [a, b, c, d, e, f] = matrix;
+    let corners = [(rect[0], rect[1]), (rect[2], rect[3])]
+        .map(|(x, y)| (a * x + c * y + e, b * x + d * y + f));
+    [
+        corners[0].0.min(corners[1].0),
+        corners[0].1.min(corners[1].1),
+        corners[0].0.max(corners[1].0),
+        corners[0].1.max(corners[1].1),
+    ]
+}
+
+#[tokio::test]
+async fn test_impose_keeps_links_of_pages_sharing_a_sheet() {
+    let uri_rect = [100.0, 100.0, 300.0, 150.0];
+    let goto_rect = [100.0, 600.0, 300.0, 650.0];
+    let mut source = create_labeled_pdf("a", 4);
+    let first_page = *source.get_pages().values().next().unwrap();
+    let uri = Dictionary::from_iter(vec![
+        ("S", Object::Name(b"URI".to_vec())),
+        ("URI", Object::string_literal("https://example.com/")),
+    ]);
+    add_link(&mut source, 1, uri_rect, ("A", Object::Dictionary(uri)));
+    let dest = Object::Array(vec![
+        Object::Reference(first_page),
+        Object::Name(b"Fit".to_vec()),
+    ]);
+    add_link(&mut source, 2, goto_rect, ("Dest", dest));
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("a.pdf")],
+        page_arrangement: PageArrangement::Folio,
+        compress_output: false,
+        ..Default::default()
+    };
+
+    let result = impose(&[source], &options).await.unwrap();
+
+    // Pages 2 and 3 share the back of the folio sheet
+    let (front, back) = {
+        let pages: Vec<_> = result.get_pages().into_values().collect();
+        (pages[0], pages[1])
+    };
+    assert!(
+        result
+            .get_dictionary(front)
+            .unwrap()
+            .get(b"Annots")
+            .is_err()
+    );
+    let annots = result
+        .get_dictionary(back)
+        .unwrap()
+        .get(b"Annots")
+        .and_then(Object::as_array)
+        .unwrap();
+    assert_eq!(annots.len(), 2);
+    assert_ne!(annots[0], annots[1]);
+
+    let matrices: Vec<(String, [f32; 6])> = placed_labels(&result, back)
+        .into_iter()
+        .zip(placed_matrices(&result, back).into_iter().map(|(_, m)| m))
+        .collect();
+    let matrix_of = |label: &str| matrices.iter().find(|(l, _)| l == label).unwrap().1;
+    for annot in annots {
+        let annot = result
+            .get_dictionary(annot.as_reference().unwrap())
+            .unwrap();
+        let rect: Vec<f32> = annot
+            .get(b"Rect")
+            .and_then(Object::as_array)
+            .unwrap()
+            .iter()
+            .map(|v| v.as_float().unwrap())
+            .collect();
+        let expected = match annot.get(b"A") {
+            Ok(action) => {
+                let uri = action.as_dict().unwrap().get(b"URI").unwrap();
+                assert_eq!(uri.as_str().unwrap(), b"https://example.com/");
+                placed_rect(matrix_of("% a2"), uri_rect)
+            }
+            Err(_) => {
+                let dest = annot.get(b"Dest").and_then(Object::as_array).unwrap();
+                assert_eq!(dest[0].as_reference().unwrap(), front);
+                placed_rect(matrix_of("% a3"), goto_rect)
+            }
+        };
+        for (got, want) in rect.iter().zip(expected) {
+            assert!((got - want).abs() < 0.01, "{rect:?} != {expected:?}");
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_impose_drops_links_to_pages_left_out() {
+    let mut source = create_labeled_pdf("a", 4);
+    let last_page = *source.get_pages().values().last().unwrap();
+    let dest = Object::Array(vec![
+        Object::Reference(last_page),
+        Object::Name(b"Fit".to_vec()),
+    ]);
+    add_link(&mut source, 0, [100.0, 100.0, 300.0, 150.0], ("Dest", dest));
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("a.pdf")],
+        input_ranges: vec![Some("1-3".parse().unwrap())],
+        page_arrangement: PageArrangement::Folio,
+        ..Default::default()
+    };
+
+    let result = impose(&[source], &options).await.unwrap();
+
+    for page_id in result.get_pages().into_values() {
+        assert!(
+            result
+                .get_dictionary(page_id)
+                .unwrap()
+                .get(b"Annots")
+                .is_err()
+        );
+    }
+}
+
+/// Give `doc` document information with the given entries
+fn set_document_info(doc: &mut Document, entries: &[(&str, &str)]) {
+    let info = Dictionary::from_iter(
+        entries
+            .iter()
+            .map(|&(key, value)| (key, Object::string_literal(value))),
+    );
+    let info_id = doc.add_object(info);
+    doc.trailer.set("Info", Object::Reference(info_id));
+}
+
+/// The text of an entry in `doc`'s document information
+fn document_info_entry(doc: &Document, key: &str) -> Option<String> {
+    let info = doc.trailer.get(b"Info").ok()?.as_reference().ok()?;
+    let value = doc.get_dictionary(info).ok()?.get(key.as_bytes()).ok()?;
+    Some(String::from_utf8(value.as_str().ok()?.to_vec()).unwrap())
+}
+
+#[tokio::test]
+async fn test_impose_keeps_first_input_metadata() {
+    let mut first = create_test_pdf(4);
+    set_document_info(
+        &mut first,
+        &[
+            ("Title", "Alpha"),
+            ("Author", "Ann"),
+            ("Producer", "Scanner"),
+        ],
+    );
+    let mut second = create_test_pdf(4);
+    set_document_info(&mut second, &[("Title", "Beta")]);
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("a.pdf"), PathBuf::from("b.pdf")],
+        ..Default::default()
+    };
+
+    let result = impose(&[first, second], &options).await.unwrap();
+
+    assert_eq!(
+        document_info_entry(&result, "Title").as_deref(),
+        Some("Alpha")
+    );
+    assert_eq!(
+        document_info_entry(&result, "Author").as_deref(),
+        Some("Ann")
+    );
+    assert_eq!(
+        document_info_entry(&result, "Producer"),
+        Some(format!("pdf-impose {}", env!("CARGO_PKG_VERSION")))
+    );
+}
+
+#[tokio::test]
+async fn test_saved_output_round_trips_metadata() {
+    let mut source = create_test_pdf(4);
+    set_document_info(
+        &mut source,
+        &[
+            ("Title", "Alpha"),
+            ("Author", "Ann"),
+            ("Subject", "Tests"),
+            ("Producer", "Scanner"),
+        ],
+    );
+    let preferences = source.add_object(Dictionary::from_iter(vec![(
+        "Direction",
+        Object::Name(b"R2L".to_vec()),
+    )]));
+    let catalog = source.catalog_mut().unwrap();
+    catalog.set("Lang", Object::string_literal("en-GB"));
+    catalog.set("ViewerPreferences", Object::Reference(preferences));
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("a.pdf")],
+        ..Default::default()
+    };
+
+    let imposed = impose(&[source], &options).await.unwrap();
+    let temp = tempfile::NamedTempFile::new().unwrap();
+    save_pdf(imposed, temp.path(), SaveOptions::default())
+        .await
+        .unwrap();
+    let saved = Document::load(temp.path()).unwrap();
+
+    for (key, value) in [("Title", "Alpha"), ("Author", "Ann"), ("Subject", "Tests")] {
+        assert_eq!(document_info_entry(&saved, key).as_deref(), Some(value));
+    }
+    assert_eq!(
+        document_info_entry(&saved, "Producer"),
+        Some(format!("pdf-impose {}", env!("CARGO_PKG_VERSION")))
+    );
+    let mod_date = document_info_entry(&saved, "ModDate").unwrap();
+    assert!(
+        mod_date.starts_with("D:20") && mod_date.ends_with('Z'),
+        "{mod_date}"
+    );
+    assert_eq!(mod_date.len(), "D:20240131235959Z".len());
+
+    let catalog = saved.catalog().unwrap();
+    assert_eq!(catalog.get(b"Lang").unwrap().as_str().unwrap(), b"en-GB");
+    let direction = catalog
+        .get(b"ViewerPreferences")
+        .and_then(Object::as_dict)
+        .and_then(|preferences| preferences.get(b"Direction"))
+        .and_then(Object::as_name)
+        .unwrap();
+    assert_eq!(direction, b"R2L");
+}
+
+#[tokio::test]
+async fn test_impose_split_parts_use_title_override() {
+    let mut source = create_test_pdf(16);
+    set_document_info(&mut source, &[("Title", "Scan"), ("Author", "Ann")]);
+    let options = ImpositionOptions {
+        title_override: Some("Bound Edition".to_string()),
+        ..split_options(SplitMode::BySheets(1))
+    };
+
+    let parts = impose_split(&[source], &options).await.unwrap();
+
+    assert_eq!(parts.len(), 4);
+    for (_, part) in &parts {
+        assert_eq!(
+            document_info_entry(part, "Title").as_deref(),
+            Some("Bound Edition")
+        );
+        assert_eq!(document_info_entry(part, "Author").as_deref(), Some("Ann"));
+    }
+}
+
+#[tokio::test]
+async fn test_impose_rejects_extra_page_ranges() {
+    let mut options = ImpositionOptions::default();
+    options.input_files.push(PathBuf::from("a.pdf"));
+    options.input_ranges = vec![None, Some("1".parse().unwrap())];
+
+    assert!(impose(&[create_test_pdf(4)], &options).await.is_err());
+}
+
+fn count_objects(doc: &Document, subtype: &[u8]) -> usize {
+    doc.objects
+        .values()
+        .filter(|obj| {
+            let dict = match obj {
+                Object::Stream(stream) => &stream.dict,
+                Object::Dictionary(dict) => dict,
+                _ => return false,
+            };
+            dict.get(b"Subtype")
+                .and_then(Object::as_name)
+                .is_ok_and(|name| name == subtype)
+        })
+        .count()
+}
+
+#[tokio::test]
+async fn test_impose_creates_one_xobject_per_source_page() {
+    for arrangement in [
+        PageArrangement::Folio,
+        PageArrangement::Quarto,
+        PageArrangement::Octavo,
+    ] {
+        let options = ImpositionOptions {
+            input_files: vec![PathBuf::from("test.pdf")],
+            page_arrangement: arrangement,
+            ..Default::default()
+        };
+
+        let output = impose(&[create_test_pdf(8)], &options).await.unwrap();
+
+        assert!(count_objects(&output, b"Form") <= 8, "{arrangement:?}");
+    }
+}
+
+#[tokio::test]
+async fn test_impose_copies_shared_resources_once() {
+    let mut doc = create_test_pdf(8);
+    let font_id = doc.add_object(Dictionary::from_iter(vec![
+        ("Type", Object::Name(b"Font".to_vec())),
+        ("Subtype", Object::Name(b"TrueType".to_vec())),
+        ("BaseFont", Object::Name(b"Shared".to_vec())),
+    ]));
+    let font_resources = Dictionary::from_iter(vec![(
+        "Font",
+        Object::Dictionary(Dictionary::from_iter(vec![(
+            "F1",
+            Object::Reference(font_id),
+        )])),
+    )]);
+    for page_id in doc.get_pages().into_values() {
+        doc.get_dictionary_mut(page_id)
+            .unwrap()
+            .set("Resources", font_resources.clone());
+    }
+
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        page_arrangement: PageArrangement::Folio,
+        ..Default::default()
+    };
+    let output = impose(&[doc], &options).await.unwrap();
+
+    // Folio puts 8 pages on 2 sheets; the font is still copied only once
+    assert_eq!(output.get_pages().len(), 4);
+    assert_eq!(count_objects(&output, b"TrueType"), 1);
+}
+
+#[tokio::test]
+async fn test_impose_shares_one_helvetica_across_sheets() {
+    let mut options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        page_arrangement: PageArrangement::Folio,
+        add_page_numbers: true,
+        ..Default::default()
+    };
+    options.marks.slot_labels = true;
+
+    // Marks and page numbers on all 4 sheet sides draw in the same font
+    let output = impose(&[create_test_pdf(8)], &options).await.unwrap();
+    assert_eq!(output.get_pages().len(), 4);
+    assert_eq!(count_objects(&output, b"Type1"), 1);
+
+    options.dummy_mode = true;
+    let output = impose(&[create_test_pdf(8)], &options).await.unwrap();
+    assert_eq!(count_objects(&output, b"Type1"), 1);
+}
+
+fn stream_filters(doc: &Document) -> Vec<Option<Vec<u8>>> {
+    doc.objects
+        .values()
+        .filter_map(|obj| obj.as_stream().ok())
+        .map(|stream| {
+            stream
+                .dict
+                .get(b"Filter")
+                .and_then(Object::as_name)
+                .ok()
+                .map(<[u8]>::to_vec)
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_impose_compresses_output_streams() {
+    // Long enough content that Flate actually shrinks it
+    let mut doc = create_test_pdf(4);
+    let content = "0 0 m 100 100 l S\n".repeat(50).into_bytes();
+    for page_id in doc.get_pages().into_values() {
+        doc.change_page_content(page_id, content.clone()).unwrap();
+    }
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        page_arrangement: PageArrangement::Folio,
+        ..Default::default()
+    };
+
+    let mut output = impose(&[doc], &options).await.unwrap();
+
+    let filters = stream_filters(&output);
+    assert!(!filters.is_empty());
+    assert!(
+        filters
+            .iter()
+            .all(|filter| filter.as_deref() == Some(b"FlateDecode".as_slice()))
+    );
+
+    // Round trip through a saved file
+    let mut bytes = Vec::new();
+    output.save_to(&mut bytes).unwrap();
+    let reloaded = Document::load_mem(&bytes).unwrap();
+    let form = reloaded
+        .objects
+        .values()
+        .filter_map(|obj| obj.as_stream().ok())
+        .find(|stream| stream.dict.get(b"Subtype").and_then(Object::as_name).ok() == Some(b"Form"))
+        .unwrap();
+    assert_eq!(form.decompressed_content().unwrap(), content);
+}
+
+#[tokio::test]
+async fn test_impose_does_not_recompress_filtered_streams() {
+    let mut doc = create_test_pdf(4);
+    let image_id = doc.add_object(Stream::new(
+        Dictionary::from_iter(vec![
+            ("Type", Object::Name(b"XObject".to_vec())),
+            ("Subtype", Object::Name(b"Image".to_vec())),
+            ("Filter", Object::Name(b"DCTDecode".to_vec())),
+        ]),
+        vec![0xFF; 256],
+    ));
+    for page_id in doc.get_pages().into_values() {
+        doc.get_dictionary_mut(page_id).unwrap().set(
+            "Resources",
+            Dictionary::from_iter(vec![(
+                "XObject",
+                Object::Dictionary(Dictionary::from_iter(vec![(
+                    "Im1",
+                    Object::Reference(image_id),
+                )])),
+            )]),
+        );
+    }
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        page_arrangement: PageArrangement::Folio,
+        ..Default::default()
+    };
+
+    let output = impose(&[doc], &options).await.unwrap();
+
+    let image = output
+        .objects
+        .values()
+        .filter_map(|obj| obj.as_stream().ok())
+        .find(|stream| stream.dict.get(b"Subtype").and_then(Object::as_name).ok() == Some(b"Image"))
+        .unwrap();
+    assert_eq!(
+        image.dict.get(b"Filter").unwrap().as_name().unwrap(),
+        b"DCTDecode"
+    );
+    assert_eq!(image.content, vec![0xFF; 256]);
+}
+
+#[tokio::test]
+async fn test_impose_without_compression() {
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        page_arrangement: PageArrangement::Folio,
+        compress_output: false,
+        ..Default::default()
+    };
+
+    let output = impose(&[create_test_pdf(4)], &options).await.unwrap();
+
+    assert!(stream_filters(&output).iter().all(Option::is_none));
+}
+
+/// X translation of each placed page on an output page, in drawing order
+fn placement_x_offsets(doc: &Document, page_id: lopdf::ObjectId) -> Vec<f32> {
+    placed_matrices(doc, page_id)
+        .into_iter()
+        .map(|(_, matrix)| matrix[4])
+        .collect()
+}
+
+#[tokio::test]
+async fn test_impose_stretch_scales_each_axis() {
+    // An 800 x 300 pt sheet with no margins gives two 400 x 300 pt folio cells
+    let pt_to_mm = |pt: f32| pt * 25.4 / 72.0;
+    let mut options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        page_arrangement: PageArrangement::Folio,
+        output_paper_size: PaperSize::Custom {
+            width_mm: pt_to_mm(800.0),
+            height_mm: pt_to_mm(300.0),
+        },
+        output_orientation: Orientation::Portrait,
+        scaling_mode: ScalingMode::Stretch,
+        compress_output: false,
+        ..Default::default()
+    };
+    options.margins.sheet = SheetMargins::uniform(0.0);
+
+    let output = impose(&[create_test_pdf(4)], &options).await.unwrap();
+    let front = *output.get_pages().values().next().unwrap();
+    let (_, matrix) = &placed_matrices(&output, front)[0];
+
+    let expected = [0.653, 0.0, 0.0, 0.378];
+    for (value, expected) in matrix.iter().zip(expected) {
+        assert!((value - expected).abs() < 0.001, "{matrix:?}");
+    }
+}
+
+/// `num_pages` landscape 792 x 612 pt pages
+fn create_landscape_pdf(num_pages: usize) -> Document {
+    let mut doc = create_test_pdf(num_pages);
+    let page_ids: Vec<_> = doc.get_pages().into_values().collect();
+    for page_id in page_ids {
+        doc.get_dictionary_mut(page_id).unwrap().set(
+            "MediaBox",
+            Object::Array(vec![0.into(), 0.into(), 792.into(), 612.into()]),
+        );
+    }
+    doc
+}
+
+/// The `a b c d` of each placement matrix on the first output page
+fn placement_matrices(doc: &Document) -> Vec<[f32; 4]> {
+    let front = *doc.get_pages().values().next().unwrap();
+    placed_matrices(doc, front)
+        .into_iter()
+        .map(|(_, [a, b, c, d, _, _])| [a, b, c, d])
+        .collect()
+}
+
+#[tokio::test]
+async fn test_impose_auto_rotate_turns_landscape_page_in_portrait_cell() {
+    let mut options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        page_arrangement: PageArrangement::Folio,
+        compress_output: false,
+        ..Default::default()
+    };
+    let source = [create_landscape_pdf(4)];
+
+    let upright = placement_matrices(&impose(&source, &options).await.unwrap());
+    options.auto_rotate_to_fit = true;
+    let turned = placement_matrices(&impose(&source, &options).await.unwrap());
+
+    let [upright_scale, b, c, _] = upright[0];
+    assert_eq!((b, c), (0.0, 0.0));
+
+    // A quarter turn clockwise, at a larger scale than the upright page
+    let [a, b, c, d] = turned[0];
+    assert_eq!((a, d), (0.0, 0.0));
+    assert!((b + c).abs() < 0.001, "{turned:?}");
+    assert!(c > upright_scale * 1.2, "{turned:?} vs {upright:?}");
+    assert_eq!(
+        auto_rotated_pages(&source, &options).await.unwrap(),
+        [0, 1, 2, 3]
+    );
+}
+
+#[tokio::test]
+async fn test_impose_auto_rotate_composes_with_upside_down_slots() {
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        page_arrangement: PageArrangement::Quarto,
+        auto_rotate_to_fit: true,
+        compress_output: false,
+        ..Default::default()
+    };
+
+    let matrices = placement_matrices(&impose(&[create_landscape_pdf(8)], &options).await.unwrap());
+
+    // Top-row slots are already turned 180°, so their pages end up at 270°
+    let turns: Vec<i32> = matrices
+        .iter()
+        .map(|&[_, b, _, _]| if b < 0.0 { 90 } else { 270 })
+        .collect();
+    assert_eq!(turns.iter().filter(|&&turn| turn == 90).count(), 2);
+    assert_eq!(turns.iter().filter(|&&turn| turn == 270).count(), 2);
+}
+
+/// Form matrix and placement scale of each page placed on `page_id`
+fn placed_form_matrices(doc: &Document, page_id: lopdf::ObjectId) -> Vec<(Vec<f32>, f32)> {
+    let page = doc.get_dictionary(page_id).unwrap();
+    let xobjects = page
+        .get(b"Resources")
+        .and_then(Object::as_dict)
+        .and_then(|resources| resources.get(b"XObject"))
+        .and_then(Object::as_dict)
+        .unwrap();
+    placed_matrices(doc, page_id)
+        .into_iter()
+        .map(|(name, placement)| {
+            let id = xobjects
+                .get(name.as_bytes())
+                .unwrap()
+                .as_reference()
+                .unwrap();
+            let form = doc.get_object(id).unwrap().as_stream().unwrap();
+            let matrix = match form.dict.get(b"Matrix") {
+                Ok(matrix) => matrix
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|v| v.as_float().unwrap())
+                    .collect(),
+                Err(_) => vec![1.0, 0.0, 0.0, 1.0, 0.0, 0.0],
+            };
+            (matrix, placement[0])
+        })
+        .collect()
+}
+
+async fn impose_rotated_quarto(rotation: Rotation) -> Document {
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        page_arrangement: PageArrangement::Quarto,
+        source_rotation: rotation,
+        compress_output: false,
+        ..Default::default()
+    };
+    impose(&[create_test_pdf(8)], &options).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_impose_source_rotation_90_turns_every_form() {
+    let output = impose_rotated_quarto(Rotation::Clockwise90).await;
+    let front = *output.get_pages().values().next().unwrap();
+    let placed = placed_form_matrices(&output, front);
+
+    assert_eq!(placed.len(), 4);
+    for (matrix, _) in &placed {
+        assert_eq!(matrix, &[0.0, -1.0, 1.0, 0.0, 0.0, 612.0]);
+    }
+    // The top row still gets its 180° turn on top of the source rotation
+    assert_eq!(placed.iter().filter(|(_, scale)| *scale < 0.0).count(), 2);
+}
+
+#[tokio::test]
+async fn test_impose_source_rotation_270_turns_every_form() {
+    let output = impose_rotated_quarto(Rotation::Clockwise270).await;
+    let front = *output.get_pages().values().next().unwrap();
+    let placed = placed_form_matrices(&output, front);
+
+    assert_eq!(placed.len(), 4);
+    for (matrix, _) in &placed {
+        assert_eq!(matrix, &[0.0, 1.0, -1.0, 0.0, 792.0, 0.0]);
+    }
+    assert_eq!(placed.iter().filter(|(_, scale)| *scale < 0.0).count(), 2);
+
+    // Landscape pages in portrait-shaped cells scale down further than upright ones
+    let upright = impose_rotated_quarto(Rotation::None).await;
+    let front = *upright.get_pages().values().next().unwrap();
+    let (_, upright_scale) = placed_form_matrices(&upright, front)[0].clone();
+    assert!(placed[0].1.abs() < upright_scale.abs());
+}
+
+#[tokio::test]
+async fn test_impose_creep_shifts_outer_sheets_most() {
+    let mut options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        page_arrangement: PageArrangement::Custom {
+            pages_per_signature: 16,
+        },
+        ..Default::default()
+    };
+    options.margins.leaf.spine_mm = 5.0;
+
+    // Fronts of the four nested sheets, outermost first
+    let front_offsets = |doc: &Document| {
+        doc.get_pages()
+            .values()
+            .step_by(2)
+            .flat_map(|&front| placement_x_offsets(doc, front))
+            .collect::<Vec<f32>>()
+    };
+    let plain = impose(&[create_test_pdf(16)], &options).await.unwrap();
+    options.creep_mm = 0.5;
+    let creeped = impose(&[create_test_pdf(16)], &options).await.unwrap();
+
+    let shifts: Vec<f32> = front_offsets(&creeped)
+        .iter()
+        .zip(front_offsets(&plain))
+        .map(|(with, without)| (with - without).abs())
+        .collect();
+
+    // Front slots come in pairs per nested sheet
+    assert_eq!(shifts.len(), 8);
+    for pair in shifts.chunks(2) {
+        assert!((pair[0] - pair[1]).abs() < 0.01);
+    }
+    let per_sheet: Vec<f32> = shifts.iter().step_by(2).copied().collect();
+    assert!(per_sheet.windows(2).all(|w| w[0] > w[1]), "{per_sheet:?}");
+    assert!(per_sheet[3].abs() < 0.01);
+    assert!((per_sheet[0] - 3.0 * 0.5 * 72.0 / 25.4).abs() < 0.01);
+}
+
+#[tokio::test]
+async fn test_impose_back_side_offset_shifts_backs_only() {
+    // Translation wrapped around the whole page, if any
+    let page_offsets = |doc: &Document| -> Vec<Option<(f32, f32)>> {
+        doc.get_pages()
+            .values()
+            .map(|&id| {
+                let content = String::from_utf8(doc.get_page_content(id).unwrap()).unwrap();
+                let ops: Vec<&str> = content.lines().next()?.split_whitespace().collect();
+                (ops.len() == 8 && ops[..5] == ["q", "1", "0", "0", "1"] && ops[7] == "cm")
+                    .then(|| (ops[5].parse().unwrap(), ops[6].parse().unwrap()))
+            })
+            .collect()
+    };
+    let shift = Some((-1.2 * 72.0 / 25.4, 0.5 * 72.0 / 25.4));
+
+    for binding_type in [BindingType::Signature, BindingType::PerfectBinding] {
+        let options = ImpositionOptions {
+            input_files: vec![PathBuf::from("test.pdf")],
+            binding_type,
+            page_arrangement: PageArrangement::Folio,
+            back_side_offset_mm: (-1.2, 0.5),
+            ..Default::default()
+        };
+        let output = impose(&[create_test_pdf(8)], &options).await.unwrap();
+        let offsets = page_offsets(&output);
+        assert_eq!(offsets.len(), 4);
+        for (idx, offset) in offsets.iter().enumerate() {
+            let expected = if idx % 2 == 1 { shift } else { None };
+            match (offset, expected) {
+                (Some((x, y)), Some((ex, ey))) => {
+                    assert!((x - ex).abs() < 0.01 && (y - ey).abs() < 0.01)
+                }
+                (offset, expected) => assert_eq!(*offset, expected, "{binding_type:?} {idx}"),
+            }
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_impose_rejects_negative_creep() {
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        creep_mm: -1.0,
+        ..Default::default()
+    };
+
+    let result = impose(&[create_test_pdf(8)], &options).await;
+    assert!(matches!(result, Err(ImposeError::Config(_))));
+}
+
+#[tokio::test]
+async fn test_impose_bleed_extends_past_fore_edge_only() {
+    let mut options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        page_arrangement: PageArrangement::Folio,
+        scaling_mode: ScalingMode::Stretch,
+        ..Default::default()
+    };
+    options.margins.leaf.fore_edge_mm = 5.0;
+
+    let front_offsets = |doc: &Document| {
+        let front = *doc.get_pages().values().next().unwrap();
+        placement_x_offsets(doc, front)
+    };
+    let plain = impose(&[create_test_pdf(4)], &options).await.unwrap();
+    options.bleed_mm = 3.0;
+    let bled = impose(&[create_test_pdf(4)], &options).await.unwrap();
+
+    // Left page starts further left; right page still starts at the spine
+    let (plain, bled) = (front_offsets(&plain), front_offsets(&bled));
+    assert!((plain[0] - bled[0] - 3.0 * 72.0 / 25.4).abs() < 0.01);
+    assert!((plain[1] - bled[1]).abs() < 0.01);
+}
+
+#[test]
+fn test_bleed_grows_content_past_trim_on_every_cut_edge() {
+    let dims = [(612.0, 792.0); 4];
+    let mut options = ImpositionOptions {
+        binding_type: BindingType::NUp { rows: 2, cols: 2 },
+        scaling_mode: ScalingMode::Stretch,
+        bleed_mm: 3.0,
+        ..Default::default()
+    };
+    options.margins.leaf.cut_mm = 5.0;
+    let bleed = constants::mm_to_pt(3.0);
+
+    for placement in &plan_imposition(&dims, &options).unwrap()[0].placements {
+        let (content, trim) = (&placement.content_rect, &placement.trim_rect);
+        assert!((trim.x - content.x - bleed).abs() < 0.01);
+        assert!((trim.y - content.y - bleed).abs() < 0.01);
+        assert!((content.right() - trim.right() - bleed).abs() < 0.01);
+        assert!((content.top() - trim.top() - bleed).abs() < 0.01);
+    }
+
+    // Abutting cells leave no room, so the bleed is clamped to nothing
+    // rather than spilling into the neighboring page
+    options.margins.leaf.cut_mm = 0.0;
+    let placements = &plan_imposition(&dims, &options).unwrap()[0].placements;
+    for placement in placements {
+        assert_eq!(placement.content_rect, placement.trim_rect);
+    }
+    assert!(placements[0].content_rect.right() <= placements[1].content_rect.x + 0.01);
+}
+
+/// Filled rectangles (x, y, width, height) drawn on an output page
+fn filled_rects(doc: &Document, page_id: lopdf::ObjectId) -> Vec<(f32, f32, f32, f32)> {
+    let content = String::from_utf8(doc.get_page_content(page_id).unwrap()).unwrap();
+    content
+        .lines()
+        .filter(|line| line.ends_with("re f"))
+        .map(|line| {
+            let v: Vec<f32> = line
+                .split_whitespace()
+                .take(4)
+                .map(|n| n.parse().unwrap())
+                .collect();
+            (v[0], v[1], v[2], v[3])
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_collation_marks_step_down_the_spine() {
+    let mut options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        page_arrangement: PageArrangement::Folio,
+        output_orientation: Orientation::Landscape,
+        ..Default::default()
+    };
+    options.marks.collation_marks = true;
+    options.margins.sheet = SheetMargins::uniform(0.0);
+
+    let output = impose(&[create_test_pdf(12)], &options).await.unwrap();
+    let pages: Vec<_> = output.get_pages().into_values().collect();
+    assert_eq!(pages.len(), 6);
+
+    let mut tops = Vec::new();
+    for (idx, &page_id) in pages.iter().enumerate() {
+        let rects = filled_rects(&output, page_id);
+        if idx % 2 == 1 {
+            // Only the outside of each signature is marked
+            assert!(rects.is_empty(), "back of sheet {}", idx / 2);
+            continue;
+        }
+        assert_eq!(rects.len(), 1, "front of sheet {}", idx / 2);
+        let (x, y, width, height) = rects[0];
+        // Centered on the central fold
+        assert!((x + width / 2.0 - 396.0).abs() < 0.01);
+        tops.push(y + height);
+    }
+
+    // First signature starts at the head; each later one is a block lower
+    assert!((tops[0] - 612.0).abs() < 0.01);
+    for pair in tops.windows(2) {
+        assert!((pair[0] - pair[1] - 17.0).abs() < 0.01, "{tops:?}");
+    }
+}
+
+/// Stroked lines ((x1, y1), (x2, y2)) drawn on an output page
+/// A stroked line, from one (x, y) point to another
+type Line = ((f32, f32), (f32, f32));
+
+fn stroked_lines(doc: &Document, page_id: lopdf::ObjectId) -> Vec<Line> {
+    let content = String::from_utf8(doc.get_page_content(page_id).unwrap()).unwrap();
+    content
+        .lines()
+        .filter(|line| line.ends_with(" l S"))
+        .map(|line| {
+            let v: Vec<f32> = line
+                .split_whitespace()
+                .filter_map(|n| n.parse().ok())
+                .collect();
+            ((v[0], v[1]), (v[2], v[3]))
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_fold_and_cut_lines_match_the_grid() {
+    for arrangement in [
+        PageArrangement::Folio,
+        PageArrangement::Quarto,
+        PageArrangement::Octavo,
+        PageArrangement::Sexto,
+        PageArrangement::Duodecimo,
+        PageArrangement::Custom {
+            pages_per_signature: 12,
+        },
+    ] {
+        let mut options = ImpositionOptions {
+            input_files: vec![PathBuf::from("test.pdf")],
+            page_arrangement: arrangement,
+            ..Default::default()
+        };
+        options.margins.sheet = SheetMargins::uniform(0.0);
+        let docs = [create_test_pdf(arrangement.pages_per_signature())];
+        let plan = plan(&docs, &options).await.unwrap();
+        let grid = &plan.grid;
+        let (width, height) = plan.sheet_size_pt;
+        let (col_pitch, row_pitch) = (grid.col_pitch_pt(), grid.row_pitch_pt());
+
+        let vertical = |col: usize| {
+            let x = (col + 1) as f32 * col_pitch;
+            ((x, 0.0), (x, height))
+        };
+        let horizontal = |row: usize| {
+            let y = height - (row + 1) as f32 * row_pitch;
+            ((0.0, y), (width, y))
+        };
+        let close = |lines: &[Line], expected: &[Line]| {
+            lines.len() == expected.len()
+                && expected.iter().all(|((x1, y1), (x2, y2))| {
+                    lines.iter().any(|((a1, b1), (a2, b2))| {
+                        [a1 - x1, b1 - y1, a2 - x2, b2 - y2]
+                            .iter()
+                            .all(|d| d.abs() < 0.01)
+                    })
+                })
+        };
+
+        options.marks.fold_lines = true;
+        let output = impose(&docs, &options).await.unwrap();
+        let front = *output.get_pages().values().next().unwrap();
+        let folds: Vec<_> = grid
+            .vertical_folds
+            .iter()
+            .map(|&col| vertical(col))
+            .chain(grid.horizontal_folds.iter().map(|&row| horizontal(row)))
+            .collect();
+        assert!(
+            close(&stroked_lines(&output, front), &folds),
+            "{arrangement:?} folds"
+        );
+
+        // Cut lines also draw scissors, so only the full-length lines count
+        options.marks.fold_lines = false;
+        options.marks.cut_lines = true;
+        let output = impose(&docs, &options).await.unwrap();
+        let front = *output.get_pages().values().next().unwrap();
+        let cuts: Vec<_> = grid
+            .vertical_cuts
+            .iter()
+            .map(|&col| vertical(col))
+            .chain(grid.horizontal_cuts.iter().map(|&row| horizontal(row)))
+            .collect();
+        let drawn: Vec<_> = stroked_lines(&output, front)
+            .into_iter()
+            .filter(|((x1, y1), (x2, y2))| {
+                (x2 - x1).abs() >= width - 0.01 || (y2 - y1).abs() >= height - 0.01
+            })
+            .collect();
+        assert!(close(&drawn, &cuts), "{arrangement:?} cuts");
+    }
+}
+
+#[tokio::test]
+async fn test_fold_labels_give_order_and_direction() {
+    let mut options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        page_arrangement: PageArrangement::Quarto,
+        compress_output: false,
+        ..Default::default()
+    };
+    options.marks.fold_lines = true;
+
+    // Unlabelled folds are all dashed alike
+    let output = impose(&[create_test_pdf(8)], &options).await.unwrap();
+    for page_id in output.get_pages().into_values() {
+        let content = String::from_utf8(output.get_page_content(page_id).unwrap()).unwrap();
+        assert!(content.contains("[6 3] 0 d\n"));
+        assert!(!content.contains("Tj"));
+    }
+
+    options.marks.fold_labels = true;
+    let output = impose(&[create_test_pdf(8)], &options).await.unwrap();
+    let pages: Vec<_> = output.get_pages().into_values().collect();
+    assert_eq!(pages.len(), 2);
+
+    let labels = |page_id| {
+        let content = String::from_utf8(output.get_page_content(page_id).unwrap()).unwrap();
+        let labels: Vec<String> = content
+            .lines()
+            .filter(|line| line.starts_with("BT /FM 6 Tf"))
+            .map(|line| line[line.find('(').unwrap() + 1..line.find(')').unwrap()].to_string())
+            .collect();
+        (content, labels)
+    };
+
+    // The front is the outside of every fold: the head fold comes first,
+    // then the spine
+    let (front, front_labels) = labels(pages[0]);
+    assert!(front.contains("[6 3 1 3] 0 d\n"));
+    assert_eq!(front_labels, ["2 mountain", "1 mountain"]);
+
+    let (back, back_labels) = labels(pages[1]);
+    assert!(back.contains("[6 3] 0 d\n"));
+    assert_eq!(back_labels, ["2 valley", "1 valley"]);
+
+    let (resources, _) = output.get_page_resources(pages[0]).unwrap();
+    let fonts = resources
+        .unwrap()
+        .get(b"Font")
+        .and_then(Object::as_dict)
+        .unwrap();
+    assert!(fonts.has(b"FM"));
+}
+
+#[tokio::test]
+async fn test_sewing_marks_cross_the_outer_spine() {
+    let mut options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        page_arrangement: PageArrangement::Folio,
+        output_orientation: Orientation::Landscape,
+        ..Default::default()
+    };
+    options.margins.sheet = SheetMargins::uniform(0.0);
+    options.marks.sewing_marks = Some(SewingMarks {
+        stations: 4,
+        head_tail_margin_mm: 15.0,
+    });
+
+    let output = impose(&[create_test_pdf(8)], &options).await.unwrap();
+    let pages: Vec<_> = output.get_pages().into_values().collect();
+    assert_eq!(pages.len(), 4);
+
+    let margin = 15.0 * 72.0 / 25.4;
+    let gap = (612.0 - 2.0 * margin) / 3.0;
+    for (idx, &page_id) in pages.iter().enumerate() {
+        let ticks = stroked_lines(&output, page_id);
+        if idx % 2 == 1 {
+            assert!(ticks.is_empty(), "back of sheet {}", idx / 2);
+            continue;
+        }
+
+        assert_eq!(ticks.len(), 4);
+        for (i, ((x1, y1), (x2, y2))) in ticks.into_iter().enumerate() {
+            // Horizontal ticks centered on the central fold, head first
+            assert!((y1 - y2).abs() < 0.01);
+            assert!(((x1 + x2) / 2.0 - 396.0).abs() < 0.01);
+            assert!(x1 < 396.0 && x2 > 396.0);
+            let expected = 612.0 - margin - i as f32 * gap;
+            assert!((y1 - expected).abs() < 0.01, "station {i}: {y1}");
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_spine_marks_continue_the_outer_spine_past_head_and_tail() {
+    let mut options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        page_arrangement: PageArrangement::Folio,
+        output_orientation: Orientation::Landscape,
+        ..Default::default()
+    };
+    options.margins.sheet = SheetMargins::uniform(10.0);
+    options.marks.spine_marks = true;
+
+    let output = impose(&[create_test_pdf(8)], &options).await.unwrap();
+    let pages: Vec<_> = output.get_pages().into_values().collect();
+    assert_eq!(pages.len(), 4);
+
+    let margin = 10.0 * 72.0 / 25.4;
+    let (head, tail) = (612.0 - margin, margin);
+    for (idx, &page_id) in pages.iter().enumerate() {
+        let ticks = stroked_lines(&output, page_id);
+        if idx % 2 == 1 {
+            assert!(ticks.is_empty(), "back of sheet {}", idx / 2);
+            continue;
+        }
+        assert_eq!(ticks.len(), 2);
+        let ((x1, y1), (x2, y2)) = ticks[0];
+        assert!((x1 - 396.0).abs() < 0.01 && x1 == x2);
+        assert!((y1 - (head + 3.0)).abs() < 0.01 && (y2 - (head + 11.0)).abs() < 0.01);
+        let ((_, y1), (_, y2)) = ticks[1];
+        assert!((y1 - (tail - 3.0)).abs() < 0.01 && (y2 - (tail - 11.0)).abs() < 0.01);
+    }
+}
+
+#[tokio::test]
+async fn test_slot_labels_mark_each_cell_without_moving_pages() {
+    let mut options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        page_arrangement: PageArrangement::Quarto,
+        compress_output: false,
+        ..Default::default()
+    };
+    let plain = impose(&[create_test_pdf(6)], &options).await.unwrap();
+    options.marks.slot_labels = true;
+    let labelled = impose(&[create_test_pdf(6)], &options).await.unwrap();
+
+    let labels = |page_id| {
+        let content = String::from_utf8(labelled.get_page_content(page_id).unwrap()).unwrap();
+        content
+            .lines()
+            .filter(|line| line.contains("/FM"))
+            .map(|line| {
+                let text = line.rsplit('(').next().unwrap();
+                let text = text.split(')').next().unwrap().to_string();
+                (text, line.starts_with("q -1 0 0 -1"))
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let mut pages = Vec::new();
+    for (&plain_id, &labelled_id) in plain
+        .get_pages()
+        .values()
+        .zip(labelled.get_pages().values())
+    {
+        // Labels are drawn over the pages, which stay where they were
+        assert_eq!(
+            placed_matrices(&plain, plain_id),
+            placed_matrices(&labelled, labelled_id)
+        );
+        let labels = labels(labelled_id);
+        assert_eq!(labels.len(), 4);
+        // The top row of a quarto is turned, and its labels with it
+        assert_eq!(labels.iter().filter(|(_, turned)| *turned).count(), 2);
+        pages.extend(labels.into_iter().map(|(text, _)| text));
+    }
+    pages.sort();
+    assert_eq!(pages, ["1", "2", "3", "4", "5", "6", "BLANK", "BLANK"]);
+}
+
+#[tokio::test]
+async fn test_trim_marks_track_scaled_content() {
+    let mut options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        page_arrangement: PageArrangement::Folio,
+        output_orientation: Orientation::Landscape,
+        ..Default::default()
+    };
+    options.margins.sheet = SheetMargins::uniform(0.0);
+    options.marks.trim_marks = true;
+
+    // Landscape pages fit into portrait cells at half height
+    let output = impose(&[create_landscape_pdf(4)], &options).await.unwrap();
+    let front = *output.get_pages().values().next().unwrap();
+    let lines = stroked_lines(&output, front);
+    assert_eq!(lines.len(), 16);
+
+    let layouts = plan_imposition(&[(792.0, 612.0); 4], &options).unwrap();
+    for placement in &layouts[0].placements {
+        let trim = placement.trim_rect;
+        assert!(trim.height < 612.0 / 2.0 + 0.01);
+        // Each corner's vertical arms start just past the content's top and
+        // bottom edges, not the cell's
+        for (x, y1, y2) in [
+            (trim.x, trim.top() + 3.0, trim.top() + 15.0),
+            (trim.right(), trim.y - 3.0, trim.y - 15.0),
+        ] {
+            assert!(
+                lines.iter().any(|&((lx1, ly1), (lx2, ly2))| {
+                    (lx1 - x).abs() < 0.01
+                        && (lx2 - x).abs() < 0.01
+                        && (ly1 - y1).abs() < 0.01
+                        && (ly2 - y2).abs() < 0.01
+                }),
+                "no arm at x={x} from y={y1}"
+            );
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_marks_warn_when_the_sheet_margin_is_too_narrow() {
+    use std::sync::{Arc, Mutex};
+
+    let mut options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        page_arrangement: PageArrangement::Folio,
+        output_orientation: Orientation::Landscape,
+        ..Default::default()
+    };
+    options.marks.trim_marks = true;
+
+    let warnings = |options: ImpositionOptions| async move {
+        let stages = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&stages);
+        impose_with_progress(&[create_test_pdf(4)], &options, move |stage| {
+            recorded.lock().unwrap().push(stage)
+        })
+        .await
+        .unwrap();
+        let warnings: Vec<ImposeWarning> = stages
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|stage| match stage {
+                ImposeProgress::Warning(warning) => Some(*warning),
+                _ => None,
+            })
+            .collect();
+        warnings
+    };
+
+    // Trim marks reach 15pt past the leaf, a little over 5mm
+    options.margins.sheet = SheetMargins::uniform(6.0);
+    assert!(warnings(options.clone()).await.is_empty());
+
+    options.margins.sheet.left_mm = 3.0;
+    let found = warnings(options.clone()).await;
+    let [
+        ImposeWarning::MarksExceedSheetMargin {
+            margin_mm,
+            needed_mm,
+        },
+    ] = found[..]
+    else {
+        panic!("{found:?}");
+    };
+    assert!((margin_mm - 3.0).abs() < 0.01);
+    assert!((needed_mm - 15.0 * 25.4 / 72.0).abs() < 0.01);
+    assert!(found[0].to_string().contains("at least 5.3 mm"));
+}
+
+#[tokio::test]
+async fn test_crop_marks_sit_at_the_trimmed_sheet_not_the_leaf_area() {
+    let mut options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        page_arrangement: PageArrangement::Folio,
+        output_orientation: Orientation::Landscape,
+        bleed_mm: 2.0,
+        ..Default::default()
+    };
+    options.margins.sheet = SheetMargins::uniform(10.0);
+    options.marks.crop_marks = true;
+
+    let output = impose(&[create_test_pdf(4)], &options).await.unwrap();
+    let front = *output.get_pages().values().next().unwrap();
+    let lines = stroked_lines(&output, front);
+    assert_eq!(lines.len(), 8);
+
+    // The marks frame the 792x612pt sheet less the bleed
+    let bleed = constants::mm_to_pt(2.0);
+    let leaf = constants::mm_to_pt(10.0);
+    let near = |a: f32, b: f32| (a - b).abs() < 0.01;
+    let top_left_vertical = lines
+        .iter()
+        .find(|((x1, y1), (x2, _))| x1 == x2 && *y1 > 306.0 && *x1 < 396.0)
+        .unwrap();
+    assert!(near(top_left_vertical.0.0, bleed));
+    assert!(near(top_left_vertical.0.1, 612.0 - bleed + 3.0));
+    let bottom_right_horizontal = lines
+        .iter()
+        .find(|((x1, y1), (_, y2))| y1 == y2 && *y1 < 306.0 && *x1 > 396.0)
+        .unwrap();
+    assert!(near(bottom_right_horizontal.0.0, 792.0 - bleed + 3.0));
+    assert!(near(bottom_right_horizontal.0.1, bleed));
+
+    // None of them lines up with the leaf area's edges
+    for ((x1, y1), (x2, y2)) in &lines {
+        for v in [x1, y1, x2, y2] {
+            assert!(!near(*v, leaf) && !near(*v, 612.0 - leaf) && !near(*v, 792.0 - leaf));
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_mark_style_sets_crop_mark_color_weight_and_size() {
+    let mut options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        page_arrangement: PageArrangement::Folio,
+        output_orientation: Orientation::Landscape,
+        ..Default::default()
+    };
+    options.margins.sheet = SheetMargins::uniform(0.0);
+    options.marks.crop_marks = true;
+
+    // The default style draws what the marks always have
+    let output = impose(&[create_test_pdf(4)], &options).await.unwrap();
+    let front = *output.get_pages().values().next().unwrap();
+    let content = String::from_utf8(output.get_page_content(front).unwrap()).unwrap();
+    assert!(content.contains("q\n0 0 0 RG\n0.25 w\n"));
+    assert!(stroked_lines(&output, front).contains(&((0.0, 615.0), (0.0, 627.0))));
+
+    options.marks.style = MarkStyle {
+        color_rgb: [1.0, 0.0, 0.0],
+        line_width_pt: 1.5,
+        mark_length_pt: 20.0,
+        gap_pt: 6.0,
+    };
+    let output = impose(&[create_test_pdf(4)], &options).await.unwrap();
+    let front = *output.get_pages().values().next().unwrap();
+    let content = String::from_utf8(output.get_page_content(front).unwrap()).unwrap();
+    assert!(content.contains("q\n1 0 0 RG\n1.5 w\n"));
+
+    let lines = stroked_lines(&output, front);
+    assert_eq!(lines.len(), 8);
+    for ((x1, y1), (x2, y2)) in &lines {
+        assert!(((x2 - x1).abs() + (y2 - y1).abs() - 20.0).abs() < 0.01);
+    }
+    // Top-left corner: the vertical arm starts 6pt above the sheet's top edge
+    assert!(lines.contains(&((0.0, 618.0), (0.0, 638.0))));
+}
+
+#[tokio::test]
+async fn test_color_bars_only_fit_a_wide_enough_bottom_margin() {
+    use std::sync::{Arc, Mutex};
+
+    let mut options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        page_arrangement: PageArrangement::Folio,
+        output_orientation: Orientation::Landscape,
+        ..Default::default()
+    };
+    options.marks.crop_marks = true;
+    options.marks.color_bars = true;
+
+    // 15mm leaves room for the bar below the reach of the marks
+    options.margins.sheet = SheetMargins::uniform(15.0);
+    let output = impose(&[create_test_pdf(4)], &options).await.unwrap();
+    let front = *output.get_pages().values().next().unwrap();
+    let content = String::from_utf8(output.get_page_content(front).unwrap()).unwrap();
+    let colors = [
+        "1 0 0 0 k",
+        "0 1 0 0 k",
+        "0 0 1 0 k",
+        "0 0 0 1 k",
+        "0 0 0 0.75 k",
+        "0 0 0 0.5 k",
+        "0 0 0 0.25 k",
+        "1 0 0 rg",
+        "0 1 0 rg",
+        "0 0 1 rg",
+    ];
+    for color in colors {
+        assert!(content.contains(color), "{color}");
+    }
+    // The bar is centered on the 792pt wide sheet, clear of the marks
+    let leaf_edge = 15.0 * 72.0 / 25.4;
+    let patch = content
+        .lines()
+        .skip_while(|line| *line != "1 0 0 0 k")
+        .nth(1)
+        .unwrap();
+    let values: Vec<f32> = patch
+        .split_whitespace()
+        .take(4)
+        .map(|v| v.parse().unwrap())
+        .collect();
+    assert!((values[0] - (792.0 - 10.0 * values[2]) / 2.0).abs() < 0.01);
+    assert!(values[1] >= 0.0);
+    assert!(values[1] + values[3] < leaf_edge - 3.0 - 12.0);
+
+    // The default 5mm margin is within the marks' reach, which is reported
+    // as a warning
+    options.margins.sheet = SheetMargins::uniform(5.0);
+    let stages = Arc::new(Mutex::new(Vec::new()));
+    let recorded = Arc::clone(&stages);
+    let output = impose_with_progress(&[create_test_pdf(4)], &options, move |stage| {
+        recorded.lock().unwrap().push(stage)
+    })
+    .await
+    .unwrap();
+    let front = *output.get_pages().values().next().unwrap();
+    let content = String::from_utf8(output.get_page_content(front).unwrap()).unwrap();
+    assert!(!content.contains(" k\n"));
+    assert!(
+        stages
+            .lock()
+            .unwrap()
+            .contains(&ImposeProgress::Warning(ImposeWarning::NoRoomForColorBars))
+    );
+}
+
+#[tokio::test]
+async fn test_registration_marks_print_on_every_separation() {
+    let mut options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        page_arrangement: PageArrangement::Folio,
+        output_orientation: Orientation::Landscape,
+        compress_output: false,
+        ..Default::default()
+    };
+    options.marks.registration_marks = true;
+    options.marks.style.color_rgb = [1.0, 0.0, 0.0];
+
+    let output = impose(&[create_test_pdf(4)], &options).await.unwrap();
+    let front = *output.get_pages().values().next().unwrap();
+    let content = String::from_utf8(output.get_page_content(front).unwrap()).unwrap();
+    assert!(content.contains("q\n/CSReg CS\n1 SCN\n"));
 
-    // Create catalog
-    let catalog_id = doc.add_object(Dictionary::from_iter(vec![
-        ("Type", Object::Name(b"Catalog".to_vec())),
-        ("Pages", Object::Reference(pages_id)),
-    ]));
+    let (resources, _) = output.get_page_resources(front).unwrap();
+    let color_space = resources
+        .unwrap()
+        .get(b"ColorSpace")
+        .and_then(Object::as_dict)
+        .and_then(|spaces| spaces.get(b"CSReg"))
+        .and_then(Object::as_array)
+        .unwrap();
+    let names: Vec<&[u8]> = color_space[..3]
+        .iter()
+        .map(|name| name.as_name().unwrap())
+        .collect();
+    assert_eq!(names, [&b"Separation"[..], b"All", b"DeviceCMYK"]);
 
-    doc.trailer.set("Root", catalog_id);
+    // Without registration marks the pages don't carry the color space
+    options.marks.registration_marks = false;
+    options.marks.crop_marks = true;
+    let output = impose(&[create_test_pdf(4)], &options).await.unwrap();
+    let front = *output.get_pages().values().next().unwrap();
+    let (resources, _) = output.get_page_resources(front).unwrap();
+    assert!(resources.unwrap().get(b"ColorSpace").is_err());
+}
 
-    doc
+#[tokio::test]
+async fn test_punch_guides_follow_each_leafs_binding_edge() {
+    let mut options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        binding_type: BindingType::Spiral,
+        output_orientation: Orientation::Landscape,
+        ..Default::default()
+    };
+    options.margins.sheet = SheetMargins::uniform(0.0);
+    options.marks.punch_guides = Some(PunchGuides {
+        spacing: HoleSpacing::Count(3),
+        edge_offset_mm: 5.0,
+        hole_diameter_mm: 4.0,
+    });
+
+    let output = impose(&[create_test_pdf(4)], &options).await.unwrap();
+    let pages: Vec<_> = output.get_pages().into_values().collect();
+    assert_eq!(pages.len(), 2);
+
+    // Each circle starts at its rightmost point (cx + r, cy)
+    let offset = 5.0 * 72.0 / 25.4;
+    let radius = 2.0 * 72.0 / 25.4;
+    for &page_id in &pages {
+        let content = String::from_utf8(output.get_page_content(page_id).unwrap()).unwrap();
+        let starts: Vec<(f32, f32)> = content
+            .lines()
+            .filter(|line| line.ends_with(" m"))
+            .map(|line| {
+                let v: Vec<f32> = line
+                    .split_whitespace()
+                    .filter_map(|n| n.parse().ok())
+                    .collect();
+                (v[0] - radius, v[1])
+            })
+            .collect();
+        assert_eq!(starts.len(), 6);
+
+        // Left leaf binds on its right edge, right leaf on its left edge
+        let (left, right): (Vec<_>, Vec<_>) = starts.iter().partition(|(x, _)| *x < 396.0);
+        for (holes, x) in [(left, 396.0 - offset), (right, 396.0 + offset)] {
+            assert_eq!(holes.len(), 3);
+            for (i, (cx, cy)) in holes.into_iter().enumerate() {
+                assert!((cx - x).abs() < 0.01);
+                assert!((cy - (612.0 - (i as f32 + 0.5) * 204.0)).abs() < 0.01);
+            }
+        }
+    }
+}
+
+/// Content stream lines that draw a page number, keyed by the number
+fn page_number_ops(doc: &Document) -> Vec<(usize, String)> {
+    doc.get_pages()
+        .into_values()
+        .flat_map(|id| {
+            let content = String::from_utf8(doc.get_page_content(id).unwrap()).unwrap();
+            content
+                .lines()
+                .filter(|line| line.contains(") Tj"))
+                .map(|line| {
+                    let number = line.rsplit('(').next().unwrap();
+                    let number = number.split(')').next().unwrap().parse().unwrap();
+                    (number, line.to_string())
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
 }
 
 #[tokio::test]
-async fn test_load_pdf() {
-    use tempfile::NamedTempFile;
+async fn test_page_numbers_skip_blank_padding() {
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        page_arrangement: PageArrangement::Quarto,
+        add_page_numbers: true,
+        page_number_start: 3,
+        ..Default::default()
+    };
 
-    let mut doc = create_test_pdf(5);
-    let temp = NamedTempFile::new().unwrap();
-    let path = temp.path();
+    let output = impose(&[create_test_pdf(6)], &options).await.unwrap();
 
-    // Save test PDF
-    let mut writer = Vec::new();
-    doc.save_to(&mut writer).unwrap();
-    std::fs::write(path, writer).unwrap();
+    let mut numbers: Vec<usize> = page_number_ops(&output)
+        .into_iter()
+        .map(|(n, _)| n)
+        .collect();
+    numbers.sort();
+    assert_eq!(numbers, vec![3, 4, 5, 6, 7, 8]);
+}
 
-    // Load it back
-    let loaded = load_pdf(path).await.unwrap();
-    assert_eq!(loaded.get_pages().len(), 5);
+#[tokio::test]
+async fn test_page_numbers_upright_in_rotated_quarto_cells() {
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        page_arrangement: PageArrangement::Quarto,
+        add_page_numbers: true,
+        ..Default::default()
+    };
+
+    let output = impose(&[create_test_pdf(8)], &options).await.unwrap();
+
+    // Quarto top row holds pages 3-6, printed upside down
+    for (number, op) in page_number_ops(&output) {
+        let rotated = op.contains("-1 0 0 -1");
+        assert_eq!(rotated, (3..=6).contains(&number), "page {number}: {op}");
+    }
 }
 
 #[tokio::test]
-async fn test_load_multiple_pdfs() {
-    use tempfile::NamedTempFile;
+async fn test_page_numbers_sit_in_tail_margin() {
+    let mut options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        page_arrangement: PageArrangement::Folio,
+        add_page_numbers: true,
+        ..Default::default()
+    };
+    options.margins.sheet = SheetMargins::uniform(0.0);
+    options.margins.leaf.bottom_mm = 20.0;
 
-    let mut doc1 = create_test_pdf(3);
-    let mut doc2 = create_test_pdf(4);
+    let output = impose(&[create_test_pdf(4)], &options).await.unwrap();
 
-    let temp1 = NamedTempFile::new().unwrap();
-    let temp2 = NamedTempFile::new().unwrap();
+    let tail_pt = 20.0 * 72.0 / 25.4;
+    for (number, op) in page_number_ops(&output) {
+        let ops: Vec<&str> = op.split_whitespace().collect();
+        let baseline: f32 = ops[5].parse().unwrap();
+        assert!(
+            baseline > 0.0 && baseline + 8.0 <= tail_pt,
+            "page {number} baseline {baseline}"
+        );
+    }
+}
 
-    let mut writer = Vec::new();
-    doc1.save_to(&mut writer).unwrap();
-    std::fs::write(temp1.path(), &writer).unwrap();
+/// Text origin (x, y) of an unrotated page number op
+fn page_number_origin(op: &str) -> (f32, f32) {
+    let ops: Vec<&str> = op.split_whitespace().collect();
+    (ops[4].parse().unwrap(), ops[5].parse().unwrap())
+}
 
-    writer.clear();
-    doc2.save_to(&mut writer).unwrap();
-    std::fs::write(temp2.path(), &writer).unwrap();
+#[tokio::test]
+async fn test_page_numbers_at_folio_fore_edges() {
+    let mut options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        page_arrangement: PageArrangement::Folio,
+        output_orientation: Orientation::Landscape,
+        add_page_numbers: true,
+        ..Default::default()
+    };
+    options.margins.sheet = SheetMargins::uniform(0.0);
 
-    let paths = vec![temp1.path(), temp2.path()];
-    let docs = load_multiple_pdfs(&paths).await.unwrap();
+    let output = impose(&[create_test_pdf(4)], &options).await.unwrap();
+    let ops = page_number_ops(&output);
 
-    assert_eq!(docs.len(), 2);
-    assert_eq!(docs[0].get_pages().len(), 3);
-    assert_eq!(docs[1].get_pages().len(), 4);
+    let mut numbers: Vec<usize> = ops.iter().map(|(n, _)| *n).collect();
+    numbers.sort();
+    assert_eq!(numbers, vec![1, 2, 3, 4]);
+
+    // Letter landscape: rectos sit at the right sheet edge, versos at the left
+    let sheet_width = 792.0;
+    for (number, op) in &ops {
+        assert!(op.contains(&format!("({number}) Tj")));
+        let (x, _) = page_number_origin(op);
+        if number % 2 == 1 {
+            assert!(x > sheet_width * 0.75, "page {number}: {op}");
+        } else {
+            assert!(x < sheet_width * 0.25, "page {number}: {op}");
+        }
+    }
 }
 
 #[tokio::test]
-async fn test_save_pdf() {
-    use tempfile::NamedTempFile;
+async fn test_page_numbers_header_inner_with_font_size() {
+    let mut options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        page_arrangement: PageArrangement::Folio,
+        output_orientation: Orientation::Landscape,
+        add_page_numbers: true,
+        page_number_font_size: 12.0,
+        page_number_vertical: PageNumberVertical::Header,
+        page_number_horizontal: PageNumberHorizontal::Inner,
+        ..Default::default()
+    };
+    options.margins.sheet = SheetMargins::uniform(0.0);
 
-    let doc = create_test_pdf(2);
-    let temp = NamedTempFile::new().unwrap();
+    let output = impose(&[create_test_pdf(4)], &options).await.unwrap();
 
-    save_pdf(doc, temp.path()).await.unwrap();
+    let (sheet_width, sheet_height) = (792.0, 612.0);
+    for (number, op) in page_number_ops(&output) {
+        assert!(op.starts_with("BT /F1 12 Tf"), "page {number}: {op}");
+        let (x, y) = page_number_origin(&op);
+        assert!(y > sheet_height / 2.0, "page {number}: {op}");
+        // Inner numbers hug the central fold
+        assert!((x - sheet_width / 2.0).abs() < 20.0, "page {number}: {op}");
+    }
+}
 
-    // Verify file was created and can be loaded
-    assert!(temp.path().exists());
-    let loaded = Document::load(temp.path()).unwrap();
-    assert_eq!(loaded.get_pages().len(), 2);
+#[tokio::test]
+async fn test_impose_rejects_zero_page_number_size() {
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        page_number_font_size: 0.0,
+        ..Default::default()
+    };
+
+    let result = impose(&[create_test_pdf(4)], &options).await;
+    assert!(matches!(result, Err(ImposeError::Config(_))));
 }
 
 #[tokio::test]
@@ -189,7 +3168,7 @@ async fn test_impose_with_different_paper_sizes() {
 
     for paper_size in paper_sizes {
         options.output_paper_size = paper_size;
-        let result = impose(&[doc.clone()], &options).await;
+        let result = impose(std::slice::from_ref(&doc), &options).await;
         assert!(result.is_ok(), "Failed for paper size: {:?}", paper_size);
     }
 }
@@ -209,7 +3188,7 @@ async fn test_impose_with_scaling_modes() {
 
     for mode in scaling_modes {
         options.scaling_mode = mode;
-        let result = impose(&[doc.clone()], &options).await;
+        let result = impose(std::slice::from_ref(&doc), &options).await;
         assert!(result.is_ok(), "Failed for scaling mode: {:?}", mode);
     }
 }
@@ -257,8 +3236,34 @@ async fn test_impose_with_custom_arrangement() {
     assert!(result.is_ok());
 
     let output = result.unwrap();
-    // Custom: 12 pages per signature = 1 signature = 1 sheet with 6 pages per side = 2 output pages
-    assert_eq!(output.get_pages().len(), 2);
+    // Custom: 12 pages per signature = 1 signature = 3 nested folio sheets = 6 output pages
+    assert_eq!(output.get_pages().len(), 6);
+}
+
+#[tokio::test]
+async fn test_impose_custom_signature_nests_folio_sheets() {
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        page_arrangement: PageArrangement::Custom {
+            pages_per_signature: 12,
+        },
+        add_page_numbers: true,
+        ..Default::default()
+    };
+
+    // Each sheet is a folio; the outermost carries pages 1, 2, 11 and 12
+    let output = impose(&[create_test_pdf(12)], &options).await.unwrap();
+    assert_eq!(
+        all_page_numbers(&output),
+        vec![
+            vec![12, 1],
+            vec![2, 11],
+            vec![10, 3],
+            vec![4, 9],
+            vec![8, 5],
+            vec![6, 7],
+        ]
+    );
 }
 
 #[tokio::test]
@@ -306,6 +3311,623 @@ async fn test_impose_case_binding() {
     assert_eq!(output.get_pages().len(), 4);
 }
 
+#[tokio::test]
+async fn test_impose_with_progress_reports_each_stage() {
+    use std::sync::{Arc, Mutex};
+
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        binding_type: BindingType::Signature,
+        page_arrangement: PageArrangement::Quarto,
+        compress_output: true,
+        ..Default::default()
+    };
+    let stages = Arc::new(Mutex::new(Vec::new()));
+    let recorded = Arc::clone(&stages);
+
+    let output = impose_with_progress(&[create_test_pdf(16)], &options, move |stage| {
+        recorded.lock().unwrap().push(stage)
+    })
+    .await
+    .unwrap();
+    assert_eq!(output.get_pages().len(), 4);
+
+    let mut expected = vec![ImposeProgress::Merging];
+    expected.extend((1..=4).map(|current| ImposeProgress::Rendering { current, total: 4 }));
+    expected.push(ImposeProgress::Compressing);
+    assert_eq!(*stages.lock().unwrap(), expected);
+}
+
+#[tokio::test]
+async fn test_impose_substitutes_fallback_size_for_empty_media_box() {
+    use std::sync::{Arc, Mutex};
+
+    let mut doc = create_labeled_pdf("a", 4);
+    let second = doc.get_pages()[&2];
+    let empty = vec![0.into(), 0.into(), 0.into(), 0.into()];
+    doc.get_dictionary_mut(second)
+        .unwrap()
+        .set("MediaBox", Object::Array(empty));
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("a.pdf")],
+        binding_type: BindingType::PerfectBinding,
+        fallback_page_size: PaperSize::A5,
+        compress_output: false,
+        ..Default::default()
+    };
+    let stages = Arc::new(Mutex::new(Vec::new()));
+    let recorded = Arc::clone(&stages);
+
+    let output = impose_with_progress(&[doc], &options, move |stage| {
+        recorded.lock().unwrap().push(stage)
+    })
+    .await
+    .unwrap();
+
+    let warnings: Vec<ImposeWarning> = stages
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(|stage| match stage {
+            ImposeProgress::Warning(warning) => Some(*warning),
+            _ => None,
+        })
+        .collect();
+    let expected = ImposeWarning::InvalidPageBox {
+        input: 0,
+        page: 2,
+        substituted: PaperSize::A5,
+    };
+    assert_eq!(warnings, [expected]);
+    assert_eq!(
+        expected.to_string(),
+        "page 2 of input 1 had an invalid page box, substituted A5"
+    );
+
+    // Every page is placed, at a finite, non-zero scale
+    let placed: Vec<(String, [f32; 6])> = output
+        .get_pages()
+        .values()
+        .flat_map(|&id| placed_matrices(&output, id))
+        .collect();
+    assert_eq!(placed.len(), 4);
+    for (label, matrix) in placed {
+        assert!(matrix.iter().all(|v| v.is_finite()), "{label}: {matrix:?}");
+        assert!(
+            matrix[0] * matrix[3] - matrix[1] * matrix[2] != 0.0,
+            "{label}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_impose_cancellable_stops_between_pages() {
+    use std::sync::{Arc, Mutex};
+
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        binding_type: BindingType::Signature,
+        page_arrangement: PageArrangement::Quarto,
+        ..Default::default()
+    };
+    let cancel = CancellationToken::new();
+    let stages = Arc::new(Mutex::new(Vec::new()));
+
+    // Cancel as soon as the first output page is reported
+    let (recorded, token) = (Arc::clone(&stages), cancel.clone());
+    let result = impose_cancellable(
+        &[create_test_pdf(16)],
+        &options,
+        move |stage| {
+            if let ImposeProgress::Rendering { .. } = stage {
+                token.cancel();
+            }
+            recorded.lock().unwrap().push(stage);
+        },
+        cancel,
+    )
+    .await;
+
+    assert!(matches!(result, Err(ImposeError::Cancelled)));
+    assert_eq!(
+        *stages.lock().unwrap(),
+        vec![
+            ImposeProgress::Merging,
+            ImposeProgress::Rendering {
+                current: 1,
+                total: 4
+            }
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_impose_split_cancellable_honours_an_early_cancel() {
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        split_mode: SplitMode::BySignatures(1),
+        ..Default::default()
+    };
+    let cancel = CancellationToken::new();
+    cancel.cancel();
+
+    let result = impose_split_cancellable(&[create_test_pdf(16)], &options, |_| {}, cancel).await;
+    assert!(matches!(result, Err(ImposeError::Cancelled)));
+}
+
+#[tokio::test]
+async fn test_impose_split_with_progress_reports_each_sheet() {
+    use std::sync::{Arc, Mutex};
+
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        page_arrangement: PageArrangement::Quarto,
+        split_mode: SplitMode::BySignatures(1),
+        ..Default::default()
+    };
+    let stages = Arc::new(Mutex::new(Vec::new()));
+    let recorded = Arc::clone(&stages);
+
+    let parts = impose_split_with_progress(&[create_test_pdf(16)], &options, move |stage| {
+        recorded.lock().unwrap().push(stage)
+    })
+    .await
+    .unwrap();
+    assert_eq!(parts.len(), 2);
+
+    let rendered = stages
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|stage| matches!(stage, ImposeProgress::Rendering { .. }))
+        .count();
+    assert_eq!(rendered, 4);
+}
+
+const EMBEDDED_FONT_DATA: [u8; 4096] = [7; 4096];
+
+/// A document whose pages all use one font with an embedded font program
+fn create_pdf_with_embedded_font(num_pages: usize) -> Document {
+    let mut doc = create_labeled_pdf("A", num_pages);
+    let font_file_id = doc.add_object(Stream::new(Dictionary::new(), EMBEDDED_FONT_DATA.to_vec()));
+    let descriptor_id = doc.add_object(Dictionary::from_iter(vec![
+        ("Type", Object::Name(b"FontDescriptor".to_vec())),
+        ("FontName", Object::Name(b"Embedded".to_vec())),
+        ("FontFile2", Object::Reference(font_file_id)),
+    ]));
+    let font_id = doc.add_object(Dictionary::from_iter(vec![
+        ("Type", Object::Name(b"Font".to_vec())),
+        ("Subtype", Object::Name(b"TrueType".to_vec())),
+        ("BaseFont", Object::Name(b"Embedded".to_vec())),
+        ("FontDescriptor", Object::Reference(descriptor_id)),
+    ]));
+    for page_id in doc.get_pages().into_values().collect::<Vec<_>>() {
+        let fonts = Dictionary::from_iter(vec![("F1", Object::Reference(font_id))]);
+        let resources = Dictionary::from_iter(vec![("Font", Object::Dictionary(fonts))]);
+        doc.get_dictionary_mut(page_id)
+            .unwrap()
+            .set("Resources", Object::Dictionary(resources));
+    }
+    doc
+}
+
+/// How many copies of the embedded font program `doc` holds
+fn embedded_font_copies(doc: &Document) -> usize {
+    doc.objects
+        .values()
+        .filter(|obj| {
+            obj.as_stream()
+                .is_ok_and(|stream| stream.content == EMBEDDED_FONT_DATA)
+        })
+        .count()
+}
+
+#[tokio::test]
+async fn test_impose_copies_shared_font_program_once() {
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        page_arrangement: PageArrangement::Octavo,
+        compress_output: false,
+        ..Default::default()
+    };
+
+    let result = impose(&[create_pdf_with_embedded_font(40)], &options)
+        .await
+        .unwrap();
+
+    assert_eq!(embedded_font_copies(&result), 1);
+}
+
+#[test]
+fn test_render_imposed_page_shares_copied_objects_through_cache() {
+    let source = create_pdf_with_embedded_font(8);
+    let page_ids: Vec<_> = source.get_pages().into_values().collect();
+    let options = ImpositionOptions {
+        page_arrangement: PageArrangement::Quarto,
+        ..Default::default()
+    };
+    let sides = plan_imposition(&vec![(612.0, 792.0); page_ids.len()], &options).unwrap();
+
+    let mut output = Document::with_version("1.7");
+    let pages_id = output.new_object_id();
+    let mut cache = std::collections::HashMap::new();
+    for side in &sides {
+        render_imposed_page(
+            &mut output,
+            &source,
+            &page_ids,
+            &side.placements,
+            792.0,
+            612.0,
+            pages_id,
+            &PrinterMarks::default(),
+            &side.leaf_bounds,
+            2,
+            2,
+            396.0,
+            306.0,
+            false,
+            1,
+            &mut cache,
+        )
+        .unwrap();
+    }
+
+    assert_eq!(sides.len(), 2);
+    assert_eq!(embedded_font_copies(&output), 1);
+}
+
+#[tokio::test]
+async fn test_impose_output_is_deterministic() {
+    // Every page has an image of its own and shares one font
+    let mut doc = create_labeled_pdf("A", 20);
+    let font_id = doc.add_object(Dictionary::from_iter(vec![
+        ("Type", Object::Name(b"Font".to_vec())),
+        ("BaseFont", Object::Name(b"Helvetica".to_vec())),
+    ]));
+    for page_id in doc.get_pages().into_values().collect::<Vec<_>>() {
+        let image_id = doc.add_object(Stream::new(Dictionary::new(), vec![0; 64]));
+        let resources = Dictionary::from_iter(vec![
+            (
+                "Font",
+                Object::Dictionary(Dictionary::from_iter(vec![(
+                    "F1",
+                    Object::Reference(font_id),
+                )])),
+            ),
+            (
+                "XObject",
+                Object::Dictionary(Dictionary::from_iter(vec![(
+                    "Im1",
+                    Object::Reference(image_id),
+                )])),
+            ),
+        ]);
+        doc.get_dictionary_mut(page_id)
+            .unwrap()
+            .set("Resources", Object::Dictionary(resources));
+    }
+    let documents = [doc];
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        page_arrangement: PageArrangement::Octavo,
+        compress_output: false,
+        ..Default::default()
+    };
+
+    let mut first = impose(&documents, &options).await.unwrap();
+    let mut second = impose(&documents, &options).await.unwrap();
+
+    // The shared font is copied once
+    let fonts = first
+        .objects
+        .values()
+        .filter(|obj| {
+            obj.as_dict()
+                .is_ok_and(|dict| dict.get(b"BaseFont").is_ok())
+        })
+        .count();
+    assert_eq!(fonts, 1);
+
+    let (mut first_bytes, mut second_bytes) = (Vec::new(), Vec::new());
+    first.save_to(&mut first_bytes).unwrap();
+    second.save_to(&mut second_bytes).unwrap();
+    assert!(first_bytes == second_bytes);
+}
+
+#[test]
+fn test_plan_imposition_swaps_side_margins_on_back() {
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        binding_type: BindingType::Signature,
+        page_arrangement: PageArrangement::Folio,
+        margins: Margins {
+            sheet: SheetMargins {
+                top_mm: 5.0,
+                bottom_mm: 5.0,
+                left_mm: 10.0,
+                right_mm: 20.0,
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let layouts = plan_imposition(&[(612.0, 792.0); 4], &options).unwrap();
+    let front = layouts.iter().find(|l| l.side == SheetSide::Front).unwrap();
+    let back = layouts.iter().find(|l| l.side == SheetSide::Back).unwrap();
+
+    // The back is mirrored for duplex, so its left margin is the front's right
+    assert!((front.leaf_bounds.x - constants::mm_to_pt(10.0)).abs() < 0.01);
+    assert!((back.leaf_bounds.x - constants::mm_to_pt(20.0)).abs() < 0.01);
+    assert_eq!(front.leaf_bounds.width, back.leaf_bounds.width);
+}
+
+#[tokio::test]
+async fn test_plan_imposition_matches_rendered_sheets() {
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        binding_type: BindingType::Signature,
+        page_arrangement: PageArrangement::Quarto,
+        ..Default::default()
+    };
+    let dims = vec![(612.0, 792.0); 16];
+
+    let layouts = plan_imposition(&dims, &options).unwrap();
+    let output = impose(&[create_test_pdf(16)], &options).await.unwrap();
+    assert_eq!(layouts.len(), output.get_pages().len());
+
+    // Every page is placed exactly once, inside its leaf area
+    let mut placed: Vec<usize> = layouts
+        .iter()
+        .flat_map(|layout| layout.placements.iter().filter_map(|p| p.source_page))
+        .collect();
+    placed.sort_unstable();
+    assert_eq!(placed, (0..16).collect::<Vec<_>>());
+    for layout in &layouts {
+        for placement in &layout.placements {
+            let rect = placement.content_rect;
+            assert!(rect.left() >= layout.leaf_bounds.left() - 0.01);
+            assert!(rect.right() <= layout.leaf_bounds.right() + 0.01);
+        }
+    }
+
+    // Quarto turns the top row of each side upside down
+    assert!(
+        layouts
+            .iter()
+            .all(|layout| layout.placements.iter().any(|p| p.is_rotated()))
+    );
+    assert_eq!(layouts[0].side, SheetSide::Front);
+    assert_eq!(layouts[1].side, SheetSide::Back);
+}
+
+#[test]
+fn test_plan_imposition_rejects_empty_input() {
+    let result = plan_imposition(&[], &ImpositionOptions::default());
+    assert!(matches!(result, Err(ImposeError::NoPages)));
+}
+
+#[tokio::test]
+async fn test_plan_counts_flyleaves_and_signatures() {
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        binding_type: BindingType::Signature,
+        page_arrangement: PageArrangement::Folio,
+        front_flyleaves: 1,
+        ..Default::default()
+    };
+
+    let plan = plan(&[create_test_pdf(6)], &options).await.unwrap();
+    let stats = calculate_statistics(&[create_test_pdf(6)], &options).unwrap();
+    assert_eq!(plan.sheets.len(), stats.output_sheets);
+    assert_eq!(plan.sides().count(), stats.output_pages);
+
+    // The flyleaf's two pages come before the six source pages
+    let mut placed: Vec<usize> = plan
+        .sides()
+        .flat_map(|side| side.placements.iter().filter_map(|p| p.source_page))
+        .collect();
+    placed.sort_unstable();
+    assert_eq!(placed, (0..8).collect::<Vec<_>>());
+    assert!(plan.sheets.iter().all(|sheet| sheet.signature.is_some()));
+}
+
+#[cfg(feature = "serde")]
+#[tokio::test]
+async fn test_plan_serializes_placements() {
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        binding_type: BindingType::PerfectBinding,
+        ..Default::default()
+    };
+
+    let plan = plan(&[create_test_pdf(4)], &options).await.unwrap();
+    let json = serde_json::to_value(&plan).unwrap();
+    let placement = &json["sheets"][0]["sides"][0]["placements"][0];
+    assert!(placement["source_page"].is_u64());
+    assert!(placement["content_rect"]["width"].as_f64().unwrap() > 0.0);
+    assert_eq!(json["sheets"][0]["signature"], serde_json::Value::Null);
+
+    let round_trip: ImpositionPlan = serde_json::from_value(json).unwrap();
+    assert_eq!(round_trip.sheets.len(), plan.sheets.len());
+}
+
+#[tokio::test]
+async fn test_n_up_places_pages_in_reading_order() {
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        binding_type: BindingType::NUp { rows: 3, cols: 3 },
+        ..Default::default()
+    };
+
+    let output = impose(&[create_test_pdf(9)], &options).await.unwrap();
+    assert_eq!(output.get_pages().len(), 1);
+
+    let layouts = plan_imposition(&[(612.0, 792.0); 9], &options).unwrap();
+    assert_eq!(layouts.len(), 1);
+    let placements = &layouts[0].placements;
+    for (idx, placement) in placements.iter().enumerate() {
+        assert_eq!(placement.source_page, Some(idx));
+        assert_eq!(placement.slot.grid_pos, GridPosition::from_index(idx, 3));
+        assert!(!placement.is_rotated());
+    }
+
+    // A tenth page starts a second sheet, the rest of which stays blank
+    let stats = calculate_statistics(&[create_test_pdf(10)], &options).unwrap();
+    assert_eq!(stats.output_sheets, 2);
+    assert_eq!(stats.blank_pages_added, 8);
+}
+
+#[tokio::test]
+async fn test_n_up_column_major_order_and_cell_borders() {
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        binding_type: BindingType::NUp { rows: 2, cols: 3 },
+        n_up_order: NUpOrder::ColumnMajor,
+        cell_borders: true,
+        compress_output: false,
+        ..Default::default()
+    };
+
+    // Pages run down each column before moving right
+    let layouts = plan_imposition(&[(612.0, 792.0); 5], &options).unwrap();
+    let cells: Vec<(usize, usize)> = layouts[0]
+        .placements
+        .iter()
+        .filter(|p| p.source_page.is_some())
+        .map(|p| (p.slot.grid_pos.row, p.slot.grid_pos.col))
+        .collect();
+    assert_eq!(cells, [(0, 0), (1, 0), (0, 1), (1, 1), (0, 2)]);
+
+    // Each placed page gets a frame; the empty cell doesn't
+    let output = impose(&[create_test_pdf(5)], &options).await.unwrap();
+    let page = *output.get_pages().values().next().unwrap();
+    let content = String::from_utf8(output.get_page_content(page).unwrap()).unwrap();
+    assert_eq!(content.matches(" re S").count(), 5);
+}
+
+#[test]
+fn test_forced_spine_edge_moves_the_quarto_spine_margin() {
+    let spine_pt = 20.0 * 72.0 / 25.4;
+    for orientation in [Orientation::Portrait, Orientation::Landscape] {
+        for (spine_edge, horizontal) in
+            [(SpineEdge::Vertical, false), (SpineEdge::Horizontal, true)]
+        {
+            let mut options = ImpositionOptions {
+                input_files: vec![PathBuf::from("test.pdf")],
+                page_arrangement: PageArrangement::Quarto,
+                output_orientation: orientation,
+                spine_edge,
+                scaling_mode: ScalingMode::Stretch,
+                ..Default::default()
+            };
+            options.margins.leaf = LeafMargins {
+                top_mm: 0.0,
+                bottom_mm: 0.0,
+                fore_edge_mm: 0.0,
+                spine_mm: 20.0,
+                cut_mm: 0.0,
+            };
+
+            let layouts = plan_imposition(&[(612.0, 792.0); 8], &options).unwrap();
+            let front = &layouts[0];
+            let leaf = &front.leaf_bounds;
+            let top_left = front
+                .placements
+                .iter()
+                .find(|p| p.slot.grid_pos.row == 0 && p.slot.grid_pos.col == 0)
+                .unwrap();
+
+            // The top-left cell meets the vertical spine on its right and
+            // the horizontal one on its bottom
+            let rect = &top_left.content_rect;
+            let (right_margin, bottom_margin) = (
+                leaf.x + leaf.width / 2.0 - rect.right(),
+                rect.y - (leaf.y + leaf.height / 2.0),
+            );
+            let (expected_right, expected_bottom) = if horizontal {
+                (0.0, spine_pt)
+            } else {
+                (spine_pt, 0.0)
+            };
+            assert!(
+                (right_margin - expected_right).abs() < 0.01
+                    && (bottom_margin - expected_bottom).abs() < 0.01,
+                "{orientation:?} {spine_edge:?}: {right_margin} {bottom_margin}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_short_edge_duplex_turns_back_sides() {
+    let mut options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        page_arrangement: PageArrangement::Quarto,
+        duplex_flip: FlipEdge::ShortEdge,
+        ..Default::default()
+    };
+    options.margins.sheet.top_mm = 20.0;
+    options.margins.sheet.bottom_mm = 5.0;
+
+    let layouts = plan_imposition(&[(612.0, 792.0); 8], &options).unwrap();
+    let (front, back) = (&layouts[0], &layouts[1]);
+    assert_eq!(back.side, SheetSide::Back);
+
+    // Top [7↓, 2↓], Bottom [6, 3] instead of Top [3↓, 6↓], Bottom [2, 7]
+    let cells: Vec<_> = back
+        .placements
+        .iter()
+        .map(|p| {
+            let pos = p.slot.grid_pos;
+            (pos.row, pos.col, p.source_page.unwrap() + 1, p.is_rotated())
+        })
+        .collect();
+    for cell in [
+        (0, 0, 7, true),
+        (0, 1, 2, true),
+        (1, 0, 6, false),
+        (1, 1, 3, false),
+    ] {
+        assert!(cells.contains(&cell), "{cell:?} in {cells:?}");
+    }
+
+    // The back's leaf area is turned with it, so top and bottom margins swap
+    let mm = 72.0 / 25.4;
+    assert!((front.leaf_bounds.y - 5.0 * mm).abs() < 0.01);
+    assert!((back.leaf_bounds.y - 20.0 * mm).abs() < 0.01);
+    assert!((back.leaf_bounds.x - front.leaf_bounds.x).abs() < 0.01);
+}
+
+#[test]
+fn test_cell_gap_only_applies_to_cut_grids() {
+    let dims = [(612.0, 792.0); 8];
+    let gapped = |binding_type| {
+        let base = ImpositionOptions {
+            binding_type,
+            output_orientation: Orientation::Landscape,
+            scaling_mode: ScalingMode::Stretch,
+            ..Default::default()
+        };
+        let with_gap = ImpositionOptions {
+            cell_gap_mm: 10.0,
+            ..base.clone()
+        };
+        let width = |options: &ImpositionOptions| {
+            plan_imposition(&dims, options).unwrap()[0].placements[0]
+                .content_rect
+                .width
+        };
+        width(&base) - width(&with_gap)
+    };
+
+    assert!(
+        (gapped(BindingType::NUp { rows: 2, cols: 2 }) - constants::mm_to_pt(10.0)).abs() < 0.01
+    );
+    assert_eq!(gapped(BindingType::Signature), 0.0);
+}
+
 #[tokio::test]
 async fn test_full_workflow() {
     use tempfile::TempDir;
@@ -335,7 +3957,9 @@ async fn test_full_workflow() {
     let imposed = impose(&[loaded], &options).await.unwrap();
 
     // Save output
-    save_pdf(imposed, &output_path).await.unwrap();
+    save_pdf(imposed, &output_path, SaveOptions::default())
+        .await
+        .unwrap();
 
     // Verify output exists
     assert!(output_path.exists());
@@ -352,7 +3976,7 @@ fn test_folio_page_order() {
     use pdf_impose::PageArrangement;
     use pdf_impose::layout::map_pages_to_slots;
 
-    let order = map_pages_to_slots(PageArrangement::Folio, 0, 4);
+    let order = map_pages_to_slots(PageArrangement::Folio, BindingEdge::Left, 0, 4);
 
     // Side A: [4, 1], Side B: [2, 3]
     assert_eq!(order.len(), 4);
@@ -362,13 +3986,38 @@ fn test_folio_page_order() {
     assert_eq!(order[3], Some(2)); // Side B right: page 3
 }
 
+#[test]
+fn test_folio_page_order_right_bound() {
+    use pdf_impose::PageArrangement;
+    use pdf_impose::layout::map_pages_to_slots;
+
+    let order = map_pages_to_slots(PageArrangement::Folio, BindingEdge::Right, 0, 4);
+
+    // Side A: [1, 4], Side B: [3, 2]
+    assert_eq!(order, vec![Some(0), Some(3), Some(2), Some(1)]);
+}
+
+#[tokio::test]
+async fn test_impose_right_bound_folio() {
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        page_arrangement: PageArrangement::Folio,
+        binding_edge: BindingEdge::Right,
+        add_page_numbers: true,
+        ..Default::default()
+    };
+
+    let output = impose(&[create_test_pdf(4)], &options).await.unwrap();
+    assert_eq!(all_page_numbers(&output), vec![vec![1, 4], vec![3, 2]]);
+}
+
 #[test]
 fn test_quarto_page_order() {
     // Quarto: 2 folds = 8 pages per signature
     use pdf_impose::PageArrangement;
     use pdf_impose::layout::map_pages_to_slots;
 
-    let order = map_pages_to_slots(PageArrangement::Quarto, 0, 8);
+    let order = map_pages_to_slots(PageArrangement::Quarto, BindingEdge::Left, 0, 8);
 
     assert_eq!(order.len(), 8);
 
@@ -391,7 +4040,7 @@ fn test_octavo_page_order() {
     use pdf_impose::PageArrangement;
     use pdf_impose::layout::map_pages_to_slots;
 
-    let order = map_pages_to_slots(PageArrangement::Octavo, 0, 16);
+    let order = map_pages_to_slots(PageArrangement::Octavo, BindingEdge::Left, 0, 16);
 
     assert_eq!(order.len(), 16);
 
@@ -419,6 +4068,51 @@ fn test_octavo_page_order() {
     }
 }
 
+#[test]
+fn test_sexto_page_order() {
+    // Sexto: quarto plus a folio offcut = 12 pages per signature
+    use pdf_impose::PageArrangement;
+    use pdf_impose::layout::map_pages_to_slots;
+
+    let order = map_pages_to_slots(PageArrangement::Sexto, BindingEdge::Left, 0, 12);
+
+    // Side A top:    [9, 4]   -> [8, 3]
+    // Side A middle: [12, 1]  -> [11, 0]
+    // Side A bottom: [8, 5]   -> [7, 4]  (offcut)
+    // Side B top:    [3, 10]  -> [2, 9]  (mirrored for duplex)
+    // Side B middle: [2, 11]  -> [1, 10] (mirrored for duplex)
+    // Side B bottom: [6, 7]   -> [5, 6]  (offcut, mirrored for duplex)
+    let expected = vec![8, 3, 11, 0, 7, 4, 2, 9, 1, 10, 5, 6];
+    let order: Vec<usize> = order.into_iter().map(Option::unwrap).collect();
+    assert_eq!(order, expected);
+}
+
+#[test]
+fn test_duodecimo_page_order() {
+    // Duodecimo: octavo plus two folio offcuts = 24 pages per signature
+    use pdf_impose::PageArrangement;
+    use pdf_impose::layout::map_pages_to_slots;
+
+    let order = map_pages_to_slots(PageArrangement::Duodecimo, BindingEdge::Left, 0, 24);
+
+    // Side A top:    [5, 20, 17, 8]   -> [4, 19, 16, 7]
+    // Side A middle: [4, 21, 24, 1]   -> [3, 20, 23, 0]
+    // Side A bottom: [14, 11, 16, 9]  -> [13, 10, 15, 8] (offcuts)
+    // Side B top:    [6, 19, 18, 7]   -> [5, 18, 17, 6] (mirrored for duplex)
+    // Side B middle: [3, 22, 23, 2]   -> [2, 21, 22, 1] (mirrored for duplex)
+    // Side B bottom: [10, 15, 12, 13] -> [9, 14, 11, 12] (offcuts, mirrored)
+    let expected = vec![
+        4, 19, 16, 7, // Side A top row
+        3, 20, 23, 0, // Side A middle row
+        13, 10, 15, 8, // Side A bottom row (offcuts)
+        5, 18, 17, 6, // Side B top row (mirrored)
+        2, 21, 22, 1, // Side B middle row (mirrored)
+        9, 14, 11, 12, // Side B bottom row (offcuts, mirrored)
+    ];
+    let order: Vec<usize> = order.into_iter().map(Option::unwrap).collect();
+    assert_eq!(order, expected);
+}
+
 #[test]
 fn test_multiple_signatures() {
     // Test with 2 quarto signatures (16 pages total)
@@ -426,9 +4120,9 @@ fn test_multiple_signatures() {
     use pdf_impose::layout::map_pages_to_slots;
 
     // First signature
-    let order1 = map_pages_to_slots(PageArrangement::Quarto, 0, 16);
+    let order1 = map_pages_to_slots(PageArrangement::Quarto, BindingEdge::Left, 0, 16);
     // Second signature
-    let order2 = map_pages_to_slots(PageArrangement::Quarto, 8, 16);
+    let order2 = map_pages_to_slots(PageArrangement::Quarto, BindingEdge::Left, 8, 16);
 
     // First signature: pages 1-8 (indices 0-7)
     // Side A: [5, 4, 8, 1] -> [4, 3, 7, 0]
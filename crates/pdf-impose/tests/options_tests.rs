@@ -56,6 +56,255 @@ fn test_validation_invalid_pages_per_signature() {
     assert!(options.validate().is_ok());
 }
 
+#[test]
+fn test_validation_negative_bleed() {
+    let mut options = ImpositionOptions::default();
+    options.input_files.push(PathBuf::from("test.pdf"));
+
+    options.bleed_mm = 3.0;
+    assert!(options.validate().is_ok());
+
+    options.bleed_mm = -1.0;
+    match options.validate() {
+        Err(ImposeError::Config(msg)) => assert!(msg.contains("Bleed")),
+        _ => panic!("Expected Config error"),
+    }
+}
+
+#[test]
+fn test_validation_back_side_offset() {
+    let mut options = ImpositionOptions::default();
+    options.input_files.push(PathBuf::from("test.pdf"));
+
+    options.back_side_offset_mm = (-1.2, 0.5);
+    assert!(options.validate().is_ok());
+
+    options.back_side_offset_mm = (f32::NAN, 0.0);
+    match options.validate() {
+        Err(ImposeError::Config(msg)) => assert!(msg.contains("Back side offset")),
+        _ => panic!("Expected Config error"),
+    }
+}
+
+#[test]
+fn test_validation_mark_style() {
+    let mut options = ImpositionOptions::default();
+    options.input_files.push(PathBuf::from("test.pdf"));
+
+    options.marks.style.color_rgb = [0.0, 0.5, 1.0];
+    assert!(options.validate().is_ok());
+
+    options.marks.style.color_rgb = [0.0, 0.0, 2.0];
+    match options.validate() {
+        Err(ImposeError::Config(msg)) => assert!(msg.contains("Mark color")),
+        _ => panic!("Expected Config error"),
+    }
+
+    options.marks.style = MarkStyle {
+        line_width_pt: -0.5,
+        ..Default::default()
+    };
+    match options.validate() {
+        Err(ImposeError::Config(msg)) => assert!(msg.contains("Mark weight")),
+        _ => panic!("Expected Config error"),
+    }
+}
+
+#[test]
+fn test_validation_cover_pages() {
+    let mut options = ImpositionOptions::default();
+    options.input_files.push(PathBuf::from("test.pdf"));
+
+    // Skipped covers may be any length
+    options.cover.front_pages = 4;
+    assert!(options.validate().is_ok());
+
+    options.cover.mode = CoverMode::SeparateDocument;
+    match options.validate() {
+        Err(ImposeError::Config(msg)) => assert!(msg.contains("at most two pages")),
+        _ => panic!("Expected Config error"),
+    }
+
+    options.cover.front_pages = 2;
+    options.cover.paper_thickness_mm = -0.1;
+    match options.validate() {
+        Err(ImposeError::Config(msg)) => assert!(msg.contains("Paper thickness")),
+        _ => panic!("Expected Config error"),
+    }
+}
+
+#[test]
+fn test_validation_custom_paper_size() {
+    let mut options = ImpositionOptions::default();
+    options.input_files.push(PathBuf::from("test.pdf"));
+
+    options.output_paper_size = PaperSize::Custom {
+        width_mm: 320.0,
+        height_mm: 450.0,
+    };
+    assert!(options.validate().is_ok());
+
+    options.output_paper_size = PaperSize::Custom {
+        width_mm: 320.0,
+        height_mm: 0.0,
+    };
+    match options.validate() {
+        Err(ImposeError::Config(msg)) => assert!(msg.contains("paper")),
+        _ => panic!("Expected Config error"),
+    }
+}
+
+#[test]
+fn test_validation_negative_sheet_margin() {
+    let mut options = ImpositionOptions::default();
+    options.input_files.push(PathBuf::from("test.pdf"));
+
+    options.margins.sheet.left_mm = 0.0;
+    assert!(options.validate().is_ok());
+
+    options.margins.sheet.left_mm = -2.0;
+    match options.validate() {
+        Err(ImposeError::Config(msg)) => assert!(msg.contains("Sheet margins")),
+        _ => panic!("Expected Config error"),
+    }
+}
+
+#[test]
+fn test_validation_sheet_margins_exceeding_sheet() {
+    let mut options = ImpositionOptions::default();
+    options.input_files.push(PathBuf::from("test.pdf"));
+    options.output_paper_size = PaperSize::Custom {
+        width_mm: 200.0,
+        height_mm: 300.0,
+    };
+
+    options.margins.sheet.left_mm = 95.0;
+    options.margins.sheet.right_mm = 95.0;
+    assert!(options.validate().is_ok());
+
+    // Together the left and right margins cover the whole width
+    options.margins.sheet.right_mm = 105.0;
+    match options.validate() {
+        Err(ImposeError::Config(msg)) => assert!(msg.contains("no room")),
+        _ => panic!("Expected Config error"),
+    }
+
+    options.margins.sheet.right_mm = 95.0;
+    options.margins.sheet.top_mm = 150.0;
+    options.margins.sheet.bottom_mm = 150.0;
+    match options.validate() {
+        Err(ImposeError::Config(msg)) => assert!(msg.contains("no room")),
+        _ => panic!("Expected Config error"),
+    }
+}
+
+#[test]
+fn test_validation_punch_guides_need_punched_binding() {
+    let mut options = ImpositionOptions::default();
+    options.input_files.push(PathBuf::from("test.pdf"));
+    options.marks.punch_guides = Some(PunchGuides::default());
+
+    match options.validate() {
+        Err(ImposeError::Config(msg)) => assert!(msg.contains("Punch guides")),
+        _ => panic!("Expected Config error"),
+    }
+
+    options.binding_type = BindingType::Spiral;
+    assert!(options.validate().is_ok());
+    options.binding_type = BindingType::SideStitch;
+    assert!(options.validate().is_ok());
+
+    options.marks.punch_guides = Some(PunchGuides {
+        spacing: HoleSpacing::Count(0),
+        ..Default::default()
+    });
+    assert!(matches!(options.validate(), Err(ImposeError::Config(_))));
+}
+
+#[test]
+fn test_validation_horizontal_spine_needs_quarto_signature() {
+    let mut options = ImpositionOptions {
+        input_files: vec![PathBuf::from("test.pdf")],
+        page_arrangement: PageArrangement::Quarto,
+        spine_edge: SpineEdge::Horizontal,
+        ..Default::default()
+    };
+    assert!(options.validate().is_ok());
+
+    options.page_arrangement = PageArrangement::Folio;
+    match options.validate() {
+        Err(ImposeError::Config(msg)) => assert!(msg.contains("horizontal spine")),
+        _ => panic!("Expected Config error"),
+    }
+
+    options.page_arrangement = PageArrangement::Quarto;
+    options.binding_type = BindingType::PerfectBinding;
+    assert!(matches!(options.validate(), Err(ImposeError::Config(_))));
+
+    // A vertical spine is what every other layout has already
+    options.spine_edge = SpineEdge::Vertical;
+    assert!(options.validate().is_ok());
+}
+
+#[test]
+fn test_validation_step_and_repeat() {
+    let mut options = ImpositionOptions::default();
+    options.input_files.push(PathBuf::from("test.pdf"));
+    options.step_and_repeat = Some(StepAndRepeat {
+        cols: 2,
+        rows: 5,
+        copies: Some(100),
+    });
+    assert!(options.validate().is_ok());
+
+    options.output_format = OutputFormat::TwoSided;
+    assert!(matches!(options.validate(), Err(ImposeError::Config(_))));
+
+    options.output_format = OutputFormat::DoubleSided;
+    options.step_and_repeat = Some(StepAndRepeat {
+        cols: 0,
+        rows: 5,
+        copies: None,
+    });
+    assert!(matches!(options.validate(), Err(ImposeError::Config(_))));
+}
+
+#[test]
+fn test_validation_n_up() {
+    let mut options = ImpositionOptions::default();
+    options.input_files.push(PathBuf::from("test.pdf"));
+    options.binding_type = BindingType::NUp { rows: 3, cols: 3 };
+    assert!(options.validate().is_ok());
+
+    options.output_format = OutputFormat::TwoSided;
+    assert!(matches!(options.validate(), Err(ImposeError::Config(_))));
+
+    options.output_format = OutputFormat::DoubleSided;
+    options.binding_type = BindingType::NUp { rows: 0, cols: 3 };
+    assert!(matches!(options.validate(), Err(ImposeError::Config(_))));
+}
+
+#[test]
+fn test_validation_signature_plan() {
+    let mut options = ImpositionOptions::default();
+    options.input_files.push(PathBuf::from("test.pdf"));
+    options.signature_plan = Some(SignaturePlan {
+        pages_per_signature: vec![12, 12, 4],
+    });
+    assert!(options.validate().is_ok());
+
+    options.signature_plan = Some(SignaturePlan {
+        pages_per_signature: vec![12, 6],
+    });
+    assert!(matches!(options.validate(), Err(ImposeError::Config(_))));
+
+    options.signature_plan = Some(SignaturePlan {
+        pages_per_signature: vec![8],
+    });
+    options.binding_type = BindingType::PerfectBinding;
+    assert!(matches!(options.validate(), Err(ImposeError::Config(_))));
+}
+
 #[cfg(feature = "serde")]
 #[tokio::test]
 async fn test_save_and_load_options() {
@@ -87,3 +336,151 @@ async fn test_save_and_load_options() {
     assert_eq!(loaded.back_flyleaves, options.back_flyleaves);
     assert_eq!(loaded.add_page_numbers, options.add_page_numbers);
 }
+
+#[cfg(feature = "serde")]
+#[tokio::test]
+async fn test_save_and_load_scaling_modes() {
+    use tempfile::NamedTempFile;
+
+    let temp_file = NamedTempFile::new().unwrap();
+    let path = temp_file.path();
+
+    for scaling_mode in [
+        ScalingMode::FitNoUpscale,
+        ScalingMode::Percent(85.0),
+        ScalingMode::Stretch,
+    ] {
+        let options = ImpositionOptions {
+            scaling_mode,
+            ..Default::default()
+        };
+        options.save(path).await.unwrap();
+        let loaded = ImpositionOptions::load(path).await.unwrap();
+        assert_eq!(loaded.scaling_mode, scaling_mode);
+    }
+}
+
+#[cfg(feature = "serde")]
+#[tokio::test]
+async fn test_save_and_load_n_up_binding() {
+    use tempfile::NamedTempFile;
+
+    let temp_file = NamedTempFile::new().unwrap();
+    let options = ImpositionOptions {
+        binding_type: BindingType::NUp { rows: 2, cols: 3 },
+        n_up_order: NUpOrder::ColumnMajor,
+        cell_borders: true,
+        ..Default::default()
+    };
+    options.save(temp_file.path()).await.unwrap();
+    let loaded = ImpositionOptions::load(temp_file.path()).await.unwrap();
+    assert_eq!(loaded.binding_type, BindingType::NUp { rows: 2, cols: 3 });
+    assert_eq!(loaded.n_up_order, NUpOrder::ColumnMajor);
+    assert!(loaded.cell_borders);
+}
+
+#[test]
+fn test_validation_scale_percent() {
+    let mut options = ImpositionOptions::default();
+    options.input_files.push(PathBuf::from("test.pdf"));
+
+    options.scaling_mode = ScalingMode::Percent(85.0);
+    assert!(options.validate().is_ok());
+
+    options.scaling_mode = ScalingMode::Percent(0.0);
+    match options.validate() {
+        Err(ImposeError::Config(msg)) => assert!(msg.contains("Scale percentage")),
+        _ => panic!("Expected Config error"),
+    }
+}
+
+#[cfg(feature = "serde")]
+#[tokio::test]
+async fn test_load_options_without_compress_output_defaults_to_compressed() {
+    use tempfile::NamedTempFile;
+
+    let temp_file = NamedTempFile::new().unwrap();
+    let path = temp_file.path();
+    ImpositionOptions::default().save(path).await.unwrap();
+
+    // Simulate a config written before the option existed
+    let mut json: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(path).unwrap()).unwrap();
+    json.as_object_mut().unwrap().remove("compress_output");
+    std::fs::write(path, json.to_string()).unwrap();
+
+    let loaded = ImpositionOptions::load(path).await.unwrap();
+    assert!(loaded.compress_output);
+}
+
+#[cfg(feature = "serde")]
+#[tokio::test]
+async fn test_load_options_rejects_unknown_fields() {
+    use tempfile::NamedTempFile;
+
+    let temp_file = NamedTempFile::new().unwrap();
+    let path = temp_file.path();
+    ImpositionOptions::default().save(path).await.unwrap();
+
+    let mut json: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(path).unwrap()).unwrap();
+    json["margins"]["leaf"]["gutter_mm"] = 3.0.into();
+    std::fs::write(path, json.to_string()).unwrap();
+
+    match ImpositionOptions::load(path).await {
+        Err(ImposeError::Config(msg)) => assert!(msg.contains("gutter_mm"), "{msg}"),
+        other => panic!("expected a config error, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_builder_sets_fields_and_validates() {
+    let marks = PrinterMarks {
+        fold_lines: true,
+        ..Default::default()
+    };
+    let options = ImpositionOptions::builder()
+        .input("a.pdf")
+        .input(PathBuf::from("b.pdf"))
+        .binding(BindingType::CaseBinding)
+        .arrangement(PageArrangement::Octavo)
+        .paper(PaperSize::A3)
+        .sheet_margin_mm(8.0)
+        .leaf_margins(LeafMargins::uniform(3.0))
+        .marks(marks)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        options.input_files,
+        vec![PathBuf::from("a.pdf"), PathBuf::from("b.pdf")]
+    );
+    assert_eq!(options.binding_type, BindingType::CaseBinding);
+    assert_eq!(options.page_arrangement, PageArrangement::Octavo);
+    assert_eq!(options.output_paper_size, PaperSize::A3);
+    assert_eq!(options.margins.sheet, SheetMargins::uniform(8.0));
+    assert_eq!(options.margins.leaf, LeafMargins::uniform(3.0));
+    assert_eq!(options.marks, marks);
+
+    // Everything not set keeps its default
+    assert_eq!(
+        ImpositionOptions {
+            input_files: options.input_files.clone(),
+            binding_type: options.binding_type,
+            page_arrangement: options.page_arrangement,
+            output_paper_size: options.output_paper_size,
+            margins: options.margins,
+            marks: options.marks,
+            ..Default::default()
+        },
+        options
+    );
+}
+
+#[test]
+fn test_builder_build_runs_validation() {
+    let result = ImpositionOptions::builder()
+        .binding(BindingType::Signature)
+        .build();
+    assert!(matches!(result, Err(ImposeError::Config(msg)) if msg.contains("No input files")));
+}
@@ -0,0 +1,93 @@
+#![cfg(feature = "presets")]
+
+use pdf_impose::*;
+use std::path::PathBuf;
+
+#[test]
+fn test_builtin_presets_are_valid_layouts() {
+    let presets = builtin_presets();
+    assert!(!presets.is_empty());
+    for (name, options) in &presets {
+        assert!(options.validate_layout().is_ok(), "{name}");
+        assert!(options.input_files.is_empty(), "{name}");
+    }
+}
+
+#[tokio::test]
+async fn test_preset_store_round_trip() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = PresetStore::new(dir.path().join("presets"));
+    assert!(store.list().await.unwrap().is_empty());
+
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("book.pdf")],
+        input_ranges: vec![Some("1-10".parse().unwrap())],
+        binding_type: BindingType::CaseBinding,
+        page_arrangement: PageArrangement::Octavo,
+        front_flyleaves: 2,
+        ..Default::default()
+    };
+    store.save("My octavo", &options).await.unwrap();
+    store
+        .save("A zine", &ImpositionOptions::default())
+        .await
+        .unwrap();
+    assert_eq!(store.list().await.unwrap(), vec!["A zine", "My octavo"]);
+
+    // The layout comes back without the inputs it was saved with
+    let loaded = store.load("My octavo").await.unwrap();
+    assert_eq!(
+        loaded,
+        ImpositionOptions {
+            input_files: Vec::new(),
+            input_ranges: Vec::new(),
+            ..options
+        }
+    );
+
+    store.delete("My octavo").await.unwrap();
+    assert_eq!(store.list().await.unwrap(), vec!["A zine"]);
+    assert!(matches!(
+        store.delete("My octavo").await,
+        Err(ImposeError::Config(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_preset_store_falls_back_to_builtins() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = PresetStore::new(dir.path());
+
+    let (name, builtin) = builtin_presets().remove(1);
+    assert_eq!(store.load(&name).await.unwrap(), builtin);
+
+    // A saved preset of the same name takes precedence
+    let custom = ImpositionOptions {
+        front_flyleaves: 3,
+        ..builtin
+    };
+    store.save(&name, &custom).await.unwrap();
+    assert_eq!(store.load(&name).await.unwrap(), custom);
+
+    assert!(matches!(
+        store.load("No such preset").await,
+        Err(ImposeError::Config(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_preset_names_must_be_plain_file_names() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = PresetStore::new(dir.path());
+    let options = ImpositionOptions::default();
+
+    for name in ["", "  ", "../escape", "a/b", "a\\b", ".hidden"] {
+        assert!(
+            matches!(
+                store.save(name, &options).await,
+                Err(ImposeError::Config(_))
+            ),
+            "{name:?}"
+        );
+    }
+}
@@ -84,7 +84,7 @@ async fn test_generate_preview_different_sheet_counts() {
     options.input_files.push(PathBuf::from("test.pdf"));
 
     for max_sheets in 1..=5 {
-        let preview = generate_preview(&[doc.clone()], &options, max_sheets).await;
+        let preview = generate_preview(std::slice::from_ref(&doc), &options, max_sheets).await;
         assert!(preview.is_ok(), "Failed with max_sheets: {}", max_sheets);
 
         let output = preview.unwrap();
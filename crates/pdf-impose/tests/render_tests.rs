@@ -0,0 +1,407 @@
+use lopdf::{Dictionary, Document, Object};
+use pdf_impose::*;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+mod common;
+use common::{create_pdf, create_test_pdf, rect};
+
+/// Create a document whose pages are 612x792 with the given `/Rotate`
+fn create_rotated_pdf(num_pages: usize, rotate: i64) -> Document {
+    let mut doc = create_test_pdf(num_pages);
+    for page_id in doc.get_pages().into_values().collect::<Vec<_>>() {
+        doc.get_dictionary_mut(page_id)
+            .unwrap()
+            .set("Rotate", Object::Integer(rotate));
+    }
+    doc
+}
+
+fn first_page_id(doc: &Document) -> lopdf::ObjectId {
+    *doc.get_pages().values().next().unwrap()
+}
+
+fn form_matrix(doc: &Document, xobject_id: lopdf::ObjectId) -> Vec<f32> {
+    let stream = doc.get_object(xobject_id).unwrap().as_stream().unwrap();
+    stream
+        .dict
+        .get(b"Matrix")
+        .unwrap()
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| v.as_float().unwrap())
+        .collect()
+}
+
+#[test]
+fn test_page_dimensions_swap_for_quarter_turns() {
+    for (rotate, expected) in [
+        (0, (612.0, 792.0)),
+        (90, (792.0, 612.0)),
+        (180, (612.0, 792.0)),
+        (270, (792.0, 612.0)),
+        (-90, (792.0, 612.0)),
+        (450, (792.0, 612.0)),
+    ] {
+        let doc = create_rotated_pdf(1, rotate);
+        let dims = get_page_dimensions(&doc, first_page_id(&doc), PageBox::CropBox).unwrap();
+        assert_eq!(dims, expected, "Rotate {rotate}");
+    }
+}
+
+#[test]
+fn test_page_dimensions_reject_empty_box() {
+    let mut doc = create_rotated_pdf(1, 0);
+    let page_id = first_page_id(&doc);
+    let empty = vec![0.into(), 0.into(), 0.into(), 0.into()];
+    doc.get_dictionary_mut(page_id)
+        .unwrap()
+        .set("MediaBox", Object::Array(empty));
+
+    let dims = get_page_dimensions(&doc, page_id, PageBox::CropBox);
+    assert!(matches!(dims, Err(ImposeError::InvalidPageBox(id)) if id == page_id));
+}
+
+#[test]
+fn test_xobject_matrix_makes_rotate_270_upright() {
+    let source = create_rotated_pdf(1, 270);
+    let mut output = Document::with_version("1.7");
+    let xobject_id = create_page_xobject(
+        &mut output,
+        &source,
+        first_page_id(&source),
+        PageBox::CropBox,
+        &mut HashMap::new(),
+    )
+    .unwrap();
+
+    let m = form_matrix(&output, xobject_id);
+    assert_eq!(m, vec![0.0, 1.0, -1.0, 0.0, 792.0, 0.0]);
+
+    // The MediaBox corners land inside the upright 792x612 box
+    let apply = |x: f32, y: f32| (m[0] * x + m[2] * y + m[4], m[1] * x + m[3] * y + m[5]);
+    assert_eq!(apply(0.0, 0.0), (792.0, 0.0));
+    assert_eq!(apply(612.0, 792.0), (0.0, 612.0));
+}
+
+#[test]
+fn test_xobject_without_rotation_has_no_matrix() {
+    let source = create_rotated_pdf(1, 0);
+    let mut output = Document::with_version("1.7");
+    let xobject_id = create_page_xobject(
+        &mut output,
+        &source,
+        first_page_id(&source),
+        PageBox::CropBox,
+        &mut HashMap::new(),
+    )
+    .unwrap();
+
+    let stream = output.get_object(xobject_id).unwrap().as_stream().unwrap();
+    assert!(stream.dict.get(b"Matrix").is_err());
+}
+
+#[tokio::test]
+async fn test_impose_rotated_source_pages() {
+    let mut options = ImpositionOptions::default();
+    options.input_files.push(PathBuf::from("scan.pdf"));
+    options.page_arrangement = PageArrangement::Folio;
+
+    let output = impose(&[create_rotated_pdf(4, 270)], &options)
+        .await
+        .unwrap();
+
+    let forms: Vec<_> = output
+        .objects
+        .iter()
+        .filter_map(|(&id, obj)| {
+            let stream = obj.as_stream().ok()?;
+            (stream.dict.get(b"Subtype").ok()?.as_name().ok()? == b"Form").then_some(id)
+        })
+        .collect();
+
+    assert_eq!(forms.len(), 4);
+    for id in forms {
+        assert_eq!(
+            form_matrix(&output, id),
+            vec![0.0, 1.0, -1.0, 0.0, 792.0, 0.0]
+        );
+    }
+}
+
+/// Create a one-page document with extra entries on the page and on its Pages node
+fn create_boxed_pdf(
+    page_entries: Vec<(&str, Object)>,
+    pages_entries: Vec<(&str, Object)>,
+) -> Document {
+    create_pdf(1, &page_entries, &pages_entries)
+}
+
+#[test]
+fn test_crop_box_with_offset_origin_is_placed() {
+    let source = create_boxed_pdf(
+        vec![
+            ("MediaBox", rect([0, 0, 612, 792])),
+            ("CropBox", rect([36, 36, 576, 756])),
+        ],
+        vec![],
+    );
+    let page_id = first_page_id(&source);
+    assert_eq!(
+        get_page_dimensions(&source, page_id, PageBox::CropBox).unwrap(),
+        (540.0, 720.0)
+    );
+
+    let mut output = Document::with_version("1.7");
+    let xobject_id = create_page_xobject(
+        &mut output,
+        &source,
+        page_id,
+        PageBox::CropBox,
+        &mut HashMap::new(),
+    )
+    .unwrap();
+
+    let stream = output.get_object(xobject_id).unwrap().as_stream().unwrap();
+    assert_eq!(stream.dict.get(b"BBox").unwrap(), &rect([36, 36, 576, 756]));
+    assert_eq!(
+        form_matrix(&output, xobject_id),
+        vec![1.0, 0.0, 0.0, 1.0, -36.0, -36.0]
+    );
+}
+
+#[test]
+fn test_inherited_crop_box_is_used() {
+    let source = create_boxed_pdf(
+        vec![],
+        vec![
+            ("MediaBox", rect([0, 0, 612, 792])),
+            ("CropBox", rect([0, 0, 432, 648])),
+        ],
+    );
+    assert_eq!(
+        get_page_dimensions(&source, first_page_id(&source), PageBox::CropBox).unwrap(),
+        (432.0, 648.0)
+    );
+}
+
+#[test]
+fn test_media_box_used_without_crop_box() {
+    let source = create_boxed_pdf(vec![], vec![("MediaBox", rect([0, 0, 420, 595]))]);
+    assert_eq!(
+        get_page_dimensions(&source, first_page_id(&source), PageBox::CropBox).unwrap(),
+        (420.0, 595.0)
+    );
+}
+
+#[test]
+fn test_xobject_matrix_for_rotate_90() {
+    let source = create_rotated_pdf(1, 90);
+    let mut output = Document::with_version("1.7");
+    let xobject_id = create_page_xobject(
+        &mut output,
+        &source,
+        first_page_id(&source),
+        PageBox::CropBox,
+        &mut HashMap::new(),
+    )
+    .unwrap();
+
+    // [cos -sin sin cos tx ty] for a 90° clockwise turn, shifted back into view
+    assert_eq!(
+        form_matrix(&output, xobject_id),
+        vec![0.0, -1.0, 1.0, 0.0, 0.0, 612.0]
+    );
+}
+
+#[test]
+fn test_rotate_inherited_from_pages_node() {
+    let source = create_boxed_pdf(
+        vec![("MediaBox", rect([0, 0, 612, 792]))],
+        vec![("Rotate", Object::Integer(90))],
+    );
+    let page_id = first_page_id(&source);
+    assert_eq!(
+        get_page_dimensions(&source, page_id, PageBox::CropBox).unwrap(),
+        (792.0, 612.0)
+    );
+
+    let mut output = Document::with_version("1.7");
+    let xobject_id = create_page_xobject(
+        &mut output,
+        &source,
+        page_id,
+        PageBox::CropBox,
+        &mut HashMap::new(),
+    )
+    .unwrap();
+    assert_eq!(
+        form_matrix(&output, xobject_id),
+        vec![0.0, -1.0, 1.0, 0.0, 0.0, 612.0]
+    );
+}
+
+/// Page tree where MediaBox, Resources and Rotate live only on the Pages node
+fn create_inherited_attributes_pdf() -> Document {
+    let resources = Dictionary::from_iter(vec![(
+        "Font",
+        Object::Dictionary(Dictionary::from_iter(vec![(
+            "F1",
+            Object::Dictionary(Dictionary::from_iter(vec![
+                ("Type", Object::Name(b"Font".to_vec())),
+                ("Subtype", Object::Name(b"Type1".to_vec())),
+                ("BaseFont", Object::Name(b"Helvetica".to_vec())),
+            ])),
+        )])),
+    )]);
+    create_boxed_pdf(
+        vec![],
+        vec![
+            ("MediaBox", rect([0, 0, 420, 595])),
+            ("Resources", Object::Dictionary(resources)),
+            ("Rotate", Object::Integer(180)),
+        ],
+    )
+}
+
+#[test]
+fn test_xobject_uses_inherited_attributes() {
+    let source = create_inherited_attributes_pdf();
+    let page_id = first_page_id(&source);
+    assert_eq!(
+        get_page_dimensions(&source, page_id, PageBox::CropBox).unwrap(),
+        (420.0, 595.0)
+    );
+
+    let mut output = Document::with_version("1.7");
+    let xobject_id = create_page_xobject(
+        &mut output,
+        &source,
+        page_id,
+        PageBox::CropBox,
+        &mut HashMap::new(),
+    )
+    .unwrap();
+
+    let stream = output.get_object(xobject_id).unwrap().as_stream().unwrap();
+    assert_eq!(stream.dict.get(b"BBox").unwrap(), &rect([0, 0, 420, 595]));
+    let resources = stream.dict.get(b"Resources").unwrap().as_dict().unwrap();
+    assert!(
+        resources
+            .get(b"Font")
+            .unwrap()
+            .as_dict()
+            .unwrap()
+            .has(b"F1")
+    );
+    assert_eq!(
+        form_matrix(&output, xobject_id),
+        vec![-1.0, 0.0, 0.0, -1.0, 420.0, 595.0]
+    );
+}
+
+#[tokio::test]
+async fn test_impose_with_inherited_attributes_and_flyleaves() {
+    let options = ImpositionOptions {
+        input_files: vec![PathBuf::from("inherited.pdf")],
+        page_arrangement: PageArrangement::Folio,
+        front_flyleaves: 1,
+        ..Default::default()
+    };
+
+    let output = impose(&[create_inherited_attributes_pdf()], &options)
+        .await
+        .unwrap();
+
+    // 1 source page + 2 flyleaf pages pad to one folio sheet
+    assert_eq!(output.get_pages().len(), 2);
+}
+
+fn slug_pdf(rotate: i64) -> Document {
+    create_boxed_pdf(
+        vec![
+            ("MediaBox", rect([0, 0, 700, 900])),
+            ("CropBox", rect([20, 20, 680, 880])),
+            ("TrimBox", rect([50, 60, 470, 655])),
+            ("BleedBox", rect([41, 51, 479, 664])),
+            ("Rotate", Object::Integer(rotate)),
+        ],
+        vec![],
+    )
+}
+
+#[test]
+fn test_page_dimensions_for_each_source_box() {
+    let source = slug_pdf(0);
+    let page_id = first_page_id(&source);
+    for (page_box, expected) in [
+        (PageBox::MediaBox, (700.0, 900.0)),
+        (PageBox::CropBox, (660.0, 860.0)),
+        (PageBox::TrimBox, (420.0, 595.0)),
+        (PageBox::BleedBox, (438.0, 613.0)),
+    ] {
+        assert_eq!(
+            get_page_dimensions(&source, page_id, page_box).unwrap(),
+            expected,
+            "{page_box:?}"
+        );
+    }
+}
+
+#[test]
+fn test_trim_box_falls_back_to_crop_box() {
+    let source = create_boxed_pdf(
+        vec![
+            ("MediaBox", rect([0, 0, 700, 900])),
+            ("CropBox", rect([20, 20, 680, 880])),
+        ],
+        vec![],
+    );
+    assert_eq!(
+        get_page_dimensions(&source, first_page_id(&source), PageBox::TrimBox).unwrap(),
+        (660.0, 860.0)
+    );
+}
+
+#[test]
+fn test_trim_box_with_offset_origin_maps_to_origin() {
+    let source = slug_pdf(0);
+    let mut output = Document::with_version("1.7");
+    let xobject_id = create_page_xobject(
+        &mut output,
+        &source,
+        first_page_id(&source),
+        PageBox::TrimBox,
+        &mut HashMap::new(),
+    )
+    .unwrap();
+
+    let stream = output.get_object(xobject_id).unwrap().as_stream().unwrap();
+    assert_eq!(stream.dict.get(b"BBox").unwrap(), &rect([50, 60, 470, 655]));
+
+    let m = form_matrix(&output, xobject_id);
+    let apply = |x: f32, y: f32| (m[0] * x + m[2] * y + m[4], m[1] * x + m[3] * y + m[5]);
+    assert_eq!(apply(50.0, 60.0), (0.0, 0.0));
+    assert_eq!(apply(470.0, 655.0), (420.0, 595.0));
+}
+
+#[test]
+fn test_rotated_trim_box_with_offset_origin_maps_to_origin() {
+    let source = slug_pdf(90);
+    let mut output = Document::with_version("1.7");
+    let xobject_id = create_page_xobject(
+        &mut output,
+        &source,
+        first_page_id(&source),
+        PageBox::TrimBox,
+        &mut HashMap::new(),
+    )
+    .unwrap();
+
+    // Shown upright as a 595x420 page: the trim corners land on its corners
+    let m = form_matrix(&output, xobject_id);
+    let apply = |x: f32, y: f32| (m[0] * x + m[2] * y + m[4], m[1] * x + m[3] * y + m[5]);
+    assert_eq!(apply(50.0, 60.0), (0.0, 420.0));
+    assert_eq!(apply(470.0, 655.0), (595.0, 0.0));
+}
@@ -77,10 +77,10 @@ fn test_stats_quarto_signature() {
     // 10 pages padded to 16 (2 signatures of 8 pages each)
     assert_eq!(stats.blank_pages_added, 6);
     assert_eq!(stats.signatures, Some(2));
-    // 16 pages / 4 pages per sheet = 4 sheets
-    assert_eq!(stats.output_sheets, 4);
-    // 4 sheets * 2 sides = 8 output pages
-    assert_eq!(stats.output_pages, 8);
+    // One sheet per signature
+    assert_eq!(stats.output_sheets, 2);
+    // 2 sheets * 2 sides = 4 output pages
+    assert_eq!(stats.output_pages, 4);
 }
 
 #[test]
@@ -117,10 +117,203 @@ fn test_stats_octavo_signature() {
     // 20 pages padded to 32 (2 signatures of 16 pages each)
     assert_eq!(stats.blank_pages_added, 12);
     assert_eq!(stats.signatures, Some(2));
-    // 32 pages / 4 pages per sheet = 8 sheets
-    assert_eq!(stats.output_sheets, 8);
-    // 8 sheets * 2 sides = 16 output pages
-    assert_eq!(stats.output_pages, 16);
+    // One sheet per signature
+    assert_eq!(stats.output_sheets, 2);
+    // 2 sheets * 2 sides = 4 output pages
+    assert_eq!(stats.output_pages, 4);
+}
+
+#[test]
+fn test_stats_cut_and_stack() {
+    let mut options = ImpositionOptions::default();
+    options.input_files.push("test.pdf".into());
+    options.binding_type = BindingType::CutAndStack;
+    options.page_arrangement = PageArrangement::Folio;
+
+    // 10 pages 2-up: two stacks of 3 leaves, the last leaf blank
+    let stats = calculate_statistics(&[create_test_document(10)], &options).unwrap();
+    assert_eq!(stats.output_sheets, 3);
+    assert_eq!(stats.output_pages, 6);
+    assert_eq!(stats.stack_height, Some(3));
+    assert_eq!(stats.blank_pages_added, 2);
+    assert_eq!(stats.signatures, None);
+
+    // 4-up: 10 pages fill two sheets, four stacks of 2 leaves
+    options.page_arrangement = PageArrangement::Quarto;
+    let stats = calculate_statistics(&[create_test_document(10)], &options).unwrap();
+    assert_eq!(stats.output_sheets, 2);
+    assert_eq!(stats.stack_height, Some(2));
+    assert_eq!(stats.blank_pages_added, 6);
+}
+
+#[test]
+fn test_stats_step_and_repeat() {
+    let mut options = ImpositionOptions::default();
+    options.input_files.push("test.pdf".into());
+    options.step_and_repeat = Some(StepAndRepeat {
+        cols: 2,
+        rows: 5,
+        copies: Some(25),
+    });
+
+    // 25 cards at 10 per sheet need 3 sheets for each of the 3 pages
+    let stats = calculate_statistics(&[create_test_document(3)], &options).unwrap();
+    assert_eq!(stats.output_sheets, 9);
+    assert_eq!(stats.output_pages, 9);
+    assert_eq!(stats.blank_pages_added, 0);
+
+    // Without a copy count, one full sheet per page
+    options.step_and_repeat = Some(StepAndRepeat {
+        cols: 2,
+        rows: 5,
+        copies: None,
+    });
+    let stats = calculate_statistics(&[create_test_document(3)], &options).unwrap();
+    assert_eq!(stats.output_sheets, 3);
+}
+
+#[test]
+fn test_stats_short_last_signature() {
+    let mut options = ImpositionOptions {
+        input_files: vec!["test.pdf".into()],
+        page_arrangement: PageArrangement::Quarto,
+        allow_short_last_signature: true,
+        ..Default::default()
+    };
+
+    // 52 pages: six quartos and a folio, no blanks
+    let stats = calculate_statistics(&[create_test_document(52)], &options).unwrap();
+    assert_eq!(stats.signatures, Some(7));
+    assert_eq!(stats.pages_per_signature, Some(vec![8, 8, 8, 8, 8, 8, 4]));
+    assert_eq!(stats.blank_pages_added, 0);
+    assert_eq!(stats.output_sheets, 7);
+
+    // 50 pages still round up to the folio
+    let stats = calculate_statistics(&[create_test_document(50)], &options).unwrap();
+    assert_eq!(stats.blank_pages_added, 2);
+
+    options.allow_short_last_signature = false;
+    let stats = calculate_statistics(&[create_test_document(52)], &options).unwrap();
+    assert_eq!(stats.pages_per_signature, Some(vec![8; 7]));
+    assert_eq!(stats.blank_pages_added, 4);
+}
+
+#[test]
+fn test_stats_variable_last_signature() {
+    let options = ImpositionOptions {
+        input_files: vec!["test.pdf".into()],
+        page_arrangement: PageArrangement::Octavo,
+        variable_last_signature: true,
+        ..Default::default()
+    };
+
+    let stats = calculate_statistics(&[create_test_document(20)], &options).unwrap();
+    assert_eq!(stats.pages_per_signature, Some(vec![16, 4]));
+    assert_eq!(stats.blank_pages_added, 0);
+
+    let stats = calculate_statistics(&[create_test_document(33)], &options).unwrap();
+    assert_eq!(stats.pages_per_signature, Some(vec![16, 16, 4]));
+    assert_eq!(stats.blank_pages_added, 3);
+
+    // A 12-page remainder becomes nested folios rather than a padded octavo
+    let stats = calculate_statistics(&[create_test_document(27)], &options).unwrap();
+    assert_eq!(stats.pages_per_signature, Some(vec![16, 12]));
+    assert_eq!(stats.blank_pages_added, 1);
+    assert_eq!(stats.output_sheets, 1 + 3);
+}
+
+#[test]
+fn test_stats_signature_plan() {
+    let mut options = ImpositionOptions {
+        input_files: vec!["test.pdf".into()],
+        page_arrangement: PageArrangement::Quarto,
+        signature_plan: Some(SignaturePlan {
+            pages_per_signature: vec![8, 8, 8, 4],
+        }),
+        ..Default::default()
+    };
+
+    // 26 pages pad to 28, filling the plan exactly
+    let stats = calculate_statistics(&[create_test_document(26)], &options).unwrap();
+    assert_eq!(stats.pages_per_signature, Some(vec![8, 8, 8, 4]));
+    assert_eq!(stats.blank_pages_added, 2);
+    assert_eq!(stats.output_sheets, 4);
+
+    // 16 pages only need the first two signatures
+    let stats = calculate_statistics(&[create_test_document(16)], &options).unwrap();
+    assert_eq!(stats.pages_per_signature, Some(vec![8, 8]));
+
+    // The plan must cover the padded page count
+    options.signature_plan = Some(SignaturePlan {
+        pages_per_signature: vec![8, 4],
+    });
+    let result = calculate_statistics(&[create_test_document(13)], &options);
+    assert!(matches!(result, Err(ImposeError::Config(_))));
+}
+
+/// Signature stats for `pages` source pages in the given arrangement
+fn signature_stats(pages: usize, arrangement: PageArrangement) -> ImpositionStatistics {
+    let options = ImpositionOptions {
+        input_files: vec!["test.pdf".into()],
+        binding_type: BindingType::Signature,
+        page_arrangement: arrangement,
+        ..Default::default()
+    };
+    calculate_statistics(&[create_test_document(pages)], &options).unwrap()
+}
+
+#[test]
+fn test_stats_awkward_page_counts() {
+    // (pages, arrangement, padded, signatures, sheets)
+    let cases = [
+        (7, PageArrangement::Folio, 8, 2, 2),
+        (7, PageArrangement::Quarto, 8, 1, 1),
+        (7, PageArrangement::Octavo, 16, 1, 1),
+        (33, PageArrangement::Folio, 36, 9, 9),
+        (33, PageArrangement::Quarto, 40, 5, 5),
+        (33, PageArrangement::Octavo, 48, 3, 3),
+    ];
+
+    for (pages, arrangement, padded, signatures, sheets) in cases {
+        let stats = signature_stats(pages, arrangement);
+        let case = format!("{pages} pages, {arrangement:?}");
+        assert_eq!(stats.source_pages, pages, "{case}");
+        assert_eq!(stats.padded_pages(), padded, "{case}");
+        assert_eq!(stats.blank_pages_added, padded - pages, "{case}");
+        assert_eq!(stats.signatures, Some(signatures), "{case}");
+        assert_eq!(
+            stats.pages_per_signature,
+            Some(vec![arrangement.pages_per_signature(); signatures]),
+            "{case}"
+        );
+        assert_eq!(stats.output_sheets, sheets, "{case}");
+        assert_eq!(stats.output_pages, sheets * 2, "{case}");
+    }
+}
+
+#[tokio::test]
+async fn test_stats_match_imposed_output() {
+    for arrangement in [
+        PageArrangement::Folio,
+        PageArrangement::Quarto,
+        PageArrangement::Octavo,
+    ] {
+        let options = ImpositionOptions {
+            input_files: vec!["test.pdf".into()],
+            page_arrangement: arrangement,
+            ..Default::default()
+        };
+        let documents = [create_test_document(33)];
+
+        let stats = calculate_statistics(&documents, &options).unwrap();
+        let imposed = impose(&documents, &options).await.unwrap();
+
+        assert_eq!(
+            imposed.get_pages().len(),
+            stats.output_pages,
+            "{arrangement:?}"
+        );
+    }
 }
 
 #[test]
@@ -139,7 +332,7 @@ fn test_stats_custom_signature() {
     // 15 pages padded to 24 (2 signatures of 12 pages each)
     assert_eq!(stats.blank_pages_added, 9);
     assert_eq!(stats.signatures, Some(2));
-    // 24 pages / 4 pages per sheet = 6 sheets
+    // Three nested folio sheets per signature
     assert_eq!(stats.output_sheets, 6);
     // 6 sheets * 2 sides = 12 output pages
     assert_eq!(stats.output_pages, 12);
@@ -164,6 +357,22 @@ fn test_stats_perfect_binding() {
     assert_eq!(stats.output_pages, 12);
 }
 
+#[test]
+fn test_stats_with_page_ranges() {
+    let docs = [create_test_document(20), create_test_document(6)];
+    let options = ImpositionOptions {
+        input_files: vec!["a.pdf".into(), "b.pdf".into()],
+        binding_type: BindingType::PerfectBinding,
+        input_ranges: vec![Some("5-20".parse().unwrap()), None],
+        ..Default::default()
+    };
+
+    let stats = calculate_statistics(&docs, &options).unwrap();
+
+    // 16 selected pages from the first file plus all 6 of the second
+    assert_eq!(stats.source_pages, 22);
+}
+
 #[test]
 fn test_stats_with_flyleaves() {
     let doc = create_test_document(10);
@@ -182,8 +391,8 @@ fn test_stats_with_flyleaves() {
     // 18 pages padded to 24 (3 signatures of 8 pages each)
     assert_eq!(stats.blank_pages_added, 6);
     assert_eq!(stats.signatures, Some(3));
-    assert_eq!(stats.output_sheets, 6); // 3 signatures * 2 sheets per signature
-    assert_eq!(stats.output_pages, 12); // 6 sheets * 2 sides
+    assert_eq!(stats.output_sheets, 3); // 3 signatures * 1 sheet per signature
+    assert_eq!(stats.output_pages, 6); // 3 sheets * 2 sides
 }
 
 #[test]
@@ -200,8 +409,8 @@ fn test_stats_exact_signature_fit() {
     // Perfect fit, no padding needed
     assert_eq!(stats.blank_pages_added, 0);
     assert_eq!(stats.signatures, Some(1));
-    assert_eq!(stats.output_sheets, 4);
-    assert_eq!(stats.output_pages, 8);
+    assert_eq!(stats.output_sheets, 1);
+    assert_eq!(stats.output_pages, 2);
 }
 
 #[test]
@@ -235,3 +444,218 @@ fn test_stats_spiral() {
     assert_eq!(stats.output_sheets, 3);
     assert_eq!(stats.output_pages, 6);
 }
+
+#[test]
+fn test_stats_bleed_fits_margins() {
+    let doc = create_test_document(16);
+    let mut options = ImpositionOptions::default();
+    options.input_files.push("test.pdf".into());
+    options.binding_type = BindingType::Signature;
+    options.page_arrangement = PageArrangement::Octavo;
+    options.margins.leaf.fore_edge_mm = 5.0;
+    options.margins.leaf.cut_mm = 5.0;
+
+    // No bleed always fits
+    let stats = calculate_statistics(std::slice::from_ref(&doc), &options).unwrap();
+    assert!(stats.bleed_fits);
+
+    options.bleed_mm = 3.0;
+    let stats = calculate_statistics(std::slice::from_ref(&doc), &options).unwrap();
+    assert!(stats.bleed_fits);
+
+    // Wider than the cut margin: spills into the neighbouring page
+    options.margins.leaf.cut_mm = 2.0;
+    let stats = calculate_statistics(std::slice::from_ref(&doc), &options).unwrap();
+    assert!(!stats.bleed_fits);
+
+    // Wider than the fore-edge margin
+    options.margins.leaf.cut_mm = 5.0;
+    options.margins.leaf.fore_edge_mm = 2.0;
+    let stats = calculate_statistics(&[doc], &options).unwrap();
+    assert!(!stats.bleed_fits);
+}
+
+#[test]
+fn test_stats_count_inserted_blanks() {
+    let doc = create_test_document(6);
+    let mut options = ImpositionOptions::default();
+    options.input_files.push("test.pdf".into());
+    options.page_arrangement = PageArrangement::Folio;
+    options.blank_inserts = vec![1, 3];
+
+    // 6 pages + 2 inserted blanks fill two folios exactly
+    let stats = calculate_statistics(&[doc], &options).unwrap();
+    assert_eq!(stats.source_pages, 6);
+    assert_eq!(stats.signatures, Some(2));
+    assert_eq!(stats.blank_pages_added, 2);
+}
+
+#[test]
+fn test_stats_leave_cover_pages_out_of_the_body() {
+    let doc = create_test_document(12);
+    let mut options = ImpositionOptions::default();
+    options.input_files.push("test.pdf".into());
+    options.page_arrangement = PageArrangement::Folio;
+    options.cover.front_pages = 2;
+    options.cover.back_pages = 2;
+
+    let stats = calculate_statistics(std::slice::from_ref(&doc), &options).unwrap();
+    assert_eq!(stats.source_pages, 8);
+    assert_eq!(stats.signatures, Some(2));
+    assert_eq!(stats.cover_sheets, 0);
+
+    options.cover.mode = CoverMode::SeparateDocument;
+    let stats = calculate_statistics(&[doc], &options).unwrap();
+    assert_eq!(stats.cover_sheets, 1);
+}
+
+#[test]
+fn test_stats_count_chapter_start_blanks() {
+    let options = ImpositionOptions {
+        input_files: vec!["test.pdf".into()],
+        page_arrangement: PageArrangement::Folio,
+        blank_inserts: vec![0],
+        chapter_starts_recto: vec![2, 4],
+        ..Default::default()
+    };
+
+    // The requested blank pushes page 3 onto a verso; page 5 then lands on a recto
+    let stats = calculate_statistics(&[create_test_document(6)], &options).unwrap();
+    assert_eq!(stats.source_pages, 6);
+    assert_eq!(stats.blank_pages_added, 2);
+}
+
+#[test]
+fn test_stats_count_chapter_recto_blanks() {
+    let options = ImpositionOptions {
+        input_files: vec!["a.pdf".into(), "b.pdf".into(), "c.pdf".into()],
+        page_arrangement: PageArrangement::Folio,
+        chapter_recto: true,
+        ..Default::default()
+    };
+
+    // 3 + 4 + 2 pages: only the second file needs a blank in front of it
+    let documents = [
+        create_test_document(3),
+        create_test_document(4),
+        create_test_document(2),
+    ];
+    let stats = calculate_statistics(&documents, &options).unwrap();
+    assert_eq!(stats.source_pages, 9);
+    assert_eq!(stats.blank_pages_added, 3);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_stats_serialize_every_field() {
+    let doc = create_test_document(10);
+    let mut options = ImpositionOptions::default();
+    options.input_files.push("test.pdf".into());
+    options.page_arrangement = PageArrangement::Quarto;
+
+    let stats = calculate_statistics(&[doc], &options).unwrap();
+    let json = serde_json::to_value(&stats).unwrap();
+    assert_eq!(json["source_pages"], 10);
+    assert_eq!(json["output_sheets"], 2);
+    assert_eq!(json["signatures"], 2);
+    assert_eq!(json["pages_per_signature"], serde_json::json!([8, 8]));
+    assert_eq!(json["output_pages"], 4);
+    assert_eq!(json["blank_pages_added"], 6);
+
+    let round_trip: ImpositionStatistics = serde_json::from_value(json).unwrap();
+    assert_eq!(round_trip, stats);
+}
+
+#[test]
+fn test_stats_octavo_on_a3_print_shop_figures() {
+    let mut options = ImpositionOptions {
+        input_files: vec!["test.pdf".into()],
+        binding_type: BindingType::Signature,
+        page_arrangement: PageArrangement::Octavo,
+        output_paper_size: PaperSize::A3,
+        output_orientation: Orientation::Landscape,
+        ..Default::default()
+    };
+    options.cover.paper_thickness_mm = 0.1;
+
+    let stats = calculate_statistics(&[create_test_document(70)], &options).unwrap();
+    // 70 pages pad out to five 16-page signatures, one sheet each
+    assert_eq!(stats.signatures, Some(5));
+    assert_eq!(stats.paper_sheets, 5);
+    assert_eq!(stats.blank_pages_per_signature, Some(vec![0, 0, 0, 0, 10]));
+    // 80 pages make 40 leaves
+    assert!((stats.spine_thickness_mm.unwrap() - 4.0).abs() < 0.001);
+    // A 4 × 2 grid inside 5mm sheet margins on 420 × 297mm
+    let (width, height) = stats.trim_size_mm;
+    assert!((width - 102.5).abs() < 0.01);
+    assert!((height - 143.5).abs() < 0.01);
+}
+
+#[test]
+fn test_stats_blanks_per_signature_counts_inserted_blanks() {
+    let options = ImpositionOptions {
+        input_files: vec!["test.pdf".into()],
+        binding_type: BindingType::Signature,
+        page_arrangement: PageArrangement::Octavo,
+        output_paper_size: PaperSize::A3,
+        blank_inserts: vec![20],
+        front_flyleaves: 1,
+        ..Default::default()
+    };
+
+    let stats = calculate_statistics(&[create_test_document(70)], &options).unwrap();
+    // Flyleaf pages come first, so the blank lands at book page 22; the
+    // remaining 7 blanks pad out the last signature
+    assert_eq!(stats.blank_pages_added, 8);
+    assert_eq!(stats.blank_pages_per_signature, Some(vec![0, 1, 0, 0, 7]));
+    assert_eq!(
+        stats
+            .blank_pages_per_signature
+            .unwrap()
+            .iter()
+            .sum::<usize>(),
+        stats.blank_pages_added
+    );
+}
+
+#[test]
+fn test_stats_n_up_has_no_spine() {
+    let options = ImpositionOptions {
+        input_files: vec!["test.pdf".into()],
+        binding_type: BindingType::NUp { rows: 2, cols: 2 },
+        ..Default::default()
+    };
+
+    let stats = calculate_statistics(&[create_test_document(6)], &options).unwrap();
+    assert_eq!(stats.paper_sheets, 2);
+    assert_eq!(stats.spine_thickness_mm, None);
+    assert_eq!(stats.blank_pages_per_signature, None);
+}
+
+#[test]
+fn test_stats_for_page_count_match_loaded_documents() {
+    let mut options = ImpositionOptions {
+        input_files: vec!["test.pdf".into()],
+        binding_type: BindingType::Signature,
+        page_arrangement: PageArrangement::Quarto,
+        front_flyleaves: 1,
+        blank_inserts: vec![3],
+        ..Default::default()
+    };
+    options.cover.front_pages = 1;
+
+    for pages in [1, 9, 30] {
+        assert_eq!(
+            calculate_statistics_for_pages(pages, &options).unwrap(),
+            calculate_statistics(&[create_test_document(pages)], &options).unwrap(),
+            "{pages} pages"
+        );
+    }
+
+    // No inputs are needed, but there must be something to impose
+    options.front_flyleaves = 0;
+    assert!(matches!(
+        calculate_statistics_for_pages(1, &options),
+        Err(ImposeError::NoPages)
+    ));
+}
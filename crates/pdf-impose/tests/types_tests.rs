@@ -73,4 +73,84 @@ fn test_printer_marks_default() {
     assert!(!marks.registration_marks);
     assert!(!marks.cut_lines);
     assert!(!marks.trim_marks);
+    assert!(!marks.collation_marks);
+    assert!(marks.sewing_marks.is_none());
+}
+
+#[test]
+fn test_printer_marks_sets() {
+    let guide = PrinterMarks::folding_guide();
+    assert!(guide.fold_lines && guide.cut_lines);
+    assert_eq!(
+        guide,
+        PrinterMarks {
+            fold_lines: true,
+            cut_lines: true,
+            ..Default::default()
+        }
+    );
+    assert!((guide.min_sheet_margin_mm() - 11.0 * 25.4 / 72.0).abs() < 0.001);
+
+    let shop = PrinterMarks::print_shop();
+    assert!(shop.crop_marks && shop.trim_marks && shop.registration_marks && shop.color_bars);
+    assert!(!shop.fold_lines && !shop.cut_lines && !shop.collation_marks);
+    assert!(shop.sewing_marks.is_none());
+}
+
+#[test]
+fn test_printer_marks_min_sheet_margin() {
+    let mut marks = PrinterMarks::default();
+    assert_eq!(marks.min_sheet_margin_mm(), 0.0);
+
+    // Fold lines stay inside the leaf area
+    marks.fold_lines = true;
+    assert_eq!(marks.min_sheet_margin_mm(), 0.0);
+
+    // Crop marks sit on the trimmed sheet, outside the sheet margin
+    marks.crop_marks = true;
+    assert_eq!(marks.min_sheet_margin_mm(), 0.0);
+
+    // Trim marks reach gap + length past the leaf, registration marks further
+    marks.trim_marks = true;
+    assert!((marks.min_sheet_margin_mm() - 15.0 * 25.4 / 72.0).abs() < 0.001);
+    marks.registration_marks = true;
+    assert!((marks.min_sheet_margin_mm() - 18.0 * 25.4 / 72.0).abs() < 0.001);
+
+    // Longer trim marks need more room
+    marks.style.mark_length_pt = 30.0;
+    assert!((marks.min_sheet_margin_mm() - 33.0 * 25.4 / 72.0).abs() < 0.001);
+}
+
+#[test]
+fn test_page_range_parse_and_select() {
+    let range: PageRange = "1-4,7,10-".parse().unwrap();
+    assert_eq!(range.select(12), vec![0, 1, 2, 3, 6, 9, 10, 11]);
+    assert_eq!(range.count(12), 8);
+    assert_eq!(range.to_string(), "1-4,7,10-");
+
+    // Pages past the end of the document are skipped
+    assert_eq!(range.select(5), vec![0, 1, 2, 3]);
+}
+
+#[test]
+fn test_page_range_exceeds() {
+    let range: PageRange = "3-10,15".parse().unwrap();
+    assert!(!range.exceeds(15));
+    assert!(range.exceeds(12));
+
+    // An open-ended span only exceeds the document when it starts past the end
+    let open: PageRange = "5-".parse().unwrap();
+    assert!(!open.exceeds(5));
+    assert!(open.exceeds(4));
+}
+
+#[test]
+fn test_page_range_parse_errors() {
+    for bad in ["", "0", "4-2", "a-3", "1,,2", "-3"] {
+        assert!(
+            bad.parse::<PageRange>().is_err(),
+            "{bad:?} should not parse"
+        );
+    }
+    assert!(" 2 - 5 ".parse::<PageRange>().is_ok());
 }
@@ -0,0 +1,153 @@
+//! Rendering PDFs to raster images with PDFium
+
+use pdfium_render::prelude::*;
+use std::path::{Path, PathBuf};
+
+/// Points per inch, the unit PDF page sizes are given in
+const POINTS_PER_INCH: f32 = 72.0;
+
+#[derive(Debug, thiserror::Error)]
+pub enum RenderError {
+    #[error("PDFium error: {0}")]
+    Pdfium(#[from] PdfiumError),
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to write {}: {message}", path.display())]
+    Image { path: PathBuf, message: String },
+    #[error("Resolution must be a finite number above 0 DPI, got {0}")]
+    InvalidDpi(f32),
+}
+
+pub type Result<T> = std::result::Result<T, RenderError>;
+
+/// Initialize Pdfium, trying the vendored library first, then falling back to system
+pub fn init_pdfium() -> std::result::Result<Pdfium, PdfiumError> {
+    // Try to load from vendor directory (relative to workspace root)
+    // When running from cargo, the working directory is the workspace root
+    let vendor_path = std::env::current_dir().ok().and_then(|mut p| {
+        p.push("vendor/pdfium/lib");
+        if p.exists() { Some(p) } else { None }
+    });
+
+    if let Some(vendor_path) = vendor_path
+        && let Ok(binding) =
+            Pdfium::bind_to_library(Pdfium::pdfium_platform_library_name_at_path(&vendor_path))
+    {
+        return Ok(Pdfium::new(binding));
+    }
+
+    // Fallback to system library or default search paths
+    Pdfium::bind_to_system_library().map(Pdfium::new)
+}
+
+/// File name for page `page_index`: `page_001.png`, `page_002.png`, ...,
+/// or, for a `double_sided` imposition whose pages alternate front and back,
+/// `sheet_001_front.png`, `sheet_001_back.png`, ...
+pub fn sheet_image_name(page_index: usize, double_sided: bool) -> String {
+    if !double_sided {
+        return format!("page_{:03}.png", page_index + 1);
+    }
+    let side = if page_index.is_multiple_of(2) {
+        "front"
+    } else {
+        "back"
+    };
+    format!("sheet_{:03}_{side}.png", page_index / 2 + 1)
+}
+
+/// Render every page of `doc` at `dpi` and write each to `out_dir` as a PNG
+/// named by [`sheet_image_name`], creating the folder if needed
+///
+/// `double_sided` names the pages by sheet and side, for imposed PDFs with
+/// fronts and backs interleaved.
+///
+/// `on_page` is called with the number of pages written so far and the
+/// total after each one. Returns the paths written, in page order.
+pub fn export_sheets_as_images(
+    doc: &Path,
+    dpi: f32,
+    double_sided: bool,
+    out_dir: &Path,
+    mut on_page: impl FnMut(usize, usize),
+) -> Result<Vec<PathBuf>> {
+    if !dpi.is_finite() || dpi <= 0.0 {
+        return Err(RenderError::InvalidDpi(dpi));
+    }
+    std::fs::create_dir_all(out_dir)?;
+
+    let pdfium = init_pdfium()?;
+    let document = pdfium.load_pdf_from_file(doc, None)?;
+    let pages = document.pages();
+    let total = pages.len() as usize;
+    // Printers want an opaque sheet, so render onto white and drop the alpha
+    let config = PdfRenderConfig::new()
+        .scale_page_by_factor(dpi / POINTS_PER_INCH)
+        .set_clear_color(PdfColor::WHITE);
+
+    let mut written = Vec::with_capacity(total);
+    for (page_index, page) in pages.iter().enumerate() {
+        let bitmap = page.render_with_config(&config)?;
+        let path = out_dir.join(sheet_image_name(page_index, double_sided));
+        let image_error = |message: String| RenderError::Image {
+            path: path.clone(),
+            message,
+        };
+        let rgba = image::RgbaImage::from_raw(
+            bitmap.width() as u32,
+            bitmap.height() as u32,
+            bitmap.as_rgba_bytes(),
+        )
+        .ok_or_else(|| image_error("rendered bitmap has the wrong size".to_string()))?;
+        image::DynamicImage::ImageRgba8(rgba)
+            .to_rgb8()
+            .save_with_format(&path, image::ImageFormat::Png)
+            .map_err(|e| image_error(e.to_string()))?;
+
+        written.push(path);
+        on_page(page_index + 1, total);
+    }
+
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sheet_image_names_alternate_front_and_back() {
+        let names: Vec<String> = (0..5).map(|idx| sheet_image_name(idx, true)).collect();
+        assert_eq!(
+            names,
+            [
+                "sheet_001_front.png",
+                "sheet_001_back.png",
+                "sheet_002_front.png",
+                "sheet_002_back.png",
+                "sheet_003_front.png",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_single_sided_image_names_number_pages() {
+        let names: Vec<String> = (0..3).map(|idx| sheet_image_name(idx, false)).collect();
+        assert_eq!(names, ["page_001.png", "page_002.png", "page_003.png"]);
+    }
+
+    #[test]
+    fn test_export_rejects_zero_dpi() {
+        let dir = tempfile::tempdir().unwrap();
+        let result =
+            export_sheets_as_images(&dir.path().join("in.pdf"), 0.0, true, dir.path(), |_, _| {});
+        assert!(matches!(result, Err(RenderError::InvalidDpi(_))));
+    }
+
+    #[test]
+    fn test_export_rejects_infinite_dpi() {
+        let dir = tempfile::tempdir().unwrap();
+        let in_pdf = dir.path().join("in.pdf");
+        let result = export_sheets_as_images(&in_pdf, f32::INFINITY, true, dir.path(), |_, _| {});
+        assert!(matches!(result, Err(RenderError::InvalidDpi(_))));
+    }
+}
@@ -1,6 +1,8 @@
-use anyhow::Result;
-use clap::{Parser, Subcommand, ValueEnum};
-use std::path::PathBuf;
+use anyhow::{Result, bail};
+use clap::parser::ValueSource;
+use clap::{ArgMatches, CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
 
 #[derive(Parser)]
 #[command(name = "pdft", about = "PDF tools CLI", version)]
@@ -10,6 +12,7 @@ struct Cli {
 }
 
 #[derive(Subcommand)]
+#[allow(clippy::large_enum_variant)] // parsed once at startup
 enum Commands {
     /// Generate flashcard PDF from CSV
     Flashcards {
@@ -39,27 +42,131 @@ enum Commands {
     },
 
     /// Impose PDF pages for bookbinding
+    #[command(subcommand_negates_reqs = true, args_conflicts_with_subcommands = true)]
     Impose {
-        /// Input PDF file(s) - can specify multiple
-        #[arg(short, long, required = true, num_args = 1..)]
+        #[command(subcommand)]
+        action: Option<ImposeAction>,
+
+        /// Input PDF file(s) - can specify multiple; append ":RANGE" to use
+        /// only some pages, e.g. book.pdf:3-10,15. JPEG/PNG files and folders
+        /// of them are read as pages of --fallback-page-size
+        #[arg(short, long, required_unless_present = "dummy", num_args = 1..)]
         input: Vec<PathBuf>,
 
         /// Output PDF file
-        #[arg(short, long)]
-        output: PathBuf,
+        #[arg(short, long, required = true)]
+        output: Option<PathBuf>,
+
+        /// Page range for each input, in order, e.g. "1-4,7,10-" ("all" keeps every page)
+        #[arg(long = "pages")]
+        pages: Vec<String>,
+
+        /// Password for encrypted input files (asked for on the terminal
+        /// when needed and not given)
+        #[arg(long)]
+        password: Option<String>,
+
+        /// Insert a blank page before each of these pages (1-based, counted in
+        /// the selected pages), e.g. to start a chapter on a recto
+        #[arg(long, value_name = "PAGES", value_delimiter = ',', value_parser = parse_page_number)]
+        blank_before: Vec<usize>,
+
+        /// Reverse the page order, for sources scanned last page first
+        /// (flyleaves stay at the front and back)
+        #[arg(long)]
+        reverse: bool,
+
+        /// Start each input file on a recto, inserting a blank before any
+        /// file that would otherwise begin on a verso
+        #[arg(long)]
+        chapter_recto: bool,
+
+        /// Start these pages (1-based, counted in the selected pages) on a
+        /// recto, inserting a blank before any that would fall on a verso
+        #[arg(long, value_name = "PAGES", value_delimiter = ',', value_parser = parse_page_number)]
+        recto_pages: Vec<usize>,
+
+        /// Load options from a JSON preset; flags given on the command line
+        /// override its fields
+        #[arg(long, value_name = "FILE")]
+        config: Option<PathBuf>,
+
+        /// Save the effective options (preset plus flags) as JSON
+        #[arg(long, value_name = "FILE")]
+        save_config: Option<PathBuf>,
+
+        /// Start from a named preset (see `pdft impose presets list`); flags
+        /// given on the command line override its fields
+        #[arg(long, value_name = "NAME", conflicts_with = "config")]
+        preset: Option<String>,
+
+        /// Save the effective options as a named preset
+        #[arg(long, value_name = "NAME")]
+        save_preset: Option<String>,
 
         /// Binding type
         #[arg(long, default_value = "signature", value_enum)]
         binding: BindingArg,
 
-        /// Page arrangement (pages per signature)
+        /// Rows of pages per sheet with --binding nup
+        #[arg(long, default_value = "3")]
+        rows: usize,
+
+        /// Columns of pages per sheet with --binding nup
+        #[arg(long, default_value = "3")]
+        columns: usize,
+
+        /// Order pages fill the cells with --binding nup
+        #[arg(long, default_value = "row-major", value_enum)]
+        nup_order: NUpOrderArg,
+
+        /// Side the spine is on ("right" for right-to-left books)
+        #[arg(long, default_value = "left", value_enum)]
+        binding_edge: BindingEdgeArg,
+
+        /// Which way the spine fold runs across the sheet ("auto" makes it
+        /// horizontal for a quarto on a landscape sheet; "horizontal" is
+        /// quarto only)
+        #[arg(long, default_value = "auto", value_enum)]
+        spine_edge: SpineEdgeArg,
+
+        /// Page arrangement (pages per signature; folio, quarto and octavo
+        /// are 2-up, 4-up and 8-up for cut-stack)
         #[arg(long, default_value = "folio", value_enum)]
         arrangement: ArrangementArg,
 
-        /// Output paper size
+        /// Let the last signature be a smaller folio or quarto instead of
+        /// padding it with blank pages
+        #[arg(long)]
+        short_last_signature: bool,
+
+        /// Like --short-last-signature, but fall back to nested folios so the
+        /// job never needs more than three blank pages
+        #[arg(long, conflicts_with = "signature_plan")]
+        variable_last_signature: bool,
+
+        /// Pages in each signature, in order (e.g. 12,12,12,12,12,4); the
+        /// arrangement still sets the sheet grid
+        #[arg(
+            long,
+            value_name = "PAGES,...",
+            value_delimiter = ',',
+            conflicts_with = "short_last_signature"
+        )]
+        signature_plan: Option<Vec<usize>>,
+
+        /// Output paper size ("custom" takes --paper-width and --paper-height)
         #[arg(long, default_value = "letter", value_enum)]
         paper: PaperArg,
 
+        /// Custom paper width in mm
+        #[arg(long)]
+        paper_width: Option<f32>,
+
+        /// Custom paper height in mm
+        #[arg(long)]
+        paper_height: Option<f32>,
+
         /// Output orientation
         #[arg(long, default_value = "landscape", value_enum)]
         orientation: OrientationArg,
@@ -68,10 +175,75 @@ enum Commands {
         #[arg(long, default_value = "double-sided", value_enum)]
         format: FormatArg,
 
-        /// Scaling mode
+        /// Order of the backs for single-sided and two-sided output
+        #[arg(long, default_value = "reversed", value_enum)]
+        back_order: BackOrderArg,
+
+        /// Edge the printer flips duplex sheets on ("short-edge" turns the
+        /// backs half a turn)
+        #[arg(long, default_value = "long-edge", value_enum)]
+        duplex_flip: FlipEdgeArg,
+
+        /// Scaling mode ("fit-no-upscale" fits without enlarging pages)
         #[arg(long, default_value = "fit", value_enum)]
         scaling: ScalingArg,
 
+        /// Scale pages by this percentage of their size (with --scaling percent)
+        #[arg(long, value_name = "PERCENT")]
+        scale_percent: Option<f32>,
+
+        /// Horizontal position of pages that don't fill their leaf
+        #[arg(long, default_value = "spine", value_enum)]
+        align_x: AlignXArg,
+
+        /// Vertical position of pages that don't fill their leaf
+        #[arg(long, default_value = "fold", value_enum)]
+        align_y: AlignYArg,
+
+        /// Source page box to place on the sheet
+        #[arg(long, default_value = "crop", value_enum)]
+        source_box: SourceBoxArg,
+
+        /// Size to impose pages at whose page box is empty or invalid, and
+        /// of the pages image inputs are placed on
+        #[arg(long, default_value = "letter", value_enum)]
+        fallback_page_size: PaperArg,
+
+        /// Turn every source page clockwise before placing it
+        #[arg(long, value_name = "DEGREES", default_value = "0", value_parser = parse_rotation)]
+        rotate_source: pdf_impose::Rotation,
+
+        /// Turn pages 90° when that lets them fit their leaf at a larger
+        /// scale, e.g. landscape pages in a portrait book
+        #[arg(long)]
+        auto_rotate: bool,
+
+        /// Print book page numbers on each placed page
+        #[arg(long)]
+        page_numbers: bool,
+
+        /// First page number
+        #[arg(long, default_value = "1")]
+        page_number_start: usize,
+
+        /// Page number font size in points
+        #[arg(long, default_value = "8.0")]
+        page_number_size: f32,
+
+        /// Put page numbers in the header or footer
+        #[arg(long, default_value = "footer", value_enum)]
+        page_number_position: PageNumberPositionArg,
+
+        /// Align page numbers to the inner or outer edge, or center them
+        #[arg(long, default_value = "outer", value_enum)]
+        page_number_align: PageNumberAlignArg,
+
+        /// Print a folding dummy: each page shows its book page number,
+        /// signature and an up arrow instead of its content. Without
+        /// --input, --pages gives the dummy's page count
+        #[arg(long)]
+        dummy: bool,
+
         /// Number of blank pages at front
         #[arg(long, default_value = "0")]
         front_flyleaves: usize,
@@ -80,15 +252,44 @@ enum Commands {
         #[arg(long, default_value = "0")]
         back_flyleaves: usize,
 
+        /// Source pages at the start that form the front cover and are kept
+        /// out of the body
+        #[arg(long, value_name = "PAGES", default_value = "0")]
+        cover_front: usize,
+
+        /// Source pages at the end that form the back cover and are kept out
+        /// of the body
+        #[arg(long, value_name = "PAGES", default_value = "0")]
+        cover_back: usize,
+
+        /// Drop the cover pages, or impose them as a wraparound cover written
+        /// to a separate `_cover` file
+        #[arg(long, default_value = "skip", value_enum)]
+        cover_mode: CoverModeArg,
+
+        /// Thickness of one leaf of body paper in mm, for the cover's spine width
+        #[arg(long, value_name = "MM", default_value = "0.1")]
+        paper_thickness: f32,
+
+        /// Use a set of printer's marks in place of the individual mark
+        /// flags
+        #[arg(long, value_name = "SET")]
+        marks: Option<MarksArg>,
+
         /// Add fold lines
         #[arg(long)]
         fold_lines: bool,
 
+        /// Label fold lines with their folding order and direction
+        /// (mountain or valley)
+        #[arg(long, requires = "fold_lines")]
+        fold_labels: bool,
+
         /// Add cut lines
         #[arg(long)]
         cut_lines: bool,
 
-        /// Add crop marks (at sheet edges)
+        /// Add crop marks (at the corners of the sheet less the bleed)
         #[arg(long)]
         crop_marks: bool,
 
@@ -100,33 +301,287 @@ enum Commands {
         #[arg(long)]
         registration_marks: bool,
 
+        /// Add collation marks (back-step blocks on the spine fold)
+        #[arg(long)]
+        collation_marks: bool,
+
+        /// Add a CMYK and RGB color bar in the bottom sheet margin, if it fits
+        #[arg(long)]
+        color_bars: bool,
+
+        /// Print each cell's page number (or BLANK) faintly at its center,
+        /// for proofing the imposition
+        #[arg(long)]
+        slot_labels: bool,
+
+        /// Add sewing station ticks on the spine fold (number of stations)
+        #[arg(long)]
+        sewing_stations: Option<u8>,
+
+        /// Distance in mm from head and tail to the outermost sewing stations
+        #[arg(long, default_value = "15.0")]
+        sewing_margin: f32,
+
+        /// Add ticks continuing the spine fold past its head and tail
+        #[arg(long)]
+        spine_marks: bool,
+
+        /// Add punch-hole guides on the binding edge (number of holes;
+        /// side stitch and spiral binding only)
+        #[arg(long, conflicts_with = "punch_pitch")]
+        punch_holes: Option<u8>,
+
+        /// Add punch-hole guides spaced this many mm apart
+        #[arg(long)]
+        punch_pitch: Option<f32>,
+
+        /// Distance in mm from the binding edge to the hole centers
+        #[arg(long, default_value = "5.0")]
+        punch_offset: f32,
+
+        /// Punch hole diameter in mm
+        #[arg(long, default_value = "4.0")]
+        punch_diameter: f32,
+
         /// Sheet margin in mm (uniform on all sides)
         #[arg(long, default_value = "5.0")]
         sheet_margin: f32,
 
-        /// Leaf spine/gutter margin in mm (inner edge near binding)
-        #[arg(long, default_value = "0.0")]
+        /// Sheet margin at the top edge in mm (overrides --sheet-margin)
+        #[arg(long)]
+        sheet_margin_top: Option<f32>,
+
+        /// Sheet margin at the bottom edge in mm (overrides --sheet-margin)
+        #[arg(long)]
+        sheet_margin_bottom: Option<f32>,
+
+        /// Sheet margin at the left edge of the front side in mm (overrides
+        /// --sheet-margin; back sides swap left and right)
+        #[arg(long)]
+        sheet_margin_left: Option<f32>,
+
+        /// Sheet margin at the right edge of the front side in mm (overrides
+        /// --sheet-margin; back sides swap left and right)
+        #[arg(long)]
+        sheet_margin_right: Option<f32>,
+
+        /// Leaf spine/gutter margin in mm (inner edge, beside the binding)
+        #[arg(long, visible_alias = "spine", default_value = "0.0")]
         leaf_spine_margin: f32,
 
-        /// Leaf fore-edge margin in mm (outer edge)
-        #[arg(long, default_value = "0.0")]
+        /// Leaf fore-edge margin in mm (outer edge, opposite the spine)
+        #[arg(long, visible_alias = "fore-edge", default_value = "0.0")]
         leaf_fore_edge_margin: f32,
 
-        /// Leaf top margin in mm
-        #[arg(long, default_value = "0.0")]
+        /// Leaf head margin in mm (top edge of each page)
+        #[arg(long, visible_alias = "head", default_value = "0.0")]
         leaf_top_margin: f32,
 
-        /// Leaf bottom margin in mm
-        #[arg(long, default_value = "0.0")]
+        /// Leaf tail margin in mm (bottom edge of each page)
+        #[arg(long, visible_alias = "tail", default_value = "0.0")]
         leaf_bottom_margin: f32,
 
-        /// Leaf cut margin in mm (space around cut lines)
-        #[arg(long, default_value = "0.0")]
+        /// Leaf cut margin in mm (edges along cut lines between pages)
+        #[arg(long, visible_alias = "cut", default_value = "0.0")]
         leaf_cut_margin: f32,
 
+        /// Creep compensation per nested leaf in mm (about the paper thickness)
+        #[arg(long, default_value = "0.0")]
+        creep: f32,
+
+        /// Bleed past the trim line on outer and cut edges in mm
+        #[arg(long, default_value = "0.0")]
+        bleed: f32,
+
+        /// Gutter between cells in mm for grids that are cut apart (nup,
+        /// cut-stack, --repeat)
+        #[arg(long, default_value = "0.0")]
+        cell_gap: f32,
+
+        /// Frame each placed page with a thin border (e.g. to set handout
+        /// slides apart)
+        #[arg(long)]
+        cell_borders: bool,
+
+        /// Shift back sides by X,Y mm to line them up with the fronts when the
+        /// printer's duplex feed drifts (e.g. -1.2,0 moves backs 1.2mm left)
+        #[arg(long, value_name = "X,Y", value_parser = parse_offset, allow_hyphen_values = true)]
+        back_offset: Option<(f32, f32)>,
+
+        /// Split output into files of at most N pages (at sheet boundaries)
+        #[arg(long, conflicts_with_all = ["split_sheets", "split_signatures"])]
+        split_pages: Option<usize>,
+
+        /// Split output into files of N sheets each
+        #[arg(long, conflicts_with = "split_signatures")]
+        split_sheets: Option<usize>,
+
+        /// Split output into files of N signatures each
+        #[arg(long)]
+        split_signatures: Option<usize>,
+
+        /// Repeat each page across a COLSxROWS grid (e.g. 2x5 for business
+        /// cards) instead of binding
+        #[arg(long, value_name = "COLSxROWS", value_parser = parse_repeat_grid)]
+        repeat: Option<(usize, usize)>,
+
+        /// Copies wanted of each page with --repeat (default: one full sheet)
+        #[arg(long, requires = "repeat")]
+        copies: Option<usize>,
+
+        /// Write uncompressed content streams
+        #[arg(long)]
+        no_compress: bool,
+
+        /// Title for the output's document properties (default: the first
+        /// input's title)
+        #[arg(long)]
+        title: Option<String>,
+
+        /// Leave out the outline of signatures and sheets
+        #[arg(long)]
+        no_outline: bool,
+
+        /// Add where each input bookmark's page landed to its title
+        /// (e.g. "p. 57 → sheet 8 back, slot 3")
+        #[arg(long)]
+        bookmark_locations: bool,
+
         /// Show statistics only, don't generate PDF
         #[arg(long)]
         stats_only: bool,
+
+        /// With --stats-only, print the statistics as JSON
+        #[arg(long, requires = "stats_only")]
+        json: bool,
+
+        /// Write where every page lands (sheet, side, cell, rotation, scale)
+        /// as JSON to FILE instead of generating the PDF
+        #[arg(long, value_name = "FILE")]
+        plan_json: Option<PathBuf>,
+
+        /// Report per-page layout decisions, such as pages turned by
+        /// --auto-rotate
+        #[arg(short, long)]
+        verbose: bool,
+    },
+
+    /// Lay pages out N-up in sequence, with no binding (e.g. slides for a
+    /// handout)
+    Nup {
+        /// Input PDF file(s), laid out in the order given; JPEG/PNG files and
+        /// folders of them are read as Letter pages
+        #[arg(short, long, required = true, num_args = 1..)]
+        input: Vec<PathBuf>,
+
+        /// Output PDF file
+        #[arg(short, long)]
+        output: PathBuf,
+
+        /// Rows of pages per sheet
+        #[arg(long, default_value = "2")]
+        rows: usize,
+
+        /// Columns of pages per sheet
+        #[arg(long, visible_alias = "cols", default_value = "3")]
+        columns: usize,
+
+        /// Order pages fill the cells
+        #[arg(long, default_value = "row-major", value_enum)]
+        order: NUpOrderArg,
+
+        /// Frame each page with a thin border
+        #[arg(long)]
+        borders: bool,
+
+        /// Output paper size ("custom" takes --paper-width and --paper-height)
+        #[arg(long, default_value = "letter", value_enum)]
+        paper: PaperArg,
+
+        /// Custom paper width in mm
+        #[arg(long)]
+        paper_width: Option<f32>,
+
+        /// Custom paper height in mm
+        #[arg(long)]
+        paper_height: Option<f32>,
+
+        /// Output orientation
+        #[arg(long, default_value = "landscape", value_enum)]
+        orientation: OrientationArg,
+
+        /// Gutter between cells in mm
+        #[arg(long, default_value = "0.0")]
+        cell_gap: f32,
+    },
+
+    /// Concatenate PDF files without imposing
+    Merge {
+        /// Input PDF files, merged in the order given
+        #[arg(short, long, required = true, num_args = 1..)]
+        input: Vec<PathBuf>,
+
+        /// Output PDF file
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+
+    /// Render each page of a PDF to a PNG (page_001.png, ..., or
+    /// sheet_001_front.png, sheet_001_back.png, ... with --double-sided),
+    /// for printers that handle images better than PDFs
+    #[cfg(feature = "rasterize")]
+    Rasterize {
+        /// PDF to render, usually an imposed one
+        #[arg(short, long)]
+        input: PathBuf,
+
+        /// Folder to write the images to, created if missing
+        #[arg(short, long)]
+        output: PathBuf,
+
+        /// Resolution to render at
+        #[arg(long, default_value = "300")]
+        dpi: f32,
+
+        /// Name the images by sheet and side, for imposed PDFs with fronts
+        /// and backs interleaved as the default double-sided output is
+        #[arg(long)]
+        double_sided: bool,
+    },
+
+    /// Print a shell completion script to stdout
+    Completions {
+        /// Shell to generate completions for
+        #[arg(value_enum)]
+        shell: clap_complete::Shell,
+    },
+}
+
+#[derive(Subcommand)]
+enum ImposeAction {
+    /// Manage named imposition presets
+    Presets {
+        #[command(subcommand)]
+        command: PresetsCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum PresetsCommand {
+    /// List the bundled presets and the saved ones
+    List,
+    /// Save the options in a JSON config file as a named preset
+    Save {
+        /// Name to save the preset under
+        name: String,
+        /// Config file to take the options from (as written by --save-config)
+        #[arg(long, value_name = "FILE")]
+        config: PathBuf,
+    },
+    /// Delete a saved preset
+    Delete {
+        /// Name of the preset to delete
+        name: String,
     },
 }
 
@@ -137,6 +592,30 @@ enum BindingArg {
     SideStitch,
     Spiral,
     Case,
+    CutStack,
+    /// Plain N-up in reading order (see --rows and --columns)
+    Nup,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum NUpOrderArg {
+    /// Left to right along each row, rows top to bottom
+    RowMajor,
+    /// Top to bottom down each column, columns left to right
+    ColumnMajor,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum BindingEdgeArg {
+    Left,
+    Right,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum SpineEdgeArg {
+    Auto,
+    Vertical,
+    Horizontal,
 }
 
 #[derive(Clone, Copy, ValueEnum)]
@@ -144,6 +623,8 @@ enum ArrangementArg {
     Folio,
     Quarto,
     Octavo,
+    Sexto,
+    Duodecimo,
 }
 
 #[derive(Clone, Copy, ValueEnum)]
@@ -154,6 +635,7 @@ enum PaperArg {
     Letter,
     Legal,
     Tabloid,
+    Custom,
 }
 
 #[derive(Clone, Copy, ValueEnum)]
@@ -163,28 +645,131 @@ enum OrientationArg {
 }
 
 #[derive(Clone, Copy, ValueEnum)]
+#[allow(clippy::enum_variant_names)]
 enum FormatArg {
     DoubleSided,
     TwoSided,
     SingleSided,
 }
 
+#[derive(Clone, Copy, ValueEnum)]
+enum BackOrderArg {
+    Normal,
+    Reversed,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum FlipEdgeArg {
+    LongEdge,
+    ShortEdge,
+}
+
 #[derive(Clone, Copy, ValueEnum)]
 enum ScalingArg {
     Fit,
+    FitNoUpscale,
     Fill,
     None,
     Stretch,
+    Percent,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum CoverModeArg {
+    Skip,
+    Separate,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum SourceBoxArg {
+    Media,
+    Crop,
+    Trim,
+    Bleed,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum AlignXArg {
+    Spine,
+    Center,
+    ForeEdge,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum AlignYArg {
+    Fold,
+    Top,
+    Center,
+    Bottom,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum MarksArg {
+    /// Fold and cut lines
+    FoldingGuide,
+    /// Crop, trim and registration marks with color bars
+    PrintShop,
+    /// Every mark but punch guides
+    All,
+    /// No marks
+    None,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum PageNumberPositionArg {
+    Header,
+    Footer,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum PageNumberAlignArg {
+    Inner,
+    Outer,
+    Center,
+}
+
+impl BindingArg {
+    /// The binding type, with the grid for N-up
+    fn binding_type(self, rows: usize, cols: usize) -> pdf_impose::BindingType {
+        use pdf_impose::BindingType;
+        match self {
+            BindingArg::Signature => BindingType::Signature,
+            BindingArg::Perfect => BindingType::PerfectBinding,
+            BindingArg::SideStitch => BindingType::SideStitch,
+            BindingArg::Spiral => BindingType::Spiral,
+            BindingArg::Case => BindingType::CaseBinding,
+            BindingArg::CutStack => BindingType::CutAndStack,
+            BindingArg::Nup => BindingType::NUp { rows, cols },
+        }
+    }
+}
+
+impl From<MarksArg> for pdf_impose::PrinterMarks {
+    fn from(arg: MarksArg) -> Self {
+        match arg {
+            MarksArg::FoldingGuide => Self::folding_guide(),
+            MarksArg::PrintShop => Self::print_shop(),
+            MarksArg::All => Self::all(),
+            MarksArg::None => Self::default(),
+        }
+    }
+}
+
+impl From<BindingEdgeArg> for pdf_impose::BindingEdge {
+    fn from(arg: BindingEdgeArg) -> Self {
+        match arg {
+            BindingEdgeArg::Left => Self::Left,
+            BindingEdgeArg::Right => Self::Right,
+        }
+    }
 }
 
-impl From<BindingArg> for pdf_impose::BindingType {
-    fn from(arg: BindingArg) -> Self {
+impl From<SpineEdgeArg> for pdf_impose::SpineEdge {
+    fn from(arg: SpineEdgeArg) -> Self {
         match arg {
-            BindingArg::Signature => Self::Signature,
-            BindingArg::Perfect => Self::PerfectBinding,
-            BindingArg::SideStitch => Self::SideStitch,
-            BindingArg::Spiral => Self::Spiral,
-            BindingArg::Case => Self::CaseBinding,
+            SpineEdgeArg::Auto => Self::Auto,
+            SpineEdgeArg::Vertical => Self::Vertical,
+            SpineEdgeArg::Horizontal => Self::Horizontal,
         }
     }
 }
@@ -195,19 +780,55 @@ impl From<ArrangementArg> for pdf_impose::PageArrangement {
             ArrangementArg::Folio => Self::Folio,
             ArrangementArg::Quarto => Self::Quarto,
             ArrangementArg::Octavo => Self::Octavo,
+            ArrangementArg::Sexto => Self::Sexto,
+            ArrangementArg::Duodecimo => Self::Duodecimo,
         }
     }
 }
 
-impl From<PaperArg> for pdf_impose::PaperSize {
-    fn from(arg: PaperArg) -> Self {
+/// Paper size from `--paper`, with the dimensions `--paper custom` needs
+fn paper_size(
+    paper: PaperArg,
+    width_mm: Option<f32>,
+    height_mm: Option<f32>,
+) -> Result<pdf_impose::PaperSize> {
+    use pdf_impose::PaperSize;
+
+    Ok(match (paper, width_mm, height_mm) {
+        (PaperArg::Custom, Some(width_mm), Some(height_mm)) => PaperSize::Custom {
+            width_mm,
+            height_mm,
+        },
+        (PaperArg::Custom, _, _) => {
+            bail!("--paper custom needs both --paper-width and --paper-height")
+        }
+        (_, None, None) => match paper {
+            PaperArg::A3 => PaperSize::A3,
+            PaperArg::A4 => PaperSize::A4,
+            PaperArg::A5 => PaperSize::A5,
+            PaperArg::Letter => PaperSize::Letter,
+            PaperArg::Legal => PaperSize::Legal,
+            PaperArg::Tabloid => PaperSize::Tabloid,
+            PaperArg::Custom => unreachable!(),
+        },
+        _ => bail!("--paper-width and --paper-height only apply to --paper custom"),
+    })
+}
+
+/// Paper size for display, e.g. "A4 (210 × 297 mm)"
+fn describe_paper(paper: pdf_impose::PaperSize) -> String {
+    let (width, height) = paper.dimensions_mm();
+    match paper {
+        pdf_impose::PaperSize::Custom { .. } => format!("custom ({width} × {height} mm)"),
+        paper => format!("{paper:?} ({width} × {height} mm)"),
+    }
+}
+
+impl From<NUpOrderArg> for pdf_impose::NUpOrder {
+    fn from(arg: NUpOrderArg) -> Self {
         match arg {
-            PaperArg::A3 => Self::A3,
-            PaperArg::A4 => Self::A4,
-            PaperArg::A5 => Self::A5,
-            PaperArg::Letter => Self::Letter,
-            PaperArg::Legal => Self::Legal,
-            PaperArg::Tabloid => Self::Tabloid,
+            NUpOrderArg::RowMajor => Self::RowMajor,
+            NUpOrderArg::ColumnMajor => Self::ColumnMajor,
         }
     }
 }
@@ -231,22 +852,470 @@ impl From<FormatArg> for pdf_impose::OutputFormat {
     }
 }
 
-impl From<ScalingArg> for pdf_impose::ScalingMode {
-    fn from(arg: ScalingArg) -> Self {
+impl From<BackOrderArg> for pdf_impose::BackOrder {
+    fn from(arg: BackOrderArg) -> Self {
+        match arg {
+            BackOrderArg::Normal => Self::Normal,
+            BackOrderArg::Reversed => Self::Reversed,
+        }
+    }
+}
+
+impl From<FlipEdgeArg> for pdf_impose::FlipEdge {
+    fn from(arg: FlipEdgeArg) -> Self {
+        match arg {
+            FlipEdgeArg::LongEdge => Self::LongEdge,
+            FlipEdgeArg::ShortEdge => Self::ShortEdge,
+        }
+    }
+}
+
+/// Scaling mode from `--scaling`, with the percentage `--scaling percent` needs
+fn scaling_mode(scaling: ScalingArg, percent: Option<f32>) -> Result<pdf_impose::ScalingMode> {
+    use pdf_impose::ScalingMode;
+
+    Ok(match (scaling, percent) {
+        (ScalingArg::Percent, Some(percent)) => ScalingMode::Percent(percent),
+        (ScalingArg::Percent, None) => bail!("--scaling percent needs --scale-percent"),
+        (_, None) => match scaling {
+            ScalingArg::Fit => ScalingMode::Fit,
+            ScalingArg::FitNoUpscale => ScalingMode::FitNoUpscale,
+            ScalingArg::Fill => ScalingMode::Fill,
+            ScalingArg::None => ScalingMode::None,
+            ScalingArg::Stretch => ScalingMode::Stretch,
+            ScalingArg::Percent => unreachable!(),
+        },
+        _ => bail!("--scale-percent only applies to --scaling percent"),
+    })
+}
+
+impl From<CoverModeArg> for pdf_impose::CoverMode {
+    fn from(arg: CoverModeArg) -> Self {
+        match arg {
+            CoverModeArg::Skip => Self::Skip,
+            CoverModeArg::Separate => Self::SeparateDocument,
+        }
+    }
+}
+
+impl From<SourceBoxArg> for pdf_impose::PageBox {
+    fn from(arg: SourceBoxArg) -> Self {
+        match arg {
+            SourceBoxArg::Media => Self::MediaBox,
+            SourceBoxArg::Crop => Self::CropBox,
+            SourceBoxArg::Trim => Self::TrimBox,
+            SourceBoxArg::Bleed => Self::BleedBox,
+        }
+    }
+}
+
+impl From<AlignXArg> for pdf_impose::HorizontalAlignment {
+    fn from(arg: AlignXArg) -> Self {
+        match arg {
+            AlignXArg::Spine => Self::TowardSpine,
+            AlignXArg::Center => Self::Center,
+            AlignXArg::ForeEdge => Self::TowardForeEdge,
+        }
+    }
+}
+
+impl From<AlignYArg> for pdf_impose::VerticalAlignment {
+    fn from(arg: AlignYArg) -> Self {
+        match arg {
+            AlignYArg::Fold => Self::TowardFold,
+            AlignYArg::Top => Self::Top,
+            AlignYArg::Center => Self::Center,
+            AlignYArg::Bottom => Self::Bottom,
+        }
+    }
+}
+
+impl From<PageNumberPositionArg> for pdf_impose::PageNumberVertical {
+    fn from(arg: PageNumberPositionArg) -> Self {
+        match arg {
+            PageNumberPositionArg::Header => Self::Header,
+            PageNumberPositionArg::Footer => Self::Footer,
+        }
+    }
+}
+
+impl From<PageNumberAlignArg> for pdf_impose::PageNumberHorizontal {
+    fn from(arg: PageNumberAlignArg) -> Self {
         match arg {
-            ScalingArg::Fit => Self::Fit,
-            ScalingArg::Fill => Self::Fill,
-            ScalingArg::None => Self::None,
-            ScalingArg::Stretch => Self::Stretch,
+            PageNumberAlignArg::Inner => Self::Inner,
+            PageNumberAlignArg::Outer => Self::Outer,
+            PageNumberAlignArg::Center => Self::Center,
+        }
+    }
+}
+
+/// Split an input like "book.pdf:3-10,15" into the file and its page range
+///
+/// A path that exists as given, or whose suffix isn't a page range, is kept
+/// whole so file names containing ':' still work.
+fn split_input_spec(spec: &Path) -> (PathBuf, Option<pdf_impose::PageRange>) {
+    if !spec.exists()
+        && let Some((path, range)) = spec.to_str().and_then(|s| s.rsplit_once(':'))
+        && let Ok(range) = range.parse()
+    {
+        return (PathBuf::from(path), Some(range));
+    }
+    (spec.to_path_buf(), None)
+}
+
+/// Parse a "COLSxROWS" grid such as "2x5"
+fn parse_repeat_grid(spec: &str) -> std::result::Result<(usize, usize), String> {
+    let (cols, rows) = spec
+        .split_once(['x', 'X'])
+        .ok_or_else(|| format!("expected COLSxROWS, got '{spec}'"))?;
+    let parse = |n: &str| {
+        n.trim()
+            .parse::<usize>()
+            .ok()
+            .filter(|&n| n > 0)
+            .ok_or_else(|| format!("'{n}' is not a positive number"))
+    };
+    Ok((parse(cols)?, parse(rows)?))
+}
+
+/// Parse a 1-based page number
+fn parse_page_number(page: &str) -> std::result::Result<usize, String> {
+    page.trim()
+        .parse::<usize>()
+        .ok()
+        .filter(|&page| page > 0)
+        .ok_or_else(|| format!("'{page}' is not a page number"))
+}
+
+/// Parse a clockwise rotation of 0, 90, 180 or 270 degrees
+fn parse_rotation(degrees: &str) -> std::result::Result<pdf_impose::Rotation, String> {
+    match degrees.trim() {
+        "0" => Ok(pdf_impose::Rotation::None),
+        "90" => Ok(pdf_impose::Rotation::Clockwise90),
+        "180" => Ok(pdf_impose::Rotation::Clockwise180),
+        "270" => Ok(pdf_impose::Rotation::Clockwise270),
+        _ => Err(format!("'{degrees}' is not one of 0, 90, 180 or 270")),
+    }
+}
+
+/// Parse an "X,Y" offset in mm such as "-1.2,0"
+fn parse_offset(spec: &str) -> std::result::Result<(f32, f32), String> {
+    let (x, y) = spec
+        .split_once(',')
+        .ok_or_else(|| format!("expected X,Y, got '{spec}'"))?;
+    let parse = |n: &str| {
+        n.trim()
+            .parse::<f32>()
+            .ok()
+            .filter(|n| n.is_finite())
+            .ok_or_else(|| format!("'{n}' is not a number"))
+    };
+    Ok((parse(x)?, parse(y)?))
+}
+
+/// Print the warnings an imposition reports along the way
+fn print_warning(stage: pdf_impose::ImposeProgress) {
+    if let pdf_impose::ImposeProgress::Warning(warning) = stage {
+        eprintln!("Warning: {warning}");
+    }
+}
+
+/// Ask for the password of an encrypted input on the terminal
+///
+/// The password is read as a plain line, so it shows as it is typed.
+fn prompt_password() -> Result<String> {
+    eprint!("Password: ");
+    std::io::stderr().flush()?;
+    let mut password = String::new();
+    std::io::stdin().read_line(&mut password)?;
+    Ok(password.trim_end_matches(['\r', '\n']).to_string())
+}
+
+/// Run `pdft impose presets ...`
+async fn run_presets_command(command: PresetsCommand) -> Result<()> {
+    let store = pdf_impose::PresetStore::open_default()?;
+    match command {
+        PresetsCommand::List => {
+            println!("Bundled presets:");
+            for (name, _) in pdf_impose::builtin_presets() {
+                println!("  {}", name);
+            }
+            let saved = store.list().await?;
+            println!("Saved presets ({}):", store.dir().display());
+            if saved.is_empty() {
+                println!("  (none)");
+            }
+            for name in saved {
+                println!("  {}", name);
+            }
+        }
+        PresetsCommand::Save { name, config } => {
+            let options = pdf_impose::ImpositionOptions::load(&config).await?;
+            options.validate_layout()?;
+            store.save(&name, &options).await?;
+            println!("Saved preset \"{}\"", name);
+        }
+        PresetsCommand::Delete { name } => {
+            store.delete(&name).await?;
+            println!("Deleted preset \"{}\"", name);
+        }
+    }
+    Ok(())
+}
+
+/// Copy the fields set by flags given on the command line from `flags`
+/// onto options loaded from a preset
+fn apply_explicit_flags(
+    options: &mut pdf_impose::ImpositionOptions,
+    flags: pdf_impose::ImpositionOptions,
+    matches: Option<&ArgMatches>,
+) {
+    let explicit =
+        |id: &str| matches.is_some_and(|m| m.value_source(id) == Some(ValueSource::CommandLine));
+    let explicit_mm = |id: &str| {
+        matches
+            .filter(|_| explicit(id))
+            .and_then(|m| m.get_one::<f32>(id).copied())
+    };
+
+    // Inputs always come from the command line
+    options.input_files = flags.input_files;
+    if explicit("pages") || flags.input_ranges.iter().any(Option::is_some) {
+        options.input_ranges = flags.input_ranges;
+    }
+    // Passwords are never saved with a configuration
+    options.password = flags.password;
+    options.reverse_source |= flags.reverse_source;
+    options.chapter_recto |= flags.chapter_recto;
+    if explicit("recto_pages") {
+        options.chapter_starts_recto = flags.chapter_starts_recto;
+    }
+    if explicit("blank_before") {
+        options.blank_inserts = flags.blank_inserts;
+    }
+
+    if explicit("binding") {
+        options.binding_type = flags.binding_type;
+    } else if let pdf_impose::BindingType::NUp { rows, cols } = &mut options.binding_type {
+        // Resize a preset's N-up grid without repeating --binding
+        let explicit_count = |id: &str| {
+            matches
+                .filter(|_| explicit(id))
+                .and_then(|m| m.get_one::<usize>(id).copied())
+        };
+        *rows = explicit_count("rows").unwrap_or(*rows);
+        *cols = explicit_count("columns").unwrap_or(*cols);
+    }
+    if explicit("nup_order") {
+        options.n_up_order = flags.n_up_order;
+    }
+    if explicit("binding_edge") {
+        options.binding_edge = flags.binding_edge;
+    }
+    if explicit("spine_edge") {
+        options.spine_edge = flags.spine_edge;
+    }
+    if explicit("arrangement") {
+        options.page_arrangement = flags.page_arrangement;
+    }
+    if explicit("short_last_signature") {
+        options.allow_short_last_signature = flags.allow_short_last_signature;
+    }
+    if explicit("variable_last_signature") {
+        options.variable_last_signature = flags.variable_last_signature;
+    }
+    if explicit("signature_plan") {
+        options.signature_plan = flags.signature_plan;
+    }
+    if explicit("repeat") || explicit("copies") {
+        options.step_and_repeat = flags.step_and_repeat;
+    }
+    if explicit("paper") {
+        options.output_paper_size = flags.output_paper_size;
+    }
+    if explicit("orientation") {
+        options.output_orientation = flags.output_orientation;
+    }
+    if explicit("format") {
+        options.output_format = flags.output_format;
+    }
+    if explicit("back_order") {
+        options.single_sided_back_order = flags.single_sided_back_order;
+    }
+    if explicit("duplex_flip") {
+        options.duplex_flip = flags.duplex_flip;
+    }
+    if explicit("scaling") || explicit("scale_percent") {
+        options.scaling_mode = flags.scaling_mode;
+    }
+    if explicit("align_x") {
+        options.content_alignment.horizontal = flags.content_alignment.horizontal;
+    }
+    if explicit("align_y") {
+        options.content_alignment.vertical = flags.content_alignment.vertical;
+    }
+    if explicit("source_box") {
+        options.source_box = flags.source_box;
+    }
+    if explicit("fallback_page_size") {
+        options.fallback_page_size = flags.fallback_page_size;
+    }
+    if explicit("rotate_source") {
+        options.source_rotation = flags.source_rotation;
+    }
+    options.auto_rotate_to_fit |= flags.auto_rotate_to_fit;
+
+    // Margins
+    let (sheet, flag_sheet) = (&mut options.margins.sheet, flags.margins.sheet);
+    if explicit("sheet_margin") {
+        *sheet = flag_sheet;
+    }
+    if explicit("sheet_margin_top") {
+        sheet.top_mm = flag_sheet.top_mm;
+    }
+    if explicit("sheet_margin_bottom") {
+        sheet.bottom_mm = flag_sheet.bottom_mm;
+    }
+    if explicit("sheet_margin_left") {
+        sheet.left_mm = flag_sheet.left_mm;
+    }
+    if explicit("sheet_margin_right") {
+        sheet.right_mm = flag_sheet.right_mm;
+    }
+    let (leaf, flag_leaf) = (&mut options.margins.leaf, flags.margins.leaf);
+    if explicit("leaf_spine_margin") {
+        leaf.spine_mm = flag_leaf.spine_mm;
+    }
+    if explicit("leaf_fore_edge_margin") {
+        leaf.fore_edge_mm = flag_leaf.fore_edge_mm;
+    }
+    if explicit("leaf_top_margin") {
+        leaf.top_mm = flag_leaf.top_mm;
+    }
+    if explicit("leaf_bottom_margin") {
+        leaf.bottom_mm = flag_leaf.bottom_mm;
+    }
+    if explicit("leaf_cut_margin") {
+        leaf.cut_mm = flag_leaf.cut_mm;
+    }
+    if explicit("creep") {
+        options.creep_mm = flags.creep_mm;
+    }
+    if explicit("bleed") {
+        options.bleed_mm = flags.bleed_mm;
+    }
+    if explicit("cell_gap") {
+        options.cell_gap_mm = flags.cell_gap_mm;
+    }
+    options.cell_borders |= flags.cell_borders;
+    if explicit("back_offset") {
+        options.back_side_offset_mm = flags.back_side_offset_mm;
+    }
+
+    // Printer's marks; a set of marks replaces them, otherwise boolean flags
+    // can only turn marks on
+    let (marks, flag_marks) = (&mut options.marks, flags.marks);
+    if explicit("marks") {
+        // A set of marks replaces the loaded ones, but keeps their style
+        *marks = pdf_impose::PrinterMarks {
+            style: marks.style,
+            ..flag_marks
+        };
+    } else {
+        marks.fold_lines |= flag_marks.fold_lines;
+        marks.fold_labels |= flag_marks.fold_labels;
+        marks.cut_lines |= flag_marks.cut_lines;
+        marks.crop_marks |= flag_marks.crop_marks;
+        marks.trim_marks |= flag_marks.trim_marks;
+        marks.registration_marks |= flag_marks.registration_marks;
+        marks.collation_marks |= flag_marks.collation_marks;
+        marks.spine_marks |= flag_marks.spine_marks;
+        marks.color_bars |= flag_marks.color_bars;
+        marks.slot_labels |= flag_marks.slot_labels;
+        if explicit("sewing_stations") {
+            marks.sewing_marks = flag_marks.sewing_marks;
+        }
+        if let Some(sewing) = &mut marks.sewing_marks
+            && let Some(mm) = explicit_mm("sewing_margin")
+        {
+            sewing.head_tail_margin_mm = mm;
         }
+        if explicit("punch_holes") || explicit("punch_pitch") {
+            marks.punch_guides = flag_marks.punch_guides;
+        }
+        if let Some(punch) = &mut marks.punch_guides {
+            if let Some(mm) = explicit_mm("punch_offset") {
+                punch.edge_offset_mm = mm;
+            }
+            if let Some(mm) = explicit_mm("punch_diameter") {
+                punch.hole_diameter_mm = mm;
+            }
+        }
+    }
+
+    // Page numbers
+    options.add_page_numbers |= flags.add_page_numbers;
+    if explicit("page_number_start") {
+        options.page_number_start = flags.page_number_start;
+    }
+    if explicit("page_number_size") {
+        options.page_number_font_size = flags.page_number_font_size;
+    }
+    if explicit("page_number_position") {
+        options.page_number_vertical = flags.page_number_vertical;
+    }
+    if explicit("page_number_align") {
+        options.page_number_horizontal = flags.page_number_horizontal;
+    }
+    options.dummy_mode |= flags.dummy_mode;
+
+    if explicit("front_flyleaves") {
+        options.front_flyleaves = flags.front_flyleaves;
+    }
+    if explicit("back_flyleaves") {
+        options.back_flyleaves = flags.back_flyleaves;
+    }
+    if explicit("cover_front") {
+        options.cover.front_pages = flags.cover.front_pages;
+    }
+    if explicit("cover_back") {
+        options.cover.back_pages = flags.cover.back_pages;
+    }
+    if explicit("cover_mode") {
+        options.cover.mode = flags.cover.mode;
+    }
+    if explicit("paper_thickness") {
+        options.cover.paper_thickness_mm = flags.cover.paper_thickness_mm;
+    }
+    if explicit("split_pages") || explicit("split_sheets") || explicit("split_signatures") {
+        options.split_mode = flags.split_mode;
+    }
+    if explicit("no_compress") {
+        options.compress_output = false;
+    }
+    if explicit("title") {
+        options.title_override = flags.title_override;
+    }
+    if explicit("no_outline") {
+        options.generate_outline = false;
+    }
+    if explicit("bookmark_locations") {
+        options.bookmark_locations = true;
     }
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let cli = Cli::parse();
+    let matches = Cli::command().get_matches();
+    let cli = Cli::from_arg_matches(&matches)?;
 
     match cli.command {
+        Commands::Completions { shell } => {
+            let mut command = Cli::command();
+            let name = command.get_name().to_string();
+            clap_complete::generate(shell, &mut command, name, &mut std::io::stdout());
+        }
+
         Commands::Flashcards {
             input,
             output,
@@ -272,41 +1341,209 @@ async fn main() -> Result<()> {
         }
 
         Commands::Impose {
+            action,
             input,
             output,
+            pages,
+            password,
+            blank_before,
+            reverse,
+            chapter_recto,
+            recto_pages,
+            config,
+            save_config,
+            preset,
+            save_preset,
             binding,
+            rows,
+            columns,
+            nup_order,
+            binding_edge,
+            spine_edge,
             arrangement,
+            short_last_signature,
+            variable_last_signature,
+            signature_plan,
             paper,
+            paper_width,
+            paper_height,
             orientation,
             format,
+            back_order,
+            duplex_flip,
             scaling,
+            scale_percent,
+            align_x,
+            align_y,
+            source_box,
+            fallback_page_size,
+            rotate_source,
+            auto_rotate,
+            page_numbers,
+            page_number_start,
+            page_number_size,
+            page_number_position,
+            page_number_align,
+            dummy,
             front_flyleaves,
             back_flyleaves,
+            cover_front,
+            cover_back,
+            cover_mode,
+            paper_thickness,
+            marks,
             fold_lines,
+            fold_labels,
             cut_lines,
             crop_marks,
             trim_marks,
             registration_marks,
+            collation_marks,
+            color_bars,
+            slot_labels,
+            sewing_stations,
+            sewing_margin,
+            spine_marks,
+            punch_holes,
+            punch_pitch,
+            punch_offset,
+            punch_diameter,
             sheet_margin,
+            sheet_margin_top,
+            sheet_margin_bottom,
+            sheet_margin_left,
+            sheet_margin_right,
             leaf_spine_margin,
             leaf_fore_edge_margin,
             leaf_top_margin,
             leaf_bottom_margin,
             leaf_cut_margin,
+            creep,
+            bleed,
+            cell_gap,
+            cell_borders,
+            back_offset,
+            split_pages,
+            split_sheets,
+            split_signatures,
+            repeat,
+            copies,
+            no_compress,
+            title,
+            no_outline,
+            bookmark_locations,
             stats_only,
+            json,
+            plan_json,
+            verbose,
         } => {
-            let options = pdf_impose::ImpositionOptions {
+            if let Some(ImposeAction::Presets { command }) = action {
+                return run_presets_command(command).await;
+            }
+            let output = output.expect("--output is required without a subcommand");
+
+            let split_mode = match (split_pages, split_sheets, split_signatures) {
+                (Some(n), _, _) => pdf_impose::SplitMode::ByPages(n),
+                (_, Some(n), _) => pdf_impose::SplitMode::BySheets(n),
+                (_, _, Some(n)) => pdf_impose::SplitMode::BySignatures(n),
+                _ => pdf_impose::SplitMode::None,
+            };
+
+            // Without inputs, a dummy's --pages is its page count
+            let dummy_pages = if dummy && input.is_empty() {
+                let [count] = pages.as_slice() else {
+                    bail!("--dummy without --input needs a page count, e.g. --pages 32");
+                };
+                match count.trim().parse::<usize>() {
+                    Ok(count) => Some(count),
+                    Err(_) => bail!("Dummy page count must be a number, not {count:?}"),
+                }
+            } else {
+                None
+            };
+
+            let mut input_ranges = pages
+                .iter()
+                .filter(|_| dummy_pages.is_none())
+                .map(|spec| match spec.trim() {
+                    "" | "all" => Ok(None),
+                    spec => spec.parse().map(Some),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            // Ranges written inline as "file.pdf:3-10" take the input's slot
+            let (input, inline_ranges): (Vec<_>, Vec<_>) =
+                input.iter().map(|spec| split_input_spec(spec)).unzip();
+            if inline_ranges.iter().any(Option::is_some) {
+                input_ranges.resize(input_ranges.len().max(input.len()), None);
+                for (idx, inline) in inline_ranges.into_iter().enumerate() {
+                    if inline.is_some() && input_ranges[idx].is_some() {
+                        bail!(
+                            "{} has a page range both inline and from --pages",
+                            input[idx].display()
+                        );
+                    }
+                    input_ranges[idx] = input_ranges[idx].take().or(inline);
+                }
+            }
+
+            let flag_options = pdf_impose::ImpositionOptions {
                 input_files: input.clone(),
-                binding_type: binding.into(),
+                input_ranges,
+                password,
+                reverse_source: reverse,
+                chapter_recto,
+                chapter_starts_recto: recto_pages.iter().map(|page| page - 1).collect(),
+                blank_inserts: blank_before.iter().map(|page| page - 1).collect(),
+                binding_type: binding.binding_type(rows, columns),
+                n_up_order: nup_order.into(),
+                binding_edge: binding_edge.into(),
+                spine_edge: spine_edge.into(),
                 page_arrangement: arrangement.into(),
-                output_paper_size: paper.into(),
+                allow_short_last_signature: short_last_signature,
+                variable_last_signature,
+                signature_plan: signature_plan.map(|pages_per_signature| {
+                    pdf_impose::SignaturePlan {
+                        pages_per_signature,
+                    }
+                }),
+                step_and_repeat: repeat.map(|(cols, rows)| pdf_impose::StepAndRepeat {
+                    cols,
+                    rows,
+                    copies,
+                }),
+                output_paper_size: paper_size(paper, paper_width, paper_height)?,
                 output_orientation: orientation.into(),
                 output_format: format.into(),
-                scaling_mode: scaling.into(),
+                single_sided_back_order: back_order.into(),
+                duplex_flip: duplex_flip.into(),
+                scaling_mode: scaling_mode(scaling, scale_percent)?,
+                content_alignment: pdf_impose::ContentAlignment {
+                    horizontal: align_x.into(),
+                    vertical: align_y.into(),
+                },
+                source_box: source_box.into(),
+                fallback_page_size: match fallback_page_size {
+                    PaperArg::Custom => bail!("--fallback-page-size takes a named paper size"),
+                    paper => paper_size(paper, None, None)?,
+                },
+                source_rotation: rotate_source,
+                auto_rotate_to_fit: auto_rotate,
                 front_flyleaves,
                 back_flyleaves,
+                cover: pdf_impose::CoverOptions {
+                    front_pages: cover_front,
+                    back_pages: cover_back,
+                    mode: cover_mode.into(),
+                    paper_thickness_mm: paper_thickness,
+                },
                 margins: pdf_impose::Margins {
-                    sheet: pdf_impose::SheetMargins::uniform(sheet_margin),
+                    sheet: pdf_impose::SheetMargins {
+                        top_mm: sheet_margin_top.unwrap_or(sheet_margin),
+                        bottom_mm: sheet_margin_bottom.unwrap_or(sheet_margin),
+                        left_mm: sheet_margin_left.unwrap_or(sheet_margin),
+                        right_mm: sheet_margin_right.unwrap_or(sheet_margin),
+                    },
                     leaf: pdf_impose::LeafMargins {
                         top_mm: leaf_top_margin,
                         bottom_mm: leaf_bottom_margin,
@@ -315,38 +1552,283 @@ async fn main() -> Result<()> {
                         cut_mm: leaf_cut_margin,
                     },
                 },
-                marks: pdf_impose::PrinterMarks {
-                    fold_lines,
-                    cut_lines,
-                    crop_marks,
-                    trim_marks,
-                    registration_marks,
+                creep_mm: creep,
+                bleed_mm: bleed,
+                cell_gap_mm: cell_gap,
+                cell_borders,
+                back_side_offset_mm: back_offset.unwrap_or_default(),
+                marks: match marks {
+                    Some(set) => set.into(),
+                    None => pdf_impose::PrinterMarks {
+                        fold_lines,
+                        fold_labels,
+                        cut_lines,
+                        crop_marks,
+                        trim_marks,
+                        registration_marks,
+                        collation_marks,
+                        sewing_marks: sewing_stations.map(|stations| pdf_impose::SewingMarks {
+                            stations,
+                            head_tail_margin_mm: sewing_margin,
+                        }),
+                        spine_marks,
+                        punch_guides: punch_holes
+                            .map(pdf_impose::HoleSpacing::Count)
+                            .or(punch_pitch.map(pdf_impose::HoleSpacing::PitchMm))
+                            .map(|spacing| pdf_impose::PunchGuides {
+                                spacing,
+                                edge_offset_mm: punch_offset,
+                                hole_diameter_mm: punch_diameter,
+                            }),
+                        color_bars,
+                        slot_labels,
+                        style: pdf_impose::MarkStyle::default(),
+                    },
                 },
-                ..Default::default()
+                add_page_numbers: page_numbers,
+                page_number_start,
+                page_number_font_size: page_number_size,
+                page_number_vertical: page_number_position.into(),
+                page_number_horizontal: page_number_align.into(),
+                dummy_mode: dummy,
+                split_mode,
+                compress_output: !no_compress,
+                title_override: title,
+                generate_outline: !no_outline,
+                bookmark_locations,
             };
 
-            // Load all input PDFs
-            let documents = pdf_impose::load_multiple_pdfs(&input).await?;
+            let base = match (&config, &preset) {
+                (Some(path), _) => Some(pdf_impose::ImpositionOptions::load(path).await?),
+                (_, Some(name)) => {
+                    let store = pdf_impose::PresetStore::open_default()?;
+                    Some(store.load(name).await?)
+                }
+                _ => None,
+            };
+            let options = match base {
+                Some(mut options) => {
+                    let impose_matches = matches.subcommand_matches("impose");
+                    apply_explicit_flags(&mut options, flag_options, impose_matches);
+                    options
+                }
+                None => flag_options,
+            };
+
+            if dummy_pages.is_some() {
+                options.validate_layout()?;
+            } else {
+                options.validate()?;
+            }
+
+            if let Some(path) = &save_config {
+                options.save(path).await?;
+                println!("Saved options → {}", path.display());
+            }
+            if let Some(name) = &save_preset {
+                pdf_impose::PresetStore::open_default()?
+                    .save(name, &options)
+                    .await?;
+                println!("Saved preset \"{}\"", name);
+            }
+
+            if let Some(page_count) = dummy_pages {
+                let dummy = pdf_impose::impose_dummy(page_count, &options)?;
+                pdf_impose::save_pdf(dummy, &output, options.save_options()).await?;
+                println!("Dummy of {} pages → {}", page_count, output.display());
+                return Ok(());
+            }
+
+            // Load all inputs, asking for a password if a PDF is encrypted
+            let password = options.password.as_deref();
+            let image_page_size = options.fallback_page_size;
+            let documents = match pdf_impose::load_inputs(&input, password, image_page_size).await {
+                Err(pdf_impose::ImposeError::Encrypted {
+                    needs_password: true,
+                }) if std::io::stdin().is_terminal() => {
+                    let password = prompt_password()?;
+                    pdf_impose::load_inputs(&input, Some(&password), image_page_size).await?
+                }
+                result => result?,
+            };
+            for ((path, doc), range) in input.iter().zip(&documents).zip(&options.input_ranges) {
+                let total_pages = doc.get_pages().len();
+                if let Some(range) = range
+                    && range.exceeds(total_pages)
+                {
+                    eprintln!(
+                        "Warning: page range {} runs past the {} pages of {}; missing pages are skipped",
+                        range,
+                        total_pages,
+                        path.display()
+                    );
+                }
+            }
 
             // Calculate and show statistics
             let stats = pdf_impose::calculate_statistics(&documents, &options)?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&stats)?);
+                return Ok(());
+            }
             println!("Imposition Statistics:");
+            println!(
+                "  Paper: {}, {:?}",
+                describe_paper(options.output_paper_size),
+                options.output_orientation
+            );
             println!("  Source pages: {}", stats.source_pages);
             println!("  Output sheets: {}", stats.output_sheets);
             println!("  Output pages: {}", stats.output_pages);
             println!("  Blank pages added: {}", stats.blank_pages_added);
+            if stats.cover_sheets > 0 {
+                println!("  Cover sheets: {}", stats.cover_sheets);
+            }
+            println!("  Paper sheets: {}", stats.paper_sheets);
             if let Some(sigs) = stats.signatures {
                 println!("  Signatures: {}", sigs);
             }
+            if let Some(pages) = &stats.pages_per_signature {
+                let plan: Vec<String> = pages.iter().map(usize::to_string).collect();
+                println!("  Signature plan: {}", plan.join(","));
+            }
+            if let Some(blanks) = &stats.blank_pages_per_signature {
+                let blanks: Vec<String> = blanks.iter().map(usize::to_string).collect();
+                println!("  Blanks per signature: {}", blanks.join(","));
+            }
+            let (trim_width, trim_height) = stats.trim_size_mm;
+            println!("  Trim size: {:.1} x {:.1}mm", trim_width, trim_height);
+            if let Some(spine) = stats.spine_thickness_mm {
+                println!(
+                    "  Spine thickness: {:.1}mm at {}mm per leaf",
+                    spine, options.cover.paper_thickness_mm
+                );
+            }
+            if let Some(height) = stats.stack_height {
+                println!("  Stack height: {} leaves", height);
+            }
+            let leaf = &options.margins.leaf;
+            println!(
+                "  Leaf margins: spine {}mm, fore-edge {}mm, head {}mm, tail {}mm, cut {}mm",
+                leaf.spine_mm, leaf.fore_edge_mm, leaf.top_mm, leaf.bottom_mm, leaf.cut_mm
+            );
+            if !stats.bleed_fits {
+                eprintln!(
+                    "Warning: bleed of {}mm is wider than the fore-edge or cut margin and will be cut short",
+                    options.bleed_mm
+                );
+            }
+
+            if verbose && options.auto_rotate_to_fit {
+                let turned = pdf_impose::auto_rotated_pages(&documents, &options).await?;
+                for page in &turned {
+                    println!("  Page {} turned 90° to fit its leaf", page + 1);
+                }
+                if turned.is_empty() {
+                    println!("  No pages needed turning to fit");
+                }
+            }
+
+            if let Some(path) = &plan_json {
+                let plan = pdf_impose::plan(&documents, &options).await?;
+                std::fs::write(path, serde_json::to_string_pretty(&plan)?)?;
+                println!("Layout plan → {}", path.display());
+                return Ok(());
+            }
 
             if stats_only {
                 return Ok(());
             }
 
             // Perform imposition
-            let imposed = pdf_impose::impose(&documents, &options).await?;
-            pdf_impose::save_pdf(imposed, &output).await?;
-            println!("Imposed → {}", output.display());
+            if !options.writes_multiple_files() {
+                let imposed =
+                    pdf_impose::impose_with_progress(&documents, &options, print_warning).await?;
+                pdf_impose::save_pdf(imposed, &output, options.save_options()).await?;
+                println!("Imposed → {}", output.display());
+            } else {
+                let parts =
+                    pdf_impose::impose_split_with_progress(&documents, &options, print_warning)
+                        .await?;
+                let save_options = options.save_options();
+                for path in pdf_impose::save_split_pdfs(parts, &output, save_options).await? {
+                    println!("Imposed → {}", path.display());
+                }
+            }
+        }
+
+        Commands::Nup {
+            input,
+            output,
+            rows,
+            columns,
+            order,
+            borders,
+            paper,
+            paper_width,
+            paper_height,
+            orientation,
+            cell_gap,
+        } => {
+            let options = pdf_impose::ImpositionOptions {
+                input_files: input.clone(),
+                binding_type: pdf_impose::BindingType::NUp {
+                    rows,
+                    cols: columns,
+                },
+                n_up_order: order.into(),
+                cell_borders: borders,
+                output_paper_size: paper_size(paper, paper_width, paper_height)?,
+                output_orientation: orientation.into(),
+                cell_gap_mm: cell_gap,
+                ..Default::default()
+            };
+            let documents =
+                pdf_impose::load_inputs(&input, None, options.fallback_page_size).await?;
+            let imposed =
+                pdf_impose::impose_with_progress(&documents, &options, print_warning).await?;
+            let sheet_count = imposed.get_pages().len();
+            pdf_impose::save_pdf(imposed, &output, options.save_options()).await?;
+            println!(
+                "{}-up on {} sheets → {}",
+                rows * columns,
+                sheet_count,
+                output.display()
+            );
+        }
+
+        Commands::Merge { input, output } => {
+            let documents = pdf_impose::load_multiple_pdfs(&input).await?;
+            let merged = pdf_impose::merge_documents(&documents)?;
+            let page_count = merged.get_pages().len();
+            pdf_impose::save_pdf(merged, &output, pdf_impose::SaveOptions::default()).await?;
+            println!(
+                "Merged {} files ({} pages) → {}",
+                input.len(),
+                page_count,
+                output.display()
+            );
+        }
+
+        #[cfg(feature = "rasterize")]
+        Commands::Rasterize {
+            input,
+            output,
+            dpi,
+            double_sided,
+        } => {
+            let dir = output.clone();
+            let written = tokio::task::spawn_blocking(move || {
+                pdf_render::export_sheets_as_images(&input, dpi, double_sided, &dir, |_, _| {})
+            })
+            .await??;
+            println!(
+                "Rendered {} {} at {} DPI → {}",
+                written.len(),
+                if double_sided { "sheet sides" } else { "pages" },
+                dpi,
+                output.display()
+            );
         }
     }
 
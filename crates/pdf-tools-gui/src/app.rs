@@ -1,10 +1,11 @@
 use eframe::egui;
-use pdf_async_runtime::{PdfCommand, PdfUpdate};
+use pdf_async_runtime::{ImpositionOptions, PdfCommand, PdfUpdate};
 use tokio::sync::mpsc;
 
 use crate::logger::AppLogger;
 use crate::views::{
-    FlashcardState, ImposeState, ViewerState, show_flashcards, show_impose, show_viewer,
+    FlashcardState, ImposeState, PasswordPrompt, ViewerState, show_flashcards, show_impose,
+    show_viewer,
 };
 
 #[derive(Default, PartialEq)]
@@ -107,13 +108,13 @@ impl eframe::App for PdfToolsApp {
         ctx.input(|i| {
             if !i.raw.dropped_files.is_empty() {
                 for file in &i.raw.dropped_files {
-                    if let Some(path) = &file.path {
-                        if path.extension().and_then(|s| s.to_str()) == Some("pdf") {
-                            let _ = self
-                                .command_tx
-                                .send(PdfCommand::ViewerLoad { path: path.clone() });
-                            log::info!("Loading PDF: {}", path.display());
-                        }
+                    if let Some(path) = &file.path
+                        && path.extension().and_then(|s| s.to_str()) == Some("pdf")
+                    {
+                        let _ = self
+                            .command_tx
+                            .send(PdfCommand::ViewerLoad { path: path.clone() });
+                        log::info!("Loading PDF: {}", path.display());
                     }
                 }
             }
@@ -165,38 +166,87 @@ impl eframe::App for PdfToolsApp {
                     log::info!("Preview generated with {} pages", page_count);
                     self.impose_state.preview_doc_id = Some(doc_id);
                     self.impose_state.preview_page_count = page_count;
+                    // Keep the zoom across regenerations so a proof can be re-checked in place
+                    let mut viewer = ViewerState::new(doc_id, page_count);
+                    if let Some(previous) = &self.impose_state.preview_viewer {
+                        viewer.keep_view_of(previous);
+                    }
+                    let scale = viewer.render_scale;
+                    self.impose_state.preview_viewer = Some(viewer);
                     self.progress = None;
 
                     // Request render of first page
                     let _ = self.command_tx.send(PdfCommand::ViewerRenderPage {
                         doc_id,
                         page_index: 0,
+                        scale,
                     });
                 }
                 PdfUpdate::ImposeConfigLoaded { options } => {
                     log::info!("Configuration loaded");
-                    self.impose_state.options = options.clone();
+                    // Passwords aren't saved with a configuration, so keep the one entered
+                    let password = self.impose_state.options.password.take();
+                    let current = std::mem::replace(
+                        &mut self.impose_state.options,
+                        ImpositionOptions {
+                            password,
+                            ..*options
+                        },
+                    );
+                    // Presets carry no inputs, so keep the files already chosen
+                    if self.impose_state.options.input_files.is_empty() {
+                        self.impose_state.options.input_files = current.input_files;
+                        self.impose_state.options.input_ranges = current.input_ranges;
+                    } else {
+                        self.impose_state.page_range_inputs.clear();
+                    }
+                    self.impose_state.blank_inserts_input.clear();
+                    self.impose_state.recto_pages_input.clear();
                     self.progress = None;
 
-                    // Recalculate stats with new options
-                    let _ = self
-                        .command_tx
-                        .send(PdfCommand::ImposeCalculateStats { options });
+                    // Recalculate stats with new options, estimating until files are chosen
+                    let page_count = if self.impose_state.options.input_files.is_empty() {
+                        self.impose_state.estimate_pages
+                    } else {
+                        None
+                    };
+                    let _ = self.command_tx.send(PdfCommand::ImposeCalculateStats {
+                        options: self.impose_state.options.clone(),
+                        page_count,
+                    });
                 }
                 PdfUpdate::ImposeStatsCalculated { stats } => {
                     self.impose_state.stats = Some(stats);
                 }
+                PdfUpdate::ImposePresetsListed { names } => {
+                    self.impose_state.preset_names = Some(names);
+                }
+                PdfUpdate::ImposePasswordRequired { wrong_password } => {
+                    log::info!("Input is encrypted; asking for its password");
+                    self.progress = None;
+                    self.impose_state.password_prompt = Some(PasswordPrompt {
+                        wrong_password,
+                        ..Default::default()
+                    });
+                }
+                PdfUpdate::Warning { message } => {
+                    log::warn!("Warning: {}", message);
+                }
                 PdfUpdate::Error { message } => {
                     log::error!("Error: {}", message);
                     self.progress = None;
                 }
+                PdfUpdate::OperationCancelled { operation_id } => {
+                    log::info!("Operation {} cancelled", operation_id.0);
+                    self.progress = None;
+                }
+                PdfUpdate::OperationFinished { operation_id } => {
+                    if self.impose_state.generation == Some(operation_id) {
+                        self.impose_state.generation = None;
+                    }
+                }
                 PdfUpdate::ViewerLoaded { doc_id, page_count } => {
-                    let new_viewer_state = ViewerState {
-                        current_doc_id: Some(doc_id),
-                        current_page: 0,
-                        total_pages: page_count,
-                        page_texture: None,
-                    };
+                    let new_viewer_state = ViewerState::new(doc_id, page_count);
 
                     // Update viewer state based on current mode
                     match self.mode {
@@ -218,11 +268,13 @@ impl eframe::App for PdfToolsApp {
                     let _ = self.command_tx.send(PdfCommand::ViewerRenderPage {
                         doc_id,
                         page_index: 0,
+                        scale: 1.0,
                     });
                 }
                 PdfUpdate::ViewerPageRendered {
                     doc_id,
                     page_index,
+                    scale,
                     rgba_data,
                     width,
                     height,
@@ -232,6 +284,7 @@ impl eframe::App for PdfToolsApp {
 
                     // Update the appropriate viewer state
                     if let Some(state) = &mut self.viewer_state {
+                        state.texture_scale = scale;
                         if let Some(texture) = &mut state.page_texture {
                             texture.set(color_image.clone(), egui::TextureOptions::default());
                         } else {
@@ -244,6 +297,7 @@ impl eframe::App for PdfToolsApp {
                     }
 
                     if let Some(state) = &mut self.flashcard_state.preview_viewer {
+                        state.texture_scale = scale;
                         if let Some(texture) = &mut state.page_texture {
                             texture.set(color_image.clone(), egui::TextureOptions::default());
                         } else {
@@ -256,6 +310,7 @@ impl eframe::App for PdfToolsApp {
                     }
 
                     if let Some(state) = &mut self.impose_state.preview_viewer {
+                        state.texture_scale = scale;
                         if let Some(texture) = &mut state.page_texture {
                             texture.set(color_image.clone(), egui::TextureOptions::default());
                         } else {
@@ -302,15 +357,76 @@ impl eframe::App for PdfToolsApp {
                         let _ = self.command_tx.send(PdfCommand::ViewerPrefetchPages {
                             doc_id,
                             page_indices: prefetch_pages,
+                            scale,
                         });
                     }
 
                     self.progress = None;
                 }
+                PdfUpdate::ViewerThumbnailRendered {
+                    doc_id,
+                    page_index,
+                    width,
+                    height,
+                    rgba_data,
+                } => {
+                    let color_image =
+                        egui::ColorImage::from_rgba_unmultiplied([width, height], &rgba_data);
+
+                    // Thumbnails only belong to the viewer showing their document
+                    let viewers = [
+                        &mut self.viewer_state,
+                        &mut self.flashcard_state.preview_viewer,
+                        &mut self.impose_state.preview_viewer,
+                    ];
+                    for state in viewers.into_iter().flatten() {
+                        if state.current_doc_id == Some(doc_id) {
+                            state.thumbnails.insert(
+                                page_index,
+                                ctx.load_texture(
+                                    format!("thumbnail_{page_index}"),
+                                    color_image.clone(),
+                                    egui::TextureOptions::default(),
+                                ),
+                            );
+                        }
+                    }
+                }
+                PdfUpdate::SearchResults {
+                    doc_id,
+                    query,
+                    hits,
+                } => {
+                    let viewers = [
+                        &mut self.viewer_state,
+                        &mut self.flashcard_state.preview_viewer,
+                        &mut self.impose_state.preview_viewer,
+                    ];
+                    for state in viewers.into_iter().flatten() {
+                        if state.current_doc_id == Some(doc_id) {
+                            state.show_search_results(
+                                query.clone(),
+                                hits.clone(),
+                                &self.command_tx,
+                            );
+                        }
+                    }
+                }
                 PdfUpdate::ViewerClosed { .. } => {
                     self.viewer_state = None;
                     log::info!("Closed PDF");
                 }
+                PdfUpdate::ImagesExported {
+                    output_dir,
+                    image_count,
+                } => {
+                    log::info!(
+                        "Exported {} sheet images to {}",
+                        image_count,
+                        output_dir.display()
+                    );
+                    self.progress = None;
+                }
             }
         }
 
@@ -335,10 +451,10 @@ impl eframe::App for PdfToolsApp {
                         .show_percentage(),
                     );
                     ctx.request_repaint(); // Keep updating during operations
-                } else if let Some(latest) = self.logger.latest_message() {
-                    if ui.link(&latest).clicked() {
-                        self.log_viewer_open = true;
-                    }
+                } else if let Some(latest) = self.logger.latest_message()
+                    && ui.link(&latest).clicked()
+                {
+                    self.log_viewer_open = true;
                 }
             });
         });
@@ -1,16 +1,27 @@
 use lopdf::Document;
-use pdf_async_runtime::{ImpositionOptions, PdfUpdate};
-use pdf_impose::{calculate_statistics, generate_preview, impose, load_multiple_pdfs, save_pdf};
+use pdf_async_runtime::{ImpositionOptions, OperationId, PdfUpdate};
+use pdf_impose::{
+    CancellationToken, ImposeError, ImposeProgress, PresetStore, SaveOptions, builtin_presets,
+    calculate_statistics, calculate_statistics_for_pages, impose, impose_cancellable, impose_split,
+    impose_split_cancellable, impose_with_progress, load_multiple_pdfs_with_password, save_pdf,
+    save_split_pdfs,
+};
 use std::collections::HashMap;
 use std::path::PathBuf;
 use tokio::sync::mpsc;
 
-// Store loaded documents for impose operations
-static NEXT_DOC_ID: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(1);
+#[cfg(feature = "pdf-viewer")]
+use crate::viewer::ViewerState;
+use pdf_async_runtime::DocumentId;
 
 pub struct ImposeDocStore {
-    /// Preview documents stored by ID
-    preview_documents: HashMap<u64, Document>,
+    /// Documents loaded with `ImposeLoad`, with the path each was read from;
+    /// at most one per path, and only for paths still among the inputs
+    loaded_documents: HashMap<DocumentId, (PathBuf, Document)>,
+    next_doc_id: u64,
+    /// Viewer document showing the current preview, replaced on regeneration
+    #[cfg(feature = "pdf-viewer")]
+    preview_doc_id: Option<DocumentId>,
     /// Cached source documents by input file paths (to avoid reloading)
     source_cache: Option<SourceDocCache>,
 }
@@ -19,6 +30,8 @@ pub struct ImposeDocStore {
 struct SourceDocCache {
     /// The input file paths that were used to load these documents
     paths: Vec<PathBuf>,
+    /// The password encrypted inputs were opened with
+    password: Option<String>,
     /// The loaded documents
     documents: Vec<Document>,
 }
@@ -26,44 +39,53 @@ struct SourceDocCache {
 impl ImposeDocStore {
     pub fn new() -> Self {
         Self {
-            preview_documents: HashMap::new(),
+            loaded_documents: HashMap::new(),
+            next_doc_id: 1,
+            #[cfg(feature = "pdf-viewer")]
+            preview_doc_id: None,
             source_cache: None,
         }
     }
 
-    pub fn store(&mut self, doc: Document) -> u64 {
-        let id = NEXT_DOC_ID.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-        self.preview_documents.insert(id, doc);
+    /// Keep a loaded document and return the id to process it by
+    ///
+    /// Reloading a path replaces the document loaded from it before.
+    pub fn store(&mut self, path: PathBuf, doc: Document) -> DocumentId {
+        self.loaded_documents
+            .retain(|_, (loaded_path, _)| *loaded_path != path);
+        let id = DocumentId(self.next_doc_id);
+        self.next_doc_id += 1;
+        self.loaded_documents.insert(id, (path, doc));
         id
     }
 
-    #[allow(dead_code)]
-    pub fn get(&self, id: u64) -> Option<&Document> {
-        self.preview_documents.get(&id)
+    pub fn get(&self, id: DocumentId) -> Option<&(PathBuf, Document)> {
+        self.loaded_documents.get(&id)
     }
 
-    #[allow(dead_code)]
-    pub fn remove(&mut self, id: u64) -> Option<Document> {
-        self.preview_documents.remove(&id)
-    }
-
-    /// Get cached source documents if the paths match, otherwise load and cache
+    /// Get cached source documents if the paths and password match,
+    /// otherwise load and cache
     pub async fn get_or_load_sources(
         &mut self,
         paths: &[PathBuf],
+        password: Option<&str>,
     ) -> Result<&[Document], pdf_impose::ImposeError> {
         // Check if cache is valid (same paths in same order)
         let cache_valid = self
             .source_cache
             .as_ref()
-            .map(|c| c.paths == paths)
+            .map(|c| c.paths == paths && c.password.as_deref() == password)
             .unwrap_or(false);
 
         if !cache_valid {
             log::debug!("Loading source documents (cache miss or paths changed)");
-            let documents = load_multiple_pdfs(paths).await?;
+            // Files dropped from the inputs won't be processed again
+            self.loaded_documents
+                .retain(|_, (loaded_path, _)| paths.contains(loaded_path));
+            let documents = load_multiple_pdfs_with_password(paths, password).await?;
             self.source_cache = Some(SourceDocCache {
                 paths: paths.to_vec(),
+                password: password.map(str::to_owned),
                 documents,
             });
         } else {
@@ -80,14 +102,16 @@ impl ImposeDocStore {
     }
 }
 
-pub async fn handle_load(input_path: PathBuf, update_tx: &mpsc::UnboundedSender<PdfUpdate>) {
+pub async fn handle_load(
+    input_path: PathBuf,
+    doc_store: &mut ImposeDocStore,
+    update_tx: &mpsc::UnboundedSender<PdfUpdate>,
+) {
     match pdf_impose::load_pdf(&input_path).await {
         Ok(doc) => {
             let page_count = doc.get_pages().len();
-            let _ = update_tx.send(PdfUpdate::ImposeLoaded {
-                doc_id: pdf_async_runtime::DocumentId(0),
-                page_count,
-            });
+            let doc_id = doc_store.store(input_path, doc);
+            let _ = update_tx.send(PdfUpdate::ImposeLoaded { doc_id, page_count });
         }
         Err(e) => {
             let _ = update_tx.send(PdfUpdate::Error {
@@ -97,15 +121,75 @@ pub async fn handle_load(input_path: PathBuf, update_tx: &mpsc::UnboundedSender<
     }
 }
 
-pub async fn handle_process(update_tx: &mpsc::UnboundedSender<PdfUpdate>) {
-    let _ = update_tx.send(PdfUpdate::Error {
-        message: "Imposition not yet fully implemented".to_string(),
+/// Impose a document previously loaded with `ImposeLoad`
+///
+/// The stored document is the only input, so `options.input_files` is
+/// replaced with the path it was loaded from.
+pub async fn handle_process(
+    doc_id: DocumentId,
+    mut options: ImpositionOptions,
+    output_path: PathBuf,
+    doc_store: &ImposeDocStore,
+    update_tx: &mpsc::UnboundedSender<PdfUpdate>,
+) {
+    let Some((path, doc)) = doc_store.get(doc_id) else {
+        let _ = update_tx.send(PdfUpdate::Error {
+            message: format!("Document not found: {:?}", doc_id),
+        });
+        return;
+    };
+    options.input_files = vec![path.clone()];
+    let documents = std::slice::from_ref(doc);
+
+    let _ = update_tx.send(PdfUpdate::Progress {
+        operation: "Imposing pages".to_string(),
+        current: 1,
+        total: 3,
+    });
+
+    if options.writes_multiple_files() {
+        match impose_split(documents, &options).await {
+            Ok(parts) => save_split(parts, output_path, options.save_options(), update_tx).await,
+            Err(e) => {
+                let _ = update_tx.send(PdfUpdate::Error {
+                    message: format!("Failed to impose PDF: {}", e),
+                });
+            }
+        }
+        return;
+    }
+
+    let imposed = match impose(documents, &options).await {
+        Ok(doc) => doc,
+        Err(e) => {
+            let _ = update_tx.send(PdfUpdate::Error {
+                message: format!("Failed to impose PDF: {}", e),
+            });
+            return;
+        }
+    };
+
+    let _ = update_tx.send(PdfUpdate::Progress {
+        operation: "Saving PDF".to_string(),
+        current: 2,
+        total: 3,
     });
+
+    if let Err(e) = save_pdf(imposed, &output_path, options.save_options()).await {
+        let _ = update_tx.send(PdfUpdate::Error {
+            message: format!("Failed to save PDF: {}", e),
+        });
+        return;
+    }
+
+    let _ = update_tx.send(PdfUpdate::ImposeComplete { path: output_path });
 }
 
+#[cfg(feature = "pdf-viewer")]
 pub async fn handle_generate_preview(
     options: ImpositionOptions,
     doc_store: &mut ImposeDocStore,
+    viewer_state: &mut ViewerState,
     update_tx: &mpsc::UnboundedSender<PdfUpdate>,
 ) {
     if options.input_files.is_empty() {
@@ -116,13 +200,12 @@ pub async fn handle_generate_preview(
     }
 
     // Get cached documents or load them (avoids reloading on every preview)
-    let paths: Vec<PathBuf> = options.input_files.iter().cloned().collect();
-    let documents = match doc_store.get_or_load_sources(&paths).await {
+    let paths: Vec<PathBuf> = options.input_files.to_vec();
+    let password = options.password.as_deref();
+    let documents = match doc_store.get_or_load_sources(&paths, password).await {
         Ok(docs) => docs,
         Err(e) => {
-            let _ = update_tx.send(PdfUpdate::Error {
-                message: format!("Failed to load PDFs: {}", e),
-            });
+            let _ = update_tx.send(load_error_update("Failed to load PDFs", e));
             return;
         }
     };
@@ -132,8 +215,14 @@ pub async fn handle_generate_preview(
         let _ = update_tx.send(PdfUpdate::ImposeStatsCalculated { stats });
     }
 
-    // Generate preview (first signature or reasonable sample)
-    let preview = match generate_preview(documents, &options, 4).await {
+    // Only warnings are passed on; previews are quick enough to skip progress
+    let warning_tx = update_tx.clone();
+    let forward_warnings = move |stage| {
+        if let ImposeProgress::Warning(_) = stage {
+            let _ = warning_tx.send(progress_update(stage));
+        }
+    };
+    let imposed = match impose_with_progress(documents, &options, forward_warnings).await {
         Ok(doc) => doc,
         Err(e) => {
             let _ = update_tx.send(PdfUpdate::Error {
@@ -143,18 +232,33 @@ pub async fn handle_generate_preview(
         }
     };
 
-    let page_count = preview.get_pages().len();
-    let doc_id = doc_store.store(preview);
+    // The viewer renders from disk, so write the preview to a temp file
+    let page_count = imposed.get_pages().len();
+    let preview_path = std::env::temp_dir().join("impose_preview.pdf");
+    if let Err(e) = save_pdf(imposed, &preview_path, options.save_options()).await {
+        let _ = update_tx.send(PdfUpdate::Error {
+            message: format!("Failed to save preview: {}", e),
+        });
+        return;
+    }
+
+    // Register under a fresh id so pages cached for the old preview aren't reused
+    if let Some(old_id) = doc_store.preview_doc_id.take() {
+        viewer_state.remove_document(old_id);
+    }
+    let doc_id = viewer_state.next_id();
+    viewer_state.add_document(doc_id, preview_path);
+    doc_store.preview_doc_id = Some(doc_id);
 
-    let _ = update_tx.send(PdfUpdate::ImposePreviewGenerated {
-        doc_id: pdf_async_runtime::DocumentId(doc_id),
-        page_count,
-    });
+    let _ = update_tx.send(PdfUpdate::ImposePreviewGenerated { doc_id, page_count });
 }
 
+/// Impose and save, stopping early once `cancel` is cancelled
 pub async fn handle_generate(
+    operation_id: OperationId,
     options: ImpositionOptions,
     output_path: PathBuf,
+    cancel: CancellationToken,
     update_tx: &mpsc::UnboundedSender<PdfUpdate>,
 ) {
     if options.input_files.is_empty() {
@@ -171,16 +275,15 @@ pub async fn handle_generate(
     });
 
     // Load documents
-    let paths: Vec<PathBuf> = options.input_files.iter().cloned().collect();
-    let documents = match load_multiple_pdfs(&paths).await {
-        Ok(docs) => docs,
-        Err(e) => {
-            let _ = update_tx.send(PdfUpdate::Error {
-                message: format!("Failed to load PDFs: {}", e),
-            });
-            return;
-        }
-    };
+    let paths: Vec<PathBuf> = options.input_files.to_vec();
+    let documents =
+        match load_multiple_pdfs_with_password(&paths, options.password.as_deref()).await {
+            Ok(docs) => docs,
+            Err(e) => {
+                let _ = update_tx.send(load_error_update("Failed to load PDFs", e));
+                return;
+            }
+        };
 
     let _ = update_tx.send(PdfUpdate::Progress {
         operation: "Imposing pages".to_string(),
@@ -188,13 +291,25 @@ pub async fn handle_generate(
         total: 3,
     });
 
-    // Impose
-    let imposed = match impose(&documents, &options).await {
+    // Impose, forwarding each stage to the progress bar
+    let progress_tx = update_tx.clone();
+    let progress = move |stage| {
+        let _ = progress_tx.send(progress_update(stage));
+    };
+
+    if options.writes_multiple_files() {
+        let parts = impose_split_cancellable(&documents, &options, progress, cancel).await;
+        match parts {
+            Ok(parts) => save_split(parts, output_path, options.save_options(), update_tx).await,
+            Err(e) => report_impose_error(operation_id, e, update_tx),
+        }
+        return;
+    }
+
+    let imposed = match impose_cancellable(&documents, &options, progress, cancel).await {
         Ok(doc) => doc,
         Err(e) => {
-            let _ = update_tx.send(PdfUpdate::Error {
-                message: format!("Failed to impose PDF: {}", e),
-            });
+            report_impose_error(operation_id, e, update_tx);
             return;
         }
     };
@@ -206,7 +321,7 @@ pub async fn handle_generate(
     });
 
     // Save
-    if let Err(e) = save_pdf(imposed, &output_path).await {
+    if let Err(e) = save_pdf(imposed, &output_path, options.save_options()).await {
         let _ = update_tx.send(PdfUpdate::Error {
             message: format!("Failed to save PDF: {}", e),
         });
@@ -216,10 +331,86 @@ pub async fn handle_generate(
     let _ = update_tx.send(PdfUpdate::ImposeComplete { path: output_path });
 }
 
+/// The status bar update for a stage of the imposition
+fn progress_update(stage: ImposeProgress) -> PdfUpdate {
+    let (operation, current, total) = match stage {
+        ImposeProgress::Merging => ("Merging pages", 0, 1),
+        ImposeProgress::Rendering { current, total } => ("Rendering sheets", current, total),
+        ImposeProgress::Compressing => ("Compressing output", 1, 1),
+        ImposeProgress::Warning(warning) => {
+            return PdfUpdate::Warning {
+                message: warning.to_string(),
+            };
+        }
+    };
+    PdfUpdate::Progress {
+        operation: operation.to_string(),
+        current,
+        total,
+    }
+}
+
+/// Tell the UI the inputs failed to load, asking for a password when one
+/// is encrypted
+fn load_error_update(context: &str, error: ImposeError) -> PdfUpdate {
+    match error {
+        ImposeError::Encrypted { needs_password } => PdfUpdate::ImposePasswordRequired {
+            wrong_password: !needs_password,
+        },
+        e => PdfUpdate::Error {
+            message: format!("{context}: {e}"),
+        },
+    }
+}
+
+/// Tell the UI an imposition stopped, either cancelled or failed
+fn report_impose_error(
+    operation_id: OperationId,
+    error: ImposeError,
+    update_tx: &mpsc::UnboundedSender<PdfUpdate>,
+) {
+    let update = match error {
+        ImposeError::Cancelled => PdfUpdate::OperationCancelled { operation_id },
+        e => PdfUpdate::Error {
+            message: format!("Failed to impose PDF: {}", e),
+        },
+    };
+    let _ = update_tx.send(update);
+}
+
+/// Write one file per split part next to `output_path`
+async fn save_split(
+    parts: Vec<(String, Document)>,
+    output_path: PathBuf,
+    save_options: SaveOptions,
+    update_tx: &mpsc::UnboundedSender<PdfUpdate>,
+) {
+    let _ = update_tx.send(PdfUpdate::Progress {
+        operation: format!("Saving {} PDFs", parts.len()),
+        current: 2,
+        total: 3,
+    });
+
+    match save_split_pdfs(parts, &output_path, save_options).await {
+        Ok(paths) => {
+            for path in paths {
+                let _ = update_tx.send(PdfUpdate::ImposeComplete { path });
+            }
+        }
+        Err(e) => {
+            let _ = update_tx.send(PdfUpdate::Error {
+                message: format!("Failed to save PDF: {}", e),
+            });
+        }
+    }
+}
+
 pub async fn handle_load_config(path: PathBuf, update_tx: &mpsc::UnboundedSender<PdfUpdate>) {
     match ImpositionOptions::load(&path).await {
         Ok(options) => {
-            let _ = update_tx.send(PdfUpdate::ImposeConfigLoaded { options });
+            let _ = update_tx.send(PdfUpdate::ImposeConfigLoaded {
+                options: Box::new(options),
+            });
         }
         Err(e) => {
             let _ = update_tx.send(PdfUpdate::Error {
@@ -229,28 +420,109 @@ pub async fn handle_load_config(path: PathBuf, update_tx: &mpsc::UnboundedSender
     }
 }
 
+/// Send the names of the bundled presets, then the saved ones
+pub async fn handle_list_presets(update_tx: &mpsc::UnboundedSender<PdfUpdate>) {
+    let mut names: Vec<String> = builtin_presets()
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect();
+    let saved = match PresetStore::open_default() {
+        Ok(store) => store.list().await,
+        Err(e) => Err(e),
+    };
+    match saved {
+        // A saved preset named like a bundled one replaces it when loaded
+        Ok(saved) => {
+            for name in saved {
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+        }
+        Err(e) => {
+            let _ = update_tx.send(PdfUpdate::Warning {
+                message: format!("Failed to list saved presets: {}", e),
+            });
+        }
+    }
+    let _ = update_tx.send(PdfUpdate::ImposePresetsListed { names });
+}
+
+pub async fn handle_load_preset(name: String, update_tx: &mpsc::UnboundedSender<PdfUpdate>) {
+    let options = match PresetStore::open_default() {
+        Ok(store) => store.load(&name).await,
+        Err(e) => Err(e),
+    };
+    match options {
+        Ok(options) => {
+            let _ = update_tx.send(PdfUpdate::ImposeConfigLoaded {
+                options: Box::new(options),
+            });
+        }
+        Err(e) => {
+            let _ = update_tx.send(PdfUpdate::Error {
+                message: format!("Failed to load preset: {}", e),
+            });
+        }
+    }
+}
+
+/// Save a preset, then send the updated list of presets
+pub async fn handle_save_preset(
+    name: String,
+    options: ImpositionOptions,
+    update_tx: &mpsc::UnboundedSender<PdfUpdate>,
+) {
+    let saved = match PresetStore::open_default() {
+        Ok(store) => store.save(&name, &options).await,
+        Err(e) => Err(e),
+    };
+    match saved {
+        Ok(()) => {
+            log::info!("Preset \"{}\" saved", name);
+            handle_list_presets(update_tx).await;
+        }
+        Err(e) => {
+            let _ = update_tx.send(PdfUpdate::Error {
+                message: format!("Failed to save preset: {}", e),
+            });
+        }
+    }
+}
+
+/// Calculate statistics from page counts alone, without imposing
 pub async fn handle_calculate_stats(
     options: ImpositionOptions,
+    page_count: Option<usize>,
+    doc_store: &mut ImposeDocStore,
     update_tx: &mpsc::UnboundedSender<PdfUpdate>,
 ) {
+    // An explicit page count needs no documents, so it can run before any
+    // files are chosen
+    if let Some(page_count) = page_count {
+        if let Ok(stats) = calculate_statistics_for_pages(page_count, &options) {
+            let _ = update_tx.send(PdfUpdate::ImposeStatsCalculated { stats });
+        }
+        return;
+    }
+
     if options.input_files.is_empty() {
         return;
     }
 
-    // Load documents
-    let paths: Vec<PathBuf> = options.input_files.iter().cloned().collect();
-    let documents = match load_multiple_pdfs(&paths).await {
+    // Reuse the preview's source documents when the inputs haven't changed
+    let paths: Vec<PathBuf> = options.input_files.to_vec();
+    let password = options.password.as_deref();
+    let documents = match doc_store.get_or_load_sources(&paths, password).await {
         Ok(docs) => docs,
         Err(e) => {
-            let _ = update_tx.send(PdfUpdate::Error {
-                message: format!("Failed to load PDFs for stats: {}", e),
-            });
+            let _ = update_tx.send(load_error_update("Failed to load PDFs for stats", e));
             return;
         }
     };
 
     // Calculate statistics
-    match calculate_statistics(&documents, &options) {
+    match calculate_statistics(documents, &options) {
         Ok(stats) => {
             let _ = update_tx.send(PdfUpdate::ImposeStatsCalculated { stats });
         }
@@ -1,13 +1,51 @@
-use pdf_async_runtime::{DocumentId, PdfUpdate};
+use pdf_async_runtime::{DocumentId, PageRect, PdfUpdate};
 use std::path::PathBuf;
 use tokio::sync::mpsc;
 
 #[cfg(feature = "pdf-viewer")]
-use crate::viewer::{CachedPage, ViewerState, init_pdfium};
+use crate::viewer::{CachedPage, ViewerState, init_pdfium, page_cache_key};
 
 #[cfg(feature = "pdf-viewer")]
 use pdfium_render::prelude::*;
 
+/// Size of the box a page is fitted into at a render scale of 1.0
+#[cfg(feature = "pdf-viewer")]
+const PREVIEW_WIDTH: f32 = 600.0;
+#[cfg(feature = "pdf-viewer")]
+const PREVIEW_HEIGHT: f32 = 800.0;
+
+/// Scale thumbnails are rendered at, below every zoom so their cache
+/// entries stay apart
+#[cfg(feature = "pdf-viewer")]
+const THUMBNAIL_SCALE: f32 = 0.2;
+
+/// Render config fitting a page into the preview box grown by `scale`
+#[cfg(feature = "pdf-viewer")]
+fn render_config(scale: f32) -> PdfRenderConfig {
+    PdfRenderConfig::new()
+        .set_target_width((PREVIEW_WIDTH * scale).round() as i32)
+        .set_maximum_height((PREVIEW_HEIGHT * scale).round() as i32)
+}
+
+/// Render a page to RGBA, returning (data, width, height)
+#[cfg(feature = "pdf-viewer")]
+fn render_rgba(
+    pdf_path: &std::path::Path,
+    page_index: usize,
+    scale: f32,
+) -> Result<(Vec<u8>, usize, usize), PdfiumError> {
+    let pdfium = init_pdfium()?;
+    let document = pdfium.load_pdf_from_file(pdf_path, None)?;
+    let page = document.pages().get(page_index as u16)?;
+
+    let bitmap = page.render_with_config(&render_config(scale))?;
+    let rgba_data = bitmap.as_rgba_bytes().to_vec();
+    let width = bitmap.width() as usize;
+    let height = bitmap.height() as usize;
+
+    Ok((rgba_data, width, height))
+}
+
 #[cfg(feature = "pdf-viewer")]
 pub async fn handle_load(
     path: PathBuf,
@@ -50,40 +88,26 @@ pub async fn handle_load(
 pub async fn handle_render_page(
     doc_id: DocumentId,
     page_index: usize,
+    scale: f32,
     state: &mut ViewerState,
     update_tx: &mpsc::UnboundedSender<PdfUpdate>,
 ) {
-    let cache_key = (doc_id, page_index);
+    let cache_key = page_cache_key(doc_id, page_index, scale);
+    state.pin(cache_key);
 
     // Check cache first
     if let Some(cached) = state.get_from_cache(&cache_key) {
         let _ = update_tx.send(PdfUpdate::ViewerPageRendered {
             doc_id,
             page_index,
+            scale,
             width: cached.width,
             height: cached.height,
             rgba_data: cached.rgba_data.clone(),
         });
     } else if let Some(pdf_path) = state.get_document(&doc_id).cloned() {
         // Not in cache, need to render
-        match tokio::task::spawn_blocking(move || {
-            let pdfium = init_pdfium()?;
-            let document = pdfium.load_pdf_from_file(&pdf_path, None)?;
-            let page = document.pages().get(page_index as u16)?;
-
-            let config = PdfRenderConfig::new()
-                .set_target_width(600)
-                .set_maximum_height(800);
-
-            let bitmap = page.render_with_config(&config)?;
-            let rgba_data = bitmap.as_rgba_bytes().to_vec();
-            let width = bitmap.width() as usize;
-            let height = bitmap.height() as usize;
-
-            Ok::<_, PdfiumError>((rgba_data, width, height))
-        })
-        .await
-        {
+        match tokio::task::spawn_blocking(move || render_rgba(&pdf_path, page_index, scale)).await {
             Ok(Ok((rgba_data, width, height))) => {
                 // Add to cache
                 state.add_to_cache(
@@ -98,6 +122,7 @@ pub async fn handle_render_page(
                 let _ = update_tx.send(PdfUpdate::ViewerPageRendered {
                     doc_id,
                     page_index,
+                    scale,
                     width,
                     height,
                     rgba_data,
@@ -122,15 +147,17 @@ pub async fn handle_render_page(
 }
 
 /// Prefetch pages into cache without sending updates to UI
-/// This runs silently in the background to warm the cache
+/// This runs silently in the background to warm the cache; the page on
+/// screen is pinned, so a prefetch never evicts it
 #[cfg(feature = "pdf-viewer")]
 pub async fn handle_prefetch_pages(
     doc_id: DocumentId,
     page_indices: Vec<usize>,
+    scale: f32,
     state: &mut ViewerState,
 ) {
     for page_index in page_indices {
-        let cache_key = (doc_id, page_index);
+        let cache_key = page_cache_key(doc_id, page_index, scale);
 
         // Skip if already cached
         if state.get_from_cache(&cache_key).is_some() {
@@ -139,23 +166,8 @@ pub async fn handle_prefetch_pages(
 
         if let Some(pdf_path) = state.get_document(&doc_id).cloned() {
             // Render to cache silently (no UI update)
-            match tokio::task::spawn_blocking(move || {
-                let pdfium = init_pdfium()?;
-                let document = pdfium.load_pdf_from_file(&pdf_path, None)?;
-                let page = document.pages().get(page_index as u16)?;
-
-                let config = PdfRenderConfig::new()
-                    .set_target_width(600)
-                    .set_maximum_height(800);
-
-                let bitmap = page.render_with_config(&config)?;
-                let rgba_data = bitmap.as_rgba_bytes().to_vec();
-                let width = bitmap.width() as usize;
-                let height = bitmap.height() as usize;
-
-                Ok::<_, PdfiumError>((rgba_data, width, height))
-            })
-            .await
+            match tokio::task::spawn_blocking(move || render_rgba(&pdf_path, page_index, scale))
+                .await
             {
                 Ok(Ok((rgba_data, width, height))) => {
                     state.add_to_cache(
@@ -179,6 +191,213 @@ pub async fn handle_prefetch_pages(
     }
 }
 
+/// Render thumbnails of pages, through the page cache at the thumbnail
+/// scale, sending each as it is ready
+#[cfg(feature = "pdf-viewer")]
+pub async fn handle_render_thumbnails(
+    doc_id: DocumentId,
+    page_indices: Vec<usize>,
+    state: &mut ViewerState,
+    update_tx: &mpsc::UnboundedSender<PdfUpdate>,
+) {
+    for page_index in page_indices {
+        let cache_key = page_cache_key(doc_id, page_index, THUMBNAIL_SCALE);
+        let (rgba_data, width, height) = if let Some(cached) = state.get_from_cache(&cache_key) {
+            (cached.rgba_data.clone(), cached.width, cached.height)
+        } else if let Some(pdf_path) = state.get_document(&doc_id).cloned() {
+            match tokio::task::spawn_blocking(move || {
+                render_rgba(&pdf_path, page_index, THUMBNAIL_SCALE)
+            })
+            .await
+            {
+                Ok(Ok((rgba_data, width, height))) => {
+                    state.add_to_cache(
+                        cache_key,
+                        CachedPage {
+                            rgba_data: rgba_data.clone(),
+                            width,
+                            height,
+                        },
+                    );
+                    (rgba_data, width, height)
+                }
+                Ok(Err(e)) => {
+                    log::warn!("Failed to render thumbnail {}: {}", page_index, e);
+                    continue;
+                }
+                Err(e) => {
+                    log::warn!("Thumbnail task join error for page {}: {}", page_index, e);
+                    continue;
+                }
+            }
+        } else {
+            // The document was closed while its thumbnails were queued
+            return;
+        };
+
+        let _ = update_tx.send(PdfUpdate::ViewerThumbnailRendered {
+            doc_id,
+            page_index,
+            width,
+            height,
+            rgba_data,
+        });
+    }
+}
+
+/// Find every match of `query` in a document, ignoring case, as
+/// (page index, bounds); `None` if no page has any text to search
+#[cfg(feature = "pdf-viewer")]
+fn search_text(
+    pdf_path: &std::path::Path,
+    query: &str,
+) -> Result<Option<Vec<(usize, PageRect)>>, PdfiumError> {
+    let pdfium = init_pdfium()?;
+    let document = pdfium.load_pdf_from_file(pdf_path, None)?;
+    let options = PdfSearchOptions::new().match_case(false);
+
+    let mut hits = Vec::new();
+    let mut has_text = false;
+    for (page_index, page) in document.pages().iter().enumerate() {
+        let Ok(text) = page.text() else {
+            continue;
+        };
+        if text.is_empty() {
+            continue;
+        }
+        has_text = true;
+
+        let width = page.width().value;
+        let height = page.height().value;
+        let search = text.search(query, &options)?;
+        for segments in search.iter(PdfSearchDirection::SearchForward) {
+            // A match split over lines has a segment per line; highlight
+            // the area covering them all
+            let bounds = segments
+                .iter()
+                .map(|segment| segment.bounds())
+                .reduce(|a, b| {
+                    PdfRect::new_from_values(
+                        a.bottom().value.min(b.bottom().value),
+                        a.left().value.min(b.left().value),
+                        a.top().value.max(b.top().value),
+                        a.right().value.max(b.right().value),
+                    )
+                });
+            if let Some(bounds) = bounds {
+                hits.push((
+                    page_index,
+                    PageRect {
+                        left: bounds.left().value / width,
+                        top: 1.0 - bounds.top().value / height,
+                        right: bounds.right().value / width,
+                        bottom: 1.0 - bounds.bottom().value / height,
+                    },
+                ));
+            }
+        }
+    }
+
+    Ok(has_text.then_some(hits))
+}
+
+#[cfg(feature = "pdf-viewer")]
+pub async fn handle_search(
+    doc_id: DocumentId,
+    query: String,
+    state: &mut ViewerState,
+    update_tx: &mpsc::UnboundedSender<PdfUpdate>,
+) {
+    let Some(pdf_path) = state.get_document(&doc_id).cloned() else {
+        let _ = update_tx.send(PdfUpdate::Error {
+            message: format!("Document not found: {:?}", doc_id),
+        });
+        return;
+    };
+
+    let hits = if query.trim().is_empty() {
+        Vec::new()
+    } else {
+        let search_query = query.clone();
+        match tokio::task::spawn_blocking(move || search_text(&pdf_path, &search_query)).await {
+            Ok(Ok(Some(hits))) => hits,
+            Ok(Ok(None)) => {
+                let _ = update_tx.send(PdfUpdate::Warning {
+                    message: "This document has no text to search; it may be scanned images"
+                        .to_string(),
+                });
+                Vec::new()
+            }
+            Ok(Err(e)) => {
+                let _ = update_tx.send(PdfUpdate::Error {
+                    message: format!("Failed to search document: {}", e),
+                });
+                Vec::new()
+            }
+            Err(e) => {
+                let _ = update_tx.send(PdfUpdate::Error {
+                    message: format!("Task join error: {}", e),
+                });
+                Vec::new()
+            }
+        }
+    };
+
+    let _ = update_tx.send(PdfUpdate::SearchResults {
+        doc_id,
+        query,
+        hits,
+    });
+}
+
+#[cfg(feature = "pdf-viewer")]
+pub async fn handle_export_images(
+    doc_id: DocumentId,
+    dpi: f32,
+    double_sided: bool,
+    output_dir: PathBuf,
+    state: &ViewerState,
+    update_tx: &mpsc::UnboundedSender<PdfUpdate>,
+) {
+    let Some(pdf_path) = state.get_document(&doc_id).cloned() else {
+        let _ = update_tx.send(PdfUpdate::Error {
+            message: format!("Document not found: {:?}", doc_id),
+        });
+        return;
+    };
+
+    let progress_tx = update_tx.clone();
+    let dir = output_dir.clone();
+    match tokio::task::spawn_blocking(move || {
+        pdf_render::export_sheets_as_images(&pdf_path, dpi, double_sided, &dir, |current, total| {
+            let _ = progress_tx.send(PdfUpdate::Progress {
+                operation: "Rendering sheets".to_string(),
+                current,
+                total,
+            });
+        })
+    })
+    .await
+    {
+        Ok(Ok(written)) => {
+            let _ = update_tx.send(PdfUpdate::ImagesExported {
+                output_dir,
+                image_count: written.len(),
+            });
+        }
+        Ok(Err(e)) => {
+            let _ = update_tx.send(PdfUpdate::Error {
+                message: format!("Failed to export images: {}", e),
+            });
+        }
+        Err(e) => {
+            let _ = update_tx.send(PdfUpdate::Error {
+                message: format!("Task join error: {}", e),
+            });
+        }
+    }
+}
+
 #[cfg(feature = "pdf-viewer")]
 pub async fn handle_close(
     doc_id: DocumentId,
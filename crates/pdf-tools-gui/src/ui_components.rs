@@ -6,7 +6,6 @@ pub struct SliderBuilder<'a, T> {
     value: &'a mut T,
     range: std::ops::RangeInclusive<T>,
     text: String,
-    suffix: Option<String>,
 }
 
 impl<'a, T> SliderBuilder<'a, T>
@@ -18,7 +17,6 @@ where
             value,
             range,
             text: String::new(),
-            suffix: None,
         }
     }
 
@@ -27,11 +25,6 @@ where
         self
     }
 
-    pub fn suffix(mut self, suffix: impl Into<String>) -> Self {
-        self.suffix = Some(suffix.into());
-        self
-    }
-
     pub fn show(self, ui: &mut egui::Ui) -> bool {
         let mut slider =
             egui::Slider::new(self.value, self.range).clamping(egui::SliderClamping::Never);
@@ -40,10 +33,6 @@ where
             slider = slider.text(self.text);
         }
 
-        if let Some(suffix) = self.suffix {
-            slider = slider.suffix(suffix);
-        }
-
         ui.add(slider).changed()
     }
 }
@@ -53,7 +42,6 @@ pub struct DragValueBuilder<'a, T> {
     value: &'a mut T,
     range: Option<std::ops::RangeInclusive<T>>,
     suffix: Option<String>,
-    speed: Option<f32>,
 }
 
 impl<'a, T> DragValueBuilder<'a, T>
@@ -65,7 +53,6 @@ where
             value,
             range: None,
             suffix: None,
-            speed: None,
         }
     }
 
@@ -79,11 +66,6 @@ where
         self
     }
 
-    pub fn speed(mut self, speed: f32) -> Self {
-        self.speed = Some(speed);
-        self
-    }
-
     pub fn show(self, ui: &mut egui::Ui) -> bool {
         let mut drag = egui::DragValue::new(self.value);
 
@@ -95,43 +77,10 @@ where
             drag = drag.suffix(suffix);
         }
 
-        if let Some(speed) = self.speed {
-            drag = drag.speed(speed);
-        }
-
         ui.add(drag).changed()
     }
 }
 
-/// Helper for creating labeled horizontal drag values
-pub fn labeled_drag<T>(ui: &mut egui::Ui, label: &str, value: &mut T) -> bool
-where
-    T: egui::emath::Numeric,
-{
-    ui.horizontal(|ui| {
-        ui.label(label);
-        DragValueBuilder::new(value).show(ui)
-    })
-    .inner
-}
-
-/// Helper for creating labeled horizontal drag values with suffix
-pub fn labeled_drag_with_suffix<T>(
-    ui: &mut egui::Ui,
-    label: &str,
-    value: &mut T,
-    suffix: &str,
-) -> bool
-where
-    T: egui::emath::Numeric,
-{
-    ui.horizontal(|ui| {
-        ui.label(label);
-        DragValueBuilder::new(value).suffix(suffix).show(ui)
-    })
-    .inner
-}
-
 /// Helper for creating labeled horizontal drag values with range and suffix
 pub fn labeled_drag_clamped<T>(
     ui: &mut egui::Ui,
@@ -209,9 +158,16 @@ where
     changed
 }
 
+/// Per-file page range text, kept in step with the file list
+struct PageRangeInputs<'a> {
+    texts: &'a mut Vec<String>,
+    is_valid: fn(&str) -> bool,
+}
+
 /// File list editor with reordering and removal
 pub struct FileListEditor<'a> {
     files: &'a mut Vec<PathBuf>,
+    page_ranges: Option<PageRangeInputs<'a>>,
     changed: bool,
 }
 
@@ -219,10 +175,22 @@ impl<'a> FileListEditor<'a> {
     pub fn new(files: &'a mut Vec<PathBuf>) -> Self {
         Self {
             files,
+            page_ranges: None,
             changed: false,
         }
     }
 
+    /// Show a page range field next to each file; `texts` is resized to match
+    pub fn with_page_ranges(
+        mut self,
+        texts: &'a mut Vec<String>,
+        is_valid: fn(&str) -> bool,
+    ) -> Self {
+        texts.resize(self.files.len(), String::new());
+        self.page_ranges = Some(PageRangeInputs { texts, is_valid });
+        self
+    }
+
     pub fn show(mut self, ui: &mut egui::Ui) -> bool {
         if self.files.is_empty() {
             ui.label("No files selected");
@@ -245,6 +213,21 @@ impl<'a> FileListEditor<'a> {
 
                 ui.label(format!("{}. {}", idx + 1, path.display()));
 
+                if let Some(ranges) = &mut self.page_ranges {
+                    let text = &mut ranges.texts[idx];
+                    self.changed |= ui
+                        .add(
+                            egui::TextEdit::singleline(text)
+                                .hint_text("all pages")
+                                .desired_width(80.0),
+                        )
+                        .on_hover_text("Pages to use, e.g. 1-4,7,10-")
+                        .changed();
+                    if !text.trim().is_empty() && !(ranges.is_valid)(text) {
+                        ui.colored_label(egui::Color32::from_rgb(255, 80, 80), "invalid");
+                    }
+                }
+
                 if ui.small_button("✖").clicked() {
                     to_remove = Some(idx);
                 }
@@ -254,14 +237,23 @@ impl<'a> FileListEditor<'a> {
         // Apply changes
         if let Some(idx) = to_move_up {
             self.files.swap(idx, idx - 1);
+            if let Some(ranges) = &mut self.page_ranges {
+                ranges.texts.swap(idx, idx - 1);
+            }
             self.changed = true;
         }
         if let Some(idx) = to_move_down {
             self.files.swap(idx, idx + 1);
+            if let Some(ranges) = &mut self.page_ranges {
+                ranges.texts.swap(idx, idx + 1);
+            }
             self.changed = true;
         }
         if let Some(idx) = to_remove {
             self.files.remove(idx);
+            if let Some(ranges) = &mut self.page_ranges {
+                ranges.texts.remove(idx);
+            }
             self.changed = true;
         }
 
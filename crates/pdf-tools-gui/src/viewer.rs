@@ -4,29 +4,7 @@ use std::path::PathBuf;
 use std::sync::atomic::{AtomicU64, Ordering};
 
 #[cfg(feature = "pdf-viewer")]
-use pdfium_render::prelude::*;
-
-/// Initialize Pdfium, trying the vendored library first, then falling back to system
-#[cfg(feature = "pdf-viewer")]
-pub fn init_pdfium() -> Result<Pdfium, PdfiumError> {
-    // Try to load from vendor directory (relative to workspace root)
-    // When running from cargo, the working directory is the workspace root
-    let vendor_path = std::env::current_dir().ok().and_then(|mut p| {
-        p.push("vendor/pdfium/lib");
-        if p.exists() { Some(p) } else { None }
-    });
-
-    if let Some(vendor_path) = vendor_path {
-        if let Ok(binding) =
-            Pdfium::bind_to_library(Pdfium::pdfium_platform_library_name_at_path(&vendor_path))
-        {
-            return Ok(Pdfium::new(binding));
-        }
-    }
-
-    // Fallback to system library or default search paths
-    Pdfium::bind_to_system_library().map(Pdfium::new)
-}
+pub use pdf_render::init_pdfium;
 
 /// Cached page data
 #[cfg(feature = "pdf-viewer")]
@@ -36,6 +14,16 @@ pub struct CachedPage {
     pub height: usize,
 }
 
+/// Cache key for a rendered page: document, page index and render scale
+/// in percent, so renders at different zooms are kept apart
+#[cfg(feature = "pdf-viewer")]
+pub type PageCacheKey = (DocumentId, usize, u32);
+
+#[cfg(feature = "pdf-viewer")]
+pub fn page_cache_key(doc_id: DocumentId, page_index: usize, scale: f32) -> PageCacheKey {
+    (doc_id, page_index, (scale * 100.0).round() as u32)
+}
+
 /// Maximum number of pages to cache
 #[cfg(feature = "pdf-viewer")]
 const MAX_CACHED_PAGES: usize = 50;
@@ -44,8 +32,10 @@ const MAX_CACHED_PAGES: usize = 50;
 #[cfg(feature = "pdf-viewer")]
 pub struct ViewerState {
     documents: HashMap<DocumentId, PathBuf>,
-    page_cache: HashMap<(DocumentId, usize), CachedPage>,
-    cache_order: VecDeque<(DocumentId, usize)>,
+    page_cache: HashMap<PageCacheKey, CachedPage>,
+    cache_order: VecDeque<PageCacheKey>,
+    /// The page last rendered for display, which eviction never removes
+    pinned: Option<PageCacheKey>,
     next_doc_id: AtomicU64,
 }
 
@@ -56,6 +46,7 @@ impl ViewerState {
             documents: HashMap::new(),
             page_cache: HashMap::new(),
             cache_order: VecDeque::new(),
+            pinned: None,
             next_doc_id: AtomicU64::new(0),
         })
     }
@@ -72,15 +63,22 @@ impl ViewerState {
         self.documents.get(doc_id)
     }
 
-    pub fn add_to_cache(&mut self, key: (DocumentId, usize), page: CachedPage) {
+    pub fn add_to_cache(&mut self, key: PageCacheKey, page: CachedPage) {
         // Remove if already exists (update LRU)
         if self.page_cache.contains_key(&key) {
             self.cache_order.retain(|k| k != &key);
         }
 
-        // Evict LRU if full
+        // Evict LRU if full, skipping the page on screen
         while self.cache_order.len() >= MAX_CACHED_PAGES {
-            if let Some(old_key) = self.cache_order.pop_front() {
+            let Some(position) = self
+                .cache_order
+                .iter()
+                .position(|k| Some(*k) != self.pinned)
+            else {
+                break;
+            };
+            if let Some(old_key) = self.cache_order.remove(position) {
                 self.page_cache.remove(&old_key);
             }
         }
@@ -90,7 +88,12 @@ impl ViewerState {
         self.cache_order.push_back(key);
     }
 
-    pub fn get_from_cache(&mut self, key: &(DocumentId, usize)) -> Option<&CachedPage> {
+    /// Mark the page being displayed, so prefetching can't evict it
+    pub fn pin(&mut self, key: PageCacheKey) {
+        self.pinned = Some(key);
+    }
+
+    pub fn get_from_cache(&mut self, key: &PageCacheKey) -> Option<&CachedPage> {
         if self.page_cache.contains_key(key) {
             // Update LRU order
             self.cache_order.retain(|k| k != key);
@@ -104,7 +107,10 @@ impl ViewerState {
     pub fn remove_document(&mut self, doc_id: DocumentId) {
         self.documents.remove(&doc_id);
         // Remove all cached pages for this document
-        self.cache_order.retain(|(id, _)| *id != doc_id);
-        self.page_cache.retain(|(id, _), _| *id != doc_id);
+        self.cache_order.retain(|(id, _, _)| *id != doc_id);
+        self.page_cache.retain(|(id, _, _), _| *id != doc_id);
+        if self.pinned.is_some_and(|(id, _, _)| id == doc_id) {
+            self.pinned = None;
+        }
     }
 }
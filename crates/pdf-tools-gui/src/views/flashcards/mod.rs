@@ -200,15 +200,14 @@ fn show_csv_section(
     ui.label("CSV File:");
     ui.horizontal(|ui| {
         ui.text_edit_singleline(&mut state.csv_path);
-        if ui.button("Browse...").clicked() {
-            if let Some(path) = rfd::FileDialog::new()
+        if ui.button("Browse...").clicked()
+            && let Some(path) = rfd::FileDialog::new()
                 .add_filter("CSV", &["csv"])
                 .pick_file()
-            {
-                state.csv_path = path.display().to_string();
-                log::info!("Loading CSV: {}", path.display());
-                let _ = command_tx.send(PdfCommand::FlashcardsLoadCsv { input_path: path });
-            }
+        {
+            state.csv_path = path.display().to_string();
+            log::info!("Loading CSV: {}", path.display());
+            let _ = command_tx.send(PdfCommand::FlashcardsLoadCsv { input_path: path });
         }
     });
 
@@ -398,20 +397,20 @@ fn show_actions_section(
         });
     }
 
-    if ui.button("💾 Save PDF...").clicked() && !state.cards.is_empty() {
-        if let Some(path) = rfd::FileDialog::new()
+    if ui.button("💾 Save PDF...").clicked()
+        && !state.cards.is_empty()
+        && let Some(path) = rfd::FileDialog::new()
             .add_filter("PDF", &["pdf"])
             .set_file_name("flashcards.pdf")
             .save_file()
-        {
-            log::info!("Saving flashcards to: {}", path.display());
-            let options = state.to_options();
-            let _ = command_tx.send(PdfCommand::FlashcardsGenerate {
-                cards: state.cards.clone(),
-                options,
-                output_path: path,
-            });
-        }
+    {
+        log::info!("Saving flashcards to: {}", path.display());
+        let options = state.to_options();
+        let _ = command_tx.send(PdfCommand::FlashcardsGenerate {
+            cards: state.cards.clone(),
+            options,
+            output_path: path,
+        });
     }
 
     if state.needs_regeneration && !state.cards.is_empty() {
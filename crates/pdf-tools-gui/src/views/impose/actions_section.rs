@@ -1,8 +1,9 @@
 use eframe::egui;
-use pdf_async_runtime::PdfCommand;
+use pdf_async_runtime::{OperationId, PdfCommand};
+use pdf_impose::{ImpositionOptions, OutputFormat, SplitMode};
 use tokio::sync::mpsc;
 
-use super::state::ImposeState;
+use super::state::{DEFAULT_EXPORT_DPI, ImposeState};
 
 pub fn show(
     ui: &mut egui::Ui,
@@ -13,6 +14,7 @@ pub fn show(
         ui.horizontal(|ui| {
             show_config_buttons(ui, state, command_tx);
         });
+        show_presets(ui, state, command_tx);
 
         ui.add_space(10.0);
 
@@ -20,10 +22,21 @@ pub fn show(
 
         ui.add_space(5.0);
 
-        show_generate_button(ui, state, command_tx);
+        ui.horizontal(|ui| {
+            show_generate_button(ui, state, command_tx);
+            show_cancel_button(ui, state, command_tx);
+        });
+
+        ui.horizontal(|ui| {
+            show_export_images_button(ui, state, command_tx);
+        });
 
-        if state.needs_regeneration && !state.options.input_files.is_empty() {
-            generate_preview(state, command_tx);
+        if state.needs_regeneration {
+            if !state.options.input_files.is_empty() {
+                generate_preview(state, command_tx);
+            } else if let Some(page_count) = state.estimate_pages {
+                estimate_statistics(state, page_count, command_tx);
+            }
         }
     });
 }
@@ -51,6 +64,64 @@ fn show_config_buttons(
 ) {
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+fn show_presets(
+    ui: &mut egui::Ui,
+    state: &mut ImposeState,
+    command_tx: &mpsc::UnboundedSender<PdfCommand>,
+) {
+    let Some(names) = &state.preset_names else {
+        // Ask once; the list arrives as an update
+        state.preset_names = Some(Vec::new());
+        let _ = command_tx.send(PdfCommand::ImposeListPresets);
+        return;
+    };
+
+    ui.horizontal(|ui| {
+        egui::ComboBox::from_id_salt("impose_preset")
+            .selected_text("Load preset…")
+            .show_ui(ui, |ui| {
+                for name in names {
+                    if ui.selectable_label(false, name).clicked() {
+                        let name = name.clone();
+                        let _ = command_tx.send(PdfCommand::ImposeLoadPreset { name });
+                    }
+                }
+            });
+
+        match &mut state.preset_save_name {
+            None => {
+                if ui.button("Save as preset…").clicked() {
+                    state.preset_save_name = Some(String::new());
+                }
+            }
+            Some(name) => {
+                ui.add(egui::TextEdit::singleline(name).hint_text("Preset name"));
+                if ui
+                    .add_enabled(!name.trim().is_empty(), egui::Button::new("Save"))
+                    .clicked()
+                {
+                    let _ = command_tx.send(PdfCommand::ImposeSavePreset {
+                        name: name.trim().to_string(),
+                        options: state.options.clone(),
+                    });
+                    state.preset_save_name = None;
+                } else if ui.button("Cancel").clicked() {
+                    state.preset_save_name = None;
+                }
+            }
+        }
+    });
+}
+
+#[cfg(target_arch = "wasm32")]
+fn show_presets(
+    _ui: &mut egui::Ui,
+    _state: &mut ImposeState,
+    _command_tx: &mpsc::UnboundedSender<PdfCommand>,
+) {
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 fn save_configuration(state: &ImposeState) {
     if let Some(path) = rfd::FileDialog::new()
@@ -97,42 +168,132 @@ fn show_preview_button(
 fn generate_preview(state: &mut ImposeState, command_tx: &mpsc::UnboundedSender<PdfCommand>) {
     state.needs_regeneration = false;
     log::info!("Generating impose preview");
-    let _ = command_tx.send(PdfCommand::ImposeGenerate {
+    // The preview is always a single document, regardless of split settings
+    let options = ImpositionOptions {
+        split_mode: SplitMode::None,
+        ..state.options.clone()
+    };
+    let _ = command_tx.send(PdfCommand::ImposeGeneratePreview { options });
+}
+
+/// Work out the statistics for a page count while no files are chosen
+fn estimate_statistics(
+    state: &mut ImposeState,
+    page_count: usize,
+    command_tx: &mpsc::UnboundedSender<PdfCommand>,
+) {
+    state.needs_regeneration = false;
+    let _ = command_tx.send(PdfCommand::ImposeCalculateStats {
         options: state.options.clone(),
-        output_path: std::env::temp_dir().join("impose_preview.pdf"),
+        page_count: Some(page_count),
     });
 }
 
 #[cfg(not(target_arch = "wasm32"))]
 fn show_generate_button(
     ui: &mut egui::Ui,
-    state: &ImposeState,
+    state: &mut ImposeState,
     command_tx: &mpsc::UnboundedSender<PdfCommand>,
 ) {
-    let can_generate = !state.options.input_files.is_empty();
+    let can_generate = !state.options.input_files.is_empty() && state.generation.is_none();
 
     if ui
         .add_enabled(can_generate, egui::Button::new("💾 Save PDF..."))
         .clicked()
-    {
-        if let Some(path) = rfd::FileDialog::new()
+        && let Some(path) = rfd::FileDialog::new()
             .add_filter("PDF", &["pdf"])
             .set_file_name("imposed.pdf")
             .save_file()
-        {
-            log::info!("Saving imposed PDF to: {}", path.display());
-            let _ = command_tx.send(PdfCommand::ImposeGenerate {
-                options: state.options.clone(),
-                output_path: path,
-            });
-        }
+    {
+        log::info!("Saving imposed PDF to: {}", path.display());
+        let operation_id = OperationId::next();
+        state.generation = Some(operation_id);
+        let _ = command_tx.send(PdfCommand::ImposeGenerate {
+            operation_id,
+            options: state.options.clone(),
+            output_path: path,
+        });
     }
 }
 
 #[cfg(target_arch = "wasm32")]
 fn show_generate_button(
     _ui: &mut egui::Ui,
-    _state: &ImposeState,
+    _state: &mut ImposeState,
     _command_tx: &mpsc::UnboundedSender<PdfCommand>,
 ) {
 }
+
+/// Render the preview's sheets to PNGs, for printers that take images
+/// better than PDFs
+#[cfg(not(target_arch = "wasm32"))]
+fn show_export_images_button(
+    ui: &mut egui::Ui,
+    state: &mut ImposeState,
+    command_tx: &mpsc::UnboundedSender<PdfCommand>,
+) {
+    // Only interleaved pages can be named front and back
+    let double_sided = state.options.output_format == OutputFormat::DoubleSided;
+    let can_export = state.preview_doc_id.is_some() && !state.needs_regeneration;
+
+    let clicked = ui
+        .add_enabled(can_export, egui::Button::new("🖼 Export PNGs..."))
+        .on_hover_text(if double_sided {
+            "Render each sheet side to sheet_001_front.png, sheet_001_back.png, ..."
+        } else {
+            "Render each page to page_001.png, page_002.png, ..."
+        })
+        .on_disabled_hover_text("Generate a preview first")
+        .clicked();
+
+    let mut dpi = state.export_dpi.unwrap_or(DEFAULT_EXPORT_DPI);
+    if ui
+        .add(
+            egui::DragValue::new(&mut dpi)
+                .range(72.0..=2400.0)
+                .speed(10.0)
+                .suffix(" DPI"),
+        )
+        .changed()
+    {
+        state.export_dpi = Some(dpi);
+    }
+
+    if clicked
+        && let Some(doc_id) = state.preview_doc_id
+        && let Some(output_dir) = rfd::FileDialog::new().pick_folder()
+    {
+        log::info!(
+            "Exporting sheet images at {} DPI to: {}",
+            dpi,
+            output_dir.display()
+        );
+        let _ = command_tx.send(PdfCommand::ExportImages {
+            doc_id,
+            dpi,
+            double_sided,
+            output_dir,
+        });
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn show_export_images_button(
+    _ui: &mut egui::Ui,
+    _state: &mut ImposeState,
+    _command_tx: &mpsc::UnboundedSender<PdfCommand>,
+) {
+}
+
+/// Offer to stop the running generation, if there is one
+fn show_cancel_button(
+    ui: &mut egui::Ui,
+    state: &ImposeState,
+    command_tx: &mpsc::UnboundedSender<PdfCommand>,
+) {
+    if let Some(operation_id) = state.generation
+        && ui.button("⏹ Cancel").clicked()
+    {
+        let _ = command_tx.send(PdfCommand::CancelOperation { operation_id });
+    }
+}
@@ -1,7 +1,10 @@
 use eframe::egui;
-use pdf_impose::{BindingType, SplitMode};
+use pdf_impose::{
+    BindingType, CoverMode, PageNumberHorizontal, PageNumberVertical, Rotation, SplitMode,
+};
 
 use super::state::ImposeState;
+use crate::ui_components::button_group;
 
 pub fn show(ui: &mut egui::Ui, state: &mut ImposeState) {
     egui::CollapsingHeader::new("⚙ Additional Options")
@@ -10,6 +13,16 @@ pub fn show(ui: &mut egui::Ui, state: &mut ImposeState) {
             if show_page_numbering(ui, state) {
                 state.needs_regeneration = true;
             }
+            if ui
+                .checkbox(&mut state.options.dummy_mode, "Folding dummy")
+                .on_hover_text(
+                    "Print each page's number, signature and an up arrow instead of its \
+                     content, to fold and check before the real run",
+                )
+                .changed()
+            {
+                state.needs_regeneration = true;
+            }
             ui.add_space(5.0);
 
             if show_flyleaves(ui, state) {
@@ -17,12 +30,48 @@ pub fn show(ui: &mut egui::Ui, state: &mut ImposeState) {
             }
             ui.add_space(5.0);
 
+            if show_cover(ui, state) {
+                state.needs_regeneration = true;
+            }
+            ui.add_space(5.0);
+
+            if show_rotation_selector(ui, &mut state.options.source_rotation) {
+                state.needs_regeneration = true;
+            }
+            ui.add_space(5.0);
+
             if show_split_mode(ui, state) {
                 state.needs_regeneration = true;
             }
+            ui.add_space(5.0);
+
+            // Compression, outlines and the title don't change how the preview looks
+            ui.checkbox(&mut state.options.compress_output, "Compress output");
+            ui.checkbox(
+                &mut state.options.generate_outline,
+                "Outline of signatures and sheets",
+            );
+            ui.checkbox(
+                &mut state.options.bookmark_locations,
+                "Show sheet and slot in bookmark titles",
+            );
+            show_title_override(ui, &mut state.options.title_override);
         });
 }
 
+/// Title for the output's document properties; left empty, the first
+/// input's title is kept
+fn show_title_override(ui: &mut egui::Ui, title_override: &mut Option<String>) {
+    ui.horizontal(|ui| {
+        ui.label("Title:");
+        let mut title = title_override.clone().unwrap_or_default();
+        let edit = egui::TextEdit::singleline(&mut title).hint_text("From the first input");
+        if ui.add(edit).changed() {
+            *title_override = (!title.is_empty()).then_some(title);
+        }
+    });
+}
+
 fn show_page_numbering(ui: &mut egui::Ui, state: &mut ImposeState) -> bool {
     let mut changed = false;
 
@@ -39,6 +88,40 @@ fn show_page_numbering(ui: &mut egui::Ui, state: &mut ImposeState) -> bool {
         }
     });
 
+    if state.options.add_page_numbers {
+        ui.indent("page_number_style", |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Font size:");
+                changed |= ui
+                    .add(
+                        egui::DragValue::new(&mut state.options.page_number_font_size)
+                            .range(4.0..=24.0)
+                            .speed(0.5)
+                            .suffix(" pt"),
+                    )
+                    .changed();
+            });
+
+            changed |= button_group(
+                ui,
+                &mut state.options.page_number_vertical,
+                &[
+                    (PageNumberVertical::Header, "Header"),
+                    (PageNumberVertical::Footer, "Footer"),
+                ],
+            );
+            changed |= button_group(
+                ui,
+                &mut state.options.page_number_horizontal,
+                &[
+                    (PageNumberHorizontal::Inner, "Inner"),
+                    (PageNumberHorizontal::Center, "Center"),
+                    (PageNumberHorizontal::Outer, "Outer"),
+                ],
+            );
+        });
+    }
+
     changed
 }
 
@@ -62,6 +145,63 @@ fn show_flyleaves(ui: &mut egui::Ui, state: &mut ImposeState) -> bool {
     changed
 }
 
+fn show_cover(ui: &mut egui::Ui, state: &mut ImposeState) -> bool {
+    let cover = &mut state.options.cover;
+    let mut changed = false;
+
+    ui.horizontal(|ui| {
+        ui.label("Cover pages, front:");
+        changed |= ui
+            .add(egui::DragValue::new(&mut cover.front_pages).range(0..=2))
+            .changed();
+        ui.label("back:");
+        changed |= ui
+            .add(egui::DragValue::new(&mut cover.back_pages).range(0..=2))
+            .changed();
+    });
+
+    if cover.pages() > 0 {
+        ui.indent("cover_options", |ui| {
+            changed |= button_group(
+                ui,
+                &mut cover.mode,
+                &[
+                    (CoverMode::Skip, "Leave out"),
+                    (CoverMode::SeparateDocument, "Separate cover file"),
+                ],
+            );
+
+            if cover.mode == CoverMode::SeparateDocument {
+                ui.horizontal(|ui| {
+                    ui.label("Paper thickness:");
+                    changed |= ui
+                        .add(
+                            egui::DragValue::new(&mut cover.paper_thickness_mm)
+                                .range(0.0..=1.0)
+                                .speed(0.005)
+                                .suffix(" mm"),
+                        )
+                        .changed();
+                });
+            }
+        });
+    }
+
+    changed
+}
+
+fn show_rotation_selector(ui: &mut egui::Ui, rotation: &mut Rotation) -> bool {
+    let rotations = [
+        (Rotation::None, "None"),
+        (Rotation::Clockwise90, "90°"),
+        (Rotation::Clockwise180, "180°"),
+        (Rotation::Clockwise270, "270°"),
+    ];
+
+    ui.label("Rotate source pages:");
+    button_group(ui, rotation, &rotations)
+}
+
 fn show_split_mode(ui: &mut egui::Ui, state: &mut ImposeState) -> bool {
     ui.label("Split output:");
 
@@ -108,17 +248,16 @@ fn show_split_mode_selector(ui: &mut egui::Ui, state: &mut ImposeState) -> bool
             changed = true;
         }
 
-        if is_signature_binding(&state.options.binding_type) {
-            if ui
+        if is_signature_binding(&state.options.binding_type)
+            && ui
                 .selectable_label(
                     matches!(state.options.split_mode, SplitMode::BySignatures(_)),
                     "By signatures",
                 )
                 .clicked()
-            {
-                state.options.split_mode = SplitMode::BySignatures(5);
-                changed = true;
-            }
+        {
+            state.options.split_mode = SplitMode::BySignatures(5);
+            changed = true;
         }
     });
 
@@ -1,5 +1,5 @@
 use eframe::egui;
-use pdf_impose::{BindingType, PageArrangement};
+use pdf_impose::{BindingEdge, BindingType, NUpOrder, PageArrangement, SpineEdge, StepAndRepeat};
 
 use super::state::ImposeState;
 use crate::ui_components::button_group;
@@ -14,20 +14,103 @@ pub fn show(ui: &mut egui::Ui, state: &mut ImposeState) {
                 (BindingType::SideStitch, "Side Stitch"),
                 (BindingType::Spiral, "Spiral"),
                 (BindingType::CaseBinding, "Case"),
+                (BindingType::CutAndStack, "Cut & Stack"),
             ];
 
             ui.label("Binding type:");
-            if button_group(ui, &mut state.options.binding_type, &binding_types) {
+            if button_group(ui, &mut state.options.binding_type, &binding_types)
+                | show_n_up_selector(ui, &mut state.options.binding_type)
+            {
                 log::info!("Binding type changed to: {:?}", state.options.binding_type);
                 state.needs_regeneration = true;
             }
 
             ui.add_space(5.0);
 
+            let binding_edges = [
+                (BindingEdge::Left, "Left (LTR)"),
+                (BindingEdge::Right, "Right (RTL)"),
+            ];
+
+            ui.label("Spine on:");
+            if button_group(ui, &mut state.options.binding_edge, &binding_edges) {
+                state.needs_regeneration = true;
+            }
+
+            ui.add_space(5.0);
+
             if is_signature_binding(&state.options.binding_type) {
                 if show_arrangement_selector(ui, &mut state.options.page_arrangement) {
                     state.needs_regeneration = true;
                 }
+                if state.options.page_arrangement == PageArrangement::Quarto {
+                    let spine_edges = [
+                        (SpineEdge::Auto, "Auto"),
+                        (SpineEdge::Vertical, "Vertical"),
+                        (SpineEdge::Horizontal, "Horizontal"),
+                    ];
+                    ui.label("Spine fold runs:");
+                    if button_group(ui, &mut state.options.spine_edge, &spine_edges) {
+                        state.needs_regeneration = true;
+                    }
+                }
+                if ui
+                    .checkbox(
+                        &mut state.options.allow_short_last_signature,
+                        "Shorter last signature instead of blank pages",
+                    )
+                    .changed()
+                {
+                    state.needs_regeneration = true;
+                }
+                if ui
+                    .checkbox(
+                        &mut state.options.variable_last_signature,
+                        "Any size of last signature (nested folios if needed)",
+                    )
+                    .changed()
+                {
+                    state.needs_regeneration = true;
+                }
+            }
+
+            // Only a quarto signature can fold its spine horizontally
+            if state.options.spine_edge == SpineEdge::Horizontal
+                && !(is_signature_binding(&state.options.binding_type)
+                    && state.options.page_arrangement == PageArrangement::Quarto
+                    && state.options.step_and_repeat.is_none())
+            {
+                state.options.spine_edge = SpineEdge::Auto;
+                state.needs_regeneration = true;
+            }
+
+            if state.options.binding_type == BindingType::CutAndStack
+                && show_cut_stack_selector(ui, &mut state.options.page_arrangement)
+            {
+                state.needs_regeneration = true;
+            }
+
+            if matches!(state.options.binding_type, BindingType::NUp { .. }) {
+                let orders = [
+                    (NUpOrder::RowMajor, "Across rows"),
+                    (NUpOrder::ColumnMajor, "Down columns"),
+                ];
+                ui.label("Page order:");
+                if button_group(ui, &mut state.options.n_up_order, &orders) {
+                    state.needs_regeneration = true;
+                }
+                if ui
+                    .checkbox(&mut state.options.cell_borders, "Border around each page")
+                    .changed()
+                {
+                    state.needs_regeneration = true;
+                }
+            }
+
+            ui.add_space(5.0);
+
+            if show_step_and_repeat(ui, &mut state.options.step_and_repeat) {
+                state.needs_regeneration = true;
             }
         });
 }
@@ -43,6 +126,8 @@ fn show_arrangement_selector(ui: &mut egui::Ui, arrangement: &mut PageArrangemen
         (PageArrangement::Folio, "Folio (4pp)"),
         (PageArrangement::Quarto, "Quarto (8pp)"),
         (PageArrangement::Octavo, "Octavo (16pp)"),
+        (PageArrangement::Sexto, "Sexto (12pp)"),
+        (PageArrangement::Duodecimo, "Duodecimo (24pp)"),
     ];
 
     ui.label("Page arrangement:");
@@ -70,3 +155,91 @@ fn show_arrangement_selector(ui: &mut egui::Ui, arrangement: &mut PageArrangemen
 
     changed
 }
+
+fn show_n_up_selector(ui: &mut egui::Ui, binding: &mut BindingType) -> bool {
+    let mut changed = false;
+
+    if let BindingType::NUp { rows, cols } = binding {
+        ui.horizontal(|ui| {
+            ui.label("Grid:");
+            changed |= ui.add(egui::DragValue::new(cols).range(1..=10)).changed();
+            ui.label("×");
+            changed |= ui.add(egui::DragValue::new(rows).range(1..=10)).changed();
+        });
+    } else if ui.button("N-up (handouts)").clicked() {
+        *binding = BindingType::NUp { rows: 3, cols: 3 };
+        changed = true;
+    }
+
+    changed
+}
+
+fn show_cut_stack_selector(ui: &mut egui::Ui, arrangement: &mut PageArrangement) -> bool {
+    let mut changed = false;
+
+    // Only 2-up, 4-up and 8-up sheets can be cut and stacked
+    if !matches!(
+        arrangement,
+        PageArrangement::Folio | PageArrangement::Quarto | PageArrangement::Octavo
+    ) {
+        *arrangement = PageArrangement::Folio;
+        changed = true;
+    }
+
+    let arrangements = [
+        (PageArrangement::Folio, "2-up"),
+        (PageArrangement::Quarto, "4-up"),
+        (PageArrangement::Octavo, "8-up"),
+    ];
+
+    ui.label("Pages per side:");
+    changed |= button_group(ui, arrangement, &arrangements);
+
+    changed
+}
+
+fn show_step_and_repeat(ui: &mut egui::Ui, step_and_repeat: &mut Option<StepAndRepeat>) -> bool {
+    let mut enabled = step_and_repeat.is_some();
+    let mut changed = ui
+        .checkbox(&mut enabled, "Step and repeat (same page in every cell)")
+        .changed();
+    if changed {
+        *step_and_repeat = enabled.then_some(StepAndRepeat {
+            cols: 2,
+            rows: 5,
+            copies: None,
+        });
+    }
+
+    if let Some(repeat) = step_and_repeat {
+        ui.indent("step_and_repeat", |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Grid:");
+                changed |= ui
+                    .add(egui::DragValue::new(&mut repeat.cols).range(1..=20))
+                    .changed();
+                ui.label("×");
+                changed |= ui
+                    .add(egui::DragValue::new(&mut repeat.rows).range(1..=20))
+                    .changed();
+            });
+
+            let mut limit_copies = repeat.copies.is_some();
+            if ui.checkbox(&mut limit_copies, "Copies per page").changed() {
+                repeat.copies = limit_copies.then_some(repeat.cells());
+                changed = true;
+            }
+            let cells = repeat.cells();
+            if let Some(copies) = &mut repeat.copies {
+                ui.horizontal(|ui| {
+                    changed |= ui
+                        .add(egui::DragValue::new(copies).range(1..=10000))
+                        .changed();
+                    ui.label(format!("({} sheets each)", copies.div_ceil(cells)));
+                });
+            }
+        });
+    }
+
+    changed
+}
@@ -1,5 +1,6 @@
 use eframe::egui;
 use pdf_async_runtime::PdfCommand;
+use pdf_impose::PageRange;
 use tokio::sync::mpsc;
 
 use super::state::ImposeState;
@@ -30,8 +31,154 @@ pub fn show(
 
             ui.add_space(5.0);
 
-            if FileListEditor::new(&mut state.options.input_files).show(ui) {
+            // Rebuild the range text from the options when they've been replaced (e.g. config load)
+            if state.page_range_inputs.len() != state.options.input_files.len() {
+                state.page_range_inputs = state
+                    .options
+                    .input_ranges
+                    .iter()
+                    .map(|range| range.as_ref().map(ToString::to_string).unwrap_or_default())
+                    .collect();
+            }
+
+            if FileListEditor::new(&mut state.options.input_files)
+                .with_page_ranges(&mut state.page_range_inputs, is_valid_page_range)
+                .show(ui)
+            {
+                state.options.input_ranges = parse_page_ranges(&state.page_range_inputs);
+                state.needs_regeneration = true;
+            }
+
+            ui.horizontal(|ui| {
+                ui.label("Password:");
+                let mut password = state.options.password.clone().unwrap_or_default();
+                let edit = egui::TextEdit::singleline(&mut password)
+                    .password(true)
+                    .hint_text("For encrypted files");
+                if ui.add(edit).changed() {
+                    state.options.password = (!password.is_empty()).then_some(password);
+                    state.needs_regeneration = true;
+                }
+            });
+
+            // Rebuild the text after the options have been replaced (e.g. config load)
+            if state.blank_inserts_input.is_empty() && !state.options.blank_inserts.is_empty() {
+                state.blank_inserts_input = format_page_numbers(&state.options.blank_inserts);
+            }
+            if state.recto_pages_input.is_empty() && !state.options.chapter_starts_recto.is_empty()
+            {
+                state.recto_pages_input = format_page_numbers(&state.options.chapter_starts_recto);
+            }
+            ui.horizontal(|ui| {
+                ui.label("Blank page before pages:");
+                if ui
+                    .text_edit_singleline(&mut state.blank_inserts_input)
+                    .on_hover_text(
+                        "Comma-separated page numbers, e.g. to start a chapter on a recto",
+                    )
+                    .changed()
+                {
+                    state.options.blank_inserts = parse_page_numbers(&state.blank_inserts_input);
+                    state.needs_regeneration = true;
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label("Start on a recto:");
+                if ui
+                    .text_edit_singleline(&mut state.recto_pages_input)
+                    .on_hover_text(
+                        "Comma-separated page numbers; a blank goes before any that would fall on a verso",
+                    )
+                    .changed()
+                {
+                    state.options.chapter_starts_recto =
+                        parse_page_numbers(&state.recto_pages_input);
+                    state.needs_regeneration = true;
+                }
+            });
+
+            if ui
+                .checkbox(&mut state.options.reverse_source, "Reverse page order")
+                .on_hover_text("For sources scanned last page first")
+                .changed()
+            {
+                state.needs_regeneration = true;
+            }
+
+            if ui
+                .checkbox(
+                    &mut state.options.chapter_recto,
+                    "Start each file on a recto",
+                )
+                .on_hover_text("Inserts a blank before files that would start on a left-hand page")
+                .changed()
+            {
                 state.needs_regeneration = true;
             }
         });
 }
+
+/// Ask for the password of an encrypted input, regenerating with it once
+/// it is entered
+pub fn show_password_prompt(ctx: &egui::Context, state: &mut ImposeState) {
+    let Some(prompt) = &mut state.password_prompt else {
+        return;
+    };
+
+    let mut submitted = false;
+    let mut cancelled = false;
+    egui::Window::new("🔒 Password Required")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .show(ctx, |ui| {
+            if prompt.wrong_password {
+                ui.colored_label(ui.visuals().error_fg_color, "That password is wrong.");
+            } else {
+                ui.label("An input file is encrypted. Enter its password to open it.");
+            }
+            let response = ui.add(egui::TextEdit::singleline(&mut prompt.input).password(true));
+            response.request_focus();
+            submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+
+            ui.horizontal(|ui| {
+                submitted |= ui.button("Open").clicked();
+                cancelled = ui.button("Cancel").clicked();
+            });
+        });
+
+    if submitted && !prompt.input.is_empty() {
+        state.options.password = Some(std::mem::take(&mut prompt.input));
+        state.password_prompt = None;
+        state.needs_regeneration = true;
+    } else if cancelled {
+        state.password_prompt = None;
+    }
+}
+
+fn is_valid_page_range(text: &str) -> bool {
+    text.parse::<PageRange>().is_ok()
+}
+
+/// Parse 1-based page numbers into 0-based positions, skipping anything
+/// that isn't a page number
+fn parse_page_numbers(text: &str) -> Vec<usize> {
+    text.split(',')
+        .filter_map(|page| page.trim().parse::<usize>().ok())
+        .filter_map(|page| page.checked_sub(1))
+        .collect()
+}
+
+/// Format 0-based positions as 1-based page numbers
+fn format_page_numbers(positions: &[usize]) -> String {
+    positions
+        .iter()
+        .map(|position| (position + 1).to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Parse the range fields; blank or invalid entries select every page
+fn parse_page_ranges(texts: &[String]) -> Vec<Option<PageRange>> {
+    texts.iter().map(|text| text.trim().parse().ok()).collect()
+}
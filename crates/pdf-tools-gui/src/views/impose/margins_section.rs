@@ -35,6 +35,74 @@ pub fn show(ui: &mut egui::Ui, state: &mut ImposeState) {
                 .show(ui);
             });
 
+            ui.add_space(8.0);
+            ui.horizontal(|ui| {
+                ui.label("Bleed:");
+                changed |= ui
+                    .add(
+                        egui::DragValue::new(&mut state.options.bleed_mm)
+                            .range(0.0..=10.0)
+                            .speed(0.1)
+                            .suffix(" mm"),
+                    )
+                    .on_hover_text("Extends content past the trim line on outer and cut edges")
+                    .changed();
+            });
+
+            if state.options.uses_cell_gap() {
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    ui.label("Gap between cells:");
+                    changed |= ui
+                        .add(
+                            egui::DragValue::new(&mut state.options.cell_gap_mm)
+                                .range(0.0..=30.0)
+                                .speed(0.1)
+                                .suffix(" mm"),
+                        )
+                        .on_hover_text("Gutter left between cells for cutting them apart")
+                        .changed();
+                });
+            }
+
+            ui.add_space(8.0);
+            ui.horizontal(|ui| {
+                ui.label("Back side offset:");
+                let (x, y) = &mut state.options.back_side_offset_mm;
+                for value in [x, y] {
+                    changed |= ui
+                        .add(
+                            egui::DragValue::new(value)
+                                .range(-10.0..=10.0)
+                                .speed(0.1)
+                                .suffix(" mm"),
+                        )
+                        .on_hover_text(
+                            "Shifts back sides (x, y) to line them up with the fronts \
+                             when the printer's duplex feed drifts",
+                        )
+                        .changed();
+                }
+            });
+
+            if state.options.binding_type.uses_signatures() {
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    ui.label("Creep per leaf:");
+                    changed |= ui
+                        .add(
+                            egui::DragValue::new(&mut state.options.creep_mm)
+                                .range(0.0..=2.0)
+                                .speed(0.01)
+                                .suffix(" mm"),
+                        )
+                        .on_hover_text(
+                            "Shifts outer leaves toward the spine; about the paper thickness",
+                        )
+                        .changed();
+                });
+            }
+
             if changed {
                 state.needs_regeneration = true;
             }
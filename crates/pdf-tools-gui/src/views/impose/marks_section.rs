@@ -1,4 +1,5 @@
 use eframe::egui;
+use pdf_impose::{BindingType, HoleSpacing, MarkStyle, PrinterMarks, PunchGuides, SewingMarks};
 
 use super::state::ImposeState;
 
@@ -8,9 +9,43 @@ pub fn show(ui: &mut egui::Ui, state: &mut ImposeState) {
         .show(ui, |ui| {
             let mut changed = false;
 
+            // Common sets of marks, keeping the mark style; anything else is
+            // left to the checkboxes below
+            ui.horizontal_wrapped(|ui| {
+                let sets = [
+                    ("None", PrinterMarks::default()),
+                    ("Folding guide", PrinterMarks::folding_guide()),
+                    ("Print shop", PrinterMarks::print_shop()),
+                    ("All", PrinterMarks::all()),
+                ];
+                for (label, set) in sets {
+                    let set = PrinterMarks {
+                        style: state.options.marks.style,
+                        ..set
+                    };
+                    let selected = state.options.marks == set;
+                    if ui.radio(selected, label).clicked() && !selected {
+                        state.options.marks = set;
+                        changed = true;
+                    }
+                }
+            });
+            ui.separator();
+
             changed |= ui
                 .checkbox(&mut state.options.marks.fold_lines, "Fold lines")
                 .changed();
+            ui.indent("fold_labels", |ui| {
+                changed |= ui
+                    .add_enabled(
+                        state.options.marks.fold_lines,
+                        egui::Checkbox::new(
+                            &mut state.options.marks.fold_labels,
+                            "Label fold order and direction",
+                        ),
+                    )
+                    .changed();
+            });
             changed |= ui
                 .checkbox(
                     &mut state.options.marks.cut_lines,
@@ -20,7 +55,7 @@ pub fn show(ui: &mut egui::Ui, state: &mut ImposeState) {
             changed |= ui
                 .checkbox(
                     &mut state.options.marks.crop_marks,
-                    "Crop marks (sheet edges)",
+                    "Crop marks (trimmed sheet)",
                 )
                 .changed();
             changed |= ui
@@ -32,9 +67,197 @@ pub fn show(ui: &mut egui::Ui, state: &mut ImposeState) {
                     "Registration marks",
                 )
                 .changed();
+            changed |= ui
+                .checkbox(
+                    &mut state.options.marks.collation_marks,
+                    "Collation marks (spine back-step)",
+                )
+                .changed();
+            changed |= show_sewing_marks(ui, &mut state.options.marks.sewing_marks);
+            changed |= ui
+                .checkbox(
+                    &mut state.options.marks.spine_marks,
+                    "Spine marks (head and tail of the spine fold)",
+                )
+                .changed();
+
+            // Punch guides are only valid for bindings punched through the leaf
+            if matches!(
+                state.options.binding_type,
+                BindingType::SideStitch | BindingType::Spiral
+            ) {
+                changed |= show_punch_guides(ui, &mut state.options.marks.punch_guides);
+            } else if state.options.marks.punch_guides.take().is_some() {
+                changed = true;
+            }
+
+            changed |= ui
+                .checkbox(
+                    &mut state.options.marks.color_bars,
+                    "Color bars (bottom sheet margin)",
+                )
+                .changed();
+            changed |= ui
+                .checkbox(
+                    &mut state.options.marks.slot_labels,
+                    "Page number in each cell (proofing)",
+                )
+                .changed();
+
+            changed |= show_mark_style(ui, &mut state.options.marks.style);
+
+            let needed_mm = state.options.marks.min_sheet_margin_mm();
+            let sheet = &state.options.margins.sheet;
+            let margin_mm = sheet
+                .top_mm
+                .min(sheet.bottom_mm)
+                .min(sheet.left_mm)
+                .min(sheet.right_mm);
+            if margin_mm < needed_mm {
+                ui.colored_label(
+                    egui::Color32::from_rgb(255, 170, 0),
+                    format!(
+                        "⚠ These marks need a sheet margin of at least {:.1} mm",
+                        needed_mm
+                    ),
+                );
+            }
 
             if changed {
                 state.needs_regeneration = true;
             }
         });
 }
+
+fn show_mark_style(ui: &mut egui::Ui, style: &mut MarkStyle) -> bool {
+    let mut changed = false;
+    ui.horizontal(|ui| {
+        ui.label("Mark color:");
+        changed |= ui.color_edit_button_rgb(&mut style.color_rgb).changed();
+        ui.label("Weight:");
+        changed |= ui
+            .add(
+                egui::DragValue::new(&mut style.line_width_pt)
+                    .range(0.05..=4.0)
+                    .speed(0.05)
+                    .suffix(" pt"),
+            )
+            .changed();
+    });
+    ui.horizontal(|ui| {
+        ui.label("Mark length:");
+        changed |= ui
+            .add(
+                egui::DragValue::new(&mut style.mark_length_pt)
+                    .range(0.0..=36.0)
+                    .suffix(" pt"),
+            )
+            .changed();
+        ui.label("Gap:");
+        changed |= ui
+            .add(
+                egui::DragValue::new(&mut style.gap_pt)
+                    .range(0.0..=18.0)
+                    .suffix(" pt"),
+            )
+            .changed();
+    });
+    changed
+}
+
+fn show_sewing_marks(ui: &mut egui::Ui, sewing_marks: &mut Option<SewingMarks>) -> bool {
+    let mut enabled = sewing_marks.is_some();
+    let mut changed = ui.checkbox(&mut enabled, "Sewing stations").changed();
+    if changed {
+        *sewing_marks = enabled.then(SewingMarks::default);
+    }
+
+    if let Some(sewing) = sewing_marks {
+        ui.indent("sewing_marks", |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Stations:");
+                changed |= ui
+                    .add(egui::DragValue::new(&mut sewing.stations).range(1..=12))
+                    .changed();
+            });
+            ui.horizontal(|ui| {
+                ui.label("Head/tail margin:");
+                changed |= ui
+                    .add(
+                        egui::DragValue::new(&mut sewing.head_tail_margin_mm)
+                            .range(0.0..=50.0)
+                            .speed(0.5)
+                            .suffix(" mm"),
+                    )
+                    .changed();
+            });
+        });
+    }
+
+    changed
+}
+
+fn show_punch_guides(ui: &mut egui::Ui, punch_guides: &mut Option<PunchGuides>) -> bool {
+    let mut enabled = punch_guides.is_some();
+    let mut changed = ui.checkbox(&mut enabled, "Punch-hole guides").changed();
+    if changed {
+        *punch_guides = enabled.then(PunchGuides::default);
+    }
+
+    if let Some(punch) = punch_guides {
+        ui.indent("punch_guides", |ui| {
+            ui.horizontal(|ui| {
+                let by_count = matches!(punch.spacing, HoleSpacing::Count(_));
+                if ui.radio(by_count, "Hole count").clicked() && !by_count {
+                    punch.spacing = HoleSpacing::Count(3);
+                    changed = true;
+                }
+                if ui.radio(!by_count, "Pitch").clicked() && by_count {
+                    punch.spacing = PunchGuides::default().spacing;
+                    changed = true;
+                }
+            });
+            ui.horizontal(|ui| match &mut punch.spacing {
+                HoleSpacing::Count(count) => {
+                    ui.label("Holes:");
+                    changed |= ui.add(egui::DragValue::new(count).range(1..=60)).changed();
+                }
+                HoleSpacing::PitchMm(pitch) => {
+                    ui.label("Pitch:");
+                    changed |= ui
+                        .add(
+                            egui::DragValue::new(pitch)
+                                .range(2.0..=120.0)
+                                .speed(0.1)
+                                .suffix(" mm"),
+                        )
+                        .changed();
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label("Edge offset:");
+                changed |= ui
+                    .add(
+                        egui::DragValue::new(&mut punch.edge_offset_mm)
+                            .range(0.0..=30.0)
+                            .speed(0.5)
+                            .suffix(" mm"),
+                    )
+                    .changed();
+            });
+            ui.horizontal(|ui| {
+                ui.label("Hole diameter:");
+                changed |= ui
+                    .add(
+                        egui::DragValue::new(&mut punch.hole_diameter_mm)
+                            .range(1.0..=12.0)
+                            .speed(0.1)
+                            .suffix(" mm"),
+                    )
+                    .changed();
+            });
+        });
+    }
+
+    changed
+}
@@ -8,7 +8,7 @@ mod output_section;
 mod state;
 mod statistics_section;
 
-pub use state::ImposeState;
+pub use state::{ImposeState, PasswordPrompt};
 
 use eframe::egui;
 use pdf_async_runtime::PdfCommand;
@@ -66,6 +66,7 @@ pub fn show_impose(
         });
 
     show_preview_area(ui, state, command_tx);
+    input_section::show_password_prompt(ui.ctx(), state);
 }
 
 fn show_preview_area(
@@ -1,5 +1,8 @@
 use eframe::egui;
-use pdf_impose::{Orientation, OutputFormat, PaperSize, Rotation, ScalingMode};
+use pdf_impose::{
+    BackOrder, ContentAlignment, FlipEdge, HorizontalAlignment, Orientation, OutputFormat, PageBox,
+    PaperSize, ScalingMode, VerticalAlignment,
+};
 
 use super::state::ImposeState;
 use crate::ui_components::{button_group, enum_selector};
@@ -23,28 +26,64 @@ pub fn show(ui: &mut egui::Ui, state: &mut ImposeState) {
             }
             ui.add_space(5.0);
 
+            if matches!(
+                state.options.output_format,
+                OutputFormat::SingleSidedSequence | OutputFormat::TwoSided
+            ) && show_back_order_selector(ui, &mut state.options.single_sided_back_order)
+            {
+                state.needs_regeneration = true;
+            }
+
+            if show_duplex_flip_selector(ui, &mut state.options.duplex_flip) {
+                state.needs_regeneration = true;
+            }
+
             if show_scaling_mode_selector(ui, &mut state.options.scaling_mode) {
                 state.needs_regeneration = true;
             }
+            if ui
+                .checkbox(
+                    &mut state.options.auto_rotate_to_fit,
+                    "Turn pages to fit their leaf",
+                )
+                .on_hover_text("Rotates pages 90° when that lets them print larger")
+                .changed()
+            {
+                state.needs_regeneration = true;
+            }
             ui.add_space(5.0);
 
-            if show_rotation_selector(ui, &mut state.options.source_rotation) {
+            if show_alignment_selector(ui, &mut state.options.content_alignment) {
+                state.needs_regeneration = true;
+            }
+            ui.add_space(5.0);
+
+            if show_source_box_selector(ui, &mut state.options.source_box) {
+                state.needs_regeneration = true;
+            }
+            if enum_selector(
+                ui,
+                "fallback_page_size",
+                "Pages with an invalid box as:",
+                &mut state.options.fallback_page_size,
+                &PAPER_SIZES,
+            ) {
                 state.needs_regeneration = true;
             }
         });
 }
 
-fn show_paper_size_selector(ui: &mut egui::Ui, paper_size: &mut PaperSize) -> bool {
-    let paper_sizes = [
-        (PaperSize::Letter, "Letter"),
-        (PaperSize::Legal, "Legal"),
-        (PaperSize::Tabloid, "Tabloid"),
-        (PaperSize::A3, "A3"),
-        (PaperSize::A4, "A4"),
-        (PaperSize::A5, "A5"),
-    ];
+const PAPER_SIZES: [(PaperSize, &str); 6] = [
+    (PaperSize::Letter, "Letter"),
+    (PaperSize::Legal, "Legal"),
+    (PaperSize::Tabloid, "Tabloid"),
+    (PaperSize::A3, "A3"),
+    (PaperSize::A4, "A4"),
+    (PaperSize::A5, "A5"),
+];
 
-    enum_selector(ui, "paper_size", "Paper size:", paper_size, &paper_sizes)
+fn show_paper_size_selector(ui: &mut egui::Ui, paper_size: &mut PaperSize) -> bool {
+    enum_selector(ui, "paper_size", "Paper size:", paper_size, &PAPER_SIZES)
 }
 
 fn show_orientation_selector(ui: &mut egui::Ui, orientation: &mut Orientation) -> bool {
@@ -73,26 +112,88 @@ fn show_output_format_selector(ui: &mut egui::Ui, output_format: &mut OutputForm
     )
 }
 
+fn show_back_order_selector(ui: &mut egui::Ui, back_order: &mut BackOrder) -> bool {
+    let back_orders = [
+        (BackOrder::Reversed, "Reversed (N..1)"),
+        (BackOrder::Normal, "Normal (1..N)"),
+    ];
+
+    ui.label("Back side order:");
+    let changed = button_group(ui, back_order, &back_orders);
+    ui.add_space(5.0);
+    changed
+}
+
+fn show_duplex_flip_selector(ui: &mut egui::Ui, duplex_flip: &mut FlipEdge) -> bool {
+    let flip_edges = [
+        (FlipEdge::LongEdge, "Long edge"),
+        (FlipEdge::ShortEdge, "Short edge"),
+    ];
+
+    ui.label("Duplex flips on:");
+    let changed = button_group(ui, duplex_flip, &flip_edges);
+    ui.add_space(5.0);
+    changed
+}
+
 fn show_scaling_mode_selector(ui: &mut egui::Ui, scaling_mode: &mut ScalingMode) -> bool {
+    // Keep the current percentage so its button stays selected
+    let percent = match *scaling_mode {
+        ScalingMode::Percent(percent) => percent,
+        _ => 100.0,
+    };
     let scaling_modes = [
         (ScalingMode::Fit, "Fit"),
+        (ScalingMode::FitNoUpscale, "Fit (no upscale)"),
         (ScalingMode::Fill, "Fill"),
         (ScalingMode::None, "None"),
         (ScalingMode::Stretch, "Stretch"),
+        (ScalingMode::Percent(percent), "Percent"),
     ];
 
     ui.label("Scaling mode:");
-    button_group(ui, scaling_mode, &scaling_modes)
+    let mut changed = button_group(ui, scaling_mode, &scaling_modes);
+
+    if let ScalingMode::Percent(percent) = scaling_mode {
+        changed |= ui
+            .add(
+                egui::DragValue::new(percent)
+                    .range(1.0..=400.0)
+                    .speed(1.0)
+                    .suffix("%"),
+            )
+            .changed();
+    }
+    changed
+}
+
+fn show_alignment_selector(ui: &mut egui::Ui, alignment: &mut ContentAlignment) -> bool {
+    let horizontal = [
+        (HorizontalAlignment::TowardSpine, "Spine"),
+        (HorizontalAlignment::Center, "Center"),
+        (HorizontalAlignment::TowardForeEdge, "Fore-edge"),
+    ];
+    let vertical = [
+        (VerticalAlignment::TowardFold, "Fold"),
+        (VerticalAlignment::Top, "Top"),
+        (VerticalAlignment::Center, "Center"),
+        (VerticalAlignment::Bottom, "Bottom"),
+    ];
+
+    ui.label("Content alignment:");
+    let mut changed = button_group(ui, &mut alignment.horizontal, &horizontal);
+    changed |= button_group(ui, &mut alignment.vertical, &vertical);
+    changed
 }
 
-fn show_rotation_selector(ui: &mut egui::Ui, rotation: &mut Rotation) -> bool {
-    let rotations = [
-        (Rotation::None, "None"),
-        (Rotation::Clockwise90, "90°"),
-        (Rotation::Clockwise180, "180°"),
-        (Rotation::Clockwise270, "270°"),
+fn show_source_box_selector(ui: &mut egui::Ui, source_box: &mut PageBox) -> bool {
+    let source_boxes = [
+        (PageBox::MediaBox, "Media"),
+        (PageBox::CropBox, "Crop"),
+        (PageBox::TrimBox, "Trim"),
+        (PageBox::BleedBox, "Bleed"),
     ];
 
-    ui.label("Source rotation:");
-    button_group(ui, rotation, &rotations)
+    ui.label("Source page box:");
+    button_group(ui, source_box, &source_boxes)
 }
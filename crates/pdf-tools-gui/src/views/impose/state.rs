@@ -1,29 +1,48 @@
-use pdf_async_runtime::DocumentId;
+use pdf_async_runtime::{DocumentId, OperationId};
 use pdf_impose::{ImpositionOptions, ImpositionStatistics};
 use std::path::PathBuf;
 
 use super::super::ViewerState;
 
+#[derive(Default)]
 pub struct ImposeState {
     pub options: ImpositionOptions,
+    /// Page range text for each input file, parsed into `options.input_ranges`
+    pub page_range_inputs: Vec<String>,
+    /// 1-based page numbers to put a blank page before, parsed into
+    /// `options.blank_inserts`
+    pub blank_inserts_input: String,
+    /// 1-based page numbers that must start on a recto, parsed into
+    /// `options.chapter_starts_recto`
+    pub recto_pages_input: String,
     pub preview_doc_id: Option<DocumentId>,
     pub preview_page_count: usize,
     pub stats: Option<ImpositionStatistics>,
+    /// Page count to work out statistics for before any files are chosen
+    pub estimate_pages: Option<usize>,
+    #[allow(dead_code)]
     pub loaded_docs: Vec<(PathBuf, usize)>,
     pub preview_viewer: Option<ViewerState>,
     pub needs_regeneration: bool,
+    /// Names of the imposition presets, once listed by the worker
+    pub preset_names: Option<Vec<String>>,
+    /// Name being typed for "Save as preset…"
+    pub preset_save_name: Option<String>,
+    /// The PDF generation still running, which the Cancel button stops
+    pub generation: Option<OperationId>,
+    /// Shown while asking for the password of an encrypted input
+    pub password_prompt: Option<PasswordPrompt>,
+    /// Resolution to export sheet images at, `DEFAULT_EXPORT_DPI` until changed
+    pub export_dpi: Option<f32>,
 }
 
-impl Default for ImposeState {
-    fn default() -> Self {
-        Self {
-            options: ImpositionOptions::default(),
-            preview_doc_id: None,
-            preview_page_count: 0,
-            stats: None,
-            loaded_docs: Vec::new(),
-            preview_viewer: None,
-            needs_regeneration: false,
-        }
-    }
+/// Resolution sheet images are exported at unless another is chosen
+pub const DEFAULT_EXPORT_DPI: f32 = 300.0;
+
+/// The password dialog for encrypted inputs
+#[derive(Default)]
+pub struct PasswordPrompt {
+    pub input: String,
+    /// Whether the last password tried was wrong
+    pub wrong_password: bool,
 }
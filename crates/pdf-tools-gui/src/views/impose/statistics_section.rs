@@ -2,10 +2,17 @@ use eframe::egui;
 
 use super::state::ImposeState;
 
-pub fn show(ui: &mut egui::Ui, state: &ImposeState) {
+/// Page count the statistics assume until files are chosen
+const DEFAULT_ESTIMATE_PAGES: usize = 64;
+
+pub fn show(ui: &mut egui::Ui, state: &mut ImposeState) {
     egui::CollapsingHeader::new("📊 Statistics")
         .default_open(true)
         .show(ui, |ui| {
+            if state.options.input_files.is_empty() {
+                show_estimate_pages(ui, state);
+            }
+
             if let Some(stats) = &state.stats {
                 ui.label(format!("Source pages: {}", stats.source_pages));
                 ui.label(format!("Output sheets: {}", stats.output_sheets));
@@ -19,11 +26,43 @@ pub fn show(ui: &mut egui::Ui, state: &ImposeState) {
                     ui.label(format!("Number of signatures: {}", sig_count));
                 }
 
-                if let Some(ref pages_per_sig) = stats.pages_per_signature {
-                    if !pages_per_sig.is_empty() {
-                        let pages_display = format_pages_per_signature(pages_per_sig);
-                        ui.label(format!("Pages per signature: {}", pages_display));
-                    }
+                if let Some(height) = stats.stack_height {
+                    ui.label(format!("Stack height: {} leaves", height));
+                }
+
+                if stats.cover_sheets > 0 {
+                    ui.label(format!("Cover sheets: {}", stats.cover_sheets));
+                }
+                ui.label(format!("Paper sheets: {}", stats.paper_sheets));
+
+                if let Some(ref pages_per_sig) = stats.pages_per_signature
+                    && !pages_per_sig.is_empty()
+                {
+                    let pages_display = format_pages_per_signature(pages_per_sig);
+                    ui.label(format!("Pages per signature: {}", pages_display));
+                }
+
+                if let Some(ref blanks) = stats.blank_pages_per_signature
+                    && blanks.iter().any(|&b| b > 0)
+                {
+                    ui.label(format!("Blanks per signature: {:?}", blanks));
+                }
+
+                let (trim_width, trim_height) = stats.trim_size_mm;
+                ui.label(format!(
+                    "Trim size: {:.1} × {:.1} mm",
+                    trim_width, trim_height
+                ));
+
+                if let Some(spine) = stats.spine_thickness_mm {
+                    ui.label(format!("Spine thickness: ~{:.1} mm", spine));
+                }
+
+                if !stats.bleed_fits {
+                    ui.colored_label(
+                        egui::Color32::from_rgb(255, 170, 0),
+                        "⚠ Bleed is wider than the fore-edge or cut margin",
+                    );
                 }
             } else {
                 ui.label("No statistics available");
@@ -32,6 +71,23 @@ pub fn show(ui: &mut egui::Ui, state: &ImposeState) {
         });
 }
 
+/// Let the statistics be worked out for a page count before any files are
+/// chosen, so the layout controls show their effect right away
+fn show_estimate_pages(ui: &mut egui::Ui, state: &mut ImposeState) {
+    let first_shown = state.estimate_pages.is_none();
+    let pages = state.estimate_pages.get_or_insert(DEFAULT_ESTIMATE_PAGES);
+    ui.horizontal(|ui| {
+        ui.label("Estimate for");
+        if ui
+            .add(egui::DragValue::new(pages).range(1..=5000).suffix(" pages"))
+            .changed()
+            || first_shown
+        {
+            state.needs_regeneration = true;
+        }
+    });
+}
+
 fn format_pages_per_signature(pages_per_sig: &[usize]) -> String {
     if pages_per_sig.iter().all(|&p| p == pages_per_sig[0]) {
         format!("{} pages each", pages_per_sig[0])
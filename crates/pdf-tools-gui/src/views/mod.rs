@@ -3,5 +3,5 @@ pub mod impose;
 pub mod viewer;
 
 pub use flashcards::{FlashcardState, show_flashcards};
-pub use impose::{ImposeState, show_impose};
+pub use impose::{ImposeState, PasswordPrompt, show_impose};
 pub use viewer::{ViewerState, show_viewer};
@@ -1,23 +1,195 @@
 use eframe::egui;
-use pdf_async_runtime::{DocumentId, PdfCommand};
+use eframe::egui::scroll_area::ScrollSource;
+use pdf_async_runtime::{DocumentId, PageRect, PdfCommand};
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
 use tokio::sync::mpsc;
 
+/// Zoom limits, relative to the default preview size
+const MIN_ZOOM: f32 = 0.25;
+const MAX_ZOOM: f32 = 8.0;
+
+/// Zoom factor of one click of the zoom buttons
+const ZOOM_STEP: f32 = 1.25;
+
+/// Box each thumbnail is fitted into in the page navigator
+const THUMBNAIL_SIZE: egui::Vec2 = egui::vec2(96.0, 128.0);
+
+/// Height under a thumbnail for its page number
+const THUMBNAIL_LABEL_HEIGHT: f32 = 18.0;
+
+/// Scales pages are rendered at; zooming past one re-renders at the next,
+/// and in between the texture is scaled
+const RENDER_SCALES: [f32; 4] = [0.5, 1.0, 2.0, 3.0];
+
+/// The smallest render scale that keeps pages sharp at `pixels_per_unit`
+/// screen pixels per unit of the default preview size
+fn render_scale_for(pixels_per_unit: f32) -> f32 {
+    RENDER_SCALES
+        .into_iter()
+        .find(|&scale| scale >= pixels_per_unit)
+        .unwrap_or(RENDER_SCALES[RENDER_SCALES.len() - 1])
+}
+
 #[derive(Clone)]
 pub struct ViewerState {
     pub current_doc_id: Option<DocumentId>,
     pub current_page: usize,
     pub total_pages: usize,
     pub page_texture: Option<egui::TextureHandle>,
+    /// Display size relative to the default preview size
+    pub zoom: f32,
+    /// Work `zoom` out each frame so the whole page fits the window
+    pub fit_to_window: bool,
+    /// Scale of the last render requested
+    pub render_scale: f32,
+    /// Scale `page_texture` was rendered at
+    pub texture_scale: f32,
+    /// Scroll offset of the page, moved by dragging it
+    pub pan: egui::Vec2,
+    /// Thumbnail textures for the page navigator, by page index
+    pub thumbnails: HashMap<usize, egui::TextureHandle>,
+    /// Pages whose thumbnails have been asked for
+    thumbnails_requested: HashSet<usize>,
+    /// Pages shown in the page navigator last frame
+    visible_thumbnails: Range<usize>,
+    /// Scroll the page navigator to the current page if it is out of sight
+    reveal_current: bool,
+    /// Text typed into the search box
+    search_query: String,
+    /// Query last sent to the worker, until its results come back
+    pending_search: Option<String>,
+    /// Query `search_hits` were found for
+    searched_query: Option<String>,
+    /// Matches of the last search, as (page index, bounds) in page order
+    search_hits: Vec<(usize, PageRect)>,
+    /// Index into `search_hits` of the match being shown
+    current_hit: Option<usize>,
+    /// Scroll the page to the current match once it is drawn
+    reveal_hit: bool,
 }
 
 impl ViewerState {
-    #[allow(dead_code)]
     pub fn new(doc_id: DocumentId, page_count: usize) -> Self {
         Self {
             current_doc_id: Some(doc_id),
             current_page: 0,
             total_pages: page_count,
             page_texture: None,
+            zoom: 1.0,
+            fit_to_window: true,
+            render_scale: 1.0,
+            texture_scale: 1.0,
+            pan: egui::Vec2::ZERO,
+            thumbnails: HashMap::new(),
+            thumbnails_requested: HashSet::new(),
+            visible_thumbnails: 0..0,
+            reveal_current: false,
+            search_query: String::new(),
+            pending_search: None,
+            searched_query: None,
+            search_hits: Vec::new(),
+            current_hit: None,
+            reveal_hit: false,
+        }
+    }
+
+    /// Take the zoom and pan of `other`, e.g. when a preview is regenerated
+    pub fn keep_view_of(&mut self, other: &ViewerState) {
+        self.zoom = other.zoom;
+        self.fit_to_window = other.fit_to_window;
+        self.render_scale = other.render_scale;
+        self.pan = other.pan;
+    }
+
+    /// Zoom to `zoom`, leaving fit to window
+    fn set_zoom(&mut self, zoom: f32) {
+        self.zoom = zoom.clamp(MIN_ZOOM, MAX_ZOOM);
+        self.fit_to_window = false;
+    }
+
+    /// Show page `page_index`, clamped to the document
+    fn go_to_page(&mut self, page_index: usize, command_tx: &mpsc::UnboundedSender<PdfCommand>) {
+        let page_index = page_index.min(self.total_pages.saturating_sub(1));
+        if page_index != self.current_page {
+            self.current_page = page_index;
+            self.reveal_current = true;
+            self.request_render(command_tx);
+        }
+    }
+
+    /// Take the matches found for `query`, unless a newer search has been
+    /// sent since, and show the first one from the current page on
+    pub fn show_search_results(
+        &mut self,
+        query: String,
+        hits: Vec<(usize, PageRect)>,
+        command_tx: &mpsc::UnboundedSender<PdfCommand>,
+    ) {
+        if self.pending_search.as_ref() != Some(&query) {
+            return;
+        }
+        self.pending_search = None;
+        log::info!("Found {} matches for \"{}\"", hits.len(), query);
+        self.searched_query = Some(query);
+        self.search_hits = hits;
+        self.current_hit = None;
+        if !self.search_hits.is_empty() {
+            let first = self
+                .search_hits
+                .iter()
+                .position(|&(page, _)| page >= self.current_page)
+                .unwrap_or(0);
+            self.go_to_hit(first, command_tx);
+        }
+    }
+
+    /// Search for the text in the search box, or step to the next match if
+    /// it was searched for already
+    fn search(&mut self, command_tx: &mpsc::UnboundedSender<PdfCommand>) {
+        if self.searched_query.as_ref() == Some(&self.search_query) {
+            self.step_hit(true, command_tx);
+        } else if let Some(doc_id) = self.current_doc_id
+            && self.pending_search.as_ref() != Some(&self.search_query)
+        {
+            self.pending_search = Some(self.search_query.clone());
+            let _ = command_tx.send(PdfCommand::ViewerSearch {
+                doc_id,
+                query: self.search_query.clone(),
+            });
+        }
+    }
+
+    /// Move to the next match, or the previous one, wrapping round
+    fn step_hit(&mut self, forward: bool, command_tx: &mpsc::UnboundedSender<PdfCommand>) {
+        let count = self.search_hits.len();
+        if count == 0 {
+            return;
+        }
+        let hit = match (self.current_hit, forward) {
+            (Some(hit), true) => (hit + 1) % count,
+            (Some(hit), false) => (hit + count - 1) % count,
+            (None, _) => 0,
+        };
+        self.go_to_hit(hit, command_tx);
+    }
+
+    /// Show match `hit` of the last search
+    fn go_to_hit(&mut self, hit: usize, command_tx: &mpsc::UnboundedSender<PdfCommand>) {
+        self.current_hit = Some(hit);
+        self.reveal_hit = true;
+        self.go_to_page(self.search_hits[hit].0, command_tx);
+    }
+
+    /// Ask the worker to render the current page at the current scale
+    fn request_render(&self, command_tx: &mpsc::UnboundedSender<PdfCommand>) {
+        if let Some(doc_id) = self.current_doc_id {
+            let _ = command_tx.send(PdfCommand::ViewerRenderPage {
+                doc_id,
+                page_index: self.current_page,
+                scale: self.render_scale,
+            });
+            log::info!("Rendering page {}...", self.current_page + 1);
         }
     }
 }
@@ -37,14 +209,7 @@ pub fn show_viewer(
                 .add_enabled(can_go_back, egui::Button::new("◀ Previous"))
                 .clicked()
             {
-                state.current_page -= 1;
-                if let Some(doc_id) = state.current_doc_id {
-                    let _ = command_tx.send(PdfCommand::ViewerRenderPage {
-                        doc_id,
-                        page_index: state.current_page,
-                    });
-                    log::info!("Rendering page {}...", state.current_page + 1);
-                }
+                state.go_to_page(state.current_page - 1, command_tx);
             }
 
             ui.label(format!(
@@ -57,35 +222,59 @@ pub fn show_viewer(
                 .add_enabled(can_go_forward, egui::Button::new("Next ▶"))
                 .clicked()
             {
-                state.current_page += 1;
-                if let Some(doc_id) = state.current_doc_id {
-                    let _ = command_tx.send(PdfCommand::ViewerRenderPage {
-                        doc_id,
-                        page_index: state.current_page,
-                    });
-                    log::info!("Rendering page {}...", state.current_page + 1);
-                }
+                state.go_to_page(state.current_page + 1, command_tx);
             }
 
             ui.separator();
 
-            if ui.button("Close PDF").clicked() {
-                if let Some(doc_id) = state.current_doc_id {
-                    let _ = command_tx.send(PdfCommand::ViewerClose { doc_id });
-                }
+            if ui
+                .add_enabled(state.zoom > MIN_ZOOM, egui::Button::new("−"))
+                .on_hover_text("Zoom out (Ctrl+scroll)")
+                .clicked()
+            {
+                state.set_zoom(state.zoom / ZOOM_STEP);
+            }
+            ui.label(format!("{:.0}%", state.zoom * 100.0));
+            if ui
+                .add_enabled(state.zoom < MAX_ZOOM, egui::Button::new("+"))
+                .on_hover_text("Zoom in (Ctrl+scroll)")
+                .clicked()
+            {
+                state.set_zoom(state.zoom * ZOOM_STEP);
+            }
+            if ui
+                .selectable_label(state.fit_to_window, "Fit")
+                .on_hover_text("Fit the page to the window")
+                .clicked()
+            {
+                state.fit_to_window = true;
+                state.pan = egui::Vec2::ZERO;
+            }
+
+            ui.separator();
+
+            show_search_box(ui, state, command_tx);
+
+            ui.separator();
+
+            if ui.button("Close PDF").clicked()
+                && let Some(doc_id) = state.current_doc_id
+            {
+                let _ = command_tx.send(PdfCommand::ViewerClose { doc_id });
             }
         });
 
         ui.separator();
 
+        handle_navigation_keys(ui, state, command_tx);
+
+        egui::SidePanel::left(ui.id().with("thumbnails"))
+            .resizable(false)
+            .show_inside(ui, |ui| show_thumbnails(ui, state, command_tx));
+
         // Display page texture if available
-        if let Some(texture) = &state.page_texture {
-            // Center the image
-            egui::ScrollArea::both().show(ui, |ui| {
-                ui.centered_and_justified(|ui| {
-                    ui.image((texture.id(), texture.size_vec2()));
-                });
-            });
+        if let Some(texture) = state.page_texture.clone() {
+            show_page(ui, state, &texture, command_tx);
         } else {
             ui.centered_and_justified(|ui| {
                 ui.spinner();
@@ -93,9 +282,7 @@ pub fn show_viewer(
             });
         }
 
-        // TODO: Add zoom controls
         // TODO: Add jump to page input
-        // TODO: Add thumbnail sidebar
     } else {
         // No PDF loaded - show file loading UI
         ui.vertical_centered(|ui| {
@@ -108,14 +295,13 @@ pub fn show_viewer(
                 ui.label("Drop a PDF file here or click to open");
                 ui.add_space(10.0);
 
-                if ui.button("Open PDF...").clicked() {
-                    if let Some(path) = rfd::FileDialog::new()
+                if ui.button("Open PDF...").clicked()
+                    && let Some(path) = rfd::FileDialog::new()
                         .add_filter("PDF", &["pdf"])
                         .pick_file()
-                    {
-                        log::info!("Loading PDF: {}", path.display());
-                        let _ = command_tx.send(PdfCommand::ViewerLoad { path });
-                    }
+                {
+                    log::info!("Loading PDF: {}", path.display());
+                    let _ = command_tx.send(PdfCommand::ViewerLoad { path });
                 }
             }
 
@@ -126,3 +312,243 @@ pub fn show_viewer(
         });
     }
 }
+
+/// A search box, searching on Enter and stepping through the matches after
+/// (backwards with Shift+Enter), with buttons for the next and previous match; Ctrl+F focuses it
+fn show_search_box(
+    ui: &mut egui::Ui,
+    state: &mut ViewerState,
+    command_tx: &mpsc::UnboundedSender<PdfCommand>,
+) {
+    let response = ui.add(
+        egui::TextEdit::singleline(&mut state.search_query)
+            .hint_text("Search")
+            .desired_width(140.0),
+    );
+    if ui.input_mut(|input| input.consume_key(egui::Modifiers::COMMAND, egui::Key::F)) {
+        response.request_focus();
+    }
+    if response.lost_focus() && ui.input(|input| input.key_pressed(egui::Key::Enter)) {
+        if ui.input(|input| input.modifiers.shift)
+            && state.searched_query.as_ref() == Some(&state.search_query)
+        {
+            state.step_hit(false, command_tx);
+        } else {
+            state.search(command_tx);
+        }
+        // Keep the focus so Enter again moves to the next match
+        response.request_focus();
+    }
+
+    let has_hits =
+        !state.search_hits.is_empty() && state.searched_query.as_ref() == Some(&state.search_query);
+    if ui
+        .add_enabled(has_hits, egui::Button::new("▲"))
+        .on_hover_text("Previous match (Shift+Enter)")
+        .clicked()
+    {
+        state.step_hit(false, command_tx);
+    }
+    if ui
+        .add_enabled(has_hits, egui::Button::new("▼"))
+        .on_hover_text("Next match (Enter)")
+        .clicked()
+    {
+        state.step_hit(true, command_tx);
+    }
+
+    if state.pending_search.is_some() {
+        ui.spinner();
+    } else if state.searched_query.as_ref() == Some(&state.search_query) {
+        match state.current_hit {
+            Some(hit) => ui.label(format!("{} of {}", hit + 1, state.search_hits.len())),
+            None => ui.label("No matches"),
+        };
+    }
+}
+
+/// Home/End and Page Up/Page Down move through the pages, unless a text
+/// field has the keyboard
+fn handle_navigation_keys(
+    ui: &egui::Ui,
+    state: &mut ViewerState,
+    command_tx: &mpsc::UnboundedSender<PdfCommand>,
+) {
+    if ui.ctx().wants_keyboard_input() {
+        return;
+    }
+    let last_page = state.total_pages.saturating_sub(1);
+    let target = ui.input_mut(|input| {
+        if input.consume_key(egui::Modifiers::NONE, egui::Key::Home) {
+            Some(0)
+        } else if input.consume_key(egui::Modifiers::NONE, egui::Key::End) {
+            Some(last_page)
+        } else if input.consume_key(egui::Modifiers::NONE, egui::Key::PageUp) {
+            Some(state.current_page.saturating_sub(1))
+        } else if input.consume_key(egui::Modifiers::NONE, egui::Key::PageDown) {
+            Some(state.current_page + 1)
+        } else {
+            None
+        }
+    });
+    if let Some(page_index) = target {
+        state.go_to_page(page_index, command_tx);
+    }
+}
+
+/// A scrollable strip of page thumbnails, asking for those that come into
+/// view and jumping to a page when its thumbnail is clicked
+fn show_thumbnails(
+    ui: &mut egui::Ui,
+    state: &mut ViewerState,
+    command_tx: &mpsc::UnboundedSender<PdfCommand>,
+) {
+    let row_height = THUMBNAIL_SIZE.y + THUMBNAIL_LABEL_HEIGHT;
+    let mut area = egui::ScrollArea::vertical().auto_shrink([true, false]);
+    if std::mem::take(&mut state.reveal_current)
+        && !state.visible_thumbnails.contains(&state.current_page)
+    {
+        let row_pitch = row_height + ui.spacing().item_spacing.y;
+        area = area.vertical_scroll_offset(state.current_page as f32 * row_pitch);
+    }
+
+    area.show_rows(ui, row_height, state.total_pages, |ui, rows| {
+        state.visible_thumbnails = rows.clone();
+
+        let missing: Vec<usize> = rows
+            .clone()
+            .filter(|&page| state.thumbnails_requested.insert(page))
+            .collect();
+        if !missing.is_empty()
+            && let Some(doc_id) = state.current_doc_id
+        {
+            let _ = command_tx.send(PdfCommand::ViewerRenderThumbnails {
+                doc_id,
+                page_indices: missing,
+            });
+        }
+
+        for page_index in rows {
+            let (rect, response) = ui.allocate_exact_size(
+                egui::vec2(THUMBNAIL_SIZE.x, row_height),
+                egui::Sense::click(),
+            );
+            let visuals = ui.visuals();
+            let painter = ui.painter();
+            if page_index == state.current_page {
+                painter.rect_filled(rect, 4.0, visuals.selection.bg_fill);
+            } else if response.hovered() {
+                painter.rect_filled(rect, 4.0, visuals.widgets.hovered.weak_bg_fill);
+            }
+
+            let image_box = egui::Rect::from_min_size(rect.min, THUMBNAIL_SIZE).shrink(4.0);
+            match state.thumbnails.get(&page_index) {
+                Some(texture) => {
+                    let size = texture.size_vec2();
+                    let fit = (image_box.width() / size.x).min(image_box.height() / size.y);
+                    egui::Image::new((texture.id(), size * fit)).paint_at(
+                        ui,
+                        egui::Rect::from_center_size(image_box.center(), size * fit),
+                    );
+                }
+                None => {
+                    painter.rect_stroke(
+                        image_box,
+                        0.0,
+                        visuals.widgets.noninteractive.bg_stroke,
+                        egui::StrokeKind::Inside,
+                    );
+                }
+            }
+            painter.text(
+                egui::pos2(
+                    rect.center().x,
+                    rect.bottom() - THUMBNAIL_LABEL_HEIGHT / 2.0,
+                ),
+                egui::Align2::CENTER_CENTER,
+                (page_index + 1).to_string(),
+                egui::TextStyle::Small.resolve(ui.style()),
+                visuals.text_color(),
+            );
+
+            if response.clicked() {
+                state.go_to_page(page_index, command_tx);
+            }
+        }
+    });
+}
+
+/// Highlight the matches of the last search on the current page, drawn
+/// over `page_rect`, scrolling the current one into view when asked
+fn show_search_hits(ui: &egui::Ui, state: &mut ViewerState, page_rect: egui::Rect) {
+    let highlight = egui::Color32::from_rgba_unmultiplied(255, 210, 0, 80);
+    let current = egui::Color32::from_rgba_unmultiplied(255, 140, 0, 120);
+    for (index, &(page, bounds)) in state.search_hits.iter().enumerate() {
+        if page != state.current_page {
+            continue;
+        }
+        let hit_rect = egui::Rect::from_min_max(
+            page_rect.lerp_inside(egui::vec2(bounds.left, bounds.top)),
+            page_rect.lerp_inside(egui::vec2(bounds.right, bounds.bottom)),
+        )
+        .expand(1.0);
+        if state.current_hit == Some(index) {
+            ui.painter().rect_filled(hit_rect, 2.0, current);
+            if std::mem::take(&mut state.reveal_hit) {
+                ui.scroll_to_rect(hit_rect, Some(egui::Align::Center));
+            }
+        } else {
+            ui.painter().rect_filled(hit_rect, 2.0, highlight);
+        }
+    }
+}
+
+/// Show the page at the current zoom, zooming with Ctrl+scroll and panning
+/// by dragging, and re-render it once the zoom needs a sharper texture
+fn show_page(
+    ui: &mut egui::Ui,
+    state: &mut ViewerState,
+    texture: &egui::TextureHandle,
+    command_tx: &mpsc::UnboundedSender<PdfCommand>,
+) {
+    // The page's size at a zoom of 1, whatever scale the texture has
+    let page_size = texture.size_vec2() / state.texture_scale;
+    let view_size = ui.available_size();
+    if state.fit_to_window {
+        let fit = (view_size.x / page_size.x).min(view_size.y / page_size.y);
+        state.zoom = fit.clamp(MIN_ZOOM, MAX_ZOOM);
+    }
+
+    let zoom_delta = ui.input(|input| input.zoom_delta());
+    if zoom_delta != 1.0 && ui.ui_contains_pointer() {
+        state.set_zoom(state.zoom * zoom_delta);
+    }
+
+    let render_scale = render_scale_for(state.zoom * ui.ctx().pixels_per_point());
+    if render_scale != state.render_scale {
+        state.render_scale = render_scale;
+        state.request_render(command_tx);
+    }
+
+    let size = page_size * state.zoom;
+    let output = egui::ScrollArea::both()
+        .scroll_source(ScrollSource::SCROLL_BAR | ScrollSource::MOUSE_WHEEL)
+        .scroll_offset(state.pan)
+        .show(ui, |ui| {
+            // Center the page while it is smaller than the view
+            let (rect, response) = ui.allocate_exact_size(size.max(view_size), egui::Sense::drag());
+            let page_rect = egui::Rect::from_center_size(rect.center(), size);
+            egui::Image::new((texture.id(), size)).paint_at(ui, page_rect);
+            show_search_hits(ui, state, page_rect);
+            response
+        });
+
+    state.pan = output.state.offset;
+    let response = output.inner;
+    if response.dragged() {
+        state.pan -= response.drag_delta();
+        ui.ctx().set_cursor_icon(egui::CursorIcon::Grabbing);
+    } else if response.hovered() {
+        ui.ctx().set_cursor_icon(egui::CursorIcon::Grab);
+    }
+}
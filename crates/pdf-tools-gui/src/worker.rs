@@ -1,4 +1,8 @@
-use pdf_async_runtime::{PdfCommand, PdfUpdate};
+use pdf_async_runtime::{OperationId, PdfCommand, PdfUpdate};
+use pdf_impose::CancellationToken;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
 use tokio::sync::mpsc;
 
 use crate::{handlers, viewer};
@@ -20,11 +24,13 @@ pub async fn worker_task(
     };
 
     let mut impose_doc_store = handlers::impose::ImposeDocStore::new();
+    let in_flight = InFlight::default();
 
     while let Some(cmd) = command_rx.recv().await {
         process_command(
             cmd,
             &mut impose_doc_store,
+            &in_flight,
             #[cfg(feature = "pdf-viewer")]
             &mut viewer_state,
             &mut command_rx,
@@ -34,9 +40,24 @@ pub async fn worker_task(
     }
 }
 
+/// Cancellation tokens of the operations still running, by id
+///
+/// Each operation removes its own entry when it finishes.
+type InFlight = Arc<Mutex<HashMap<OperationId, CancellationToken>>>;
+
+/// Run an operation off the worker loop, so commands such as
+/// `CancelOperation` are handled while it runs
+fn spawn_operation(operation: impl Future<Output = ()> + Send + 'static) {
+    #[cfg(not(target_arch = "wasm32"))]
+    tokio::spawn(operation);
+    #[cfg(target_arch = "wasm32")]
+    wasm_bindgen_futures::spawn_local(operation);
+}
+
 async fn process_command(
     cmd: PdfCommand,
     impose_doc_store: &mut handlers::impose::ImposeDocStore,
+    in_flight: &InFlight,
     #[cfg(feature = "pdf-viewer")] viewer_state: &mut Option<viewer::ViewerState>,
     command_rx: &mut mpsc::UnboundedReceiver<PdfCommand>,
     update_tx: &mpsc::UnboundedSender<PdfUpdate>,
@@ -53,10 +74,21 @@ async fn process_command(
             handlers::flashcards::handle_generate(cards, options, output_path, update_tx).await;
         }
         PdfCommand::ImposeLoad { input_path } => {
-            handlers::impose::handle_load(input_path, update_tx).await;
+            handlers::impose::handle_load(input_path, impose_doc_store, update_tx).await;
         }
-        PdfCommand::ImposeProcess { .. } => {
-            handlers::impose::handle_process(update_tx).await;
+        PdfCommand::ImposeProcess {
+            doc_id,
+            options,
+            output_path,
+        } => {
+            handlers::impose::handle_process(
+                doc_id,
+                options,
+                output_path,
+                impose_doc_store,
+                update_tx,
+            )
+            .await;
         }
         PdfCommand::ImposeGeneratePreview { mut options } => {
             // Drain any queued preview commands, keeping only the most recent
@@ -73,6 +105,7 @@ async fn process_command(
                     Box::pin(process_command(
                         next_cmd,
                         impose_doc_store,
+                        in_flight,
                         #[cfg(feature = "pdf-viewer")]
                         viewer_state,
                         command_rx,
@@ -83,19 +116,81 @@ async fn process_command(
             }
 
             // Process the most recent preview
-            handlers::impose::handle_generate_preview(options, impose_doc_store, update_tx).await;
+            #[cfg(feature = "pdf-viewer")]
+            if let Some(state) = viewer_state {
+                handlers::impose::handle_generate_preview(
+                    options,
+                    impose_doc_store,
+                    state,
+                    update_tx,
+                )
+                .await;
+            } else {
+                let _ = update_tx.send(PdfUpdate::Error {
+                    message: "PDF viewer not initialized".to_string(),
+                });
+            }
+            #[cfg(not(feature = "pdf-viewer"))]
+            {
+                let _ = options;
+                handlers::viewer::handle_viewer_unavailable(update_tx).await;
+            }
         }
         PdfCommand::ImposeGenerate {
+            operation_id,
             options,
             output_path,
         } => {
-            handlers::impose::handle_generate(options, output_path, update_tx).await;
+            let cancel = CancellationToken::new();
+            in_flight
+                .lock()
+                .unwrap()
+                .insert(operation_id, cancel.clone());
+
+            let in_flight = Arc::clone(in_flight);
+            let update_tx = update_tx.clone();
+            spawn_operation(async move {
+                handlers::impose::handle_generate(
+                    operation_id,
+                    options,
+                    output_path,
+                    cancel,
+                    &update_tx,
+                )
+                .await;
+                in_flight.lock().unwrap().remove(&operation_id);
+                let _ = update_tx.send(PdfUpdate::OperationFinished { operation_id });
+            });
+        }
+        PdfCommand::CancelOperation { operation_id } => {
+            if let Some(cancel) = in_flight.lock().unwrap().remove(&operation_id) {
+                log::info!("Cancelling operation {}", operation_id.0);
+                cancel.cancel();
+            }
         }
         PdfCommand::ImposeLoadConfig { path } => {
             handlers::impose::handle_load_config(path, update_tx).await;
         }
-        PdfCommand::ImposeCalculateStats { options } => {
-            handlers::impose::handle_calculate_stats(options, update_tx).await;
+        PdfCommand::ImposeListPresets => {
+            handlers::impose::handle_list_presets(update_tx).await;
+        }
+        PdfCommand::ImposeLoadPreset { name } => {
+            handlers::impose::handle_load_preset(name, update_tx).await;
+        }
+        PdfCommand::ImposeSavePreset { name, options } => {
+            handlers::impose::handle_save_preset(name, options, update_tx).await;
+        }
+        PdfCommand::ImposeCalculateStats {
+            options,
+            page_count,
+        } => {
+            handlers::impose::handle_calculate_stats(
+                options,
+                page_count,
+                impose_doc_store,
+                update_tx,
+            )
+            .await;
         }
         #[cfg(feature = "pdf-viewer")]
         PdfCommand::ViewerLoad { path } => {
@@ -111,17 +206,20 @@ async fn process_command(
         PdfCommand::ViewerRenderPage {
             mut doc_id,
             mut page_index,
+            mut scale,
         } => {
             // Deduplicate render commands - keep the most recent one
             while let Ok(next_cmd) = command_rx.try_recv() {
                 if let PdfCommand::ViewerRenderPage {
                     doc_id: new_doc_id,
                     page_index: new_page_index,
+                    scale: new_scale,
                 } = next_cmd
                 {
                     log::debug!("Discarding queued page render, using newer request");
                     doc_id = new_doc_id;
                     page_index = new_page_index;
+                    scale = new_scale;
                 } else if let PdfCommand::ViewerPrefetchPages { .. } = next_cmd {
                     // Discard prefetch commands when we have a direct render pending
                     log::debug!("Discarding prefetch during page navigation");
@@ -130,6 +228,7 @@ async fn process_command(
                     Box::pin(process_command(
                         next_cmd,
                         impose_doc_store,
+                        in_flight,
                         viewer_state,
                         command_rx,
                         update_tx,
@@ -139,7 +238,8 @@ async fn process_command(
             }
 
             if let Some(state) = viewer_state {
-                handlers::viewer::handle_render_page(doc_id, page_index, state, update_tx).await;
+                handlers::viewer::handle_render_page(doc_id, page_index, scale, state, update_tx)
+                    .await;
             } else {
                 let _ = update_tx.send(PdfUpdate::Error {
                     message: "PDF viewer not initialized".to_string(),
@@ -148,11 +248,70 @@ async fn process_command(
         }
         #[cfg(feature = "pdf-viewer")]
         PdfCommand::ViewerPrefetchPages {
+            mut doc_id,
+            mut page_indices,
+            mut scale,
+        } => {
+            // Anything queued behind a prefetch is more urgent, so run it first
+            while let Ok(next_cmd) = command_rx.try_recv() {
+                match next_cmd {
+                    PdfCommand::ViewerPrefetchPages {
+                        doc_id: new_doc_id,
+                        page_indices: new_page_indices,
+                        scale: new_scale,
+                    } => {
+                        log::debug!("Discarding queued prefetch, using newer request");
+                        doc_id = new_doc_id;
+                        page_indices = new_page_indices;
+                        scale = new_scale;
+                    }
+                    PdfCommand::ViewerRenderPage { .. } => {
+                        // The rendered page asks for its own neighbours, so this
+                        // prefetch is stale
+                        log::debug!("Discarding prefetch for a pending page render");
+                        Box::pin(process_command(
+                            next_cmd,
+                            impose_doc_store,
+                            in_flight,
+                            viewer_state,
+                            command_rx,
+                            update_tx,
+                        ))
+                        .await;
+                        return;
+                    }
+                    _ => {
+                        Box::pin(process_command(
+                            next_cmd,
+                            impose_doc_store,
+                            in_flight,
+                            viewer_state,
+                            command_rx,
+                            update_tx,
+                        ))
+                        .await;
+                    }
+                }
+            }
+
+            if let Some(state) = viewer_state {
+                handlers::viewer::handle_prefetch_pages(doc_id, page_indices, scale, state).await;
+            }
+        }
+        #[cfg(feature = "pdf-viewer")]
+        PdfCommand::ViewerRenderThumbnails {
             doc_id,
             page_indices,
         } => {
             if let Some(state) = viewer_state {
-                handlers::viewer::handle_prefetch_pages(doc_id, page_indices, state).await;
+                handlers::viewer::handle_render_thumbnails(doc_id, page_indices, state, update_tx)
+                    .await;
+            }
+        }
+        #[cfg(feature = "pdf-viewer")]
+        PdfCommand::ViewerSearch { doc_id, query } => {
+            if let Some(state) = viewer_state {
+                handlers::viewer::handle_search(doc_id, query, state, update_tx).await;
             }
         }
         #[cfg(feature = "pdf-viewer")]
@@ -161,11 +320,33 @@ async fn process_command(
                 handlers::viewer::handle_close(doc_id, state, update_tx).await;
             }
         }
+        #[cfg(feature = "pdf-viewer")]
+        PdfCommand::ExportImages {
+            doc_id,
+            dpi,
+            double_sided,
+            output_dir,
+        } => {
+            if let Some(state) = viewer_state {
+                handlers::viewer::handle_export_images(
+                    doc_id,
+                    dpi,
+                    double_sided,
+                    output_dir,
+                    state,
+                    update_tx,
+                )
+                .await;
+            }
+        }
         #[cfg(not(feature = "pdf-viewer"))]
         PdfCommand::ViewerLoad { .. }
         | PdfCommand::ViewerRenderPage { .. }
         | PdfCommand::ViewerPrefetchPages { .. }
-        | PdfCommand::ViewerClose { .. } => {
+        | PdfCommand::ViewerRenderThumbnails { .. }
+        | PdfCommand::ViewerSearch { .. }
+        | PdfCommand::ViewerClose { .. }
+        | PdfCommand::ExportImages { .. } => {
             handlers::viewer::handle_viewer_unavailable(update_tx).await;
         }
     }